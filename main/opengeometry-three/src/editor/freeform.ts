@@ -29,6 +29,7 @@ type RawFreeformOperationCapabilities = {
   can_remove_vertex: boolean;
   can_split_edge: boolean;
   can_loop_cut: boolean;
+  can_chamfer_edge: boolean;
   reasons?: string[];
 };
 
@@ -123,6 +124,7 @@ function normalizeFeatureCapabilities(
     canRemoveVertex: raw.can_remove_vertex,
     canSplitEdge: raw.can_split_edge,
     canLoopCut: raw.can_loop_cut,
+    canChamferEdge: raw.can_chamfer_edge,
     reasons: raw.reasons,
   };
 }
@@ -147,6 +149,7 @@ function normalizeFreeformCapabilities(
     canRemoveVertex: raw.can_remove_vertex,
     canSplitEdge: raw.can_split_edge,
     canLoopCut: raw.can_loop_cut,
+    canChamferEdge: raw.can_chamfer_edge,
     reasons: raw.reasons,
   };
 }
@@ -458,6 +461,26 @@ export class FreeformEditor {
     );
   }
 
+  /**
+   * Replaces an edge with a planar chamfer face. `distance` is the setback on
+   * the edge's first face; pass `chamferSecondDistance` or `chamferAngle` in
+   * the options for asymmetric chamfers.
+   */
+  chamferEdge(
+    edgeId: TopologyId,
+    distance: number,
+    options?: EditOperationOptions
+  ): FreeformEditResult {
+    return parseEditResult(
+      this.editor.chamferEdge(
+        this.geometry.getKernelGeometry(),
+        edgeId,
+        distance,
+        serializeOptions(options)
+      )
+    );
+  }
+
   /**
    * Removes a vertex when the current topology allows the edit.
    */
@@ -21,6 +21,7 @@ export interface FreeformOperationCapabilities {
   canRemoveVertex: boolean;
   canSplitEdge: boolean;
   canLoopCut: boolean;
+  canChamferEdge: boolean;
   reasons?: string[];
 }
 
@@ -208,6 +209,10 @@ export interface EditOperationOptions {
   preserveCoplanarity?: boolean;
   constraintFrame?: "local" | "world";
   openSurfaceMode?: boolean;
+  /** Setback on the edge's second face for two-distance chamfers. */
+  chamferSecondDistance?: number;
+  /** Chamfer angle in radians measured from the edge's first face. */
+  chamferAngle?: number;
 }
 
 /**
@@ -44,15 +44,26 @@ export type BooleanOperand =
  *
  * **Recommended cutter overshoot for through-cuts:** `max(hostThickness *
  * 0.05, 0.01)` in model units. See `knowledge/boolean-tolerance-guide.md`.
+ * Set `resolveCoincidence` to have the kernel apply it to subtraction
+ * cutters whose faces lie on host faces; the report's
+ * `coincidence_adjustments` lists every face it moved.
  *
  * `mergeCoplanarFaces` (default `true`) controls whether the planar pipeline
  * merges coplanar adjacent faces in its output.
+ *
+ * `backend` selects the kernel: `"Mesh"` (default, boolmesh) or `"Native"`,
+ * an exact BSP boolean for planar BReps that honours `tolerance` end to end
+ * and has no snap window, so small reveals and offsets are kept.
  */
 export interface BooleanKernelOptions {
   tolerance?: number;
   mergeCoplanarFaces?: boolean;
+  backend?: BooleanBackend;
+  resolveCoincidence?: boolean;
 }
 
+export type BooleanBackend = "Mesh" | "Native";
+
 /**
  * Rendering options applied to the boolean result mesh.
  *
@@ -98,6 +109,49 @@ export interface BooleanReport {
   output_face_count: number;
   output_shell_count: number;
   empty: boolean;
+  /**
+   * Net volume of each separate solid in the result, in `splitLumps` order.
+   * Empty for results without closed shells.
+   */
+  lump_volumes: number[];
+  coincidence_adjustments: BooleanCoincidenceAdjustment[];
+}
+
+/**
+ * A subtraction cutter face pushed `offset` model units outward off the
+ * host face it lay on. `operand` is numbered as in `BooleanFaceOrigin`.
+ */
+export interface BooleanCoincidenceAdjustment {
+  operand: number;
+  face_id: number;
+  host_face_id: number;
+  offset: number;
+}
+
+/**
+ * Operand face an output face was cut from. Operand `0` is the host or
+ * left-hand operand; cutters follow in call order.
+ */
+export interface BooleanFaceOrigin {
+  operand: number;
+  face_id: number;
+}
+
+/**
+ * How an output edge arose, with the operand edge it lies on when there is one.
+ */
+export interface BooleanEdgeOrigin {
+  kind: "Operand" | "Intersection" | "Internal";
+  operand: number | null;
+  edge_id: number | null;
+}
+
+/**
+ * Face and edge history of a boolean result, keyed by output face / edge id.
+ */
+export interface BooleanProvenance {
+  faces: Record<string, BooleanFaceOrigin>;
+  edges: Record<string, BooleanEdgeOrigin>;
 }
 
 interface KernelBooleanResult {
@@ -105,6 +159,7 @@ interface KernelBooleanResult {
   geometrySerialized: string;
   outlineGeometrySerialized: string;
   reportJson: string;
+  provenanceJson: string;
 }
 
 
@@ -130,6 +185,7 @@ const BOOLEAN_OUTLINE_THRESHOLD_DEGREES = 26;
 export class BooleanResult extends THREE.Mesh {
   ogid: string;
   report: BooleanReport | null = null;
+  provenance: BooleanProvenance | null = null;
 
   private brepSerialized = "";
   private brepData: Record<string, unknown> | null = null;
@@ -207,6 +263,7 @@ export class BooleanResult extends THREE.Mesh {
     this.brepSerialized = kernelResult.brepSerialized;
     this.brepData = JSON.parse(kernelResult.brepSerialized) as Record<string, unknown>;
     this.report = JSON.parse(kernelResult.reportJson) as BooleanReport;
+    this.provenance = JSON.parse(kernelResult.provenanceJson) as BooleanProvenance;
 
     const positions = JSON.parse(kernelResult.geometrySerialized) as number[];
     const baseGeometry = new THREE.BufferGeometry();
@@ -398,6 +455,107 @@ export function executeBooleanSubtractionMany(
   return createBooleanResult(kernelResult, options, lhs);
 }
 
+/**
+ * Work done by the last `BooleanGraph.evaluate()` call.
+ */
+export interface BooleanGraphEvaluation {
+  cluster_count: number;
+  clusters_evaluated: number;
+  host_faces_evaluated: number;
+  host_faces_reused: number;
+}
+
+/**
+ * Persistent `host - cutters` subtraction for hosts with many openings.
+ *
+ * Cutters are named; adding, replacing, moving, or removing one and calling
+ * `evaluate()` only recomputes the cutters whose bounds overlap the edit and
+ * the host faces they reach. Always runs on the native kernel.
+ */
+export class BooleanGraph {
+  private graph: OGKernel.OGBooleanGraph;
+
+  constructor(host: BooleanOperand, kernelOptions?: BooleanKernelOptions) {
+    try {
+      this.graph = new OGKernel.OGBooleanGraph(
+        resolveOperandSerialized(host),
+        serializeKernelOptions(kernelOptions)
+      );
+    } catch (error) {
+      throw parseBooleanError(error);
+    }
+  }
+
+  /** Adds a cutter, or replaces the one already called `name`. */
+  setCutter(name: string, cutter: BooleanOperand) {
+    try {
+      this.graph.setCutter(name, resolveOperandSerialized(cutter));
+    } catch (error) {
+      throw parseBooleanError(error);
+    }
+  }
+
+  /** Returns `false` when no cutter is called `name`. */
+  removeCutter(name: string): boolean {
+    return this.graph.removeCutter(name);
+  }
+
+  /** Translates the cutter called `name` by `offset`. */
+  moveCutter(name: string, offset: OGKernel.Vector3) {
+    try {
+      this.graph.moveCutter(name, offset);
+    } catch (error) {
+      throw parseBooleanError(error);
+    }
+  }
+
+  cutterNames(): string[] {
+    return this.graph.cutterNames();
+  }
+
+  /** Brings the subtraction up to date and returns a renderable result. */
+  evaluate(options?: BooleanRenderOptions): BooleanResult {
+    let kernelResult: KernelBooleanResult;
+    try {
+      kernelResult = this.graph.evaluate();
+    } catch (error) {
+      throw parseBooleanError(error);
+    }
+    return createBooleanResult(kernelResult, options);
+  }
+
+  lastEvaluation(): BooleanGraphEvaluation {
+    return JSON.parse(this.graph.lastEvaluationJson());
+  }
+
+  dispose() {
+    this.graph.free();
+  }
+}
+
+/**
+ * Splits a BRep into one BRep per separate solid (an outer shell plus the
+ * voids inside it), followed by one per open shell. Use it on boolean results
+ * whose report lists more than one lump volume.
+ */
+export function splitLumps(operand: BooleanOperand): Record<string, unknown>[] {
+  const splitExport = (OGKernel as Record<string, unknown>).splitLumps;
+  if (typeof splitExport !== "function") {
+    throw new Error(
+      "splitLumps is not available in the loaded wasm package. Rebuild opengeometry wasm bindings."
+    );
+  }
+  let lumpsJson: string;
+  try {
+    lumpsJson = (splitExport as (brepSerialized: string) => string)(
+      resolveOperandSerialized(operand)
+    );
+  } catch (error) {
+    throw parseBooleanError(error);
+  }
+  return JSON.parse(lumpsJson);
+}
+
 /**
  * Normalizes mixed wrapper/raw operands, invokes the wasm boolean export, and
  * returns the renderable result wrapper.
@@ -496,6 +654,8 @@ function serializeKernelOptions(options?: BooleanKernelOptions) {
   return JSON.stringify({
     tolerance: options.tolerance,
     merge_coplanar_faces: options.mergeCoplanarFaces,
+    backend: options.backend,
+    resolve_coincidence: options.resolveCoincidence,
   });
 }
 
@@ -5,3 +5,4 @@ export * from "./boolean";
 export * from "./boolean-errors";
 export * from "./extrude";
 export * from "./offset-regions";
+export * from "./region-boolean";
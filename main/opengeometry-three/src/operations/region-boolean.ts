@@ -0,0 +1,44 @@
+import * as OGKernel from "../../../opengeometry/pkg/opengeometry";
+import type { OffsetRegion } from "./offset-regions";
+
+/** Set operation applied by `regionBoolean`. `Difference` is `a` minus `b`. */
+export type RegionBooleanOperation = "Union" | "Intersection" | "Difference" | "Xor";
+
+/** A polygon with holes on a work plane; offset regions fit as-is. */
+export type PlaneRegion = OffsetRegion;
+
+type KernelRegionBoolean = (
+  plane: OGKernel.WorkPlane,
+  aJson: string,
+  bJson: string,
+  operation: string,
+) => { regionsSerialized: string };
+
+/**
+ * Union / intersection / difference / xor of two region sets (polygons with
+ * holes) lying on `plane` — floor zones, slab openings, on any level or sloped
+ * plane. Input winding is free and input regions may overlap. Returns every
+ * disjoint resulting region, outers CCW about the plane normal and holes CW
+ * (on a Y-up plan plane: CW-outer / CCW-hole, as `offsetPolylineRegions`
+ * returns); EMPTY when nothing remains. THROWS when a point lies off the plane.
+ */
+export function regionBoolean(
+  plane: OGKernel.WorkPlane,
+  a: PlaneRegion[],
+  b: PlaneRegion[],
+  operation: RegionBooleanOperation,
+): PlaneRegion[] {
+  const buildExport = (OGKernel as Record<string, unknown>).regionBoolean;
+  if (typeof buildExport !== "function") {
+    throw new Error(
+      "regionBoolean is not available in the loaded wasm package. Rebuild opengeometry wasm bindings.",
+    );
+  }
+  const result = (buildExport as KernelRegionBoolean)(
+    plane,
+    JSON.stringify(a),
+    JSON.stringify(b),
+    operation,
+  );
+  return JSON.parse(result.regionsSerialized);
+}
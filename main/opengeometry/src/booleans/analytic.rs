@@ -0,0 +1,381 @@
+//! Recovers analytic geometry on boolean output (debt items D1/D4).
+//!
+//! The mesh boolean works on triangles, so every face it emits is planar and
+//! every edge straight. Afterwards each output face cut from an operand face
+//! (see `booleans::provenance`) gets that face's plane, cylinder or sphere
+//! back, and each edge separating two different surfaces whose ends lie on a
+//! circle, and which spans no more of it than one facet, gets that exact
+//! circle — either one carried by an operand edge or the section of a
+//! cylinder cut square to its axis, or of a sphere, by a plane.
+
+use std::collections::HashMap;
+
+use crate::booleans::provenance::{FaceTrace, PLANE_ALIGNMENT_COS};
+use crate::booleans::solid::Vec3f;
+use crate::brep::{Brep, CurveGeometry, SurfaceGeometry};
+
+/// Relative slack on radii, so large curved surfaces are not held to an
+/// absolute tolerance finer than their tessellation round-off.
+const RELATIVE_RADIUS_TOLERANCE: f64 = 1.0e-4;
+/// Widest facet a derived section circle may span: primitives tessellate
+/// circles with at least three segments.
+const MAX_FACET_ANGLE: f64 = 2.0 * std::f64::consts::FRAC_PI_3;
+
+/// Re-attaches operand surfaces to output faces and exact circles to output
+/// edges. `trace` maps `brep`'s faces to faces of `inputs`. Faces keep the
+/// plane the rebuild assigned when they trace to nothing; edges keep `None`
+/// (a straight segment).
+pub(crate) fn restore_analytic_geometry(
+    brep: &mut Brep,
+    trace: &FaceTrace,
+    inputs: &[&Brep],
+    tolerance: f64,
+) {
+    restore_surfaces(brep, trace);
+    restore_curves(brep, inputs, tolerance);
+}
+
+/// Gives each output face the surface of the operand face it was cut from.
+/// Every triangle the mesh boolean emits is a piece of one operand facet, so
+/// this also recovers partial facets whose cut vertices lie off the exact
+/// curved surface.
+fn restore_surfaces(brep: &mut Brep, trace: &FaceTrace) {
+    for index in 0..brep.faces.len() {
+        let Some(source) = trace.source(index) else {
+            continue;
+        };
+        let face = &mut brep.faces[index];
+        face.surface = Some(match &source.surface {
+            // The operand's plane, oriented the way this face now faces.
+            SurfaceGeometry::Plane { origin, .. } => SurfaceGeometry::Plane {
+                origin: *origin,
+                normal: face.normal,
+            },
+            surface => surface.clone(),
+        });
+    }
+}
+
+/// An edge only takes a circle when both its ends lie on it and it spans no
+/// more of it than one facet, so chords across the circle stay straight.
+fn restore_curves(brep: &mut Brep, inputs: &[&Brep], tolerance: f64) {
+    // Each operand circle with the widest facet any operand edge spans on it.
+    let mut seen: HashMap<Vec<u64>, usize> = HashMap::new();
+    let mut circles: Vec<(&CurveGeometry, f64)> = Vec::new();
+    for input in inputs {
+        for edge in &input.edges {
+            let Some(circle @ CurveGeometry::Circle { .. }) = edge.curve.as_ref() else {
+                continue;
+            };
+            let Some((from, to)) = input.get_edge_endpoints(edge.id) else {
+                continue;
+            };
+            let span = subtended_angle(
+                circle,
+                Vec3f::from_vector3(&input.vertices[from as usize].position),
+                Vec3f::from_vector3(&input.vertices[to as usize].position),
+            );
+            match seen.get(&curve_key(circle)) {
+                Some(&index) => circles[index].1 = circles[index].1.max(span),
+                None => {
+                    seen.insert(curve_key(circle), circles.len());
+                    circles.push((circle, span));
+                }
+            }
+        }
+    }
+    let face_index: HashMap<u32, usize> = brep
+        .faces
+        .iter()
+        .enumerate()
+        .map(|(index, face)| (face.id, index))
+        .collect();
+
+    for index in 0..brep.edges.len() {
+        let edge = &brep.edges[index];
+        let Some((from, to)) = brep.get_edge_endpoints(edge.id) else {
+            continue;
+        };
+        let surface_of = |halfedge: Option<u32>| {
+            let face = brep.halfedges.get(halfedge? as usize)?.face?;
+            brep.faces[*face_index.get(&face)?].surface.as_ref()
+        };
+        let twin = edge
+            .twin_halfedge
+            .or_else(|| brep.halfedges.get(edge.halfedge as usize)?.twin);
+        let (left, right) = (surface_of(Some(edge.halfedge)), surface_of(twin));
+        if let (Some(left), Some(right)) = (left, right) {
+            // Facet edges and triangulation diagonals inside one surface.
+            if same_surface(left, right, tolerance) {
+                continue;
+            }
+        }
+
+        let ends = [
+            Vec3f::from_vector3(&brep.vertices[from as usize].position),
+            Vec3f::from_vector3(&brep.vertices[to as usize].position),
+        ];
+        let on = |circle: &CurveGeometry, max_span: f64| {
+            ends.iter().all(|p| lies_on_circle(circle, *p, tolerance))
+                && subtended_angle(circle, ends[0], ends[1])
+                    <= max_span + angle_tolerance(circle, tolerance)
+        };
+        if let Some((circle, _)) = circles.iter().find(|(circle, span)| on(circle, *span)) {
+            brep.edges[index].curve = Some((*circle).clone());
+            continue;
+        }
+        let derived = match (left, right) {
+            (Some(plane @ SurfaceGeometry::Plane { .. }), Some(other))
+            | (Some(other), Some(plane @ SurfaceGeometry::Plane { .. })) => {
+                plane_section_circle(plane, other, ends[0], tolerance)
+            }
+            _ => None,
+        };
+        if let Some(circle) = derived.filter(|circle| on(circle, MAX_FACET_ANGLE)) {
+            let edge = &mut brep.edges[index];
+            edge.curve = Some(circle);
+            edge.tolerance = Some(tolerance);
+        }
+    }
+}
+
+/// The circle where `plane` (through `point`) cuts a cylinder square to its
+/// axis, or cuts a sphere anywhere.
+fn plane_section_circle(
+    plane: &SurfaceGeometry,
+    other: &SurfaceGeometry,
+    point: Vec3f,
+    tolerance: f64,
+) -> Option<CurveGeometry> {
+    let SurfaceGeometry::Plane { normal, .. } = plane else {
+        return None;
+    };
+    let normal = Vec3f::from_vector3(normal).normalized(0.0)?;
+    let (center, axis, x_axis, radius) = match other {
+        SurfaceGeometry::Cylinder {
+            origin,
+            axis,
+            ref_direction,
+            radius,
+            ..
+        } => {
+            let (origin, axis) = (Vec3f::from_vector3(origin), Vec3f::from_vector3(axis));
+            let axis = axis.normalized(0.0)?;
+            let height = point.sub(origin).dot(axis);
+            (
+                origin.add(axis.scale(height)),
+                axis,
+                Vec3f::from_vector3(ref_direction),
+                *radius,
+            )
+        }
+        SurfaceGeometry::Sphere { center, radius } => {
+            let center = Vec3f::from_vector3(center);
+            let offset = point.sub(center).dot(normal);
+            let reference = if normal.x.abs() < 0.9 {
+                Vec3f::new(1.0, 0.0, 0.0)
+            } else {
+                Vec3f::new(0.0, 1.0, 0.0)
+            };
+            (
+                center.add(normal.scale(offset)),
+                normal,
+                normal.cross(reference).cross(normal),
+                (radius * radius - offset * offset).max(0.0).sqrt(),
+            )
+        }
+        SurfaceGeometry::Plane { .. } => return None,
+    };
+    if axis.dot(normal).abs() < PLANE_ALIGNMENT_COS || radius <= tolerance {
+        return None;
+    }
+    // Keep the operand's angle reference, projected into the circle plane.
+    let x_axis = x_axis
+        .sub(axis.scale(x_axis.dot(axis)))
+        .normalized(0.0)
+        .or_else(|| point.sub(center).normalized(tolerance))?;
+    Some(CurveGeometry::Circle {
+        center: center.to_vector3(),
+        normal: axis.to_vector3(),
+        x_axis: x_axis.to_vector3(),
+        radius,
+        start_angle: 0.0,
+        end_angle: 2.0 * std::f64::consts::PI,
+    })
+}
+
+fn radius_tolerance(radius: f64, tolerance: f64) -> f64 {
+    tolerance.max(radius.abs() * RELATIVE_RADIUS_TOLERANCE)
+}
+
+/// Radial offset of `point` from an axis through `origin`, with the axial
+/// coordinate. `None` for a degenerate axis.
+fn radial_and_height(point: Vec3f, origin: Vec3f, axis: Vec3f) -> Option<(Vec3f, f64)> {
+    let axis = axis.normalized(0.0)?;
+    let relative = point.sub(origin);
+    let height = relative.dot(axis);
+    Some((relative.sub(axis.scale(height)), height))
+}
+
+fn lies_on_circle(circle: &CurveGeometry, point: Vec3f, tolerance: f64) -> bool {
+    let CurveGeometry::Circle {
+        center,
+        normal,
+        radius,
+        ..
+    } = circle
+    else {
+        return false;
+    };
+    radial_and_height(
+        point,
+        Vec3f::from_vector3(center),
+        Vec3f::from_vector3(normal),
+    )
+    .is_some_and(|(radial, height)| {
+        height.abs() <= tolerance
+            && (radial.norm() - radius).abs() <= radius_tolerance(*radius, tolerance)
+    })
+}
+
+/// Angle the chord `a`–`b` spans about the centre of `circle`.
+fn subtended_angle(circle: &CurveGeometry, a: Vec3f, b: Vec3f) -> f64 {
+    let CurveGeometry::Circle { center, normal, .. } = circle else {
+        return 0.0;
+    };
+    let (center, axis) = (Vec3f::from_vector3(center), Vec3f::from_vector3(normal));
+    let (Some((a, _)), Some((b, _))) = (
+        radial_and_height(a, center, axis),
+        radial_and_height(b, center, axis),
+    ) else {
+        return 0.0;
+    };
+    a.cross(b).norm().atan2(a.dot(b))
+}
+
+/// Angular slack matching `tolerance` at the circle's radius.
+fn angle_tolerance(circle: &CurveGeometry, tolerance: f64) -> f64 {
+    match circle {
+        CurveGeometry::Circle { radius, .. } if *radius > 0.0 => {
+            radius_tolerance(*radius, tolerance) / radius
+        }
+        _ => 0.0,
+    }
+}
+
+/// Whether two face surfaces are the same surface, so the edge between the
+/// faces is a tessellation edge rather than a feature.
+fn same_surface(a: &SurfaceGeometry, b: &SurfaceGeometry, tolerance: f64) -> bool {
+    match (a, b) {
+        (
+            SurfaceGeometry::Plane {
+                origin: origin_a,
+                normal: normal_a,
+            },
+            SurfaceGeometry::Plane {
+                origin: origin_b,
+                normal: normal_b,
+            },
+        ) => {
+            let (Some(normal_a), Some(normal_b)) = (
+                Vec3f::from_vector3(normal_a).normalized(0.0),
+                Vec3f::from_vector3(normal_b).normalized(0.0),
+            ) else {
+                return false;
+            };
+            normal_a.dot(normal_b).abs() >= PLANE_ALIGNMENT_COS
+                && Vec3f::from_vector3(origin_b)
+                    .sub(Vec3f::from_vector3(origin_a))
+                    .dot(normal_a)
+                    .abs()
+                    <= tolerance
+        }
+        (SurfaceGeometry::Plane { .. }, _) | (_, SurfaceGeometry::Plane { .. }) => false,
+        _ => surface_key(a, tolerance) == surface_key(b, tolerance),
+    }
+}
+
+/// Identity of a surface for deduplication. Planes are keyed by their
+/// quantised normal and offset, since every rebuilt face carries its own
+/// origin; curved surfaces are cloned from operands and compare exactly.
+fn surface_key(surface: &SurfaceGeometry, tolerance: f64) -> Vec<u64> {
+    match surface {
+        SurfaceGeometry::Plane { origin, normal } => {
+            let normal = Vec3f::from_vector3(normal)
+                .normalized(0.0)
+                .unwrap_or(Vec3f::new(0.0, 0.0, 0.0));
+            let offset = Vec3f::from_vector3(origin).dot(normal);
+            let quantize = |value: f64, step: f64| (value / step).round() as i64 as u64;
+            vec![
+                0,
+                quantize(normal.x, 1.0e-9),
+                quantize(normal.y, 1.0e-9),
+                quantize(normal.z, 1.0e-9),
+                quantize(offset, tolerance.max(f64::MIN_POSITIVE)),
+            ]
+        }
+        SurfaceGeometry::Cylinder {
+            origin,
+            axis,
+            ref_direction,
+            radius,
+            height,
+        } => tagged_bits(
+            1,
+            &[
+                origin.x,
+                origin.y,
+                origin.z,
+                axis.x,
+                axis.y,
+                axis.z,
+                ref_direction.x,
+                ref_direction.y,
+                ref_direction.z,
+                *radius,
+                *height,
+            ],
+        ),
+        SurfaceGeometry::Sphere { center, radius } => {
+            tagged_bits(3, &[center.x, center.y, center.z, *radius])
+        }
+    }
+}
+
+fn curve_key(curve: &CurveGeometry) -> Vec<u64> {
+    match curve {
+        CurveGeometry::Line { start, end } => {
+            tagged_bits(0, &[start.x, start.y, start.z, end.x, end.y, end.z])
+        }
+        CurveGeometry::Circle {
+            center,
+            normal,
+            x_axis,
+            radius,
+            start_angle,
+            end_angle,
+        } => tagged_bits(
+            1,
+            &[
+                center.x,
+                center.y,
+                center.z,
+                normal.x,
+                normal.y,
+                normal.z,
+                x_axis.x,
+                x_axis.y,
+                x_axis.z,
+                *radius,
+                *start_angle,
+                *end_angle,
+            ],
+        ),
+    }
+}
+
+fn tagged_bits(tag: u64, values: &[f64]) -> Vec<u64> {
+    std::iter::once(tag)
+        .chain(values.iter().map(|value| value.to_bits()))
+        .collect()
+}
@@ -0,0 +1,388 @@
+//! Opt-in cutter overshoot for subtraction (`BooleanOptions::resolve_coincidence`).
+//!
+//! Openings are usually authored flush with the wall: the cutter's front and
+//! back faces lie on the host's faces. The mesh backend's snap window turns
+//! such pairs into `CoincidentFaces`, and even the native backend leaves a
+//! zero-thickness skin when the faces are a hair apart. Before the boolean,
+//! each planar cutter face lying on a same-facing host face is pushed outward
+//! along its normal so it clears the host face by a computed overshoot.
+//!
+//! The push moves the face's vertices, so the neighbouring faces must stay on
+//! their planes (or cylinders). That holds for prisms swept along the pushed
+//! normal — boxes, extruded profiles, drilled cylinders. A cutter whose faces
+//! would be bent is left untouched and reports no adjustment.
+
+use std::collections::HashMap;
+
+use crate::booleans::provenance::PLANE_ALIGNMENT_COS;
+use crate::booleans::solid::Vec3f;
+use crate::booleans::types::{BooleanBackend, CoincidenceAdjustment};
+use crate::brep::{Brep, CurveGeometry, Face, SurfaceGeometry};
+
+/// Returns `cutter` with faces lying on host faces pushed clear of them, plus
+/// one adjustment per pushed face. `operand` is the cutter's operand number
+/// in the report (`1` for a single cutter).
+pub(crate) fn resolve_cutter_coincidence(
+    host: &Brep,
+    cutter: &Brep,
+    operand: usize,
+    tolerance: f64,
+    backend: BooleanBackend,
+) -> (Brep, Vec<CoincidenceAdjustment>) {
+    let window = coincidence_window(tolerance, backend);
+    let host_planes: Vec<FacePlane> = host
+        .faces
+        .iter()
+        .filter_map(|face| FacePlane::of(host, face))
+        .collect();
+
+    // Pushed cutter faces by index: outward normal and push distance.
+    let mut pushes: HashMap<usize, (Vec3f, f64)> = HashMap::new();
+    let mut adjustments = Vec::new();
+    for (index, face) in cutter.faces.iter().enumerate() {
+        let Some(plane) = FacePlane::of(cutter, face) else {
+            continue;
+        };
+        let mut best: Option<(u32, f64)> = None;
+        for host_plane in &host_planes {
+            if plane.normal.dot(host_plane.normal) < PLANE_ALIGNMENT_COS {
+                continue;
+            }
+            // How far the cutter face already stands outside the host face.
+            let standoff = plane.offset - host_plane.offset;
+            if standoff.abs() > window || !plane.overlaps(host_plane, window) {
+                continue;
+            }
+            let push = overshoot(host, host_plane.normal, tolerance, backend) - standoff;
+            if best.is_none_or(|(_, best_push)| push > best_push) {
+                best = Some((host_plane.face_id, push));
+            }
+        }
+        if let Some((host_face_id, push)) = best {
+            pushes.insert(index, (plane.normal, push));
+            adjustments.push(CoincidenceAdjustment {
+                operand,
+                face_id: face.id,
+                host_face_id,
+                offset: push,
+            });
+        }
+    }
+
+    if pushes.is_empty() {
+        return (cutter.clone(), Vec::new());
+    }
+    match pushed_cutter(cutter, &pushes, tolerance) {
+        Some(adjusted) => (adjusted, adjustments),
+        None => (cutter.clone(), Vec::new()),
+    }
+}
+
+/// Distance within which a cutter face counts as lying on a host face: the
+/// mesh backend's snap window (see `solid::detect_coincident_faces`), or the
+/// working tolerance on the native backend.
+fn coincidence_window(tolerance: f64, backend: BooleanBackend) -> f64 {
+    match backend {
+        BooleanBackend::Mesh => (tolerance * 1000.0).max(1.0e-4),
+        BooleanBackend::Native => tolerance,
+    }
+}
+
+/// How far a pushed face clears the host face: 5% of the host's thickness
+/// along `normal`, and at least 0.01 model units on the mesh backend (its
+/// snap window reaches ~1 cm) or ten tolerances on the native one.
+fn overshoot(host: &Brep, normal: Vec3f, tolerance: f64, backend: BooleanBackend) -> f64 {
+    let (min, max) =
+        host.vertices
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), vertex| {
+                let along = normal.dot(Vec3f::from_vector3(&vertex.position));
+                (min.min(along), max.max(along))
+            });
+    let floor = match backend {
+        BooleanBackend::Mesh => 0.01,
+        BooleanBackend::Native => tolerance * 10.0,
+    };
+    ((max - min) * 0.05).max(floor)
+}
+
+struct FacePlane {
+    face_id: u32,
+    normal: Vec3f,
+    offset: f64,
+    min: Vec3f,
+    max: Vec3f,
+}
+
+impl FacePlane {
+    /// Outward plane and bounds of a planar face; `None` for curved faces.
+    fn of(brep: &Brep, face: &Face) -> Option<Self> {
+        if !matches!(face.surface, None | Some(SurfaceGeometry::Plane { .. })) {
+            return None;
+        }
+        let normal = Vec3f::from_vector3(&face.normal).normalized(1.0e-12)?;
+        let positions: Vec<Vec3f> = face_vertex_ids(brep, face)
+            .into_iter()
+            .map(|id| Vec3f::from_vector3(&brep.vertices[id as usize].position))
+            .collect();
+        let first = *positions.first()?;
+        let (min, max) = positions.iter().fold((first, first), |(min, max), p| {
+            (
+                Vec3f::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+                Vec3f::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+            )
+        });
+        Some(Self {
+            face_id: face.id,
+            normal,
+            offset: normal.dot(first),
+            min,
+            max,
+        })
+    }
+
+    /// Bounding-box overlap, the same cheap footprint test the mesh backend's
+    /// coincident-face diagnostic uses.
+    fn overlaps(&self, other: &Self, slack: f64) -> bool {
+        self.min.x <= other.max.x + slack
+            && other.min.x <= self.max.x + slack
+            && self.min.y <= other.max.y + slack
+            && other.min.y <= self.max.y + slack
+            && self.min.z <= other.max.z + slack
+            && other.min.z <= self.max.z + slack
+    }
+}
+
+fn face_vertex_ids(brep: &Brep, face: &Face) -> Vec<u32> {
+    std::iter::once(face.outer_loop)
+        .chain(face.inner_loops.iter().copied())
+        .flat_map(|loop_id| brep.get_loop_vertex_indices(loop_id))
+        .collect()
+}
+
+/// Applies `pushes` to a copy of `cutter`, or `None` when some face would
+/// leave its plane or cylinder.
+fn pushed_cutter(
+    cutter: &Brep,
+    pushes: &HashMap<usize, (Vec3f, f64)>,
+    tolerance: f64,
+) -> Option<Brep> {
+    let zero = Vec3f::new(0.0, 0.0, 0.0);
+    let mut moves = vec![zero; cutter.vertices.len()];
+    let mut moved_along: Vec<Vec<Vec3f>> = vec![Vec::new(); cutter.vertices.len()];
+    for (&index, &(normal, push)) in pushes {
+        for id in face_vertex_ids(cutter, &cutter.faces[index]) {
+            let id = id as usize;
+            // Two coplanar pushed faces share vertices; move those once.
+            if moved_along[id]
+                .iter()
+                .any(|done| done.dot(normal) >= PLANE_ALIGNMENT_COS)
+            {
+                continue;
+            }
+            moved_along[id].push(normal);
+            moves[id] = moves[id].add(normal.scale(push));
+        }
+    }
+
+    let slack = tolerance.max(1.0e-9);
+    let mut adjusted = cutter.clone();
+    for (index, face) in cutter.faces.iter().enumerate() {
+        let ids = face_vertex_ids(cutter, face);
+        if ids.iter().all(|&id| moves[id as usize].norm() <= slack) {
+            continue;
+        }
+        match &face.surface {
+            None | Some(SurfaceGeometry::Plane { .. }) => {
+                let normal = Vec3f::from_vector3(&face.normal).normalized(1.0e-12)?;
+                let push = pushes.get(&index).map_or(0.0, |&(_, push)| push);
+                if ids
+                    .iter()
+                    .any(|&id| (moves[id as usize].dot(normal) - push).abs() > slack)
+                {
+                    return None;
+                }
+                if let Some(SurfaceGeometry::Plane { origin, .. }) =
+                    &mut adjusted.faces[index].surface
+                {
+                    *origin = Vec3f::from_vector3(origin)
+                        .add(normal.scale(push))
+                        .to_vector3();
+                }
+            }
+            Some(SurfaceGeometry::Cylinder {
+                origin,
+                axis,
+                height,
+                ..
+            }) => {
+                let axis = Vec3f::from_vector3(axis).normalized(1.0e-12)?;
+                let origin = Vec3f::from_vector3(origin);
+                let (mut low, mut high) = (0.0_f64, *height);
+                for &id in &ids {
+                    let moved = moves[id as usize];
+                    if moved.cross(axis).norm() > slack {
+                        return None;
+                    }
+                    let position = Vec3f::from_vector3(&cutter.vertices[id as usize].position);
+                    let along = position.add(moved).sub(origin).dot(axis);
+                    low = low.min(along);
+                    high = high.max(along);
+                }
+                if let Some(SurfaceGeometry::Cylinder {
+                    origin: o,
+                    height: h,
+                    ..
+                }) = &mut adjusted.faces[index].surface
+                {
+                    *o = origin.add(axis.scale(low)).to_vector3();
+                    *h = high - low;
+                }
+            }
+            Some(_) => return None,
+        }
+    }
+
+    for edge in &mut adjusted.edges {
+        let Some(curve) = &mut edge.curve else {
+            continue;
+        };
+        let (from, to) = cutter.get_edge_endpoints(edge.id)?;
+        let (from_move, to_move) = (moves[from as usize], moves[to as usize]);
+        match curve {
+            CurveGeometry::Line { start, end } => {
+                let start_position = Vec3f::from_vector3(start);
+                let from_position = Vec3f::from_vector3(&cutter.vertices[from as usize].position);
+                let (start_move, end_move) = if start_position.sub(from_position).norm() <= slack {
+                    (from_move, to_move)
+                } else {
+                    (to_move, from_move)
+                };
+                *start = start_position.add(start_move).to_vector3();
+                *end = Vec3f::from_vector3(end).add(end_move).to_vector3();
+            }
+            CurveGeometry::Circle { center, .. } => {
+                if from_move.sub(to_move).norm() > slack {
+                    return None;
+                }
+                *center = Vec3f::from_vector3(center).add(from_move).to_vector3();
+            }
+        }
+    }
+
+    for (vertex, moved) in adjusted.vertices.iter_mut().zip(&moves) {
+        vertex.position = Vec3f::from_vector3(&vertex.position)
+            .add(*moved)
+            .to_vector3();
+    }
+    Some(adjusted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::booleans::test_support::{cuboid, volume};
+    use crate::booleans::types::BooleanOptions;
+    use crate::booleans::{boolean_subtraction, boolean_subtraction_many};
+    use crate::primitives::cylinder::OGCylinder;
+    use openmaths::Vector3;
+
+    fn options(backend: BooleanBackend, resolve_coincidence: bool) -> BooleanOptions {
+        BooleanOptions {
+            backend,
+            resolve_coincidence,
+            ..BooleanOptions::default()
+        }
+    }
+
+    #[test]
+    fn flush_window_cuts_through_on_both_backends() {
+        // 4 x 3 x 0.2 wall. The mesh window stops 0.2 mm short of each wall
+        // face, inside the snap window; the native one is exactly flush.
+        let wall = cuboid(Vector3::new(0.0, 1.5, 0.0), 4.0, 3.0, 0.2);
+        for (backend, depth) in [
+            (BooleanBackend::Mesh, 0.1996),
+            (BooleanBackend::Native, 0.2),
+        ] {
+            let window = cuboid(Vector3::new(0.0, 1.5, 0.0), 1.0, 1.2, depth);
+            let output = boolean_subtraction(&wall, &window, options(backend, true))
+                .unwrap_or_else(|error| panic!("{:?}: {}", backend, error));
+            assert!(
+                (volume(&output.brep) - (2.4 - 1.2 * 0.2)).abs() < 1.0e-6,
+                "{:?} leaves the opening open",
+                backend
+            );
+            let adjustments = &output.report.coincidence_adjustments;
+            assert_eq!(adjustments.len(), 2, "{:?}", backend);
+            assert!(adjustments.iter().all(|adjustment| adjustment.operand == 1));
+            assert!(adjustments.iter().all(|adjustment| adjustment.offset > 0.0));
+        }
+
+        let window = cuboid(Vector3::new(0.0, 1.5, 0.0), 1.0, 1.2, 0.2);
+        let output =
+            boolean_subtraction(&wall, &window, options(BooleanBackend::Native, false)).unwrap();
+        assert!(output.report.coincidence_adjustments.is_empty());
+    }
+
+    #[test]
+    fn flush_cylinder_keeps_its_surface_and_numbers_cutters() {
+        let slab = cuboid(Vector3::new(0.0, 0.1, 0.0), 2.0, 0.2, 2.0);
+        let mut cylinder = OGCylinder::new("coincidence-test".to_string());
+        cylinder
+            .set_config(
+                Vector3::new(0.5, 0.1, 0.0),
+                0.2,
+                0.2,
+                std::f64::consts::TAU,
+                24,
+            )
+            .unwrap();
+        let hole = cylinder.world_brep();
+        let (low, high) = hole.bounds().unwrap();
+        assert!(low.y.abs() < 1.0e-9 && (high.y - 0.2).abs() < 1.0e-9);
+        let notch = cuboid(Vector3::new(-0.5, 0.1, 0.0), 0.4, 0.2, 0.4);
+
+        let output =
+            boolean_subtraction_many(&slab, &[notch, hole], options(BooleanBackend::Native, true))
+                .expect("flush cutters subtract");
+        let operands: Vec<usize> = output
+            .report
+            .coincidence_adjustments
+            .iter()
+            .map(|adjustment| adjustment.operand)
+            .collect();
+        assert_eq!(operands, vec![1, 1, 2, 2]);
+        assert!(output
+            .brep
+            .faces
+            .iter()
+            .any(|face| matches!(face.surface, Some(SurfaceGeometry::Cylinder { .. }))));
+        // The hole is a 24-gon prism.
+        let hole_area = 12.0 * 0.2 * 0.2 * (std::f64::consts::TAU / 24.0).sin();
+        let expected = 0.8 - 0.4 * 0.4 * 0.2 - hole_area * 0.2;
+        assert!((volume(&output.brep) - expected).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn cutters_that_would_bend_are_left_alone() {
+        let wall = cuboid(Vector3::new(0.0, 1.5, 0.0), 4.0, 3.0, 0.2);
+        let mut splayed = cuboid(Vector3::new(0.0, 1.5, 0.0), 1.0, 1.2, 0.2);
+        // Widen the front face so the side faces are no longer
+        // perpendicular to it.
+        for vertex in &mut splayed.vertices {
+            if vertex.position.z > 0.0 {
+                vertex.position.x *= 1.5;
+            }
+        }
+        splayed.recompute_face_normals();
+
+        let (resolved, adjustments) =
+            resolve_cutter_coincidence(&wall, &splayed, 1, 1.0e-9, BooleanBackend::Native);
+        assert!(adjustments.is_empty());
+        assert!(resolved
+            .vertices
+            .iter()
+            .zip(&splayed.vertices)
+            .all(|(a, b)| a.position.x == b.position.x && a.position.z == b.position.z));
+    }
+}
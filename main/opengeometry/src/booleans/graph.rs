@@ -0,0 +1,633 @@
+//! Incremental subtraction graph: one host solid and named cutters.
+//!
+//! Cutters whose bounds overlap are grouped into clusters and unioned; a
+//! cluster's cavity inside the host is cached under the revisions of its
+//! cutters. Each host face keeps its clipped pieces, cached under the
+//! clusters that reach it. Editing a cutter therefore re-runs the CSG only
+//! for its old and new cluster and the host faces they touch. Evaluation
+//! always runs on the native kernel.
+//!
+//! The output BRep is still rebuilt from the cached pieces on every
+//! evaluation, and its analytic geometry, provenance and lump volumes are
+//! recomputed over the whole result. Each output face is traced only against
+//! the operands its pieces came from: the host for host-face pieces, the
+//! cluster's cutters for cavity pieces. Tracing therefore grows with cluster
+//! size rather than with the total cutter count.
+
+use std::collections::{BTreeMap, HashMap};
+
+use openmaths::Vector3;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use super::{is_closed_solid_operand, parse_options_json, OGBooleanResult};
+use crate::booleans::analytic::restore_analytic_geometry;
+use crate::booleans::coincidence::resolve_cutter_coincidence;
+use crate::booleans::error::{BooleanError, BooleanErrorKind};
+use crate::booleans::lumps::lump_volumes;
+use crate::booleans::native::{
+    brep_to_native_polygons, execute_native_boolean, face_to_native_polygons, CutterTree,
+    SubtractionHost,
+};
+use crate::booleans::provenance::FaceTrace;
+use crate::booleans::rebuild::build_brep_from_polygon_groups;
+use crate::booleans::solid::{Polygon3, Vec3f};
+use crate::booleans::types::{
+    BooleanBackend, BooleanOperandKind, BooleanOperation, BooleanOptions, BooleanOutput,
+    BooleanReport, CoincidenceAdjustment,
+};
+use crate::brep::Brep;
+use crate::spatial::placement::Placement3D;
+
+/// How much work the last `BooleanGraph::evaluate` did.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct GraphEvaluation {
+    pub cluster_count: usize,
+    /// Clusters whose union and cavity had to be recomputed.
+    pub clusters_evaluated: usize,
+    pub host_faces_evaluated: usize,
+    pub host_faces_reused: usize,
+}
+
+/// `host - {cutters}` kept up to date across cutter edits.
+pub struct BooleanGraph {
+    host: Brep,
+    host_faces: Vec<Vec<Polygon3>>,
+    host_face_bounds: Vec<Bounds>,
+    host_triangle_count: usize,
+    subtraction_host: SubtractionHost,
+    /// In insertion order, which is also the provenance operand order.
+    cutters: Vec<GraphCutter>,
+    tolerance: f64,
+    resolve_coincidence: bool,
+    next_revision: u64,
+    clusters: HashMap<Vec<u64>, ClusterPart>,
+    /// Per host face: the clusters that reached it and the pieces left.
+    face_pieces: Vec<Option<(ClusterKeys, Vec<Polygon3>)>>,
+    last_evaluation: GraphEvaluation,
+}
+
+/// Sorted cutter revisions of each cluster reaching a host face.
+type ClusterKeys = Vec<Vec<u64>>;
+
+struct GraphCutter {
+    name: String,
+    /// The cutter as given; `brep` is what is subtracted, after
+    /// `BooleanOptions::resolve_coincidence`.
+    source: Brep,
+    brep: Brep,
+    adjustments: Vec<CoincidenceAdjustment>,
+    polygons: Vec<Polygon3>,
+    triangle_count: usize,
+    bounds: Bounds,
+    /// Unique across the graph's lifetime; bumped on every edit.
+    revision: u64,
+}
+
+struct ClusterPart {
+    tree: CutterTree,
+    cavity: Vec<Polygon3>,
+    bounds: Bounds,
+}
+
+impl BooleanGraph {
+    /// Prepares `host`, which must be a closed solid. The working tolerance
+    /// is resolved once, from the host alone.
+    pub fn new(host: Brep, options: BooleanOptions) -> Result<Self, BooleanError> {
+        host.validate_topology().map_err(BooleanError::from)?;
+        if !is_closed_solid_operand(&host) {
+            return Err(BooleanError::new(
+                BooleanErrorKind::UnsupportedOperandKind,
+                "Boolean graph hosts must be closed solids",
+            ));
+        }
+
+        let resolve_coincidence = options.resolve_coincidence;
+        let tolerance = BooleanOptions {
+            backend: BooleanBackend::Native,
+            ..options
+        }
+        .working_tolerance_many(&[&host]);
+        let mut host_faces = Vec::with_capacity(host.faces.len());
+        let mut host_triangle_count = 0;
+        for face in &host.faces {
+            let (polygons, triangles) = face_to_native_polygons(&host, face, tolerance)?;
+            host_triangle_count += triangles;
+            host_faces.push(polygons);
+        }
+        let host_face_bounds = host_faces
+            .iter()
+            .map(|polygons| Bounds::of(polygons).expanded(tolerance))
+            .collect();
+        let subtraction_host = SubtractionHost::new(host_faces.concat(), tolerance);
+
+        Ok(Self {
+            face_pieces: vec![None; host.faces.len()],
+            host,
+            host_faces,
+            host_face_bounds,
+            host_triangle_count,
+            subtraction_host,
+            cutters: Vec::new(),
+            tolerance,
+            resolve_coincidence,
+            next_revision: 0,
+            clusters: HashMap::new(),
+            last_evaluation: GraphEvaluation::default(),
+        })
+    }
+
+    /// Adds a cutter, or replaces the one already called `name`.
+    pub fn set_cutter(&mut self, name: &str, source: Brep) -> Result<(), BooleanError> {
+        source.validate_topology().map_err(BooleanError::from)?;
+        if !is_closed_solid_operand(&source) {
+            return Err(BooleanError::new(
+                BooleanErrorKind::UnsupportedOperandKind,
+                format!("Cutter '{}' is not a closed solid", name),
+            ));
+        }
+        let (brep, adjustments) = if self.resolve_coincidence {
+            // The operand number is filled in by `evaluate`.
+            resolve_cutter_coincidence(
+                &self.host,
+                &source,
+                0,
+                self.tolerance,
+                BooleanBackend::Native,
+            )
+        } else {
+            (source.clone(), Vec::new())
+        };
+        let (polygons, triangle_count) = brep_to_native_polygons(&brep, self.tolerance)?;
+        let cutter = GraphCutter {
+            name: name.to_string(),
+            bounds: Bounds::of(&polygons).expanded(self.tolerance),
+            source,
+            brep,
+            adjustments,
+            polygons,
+            triangle_count,
+            revision: self.next_revision,
+        };
+        self.next_revision += 1;
+
+        match self
+            .cutters
+            .iter_mut()
+            .find(|existing| existing.name == name)
+        {
+            Some(existing) => *existing = cutter,
+            None => self.cutters.push(cutter),
+        }
+        Ok(())
+    }
+
+    /// Removes the cutter called `name`; `false` when there is none.
+    pub fn remove_cutter(&mut self, name: &str) -> bool {
+        let before = self.cutters.len();
+        self.cutters.retain(|cutter| cutter.name != name);
+        before != self.cutters.len()
+    }
+
+    /// Translates the cutter called `name` by `offset`.
+    pub fn move_cutter(&mut self, name: &str, offset: Vector3) -> Result<(), BooleanError> {
+        let Some(cutter) = self.cutters.iter().find(|cutter| cutter.name == name) else {
+            return Err(BooleanError::new(
+                BooleanErrorKind::InvalidOperand,
+                format!("Boolean graph has no cutter '{}'", name),
+            ));
+        };
+        let mut placement = Placement3D::new();
+        placement.set_translation(offset);
+        let moved = cutter.source.transformed(&placement);
+        self.set_cutter(name, moved)
+    }
+
+    pub fn cutter_names(&self) -> Vec<String> {
+        self.cutters
+            .iter()
+            .map(|cutter| cutter.name.clone())
+            .collect()
+    }
+
+    pub fn last_evaluation(&self) -> &GraphEvaluation {
+        &self.last_evaluation
+    }
+
+    /// Brings the result up to date, redoing only what the edits since the
+    /// last call touched.
+    pub fn evaluate(&mut self) -> Result<BooleanOutput, BooleanError> {
+        let mut evaluation = GraphEvaluation::default();
+
+        let mut clusters: Vec<Vec<u64>> = Vec::new();
+        let mut used: HashMap<Vec<u64>, ClusterPart> = HashMap::new();
+        // Provenance operand numbers of each cluster's cutters.
+        let mut cluster_operands: HashMap<Vec<u64>, Vec<usize>> = HashMap::new();
+        for members in self.cluster_members() {
+            let mut key: Vec<u64> = members
+                .iter()
+                .map(|&index| self.cutters[index].revision)
+                .collect();
+            key.sort_unstable();
+            let part = match self.clusters.remove(&key) {
+                Some(part) => part,
+                None => {
+                    evaluation.clusters_evaluated += 1;
+                    self.cluster_part(&members)
+                }
+            };
+            used.insert(key.clone(), part);
+            cluster_operands.insert(
+                key.clone(),
+                members.iter().map(|&index| index + 1).collect(),
+            );
+            clusters.push(key);
+        }
+        clusters.sort();
+        self.clusters = used;
+        evaluation.cluster_count = clusters.len();
+
+        // One group per host face, then one per cluster cavity, each with
+        // the operands its faces can trace to.
+        let mut groups = Vec::with_capacity(self.host_faces.len() + clusters.len());
+        let mut group_operands = Vec::with_capacity(groups.capacity());
+        for (index, bounds) in self.host_face_bounds.iter().enumerate() {
+            let reaching: ClusterKeys = clusters
+                .iter()
+                .filter(|key| self.clusters[*key].bounds.overlaps(bounds))
+                .cloned()
+                .collect();
+            match &self.face_pieces[index] {
+                Some((keys, pieces)) if *keys == reaching => {
+                    evaluation.host_faces_reused += 1;
+                    groups.push(pieces.clone());
+                }
+                _ => {
+                    evaluation.host_faces_evaluated += 1;
+                    let pieces = reaching
+                        .iter()
+                        .fold(self.host_faces[index].clone(), |pieces, key| {
+                            self.clusters[key].tree.clip_host(pieces)
+                        });
+                    groups.push(pieces.clone());
+                    self.face_pieces[index] = Some((reaching, pieces));
+                }
+            }
+            group_operands.push(vec![0]);
+        }
+        for key in &clusters {
+            groups.push(self.clusters[key].cavity.clone());
+            group_operands.push(cluster_operands.remove(key).unwrap_or_default());
+        }
+        self.last_evaluation = evaluation;
+
+        let (mut brep, face_groups) =
+            build_brep_from_polygon_groups(&groups, self.tolerance, true)?;
+        if !brep.faces.is_empty() && !is_closed_solid_operand(&brep) {
+            return Err(BooleanError::new(
+                BooleanErrorKind::TopologyError,
+                "Boolean graph output is not watertight",
+            ));
+        }
+
+        let operands: Vec<&Brep> = std::iter::once(&self.host)
+            .chain(self.cutters.iter().map(|cutter| &cutter.brep))
+            .collect();
+        let trace = FaceTrace::within(&brep, &operands, self.tolerance, |face| {
+            &group_operands[face_groups[face]]
+        });
+        restore_analytic_geometry(&mut brep, &trace, &operands, self.tolerance);
+        let provenance = trace.provenance(&brep, &operands, self.tolerance);
+
+        Ok(BooleanOutput {
+            report: BooleanReport {
+                operation: BooleanOperation::Subtraction,
+                operand_kind: BooleanOperandKind::ClosedSolid,
+                input_face_count: operands.iter().map(|operand| operand.faces.len()).sum(),
+                input_triangle_count: self.host_triangle_count
+                    + self
+                        .cutters
+                        .iter()
+                        .map(|cutter| cutter.triangle_count)
+                        .sum::<usize>(),
+                output_face_count: brep.faces.len(),
+                output_shell_count: brep.shells.len(),
+                empty: brep.faces.is_empty(),
+                lump_volumes: lump_volumes(&brep, self.tolerance)?,
+                coincidence_adjustments: self
+                    .cutters
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(index, cutter)| {
+                        cutter
+                            .adjustments
+                            .iter()
+                            .map(move |adjustment| CoincidenceAdjustment {
+                                operand: index + 1,
+                                ..adjustment.clone()
+                            })
+                    })
+                    .collect(),
+            },
+            brep,
+            provenance,
+        })
+    }
+
+    /// Cutter indices grouped into clusters of transitively overlapping
+    /// bounds, each in insertion order. Candidate pairs come from a sweep
+    /// along x, so far-apart cutters are never compared.
+    fn cluster_members(&self) -> Vec<Vec<usize>> {
+        let count = self.cutters.len();
+        let mut parent: Vec<usize> = (0..count).collect();
+        fn root(parent: &mut [usize], mut index: usize) -> usize {
+            while parent[index] != index {
+                parent[index] = parent[parent[index]];
+                index = parent[index];
+            }
+            index
+        }
+        let bounds = |index: usize| &self.cutters[index].bounds;
+        let mut order: Vec<usize> = (0..count).collect();
+        order.sort_by(|&a, &b| bounds(a).min.x.total_cmp(&bounds(b).min.x));
+        for (position, &i) in order.iter().enumerate() {
+            for &j in &order[position + 1..] {
+                if bounds(j).min.x > bounds(i).max.x {
+                    break;
+                }
+                if bounds(i).overlaps(bounds(j)) {
+                    let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                    parent[a.max(b)] = a.min(b);
+                }
+            }
+        }
+        let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for index in 0..count {
+            groups
+                .entry(root(&mut parent, index))
+                .or_default()
+                .push(index);
+        }
+        groups.into_values().collect()
+    }
+
+    fn cluster_part(&self, members: &[usize]) -> ClusterPart {
+        let union = members[1..].iter().fold(
+            self.cutters[members[0]].polygons.clone(),
+            |union, &index| {
+                execute_native_boolean(
+                    union,
+                    self.cutters[index].polygons.clone(),
+                    BooleanOperation::Union,
+                    self.tolerance,
+                )
+            },
+        );
+        let bounds = members
+            .iter()
+            .map(|&index| self.cutters[index].bounds)
+            .reduce(Bounds::union)
+            .expect("clusters are never empty");
+        ClusterPart {
+            cavity: self.subtraction_host.cavity(union.clone()),
+            tree: CutterTree::new(union, self.tolerance),
+            bounds,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Bounds {
+    min: Vec3f,
+    max: Vec3f,
+}
+
+impl Bounds {
+    fn of(polygons: &[Polygon3]) -> Self {
+        let mut bounds = Self {
+            min: Vec3f::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            max: Vec3f::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        };
+        for vertex in polygons.iter().flat_map(|polygon| &polygon.vertices) {
+            let p = vertex.position;
+            bounds.min = Vec3f::new(
+                bounds.min.x.min(p.x),
+                bounds.min.y.min(p.y),
+                bounds.min.z.min(p.z),
+            );
+            bounds.max = Vec3f::new(
+                bounds.max.x.max(p.x),
+                bounds.max.y.max(p.y),
+                bounds.max.z.max(p.z),
+            );
+        }
+        bounds
+    }
+
+    fn expanded(self, margin: f64) -> Self {
+        let margin = Vec3f::new(margin, margin, margin);
+        Self {
+            min: self.min.sub(margin),
+            max: self.max.add(margin),
+        }
+    }
+
+    fn union(self, other: Self) -> Self {
+        Self {
+            min: Vec3f::new(
+                self.min.x.min(other.min.x),
+                self.min.y.min(other.min.y),
+                self.min.z.min(other.min.z),
+            ),
+            max: Vec3f::new(
+                self.max.x.max(other.max.x),
+                self.max.y.max(other.max.y),
+                self.max.z.max(other.max.z),
+            ),
+        }
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+            && self.min.z <= other.max.z
+            && other.min.z <= self.max.z
+    }
+}
+
+/// Wasm handle on a `BooleanGraph`. `evaluate` returns the same payload as
+/// the one-shot booleans.
+#[wasm_bindgen]
+pub struct OGBooleanGraph {
+    graph: BooleanGraph,
+}
+
+#[wasm_bindgen]
+impl OGBooleanGraph {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        host_brep_serialized: String,
+        options_json: Option<String>,
+    ) -> Result<OGBooleanGraph, JsValue> {
+        let host: Brep = serde_json::from_str(&host_brep_serialized).map_err(|error| {
+            JsValue::from_str(&format!("Invalid host BRep JSON payload: {}", error))
+        })?;
+        let options =
+            parse_options_json(options_json).map_err(|error| JsValue::from_str(&error))?;
+        let graph = BooleanGraph::new(host, options)
+            .map_err(|error| JsValue::from_str(&error.to_wasm_json()))?;
+        Ok(Self { graph })
+    }
+
+    /// Adds a cutter, or replaces the one already called `name`.
+    #[wasm_bindgen(js_name = setCutter)]
+    pub fn set_cutter(&mut self, name: String, brep_serialized: String) -> Result<(), JsValue> {
+        let brep: Brep = serde_json::from_str(&brep_serialized).map_err(|error| {
+            JsValue::from_str(&format!("Invalid cutter BRep JSON payload: {}", error))
+        })?;
+        self.graph
+            .set_cutter(&name, brep)
+            .map_err(|error| JsValue::from_str(&error.to_wasm_json()))
+    }
+
+    #[wasm_bindgen(js_name = removeCutter)]
+    pub fn remove_cutter(&mut self, name: String) -> bool {
+        self.graph.remove_cutter(&name)
+    }
+
+    #[wasm_bindgen(js_name = moveCutter)]
+    pub fn move_cutter(&mut self, name: String, offset: Vector3) -> Result<(), JsValue> {
+        self.graph
+            .move_cutter(&name, offset)
+            .map_err(|error| JsValue::from_str(&error.to_wasm_json()))
+    }
+
+    #[wasm_bindgen(js_name = cutterNames)]
+    pub fn cutter_names(&self) -> Vec<String> {
+        self.graph.cutter_names()
+    }
+
+    pub fn evaluate(&mut self) -> Result<OGBooleanResult, JsValue> {
+        let output = self
+            .graph
+            .evaluate()
+            .map_err(|error| JsValue::from_str(&error.to_wasm_json()))?;
+        OGBooleanResult::from_output(output).map_err(|error| JsValue::from_str(&error))
+    }
+
+    /// `GraphEvaluation` of the last `evaluate`, as JSON.
+    #[wasm_bindgen(js_name = lastEvaluationJson)]
+    pub fn last_evaluation_json(&self) -> Result<String, JsValue> {
+        serde_json::to_string(self.graph.last_evaluation()).map_err(|error| {
+            JsValue::from_str(&format!("Failed to serialize graph evaluation: {}", error))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::booleans::boolean_subtraction_many;
+    use crate::booleans::test_support::{cuboid, volume};
+
+    /// 12 m long, 3 m high, 0.2 m thick wall.
+    fn wall() -> Brep {
+        cuboid(Vector3::new(0.0, 1.5, 0.0), 12.0, 3.0, 0.2)
+    }
+
+    /// A 1 x 1.2 window cutter overshooting the wall thickness.
+    fn window(x: f64) -> Brep {
+        cuboid(Vector3::new(x, 1.5, 0.0), 1.0, 1.2, 0.6)
+    }
+
+    #[test]
+    fn moving_one_window_reevaluates_only_its_cluster() {
+        let mut graph = BooleanGraph::new(wall(), BooleanOptions::default()).unwrap();
+        for (index, x) in [-4.5, -1.5, 1.5, 4.5].into_iter().enumerate() {
+            graph.set_cutter(&format!("w{index}"), window(x)).unwrap();
+        }
+
+        let first = graph.evaluate().expect("evaluate");
+        assert_eq!(graph.last_evaluation().clusters_evaluated, 4);
+        assert!(is_closed_solid_operand(&first.brep));
+        assert!((volume(&first.brep) - (7.2 - 4.0 * 1.2 * 0.2)).abs() < 1.0e-9);
+
+        graph
+            .move_cutter("w1", Vector3::new(0.5, 0.0, 0.0))
+            .unwrap();
+        let moved = graph.evaluate().expect("evaluate");
+        let evaluation = graph.last_evaluation();
+        assert_eq!(evaluation.cluster_count, 4);
+        assert_eq!(evaluation.clusters_evaluated, 1);
+        // Only the front and back faces reach the window.
+        assert_eq!(evaluation.host_faces_evaluated, 2);
+        assert_eq!(evaluation.host_faces_reused, 4);
+        assert!((volume(&moved.brep) - volume(&first.brep)).abs() < 1.0e-9);
+
+        let cutters = [window(-4.5), window(-1.0), window(1.5), window(4.5)];
+        let one_shot = boolean_subtraction_many(
+            &wall(),
+            &cutters,
+            BooleanOptions {
+                backend: BooleanBackend::Native,
+                ..BooleanOptions::default()
+            },
+        )
+        .expect("one-shot subtraction");
+        assert_eq!(moved.brep.faces.len(), one_shot.brep.faces.len());
+        assert_eq!(moved.provenance.faces.len(), moved.brep.faces.len());
+        // Four reveals per window, each traced to its own window.
+        assert_eq!(
+            moved
+                .provenance
+                .faces
+                .values()
+                .filter(|origin| origin.operand > 0)
+                .count(),
+            16
+        );
+
+        graph.evaluate().expect("evaluate");
+        assert_eq!(graph.last_evaluation().clusters_evaluated, 0);
+        assert_eq!(graph.last_evaluation().host_faces_evaluated, 0);
+    }
+
+    #[test]
+    fn overlapping_cutters_share_a_cluster_and_removal_restores_the_host() {
+        let mut graph = BooleanGraph::new(wall(), BooleanOptions::default()).unwrap();
+        graph
+            .set_cutter("door", cuboid(Vector3::new(0.0, 1.0, 0.0), 1.0, 2.0, 0.6))
+            .unwrap();
+        graph
+            .set_cutter(
+                "fanlight",
+                cuboid(Vector3::new(0.0, 2.2, 0.0), 1.6, 0.6, 0.6),
+            )
+            .unwrap();
+
+        let output = graph.evaluate().expect("evaluate");
+        assert_eq!(graph.last_evaluation().cluster_count, 1);
+        // Door 1 x 2 from the floor; fanlight 1.6 x 0.6 overlapping its top
+        // 0.1 over the door's width.
+        let opening = 1.0 * 2.0 + 1.6 * 0.6 - 1.0 * 0.1;
+        assert!((volume(&output.brep) - (7.2 - opening * 0.2)).abs() < 1.0e-9);
+
+        assert!(graph.remove_cutter("door"));
+        assert!(graph.remove_cutter("fanlight"));
+        assert!(!graph.remove_cutter("fanlight"));
+        let restored = graph.evaluate().expect("evaluate");
+        assert!((volume(&restored.brep) - 7.2).abs() < 1.0e-9);
+        assert_eq!(restored.brep.faces.len(), 6);
+
+        let error = graph
+            .move_cutter("missing", Vector3::new(1.0, 0.0, 0.0))
+            .expect_err("unknown cutters are rejected");
+        assert_eq!(*error.kind(), BooleanErrorKind::InvalidOperand);
+        let error = graph
+            .set_cutter("sheet", Brep::new(uuid::Uuid::new_v4()))
+            .expect_err("open cutters are rejected");
+        assert_eq!(*error.kind(), BooleanErrorKind::UnsupportedOperandKind);
+    }
+}
@@ -0,0 +1,228 @@
+//! Imprint: stamps a tool body's boundary onto a host's faces.
+//!
+//! Each host face is split by the tool's BSP tree (the native boolean's
+//! intersection machinery) into the pieces outside the tool and the pieces
+//! inside it. The two sets are rebuilt as separate faces even though they are
+//! coplanar, so the intersection lines survive as edges and no material is
+//! removed.
+
+use std::collections::BTreeMap;
+
+use wasm_bindgen::prelude::*;
+
+use super::{detect_operand_kind, is_closed_solid_operand, parse_options_json};
+use crate::booleans::analytic::restore_analytic_geometry;
+use crate::booleans::error::{BooleanError, BooleanErrorKind};
+use crate::booleans::native::{brep_to_native_polygons, face_to_native_polygons, SolidTree};
+use crate::booleans::provenance::FaceTrace;
+use crate::booleans::rebuild::build_brep_from_polygon_groups;
+use crate::booleans::types::{BooleanOperandKind, BooleanOptions, ImprintOutput, ImprintReport};
+use crate::brep::Brep;
+
+/// Splits the faces of `host` along their intersection with the closed solid
+/// `tool`. The host keeps its shape and volume; only its faces are
+/// subdivided. Works on closed-solid and planar-face hosts and always uses
+/// the native kernel, at `options`' working tolerance.
+pub fn imprint(
+    host: &Brep,
+    tool: &Brep,
+    options: BooleanOptions,
+) -> Result<ImprintOutput, BooleanError> {
+    host.validate_topology().map_err(BooleanError::from)?;
+    tool.validate_topology().map_err(BooleanError::from)?;
+
+    let tolerance = options.working_tolerance_many(&[host, tool]);
+    let host_kind = detect_operand_kind(host, tolerance)?;
+    if !is_closed_solid_operand(tool) {
+        return Err(BooleanError::new(
+            BooleanErrorKind::UnsupportedOperandKind,
+            "Imprint tools must be closed solids",
+        ));
+    }
+
+    let (tool_polygons, _) = brep_to_native_polygons(tool, tolerance)?;
+    let tool_tree = SolidTree::new(tool_polygons, tolerance);
+    let mut groups = Vec::with_capacity(host.faces.len() * 2);
+    let mut group_sources = Vec::with_capacity(host.faces.len() * 2);
+    for face in &host.faces {
+        let (polygons, _) = face_to_native_polygons(host, face, tolerance)?;
+        let (outside, inside) = tool_tree.partition(polygons);
+        groups.push(outside);
+        group_sources.push((face.id, false));
+        groups.push(inside);
+        group_sources.push((face.id, true));
+    }
+
+    let create_shells = host_kind == BooleanOperandKind::ClosedSolid;
+    let (mut brep, face_groups) =
+        build_brep_from_polygon_groups(&groups, tolerance, create_shells)?;
+    if create_shells && !is_closed_solid_operand(&brep) {
+        return Err(BooleanError::new(
+            BooleanErrorKind::TopologyError,
+            "Imprinted host is not watertight",
+        ));
+    }
+
+    let trace = FaceTrace::new(&brep, &[host], tolerance);
+    restore_analytic_geometry(&mut brep, &trace, &[host, tool], tolerance);
+
+    let mut report = ImprintReport {
+        host_face_count: host.faces.len(),
+        output_face_count: brep.faces.len(),
+        ..ImprintReport::default()
+    };
+    let mut pieces: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+    for (face, group) in brep.faces.iter().zip(face_groups) {
+        let (source, inside) = group_sources[group];
+        pieces.entry(source).or_default().push(face.id);
+        report.face_sources.insert(face.id, source);
+        if inside {
+            report.imprinted_face_ids.push(face.id);
+        }
+    }
+    report.split_faces = pieces
+        .into_iter()
+        .filter(|(_, faces)| faces.len() > 1)
+        .collect();
+
+    Ok(ImprintOutput { brep, report })
+}
+
+/// Result payload of `imprint` returned across the wasm boundary.
+#[wasm_bindgen]
+pub struct OGImprintResult {
+    brep_serialized: String,
+    geometry_serialized: String,
+    outline_geometry_serialized: String,
+    report_json: String,
+}
+
+#[wasm_bindgen]
+impl OGImprintResult {
+    #[wasm_bindgen(getter, js_name = brepSerialized)]
+    pub fn brep_serialized(&self) -> String {
+        self.brep_serialized.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = geometrySerialized)]
+    pub fn geometry_serialized(&self) -> String {
+        self.geometry_serialized.clone()
+    }
+
+    /// Every face boundary, imprint lines included; they separate coplanar
+    /// faces, so a crease-filtered outline would hide them.
+    #[wasm_bindgen(getter, js_name = outlineGeometrySerialized)]
+    pub fn outline_geometry_serialized(&self) -> String {
+        self.outline_geometry_serialized.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = reportJson)]
+    pub fn report_json(&self) -> String {
+        self.report_json.clone()
+    }
+}
+
+/// Wasm entry point for imprint.
+#[wasm_bindgen(js_name = booleanImprint)]
+pub fn imprint_wasm(
+    host_brep_serialized: String,
+    tool_brep_serialized: String,
+    options_json: Option<String>,
+) -> Result<OGImprintResult, JsValue> {
+    let host: Brep = serde_json::from_str(&host_brep_serialized).map_err(|error| {
+        JsValue::from_str(&format!("Invalid host BRep JSON payload: {}", error))
+    })?;
+    let tool: Brep = serde_json::from_str(&tool_brep_serialized).map_err(|error| {
+        JsValue::from_str(&format!("Invalid tool BRep JSON payload: {}", error))
+    })?;
+    let options = parse_options_json(options_json).map_err(|error| JsValue::from_str(&error))?;
+    let output =
+        imprint(&host, &tool, options).map_err(|error| JsValue::from_str(&error.to_wasm_json()))?;
+
+    let serialize = |value: Result<String, serde_json::Error>, what: &str| {
+        value.map_err(|error| {
+            JsValue::from_str(&format!("Failed to serialize imprint {}: {}", what, error))
+        })
+    };
+    Ok(OGImprintResult {
+        brep_serialized: serialize(serde_json::to_string(&output.brep), "BRep")?,
+        geometry_serialized: serialize(
+            serde_json::to_string(&output.brep.get_triangle_vertex_buffer()),
+            "geometry",
+        )?,
+        outline_geometry_serialized: serialize(
+            serde_json::to_string(&output.brep.get_outline_vertex_buffer()),
+            "outline",
+        )?,
+        report_json: serialize(serde_json::to_string(&output.report), "report")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::booleans::test_support::cuboid;
+    use openmaths::Vector3;
+
+    fn face_area(brep: &Brep, face_id: u32) -> f64 {
+        let (polygons, _) =
+            face_to_native_polygons(brep, &brep.faces[face_id as usize], 1.0e-9).unwrap();
+        polygons
+            .iter()
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|i| triangle.vertices[i].position);
+                b.sub(a).cross(c.sub(a)).norm() * 0.5
+            })
+            .sum()
+    }
+
+    #[test]
+    fn imprint_splits_slab_top_under_zone_without_removing_material() {
+        // 4 x 3 slab, 0.2 thick; a 1 x 1 tile zone standing on its top face.
+        let slab = cuboid(Vector3::new(0.0, 0.0, 0.0), 4.0, 0.2, 3.0);
+        let zone = cuboid(Vector3::new(0.5, 0.15, -0.5), 1.0, 0.1, 1.0);
+
+        let output = imprint(&slab, &zone, BooleanOptions::default()).expect("imprint");
+
+        assert!(is_closed_solid_operand(&output.brep));
+        assert_eq!(output.report.output_face_count, 7);
+        assert_eq!(output.report.split_faces.len(), 1);
+        let pieces = output.report.split_faces.values().next().unwrap();
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(output.report.imprinted_face_ids.len(), 1);
+        let imprinted = output.report.imprinted_face_ids[0];
+        assert!(pieces.contains(&imprinted));
+        assert!((face_area(&output.brep, imprinted) - 1.0).abs() < 1.0e-9);
+        let remainder = pieces.iter().find(|&&id| id != imprinted).unwrap();
+        assert!((face_area(&output.brep, *remainder) - 11.0).abs() < 1.0e-9);
+    }
+
+    #[test]
+    fn imprint_through_wall_splits_both_faces_and_leaves_others() {
+        // A finish change: the tool crosses the whole wall thickness.
+        let wall = cuboid(Vector3::new(0.0, 1.5, 0.0), 4.0, 3.0, 0.2);
+        let finish = cuboid(Vector3::new(-1.0, 1.5, 0.0), 2.0, 4.0, 1.0);
+
+        let output = imprint(&wall, &finish, BooleanOptions::default()).expect("imprint");
+
+        assert!(is_closed_solid_operand(&output.brep));
+        // Front, back, top and bottom are split; the end faces are not.
+        assert_eq!(output.report.split_faces.len(), 4);
+        assert_eq!(output.report.output_face_count, 10);
+        assert_eq!(
+            output.report.face_sources.len(),
+            output.report.output_face_count
+        );
+    }
+
+    #[test]
+    fn imprint_rejects_non_solid_tool() {
+        let slab = cuboid(Vector3::new(0.0, 0.0, 0.0), 4.0, 0.2, 3.0);
+        let sheet = Brep::new(uuid::Uuid::new_v4());
+
+        let error = imprint(&slab, &sheet, BooleanOptions::default())
+            .err()
+            .expect("sheet tools are rejected");
+        assert_eq!(*error.kind(), BooleanErrorKind::UnsupportedOperandKind);
+    }
+}
@@ -0,0 +1,289 @@
+//! Lumps: the separate solids of one BRep.
+//!
+//! Boolean results keep every solid they produce in one BRep, one closed
+//! shell per connected piece: a union of disjoint operands, or a
+//! subtraction that severs its host, yields several. A lump is one outer
+//! shell together with the void shells (inward-facing, negative volume) it
+//! encloses. Open shells are not lumps.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+use wasm_bindgen::prelude::*;
+
+use crate::booleans::error::BooleanError;
+use crate::booleans::native::{enclosed_volume, face_to_faceted_polygons, pieces_inside_solid};
+use crate::brep::{Brep, BrepBuilder, BrepError, Edge};
+
+/// One lump of a BRep: `shells[0]` is the outer shell, any further shells
+/// are voids inside it. `volume` is net of the voids.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lump {
+    pub shells: Vec<u32>,
+    pub volume: f64,
+}
+
+/// The lumps of `brep`'s closed shells, ordered by outer shell id. A void
+/// that no outer shell encloses is kept as a lump of its own. Curved faces
+/// are measured by their facets; a face with a degenerate plane fails.
+pub fn find_lumps(brep: &Brep, tolerance: f64) -> Result<Vec<Lump>, BooleanError> {
+    let mut shells = Vec::new();
+    for shell in brep.shells.iter().filter(|shell| shell.is_closed) {
+        let mut polygons = Vec::new();
+        for &face_id in &shell.faces {
+            let (face_polygons, _) =
+                face_to_faceted_polygons(brep, &brep.faces[face_id as usize], tolerance)?;
+            polygons.extend(face_polygons);
+        }
+        let volume = enclosed_volume(&polygons);
+        shells.push((shell.id, polygons, volume));
+    }
+
+    let mut lumps: Vec<Lump> = shells
+        .iter()
+        .filter(|(_, _, volume)| *volume >= 0.0)
+        .map(|(id, _, volume)| Lump {
+            shells: vec![*id],
+            volume: *volume,
+        })
+        .collect();
+    for (id, polygons, volume) in shells.iter().filter(|(_, _, volume)| *volume < 0.0) {
+        // The smallest outer shell around the void is the one it belongs to.
+        let container = shells
+            .iter()
+            .filter(|(_, _, outer_volume)| *outer_volume > -volume)
+            .filter(|(_, outer, _)| {
+                !pieces_inside_solid(polygons.clone(), outer.clone(), tolerance).is_empty()
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+            .and_then(|(outer_id, _, _)| lumps.iter_mut().find(|lump| lump.shells[0] == *outer_id));
+        match container {
+            Some(lump) => {
+                lump.shells.push(*id);
+                lump.volume += volume;
+            }
+            None => lumps.push(Lump {
+                shells: vec![*id],
+                volume: *volume,
+            }),
+        }
+    }
+    lumps.sort_by_key(|lump| lump.shells[0]);
+    Ok(lumps)
+}
+
+/// Net volume of each lump of `brep`, in `find_lumps` order. Empty when
+/// `brep` has no closed shells; fails where `find_lumps` does.
+pub fn lump_volumes(brep: &Brep, tolerance: f64) -> Result<Vec<f64>, BooleanError> {
+    Ok(find_lumps(brep, tolerance)?
+        .iter()
+        .map(|lump| lump.volume)
+        .collect())
+}
+
+/// Splits `brep` into one BRep per lump, in `find_lumps` order, followed by
+/// one per open shell. Face geometry, roles, and edge curves carry over; ids
+/// are renumbered. A BRep without shells comes back whole.
+pub fn split_lumps(brep: &Brep, tolerance: f64) -> Result<Vec<Brep>, BooleanError> {
+    if brep.shells.is_empty() {
+        return Ok(vec![brep.clone()]);
+    }
+    let groups = find_lumps(brep, tolerance)?
+        .into_iter()
+        .map(|lump| lump.shells)
+        .chain(
+            brep.shells
+                .iter()
+                .filter(|shell| !shell.is_closed)
+                .map(|shell| vec![shell.id]),
+        );
+    groups
+        .map(|shells| extract_shells(brep, &shells).map_err(BooleanError::from))
+        .collect()
+}
+
+/// A new BRep holding only the faces of `shells`.
+fn extract_shells(brep: &Brep, shells: &[u32]) -> Result<Brep, BrepError> {
+    let mut builder = BrepBuilder::new(Uuid::new_v4());
+    let mut vertex_map: HashMap<u32, u32> = HashMap::new();
+    let mut old_vertices: Vec<u32> = Vec::new();
+    let mut remap = |builder: &mut BrepBuilder, ids: Vec<u32>| -> Vec<u32> {
+        ids.into_iter()
+            .map(|id| {
+                *vertex_map.entry(id).or_insert_with(|| {
+                    old_vertices.push(id);
+                    builder.add_vertex(brep.vertices[id as usize].position)
+                })
+            })
+            .collect()
+    };
+
+    let mut face_sources = Vec::new();
+    let mut shell_faces = Vec::new();
+    for &shell_id in shells {
+        let shell = &brep.shells[shell_id as usize];
+        let mut faces = Vec::new();
+        for &face_id in &shell.faces {
+            let face = &brep.faces[face_id as usize];
+            let outer = remap(&mut builder, brep.get_loop_vertex_indices(face.outer_loop));
+            let holes: Vec<Vec<u32>> = face
+                .inner_loops
+                .iter()
+                .map(|&loop_id| remap(&mut builder, brep.get_loop_vertex_indices(loop_id)))
+                .collect();
+            faces.push(builder.add_face(&outer, &holes)?);
+            face_sources.push(face_id);
+        }
+        shell_faces.push((faces, shell.is_closed));
+    }
+    for (faces, is_closed) in &shell_faces {
+        builder.add_shell(faces, *is_closed)?;
+    }
+    let mut extracted = builder.build()?;
+
+    for (face, &source) in extracted.faces.iter_mut().zip(&face_sources) {
+        let source = &brep.faces[source as usize];
+        face.normal = source.normal;
+        face.surface = source.surface.clone();
+        face.role = source.role;
+    }
+
+    // Curved source edges by endpoint pair.
+    let mut curved: HashMap<(u32, u32), Vec<&Edge>> = HashMap::new();
+    for edge in brep.edges.iter().filter(|edge| edge.curve.is_some()) {
+        if let Some((from, to)) = brep.get_edge_endpoints(edge.id) {
+            curved
+                .entry((from.min(to), from.max(to)))
+                .or_default()
+                .push(edge);
+        }
+    }
+    for index in 0..extracted.edges.len() {
+        let Some((from, to)) = extracted.get_edge_endpoints(index as u32) else {
+            continue;
+        };
+        let (from, to) = (old_vertices[from as usize], old_vertices[to as usize]);
+        if let Some(source) = curved
+            .get_mut(&(from.min(to), from.max(to)))
+            .and_then(|edges| edges.pop())
+        {
+            extracted.edges[index].curve = source.curve.clone();
+            extracted.edges[index].tolerance = source.tolerance;
+        }
+    }
+    Ok(extracted)
+}
+
+/// Wasm entry point: `brep_serialized` split into its lumps, as a JSON
+/// array of BReps. See `split_lumps`.
+#[wasm_bindgen(js_name = splitLumps)]
+pub fn split_lumps_wasm(brep_serialized: String) -> Result<String, JsValue> {
+    let brep: Brep = serde_json::from_str(&brep_serialized)
+        .map_err(|error| JsValue::from_str(&format!("Invalid BRep JSON payload: {}", error)))?;
+    let tolerance =
+        crate::booleans::types::BooleanOptions::default().resolve_tolerance_many(&[&brep]);
+    let lumps =
+        split_lumps(&brep, tolerance).map_err(|error| JsValue::from_str(&error.to_wasm_json()))?;
+    serde_json::to_string(&lumps)
+        .map_err(|error| JsValue::from_str(&format!("Failed to serialize lumps: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::booleans::is_closed_solid_operand;
+    use crate::booleans::test_support::cuboid;
+    use crate::booleans::types::{BooleanBackend, BooleanOptions};
+    use crate::booleans::{boolean_subtraction, boolean_union};
+    use openmaths::Vector3;
+
+    #[test]
+    fn disjoint_union_and_severing_subtraction_report_each_lump() {
+        let left = cuboid(Vector3::new(-2.0, 0.0, 0.0), 1.0, 1.0, 1.0);
+        let right = cuboid(Vector3::new(2.0, 0.0, 0.0), 2.0, 1.0, 1.0);
+        for backend in [BooleanBackend::Mesh, BooleanBackend::Native] {
+            let options = BooleanOptions {
+                backend,
+                ..BooleanOptions::default()
+            };
+            let union = boolean_union(&left, &right, options).expect("disjoint union");
+            assert_eq!(union.report.output_shell_count, 2, "{:?}", backend);
+            let volumes = &union.report.lump_volumes;
+            assert_eq!(volumes.len(), 2, "{:?}", backend);
+            assert!((volumes[0] + volumes[1] - 3.0).abs() < 1.0e-6);
+            assert!(volumes.iter().any(|volume| (volume - 1.0).abs() < 1.0e-6));
+        }
+
+        // A 0.2 slot through the middle of a 4 m bar leaves two 1.9 m pieces.
+        let bar = cuboid(Vector3::new(0.0, 0.0, 0.0), 4.0, 0.5, 0.5);
+        let slot = cuboid(Vector3::new(0.0, 0.0, 0.0), 0.2, 1.0, 1.0);
+        let severed = boolean_subtraction(
+            &bar,
+            &slot,
+            BooleanOptions {
+                backend: BooleanBackend::Native,
+                ..BooleanOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(severed.report.lump_volumes.len(), 2);
+        for volume in &severed.report.lump_volumes {
+            assert!((volume - 1.9 * 0.25).abs() < 1.0e-6);
+        }
+
+        let pieces = split_lumps(&severed.brep, 1.0e-9).unwrap();
+        assert_eq!(pieces.len(), 2);
+        for piece in &pieces {
+            assert!(is_closed_solid_operand(piece));
+            assert_eq!(piece.shells.len(), 1);
+            assert_eq!(piece.faces.len(), 6);
+            assert!(piece.faces.iter().all(|face| face.surface.is_some()));
+            assert!((lump_volumes(piece, 1.0e-9).unwrap()[0] - 1.9 * 0.25).abs() < 1.0e-6);
+        }
+    }
+
+    #[test]
+    fn warped_faces_are_measured_by_their_facets() {
+        let mut block = cuboid(Vector3::new(0.0, 0.0, 0.0), 1.0, 1.0, 1.0);
+        let corner = block
+            .vertices
+            .iter_mut()
+            .max_by(|a, b| {
+                let sum = |v: &Vector3| v.x + v.y + v.z;
+                sum(&a.position).total_cmp(&sum(&b.position))
+            })
+            .unwrap();
+        corner.position.y += 0.3;
+
+        let volumes = lump_volumes(&block, 1.0e-9).expect("faceted lumps");
+        assert_eq!(volumes.len(), 1);
+        assert!(volumes[0] > 1.0 && volumes[0] < 1.3);
+    }
+
+    #[test]
+    fn voids_stay_with_the_solid_around_them() {
+        let block = cuboid(Vector3::new(0.0, 0.0, 0.0), 2.0, 2.0, 2.0);
+        let pocket = cuboid(Vector3::new(0.0, 0.0, 0.0), 1.0, 1.0, 1.0);
+        let apart = cuboid(Vector3::new(5.0, 0.0, 0.0), 1.0, 1.0, 1.0);
+        let options = BooleanOptions {
+            backend: BooleanBackend::Native,
+            ..BooleanOptions::default()
+        };
+        let hollow = boolean_subtraction(&block, &pocket, options.clone()).unwrap();
+        assert_eq!(hollow.brep.shells.len(), 2);
+        let both = boolean_union(&hollow.brep, &apart, options).unwrap();
+
+        let lumps = find_lumps(&both.brep, 1.0e-9).unwrap();
+        assert_eq!(lumps.len(), 2);
+        let hollow_lump = lumps.iter().find(|lump| lump.shells.len() == 2).unwrap();
+        assert!((hollow_lump.volume - 7.0).abs() < 1.0e-6);
+
+        let pieces = split_lumps(&both.brep, 1.0e-9).unwrap();
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(
+            pieces.iter().map(|piece| piece.shells.len()).sum::<usize>(),
+            3
+        );
+        assert!(pieces.iter().all(is_closed_solid_operand));
+    }
+}
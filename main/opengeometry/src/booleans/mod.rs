@@ -1,7 +1,18 @@
+pub mod analytic;
+pub mod coincidence;
 pub mod error;
+pub mod graph;
+pub mod imprint;
+pub mod lumps;
+pub mod native;
 pub mod planar;
+pub mod predicates;
+pub mod provenance;
 pub mod rebuild;
+pub mod sheet;
 pub mod solid;
+#[cfg(test)]
+pub(crate) mod test_support;
 pub mod types;
 
 use std::collections::HashMap;
@@ -9,14 +20,22 @@ use std::collections::HashMap;
 use serde_json::Value;
 use wasm_bindgen::prelude::*;
 
+use crate::booleans::analytic::restore_analytic_geometry;
+use crate::booleans::coincidence::resolve_cutter_coincidence;
 use crate::booleans::error::{BooleanError, BooleanErrorKind};
+use crate::booleans::lumps::lump_volumes;
+use crate::booleans::native::{
+    brep_to_native_polygons, execute_native_boolean, sheet_to_native_polygons,
+};
 use crate::booleans::planar::{
     execute_planar_boolean, planar_context_from_brep, planar_input_triangle_count,
 };
+use crate::booleans::provenance::FaceTrace;
 use crate::booleans::rebuild::{build_brep_from_polygons, build_brep_from_triangle_mesh};
 use crate::booleans::solid::{brep_to_polygons, execute_solid_boolean};
 use crate::booleans::types::{
-    BooleanOperandKind, BooleanOperation, BooleanOptions, BooleanOutput, BooleanReport,
+    BooleanBackend, BooleanOperandKind, BooleanOperation, BooleanOptions, BooleanOutput,
+    BooleanReport,
 };
 use crate::brep::Brep;
 
@@ -24,17 +43,22 @@ pub use error::{
     BooleanError as OGBooleanError, BooleanErrorKind as OGBooleanErrorKind,
     BooleanErrorPhase as OGBooleanErrorPhase,
 };
-pub use types::{BooleanOperation as OGBooleanOperation, BooleanOptions as OGBooleanOptions};
+pub use types::{
+    BooleanBackend as OGBooleanBackend, BooleanOperation as OGBooleanOperation,
+    BooleanOptions as OGBooleanOptions,
+};
 
 const FEATURE_OUTLINE_CREASE_COS_THRESHOLD: f64 = 0.965;
 
 /// Result payload returned across the wasm boundary.
 #[wasm_bindgen]
+#[derive(Clone)]
 pub struct OGBooleanResult {
     brep_serialized: String,
     geometry_serialized: String,
     outline_geometry_serialized: String,
     report_json: String,
+    provenance_json: String,
 }
 
 impl OGBooleanResult {
@@ -51,12 +75,15 @@ impl OGBooleanResult {
         .map_err(|error| format!("Failed to serialize boolean outline: {}", error))?;
         let report_json = serde_json::to_string(&output.report)
             .map_err(|error| format!("Failed to serialize boolean report: {}", error))?;
+        let provenance_json = serde_json::to_string(&output.provenance)
+            .map_err(|error| format!("Failed to serialize boolean provenance: {}", error))?;
 
         Ok(Self {
             brep_serialized,
             geometry_serialized,
             outline_geometry_serialized,
             report_json,
+            provenance_json,
         })
     }
 }
@@ -82,6 +109,13 @@ impl OGBooleanResult {
     pub fn report_json(&self) -> String {
         self.report_json.clone()
     }
+
+    /// Output face id → `{operand, face_id}` and output edge id →
+    /// `{kind, operand, edge_id}`, as JSON.
+    #[wasm_bindgen(getter, js_name = provenanceJson)]
+    pub fn provenance_json(&self) -> String {
+        self.provenance_json.clone()
+    }
 }
 
 /// Applies a boolean union between two compatible BReps.
@@ -151,7 +185,35 @@ pub fn boolean_subtraction_many(
     operands.push(lhs);
     operands.extend(cutters.iter());
 
-    let working_tolerance = options.resolve_tolerance_many(&operands).max(1.0e-6);
+    let working_tolerance = options.working_tolerance_many(&operands);
+
+    let mut coincidence_adjustments = Vec::new();
+    let resolved_cutters: Vec<Brep>;
+    let cutters = if options.resolve_coincidence && is_closed_solid_operand(lhs) {
+        resolved_cutters = cutters
+            .iter()
+            .enumerate()
+            .map(|(index, cutter)| {
+                if !is_closed_solid_operand(cutter) {
+                    return cutter.clone();
+                }
+                let (resolved, adjustments) = resolve_cutter_coincidence(
+                    lhs,
+                    cutter,
+                    index + 1,
+                    working_tolerance,
+                    options.backend,
+                );
+                coincidence_adjustments.extend(adjustments);
+                resolved
+            })
+            .collect();
+        operands.truncate(1);
+        operands.extend(resolved_cutters.iter());
+        &resolved_cutters[..]
+    } else {
+        cutters
+    };
     let operand_kind = detect_operand_kind(lhs, working_tolerance)?;
     let mut input_triangle_count =
         count_operand_input_triangles(lhs, operand_kind, working_tolerance)?;
@@ -183,12 +245,13 @@ pub fn boolean_subtraction_many(
     let mut handled_via_union = false;
 
     if cutters.len() >= 2 && operand_kind == BooleanOperandKind::ClosedSolid {
-        if let Some(merged) = try_union_cutters(cutters, working_tolerance) {
+        if let Some(merged) = try_union_cutters(cutters, working_tolerance, options.backend) {
             if let Ok(output) = execute_boolean_with_tolerance(
                 &current,
                 &merged,
                 BooleanOperation::Subtraction,
                 working_tolerance,
+                options.backend,
             ) {
                 current = output.brep;
                 handled_via_union = true;
@@ -207,12 +270,18 @@ pub fn boolean_subtraction_many(
                 cutter,
                 BooleanOperation::Subtraction,
                 working_tolerance,
+                options.backend,
             )
             .map(|output| output.brep)
             .map_err(|error| indexed_subtraction_error(index, error))?;
         }
     }
 
+    let provenance = FaceTrace::new(&current, &operands, working_tolerance).provenance(
+        &current,
+        &operands,
+        working_tolerance,
+    );
     Ok(BooleanOutput {
         report: BooleanReport {
             operation: BooleanOperation::Subtraction,
@@ -222,8 +291,11 @@ pub fn boolean_subtraction_many(
             output_face_count: current.faces.len(),
             output_shell_count: current.shells.len(),
             empty: current.faces.is_empty(),
+            lump_volumes: lump_volumes(&current, working_tolerance)?,
+            coincidence_adjustments,
         },
         brep: current,
+        provenance,
     })
 }
 
@@ -292,6 +364,10 @@ pub fn batch_union(
         .expect("a non-empty operand list reduces to exactly one BRep");
     let operand_kind =
         detect_operand_kind(&brep, 1.0e-6).unwrap_or(BooleanOperandKind::ClosedSolid);
+    let operand_refs: Vec<&Brep> = operands.iter().collect();
+    let tolerance = options.working_tolerance_many(&operand_refs);
+    let provenance =
+        FaceTrace::new(&brep, &operand_refs, tolerance).provenance(&brep, &operand_refs, tolerance);
 
     Ok(BooleanOutput {
         report: BooleanReport {
@@ -304,8 +380,11 @@ pub fn batch_union(
             output_face_count: brep.faces.len(),
             output_shell_count: brep.shells.len(),
             empty: brep.faces.is_empty(),
+            lump_volumes: lump_volumes(&brep, tolerance)?,
+            coincidence_adjustments: Vec::new(),
         },
         brep,
+        provenance,
     })
 }
 
@@ -421,16 +500,33 @@ fn execute_boolean(
     lhs.validate_topology().map_err(BooleanError::from)?;
     rhs.validate_topology().map_err(BooleanError::from)?;
 
-    let tolerance = options.resolve_tolerance(lhs, rhs);
-    let working_tolerance = tolerance.max(1.0e-6);
-    execute_boolean_with_tolerance(lhs, rhs, operation, working_tolerance)
+    let working_tolerance = options.working_tolerance_many(&[lhs, rhs]);
+    if options.resolve_coincidence
+        && operation == BooleanOperation::Subtraction
+        && is_closed_solid_operand(lhs)
+        && is_closed_solid_operand(rhs)
+    {
+        let (cutter, adjustments) =
+            resolve_cutter_coincidence(lhs, rhs, 1, working_tolerance, options.backend);
+        let mut output = execute_boolean_with_tolerance(
+            lhs,
+            &cutter,
+            operation,
+            working_tolerance,
+            options.backend,
+        )?;
+        output.report.coincidence_adjustments = adjustments;
+        return Ok(output);
+    }
+    execute_boolean_with_tolerance(lhs, rhs, operation, working_tolerance, options.backend)
 }
 
 /// Validates operands, routes to the solid or planar pipeline, and packages
-/// the rebuilt BRep plus operation report using a caller-supplied tolerance.
+/// the rebuilt BRep plus operation report using a caller-supplied tolerance
+/// and backend.
 ///
-/// For `Subtraction` against `ClosedSolid` operands, this enforces an AABB
-/// pre-check on the cutter (`#3` fix):
+/// For `Subtraction` against `ClosedSolid` operands on the mesh backend, this
+/// enforces an AABB pre-check on the cutter (`#3` fix):
 ///
 /// * Cutter fully disjoint from host → result is the host unchanged
 ///   (subtracting nothing is identity).
@@ -444,6 +540,7 @@ fn execute_boolean_with_tolerance(
     rhs: &Brep,
     operation: BooleanOperation,
     working_tolerance: f64,
+    backend: BooleanBackend,
 ) -> Result<BooleanOutput, BooleanError> {
     let lhs_kind = detect_operand_kind(lhs, working_tolerance)?;
     let rhs_kind = detect_operand_kind(rhs, working_tolerance)?;
@@ -457,13 +554,12 @@ fn execute_boolean_with_tolerance(
 
     // Subtraction-only AABB enforcement. Union and Intersection are
     // mathematically well-defined for any operand pair, so the bounds check
-    // does not apply.
-    // Subtraction-only AABB enforcement. Union and Intersection are
-    // mathematically well-defined for any operand pair, so the bounds check
-    // does not apply.
+    // does not apply. The native backend clips overshooting cutters itself
+    // and needs no pre-clip.
     let clipped_storage: Brep;
     let rhs_for_dispatch: &Brep = if operation == BooleanOperation::Subtraction
         && lhs_kind == BooleanOperandKind::ClosedSolid
+        && backend == BooleanBackend::Mesh
     {
         match enforce_host_bounds_for_subtraction(lhs, rhs, working_tolerance)? {
             BoundsAction::UseAsIs => rhs,
@@ -481,12 +577,39 @@ fn execute_boolean_with_tolerance(
 
     let (brep, input_triangle_count) = match lhs_kind {
         BooleanOperandKind::ClosedSolid => {
-            let (lhs_polygons, lhs_triangles) = brep_to_polygons(lhs, working_tolerance)?;
-            let (rhs_polygons, rhs_triangles) =
-                brep_to_polygons(rhs_for_dispatch, working_tolerance)?;
-            let result_mesh =
-                execute_solid_boolean(lhs_polygons, rhs_polygons, operation, working_tolerance)?;
-            let result_brep = build_brep_from_triangle_mesh(&result_mesh, working_tolerance, true)?;
+            let (result_brep, input_triangles) = match backend {
+                BooleanBackend::Mesh => {
+                    let (lhs_polygons, lhs_triangles) = brep_to_polygons(lhs, working_tolerance)?;
+                    let (rhs_polygons, rhs_triangles) =
+                        brep_to_polygons(rhs_for_dispatch, working_tolerance)?;
+                    let result_mesh = execute_solid_boolean(
+                        lhs_polygons,
+                        rhs_polygons,
+                        operation,
+                        working_tolerance,
+                    )?;
+                    (
+                        build_brep_from_triangle_mesh(&result_mesh, working_tolerance, true)?,
+                        lhs_triangles + rhs_triangles,
+                    )
+                }
+                BooleanBackend::Native => {
+                    let (lhs_polygons, lhs_triangles) =
+                        brep_to_native_polygons(lhs, working_tolerance)?;
+                    let (rhs_polygons, rhs_triangles) =
+                        brep_to_native_polygons(rhs_for_dispatch, working_tolerance)?;
+                    let result_polygons = execute_native_boolean(
+                        lhs_polygons,
+                        rhs_polygons,
+                        operation,
+                        working_tolerance,
+                    );
+                    (
+                        build_brep_from_polygons(&result_polygons, working_tolerance, true)?,
+                        lhs_triangles + rhs_triangles,
+                    )
+                }
+            };
 
             if !result_brep.faces.is_empty() && !is_closed_solid_operand(&result_brep) {
                 return Err(BooleanError::new(
@@ -495,104 +618,77 @@ fn execute_boolean_with_tolerance(
                 ));
             }
 
-            (result_brep, lhs_triangles + rhs_triangles)
+            (result_brep, input_triangles)
         }
         BooleanOperandKind::PlanarFace => {
-            let (result_polygons, triangle_count) =
-                execute_planar_boolean(lhs, rhs_for_dispatch, operation, working_tolerance)?;
+            let (result_polygons, triangle_count) = execute_planar_boolean(
+                lhs,
+                rhs_for_dispatch,
+                operation,
+                working_tolerance,
+                backend,
+            )?;
             let result_brep = build_brep_from_polygons(&result_polygons, working_tolerance, false)?;
             (result_brep, triangle_count)
         }
+        BooleanOperandKind::OpenSheet => {
+            return Err(BooleanError::new(
+                BooleanErrorKind::UnsupportedOperandKind,
+                "Open sheets are only accepted by the sheet/solid operations",
+            ));
+        }
     };
 
-    // D4/D1: re-detect curved faces. The mesh boolean tags every output face as
-    // planar; faces that actually lie on an input cylinder are retagged so a
-    // drilled/unioned cylinder survives as analytic geometry through export.
-    let mut brep = brep;
-    propagate_cylinder_surfaces(&mut brep, &[lhs, rhs_for_dispatch], working_tolerance);
+    finish_output(
+        brep,
+        lhs,
+        rhs,
+        operation,
+        lhs_kind,
+        input_triangle_count,
+        working_tolerance,
+    )
+}
+
+/// Restores analytic geometry on a rebuilt result, traces its provenance to
+/// `lhs` and `rhs`, and packages it with the operation report.
+fn finish_output(
+    mut brep: Brep,
+    lhs: &Brep,
+    rhs: &Brep,
+    operation: BooleanOperation,
+    operand_kind: BooleanOperandKind,
+    input_triangle_count: usize,
+    working_tolerance: f64,
+) -> Result<BooleanOutput, BooleanError> {
+    // D4/D1: re-detect analytic geometry. The mesh boolean tags every output
+    // face as planar and every edge as straight; faces on an operand surface
+    // and edges on an operand or intersection circle get their exact geometry
+    // back so a drilled/unioned cylinder survives through export.
+    let operands = [lhs, rhs];
+    let trace = FaceTrace::new(&brep, &operands, working_tolerance);
+    restore_analytic_geometry(&mut brep, &trace, &operands, working_tolerance);
+    let provenance = trace.provenance(&brep, &operands, working_tolerance);
 
     // Report uses the *original* operands' face counts so callers see
     // pre-clip metrics.
     let report = BooleanReport {
         operation,
-        operand_kind: lhs_kind,
+        operand_kind,
         input_face_count: lhs.faces.len() + rhs.faces.len(),
         input_triangle_count,
         output_face_count: brep.faces.len(),
         output_shell_count: brep.shells.len(),
         empty: brep.faces.is_empty(),
+        lump_volumes: lump_volumes(&brep, working_tolerance)?,
+        coincidence_adjustments: Vec::new(),
     };
 
-    Ok(BooleanOutput { brep, report })
-}
-
-/// Re-attaches cylindrical surfaces to boolean-output faces whose every vertex
-/// lies on a cylinder carried by one of the input operands (within tolerance).
-/// This recovers analytic curved faces the mesh boolean would otherwise flatten
-/// to planar facets. Planar faces keep the plane the rebuild assigned.
-fn propagate_cylinder_surfaces(brep: &mut Brep, inputs: &[&Brep], tolerance: f64) {
-    use crate::brep::SurfaceGeometry;
-
-    let cylinders: Vec<SurfaceGeometry> = inputs
-        .iter()
-        .flat_map(|b| b.faces.iter())
-        .filter_map(|f| match &f.surface {
-            Some(c @ SurfaceGeometry::Cylinder { .. }) => Some(c.clone()),
-            _ => None,
-        })
-        .collect();
-    if cylinders.is_empty() {
-        return;
-    }
-
-    let face_ids: Vec<u32> = brep.faces.iter().map(|f| f.id).collect();
-    for face_id in face_ids {
-        let verts = brep.get_vertices_by_face_id(face_id);
-        if verts.len() < 3 {
-            continue;
-        }
-        for cyl in &cylinders {
-            if let SurfaceGeometry::Cylinder {
-                origin,
-                axis,
-                radius,
-                ..
-            } = cyl
-            {
-                let tol = tolerance.max(radius.abs() * 1.0e-4);
-                if verts
-                    .iter()
-                    .all(|p| point_on_cylinder(*p, *origin, *axis, *radius, tol))
-                {
-                    if let Some(face) = brep.faces.iter_mut().find(|f| f.id == face_id) {
-                        face.surface = Some(cyl.clone());
-                    }
-                    break;
-                }
-            }
-        }
-    }
-}
-
-/// Whether a point's radial distance from a cylinder axis equals the radius
-/// within `tol` (the point lies on the lateral surface).
-fn point_on_cylinder(
-    p: openmaths::Vector3,
-    origin: openmaths::Vector3,
-    axis: openmaths::Vector3,
-    radius: f64,
-    tol: f64,
-) -> bool {
-    let ax_len = (axis.x * axis.x + axis.y * axis.y + axis.z * axis.z).sqrt();
-    if ax_len <= 1.0e-12 {
-        return false;
-    }
-    let ax = openmaths::Vector3::new(axis.x / ax_len, axis.y / ax_len, axis.z / ax_len);
-    let d = openmaths::Vector3::new(p.x - origin.x, p.y - origin.y, p.z - origin.z);
-    let axial = d.x * ax.x + d.y * ax.y + d.z * ax.z;
-    let radial_sq = (d.x * d.x + d.y * d.y + d.z * d.z) - axial * axial;
-    let radial = radial_sq.max(0.0).sqrt();
-    (radial - radius).abs() <= tol
+    Ok(BooleanOutput {
+        brep,
+        report,
+        provenance,
+    })
 }
 
 fn count_operand_input_triangles(
@@ -605,6 +701,9 @@ fn count_operand_input_triangles(
             brep_to_polygons(brep, tolerance).map(|(_, count)| count)
         }
         BooleanOperandKind::PlanarFace => planar_input_triangle_count(brep, tolerance),
+        BooleanOperandKind::OpenSheet => {
+            sheet_to_native_polygons(brep, tolerance).map(|(_, count)| count)
+        }
     }
 }
 
@@ -754,6 +853,7 @@ fn enforce_host_bounds_for_subtraction(
         &aabb_cuboid,
         BooleanOperation::Intersection,
         tolerance,
+        BooleanBackend::Mesh,
     ) {
         Ok(output) if !output.brep.faces.is_empty() => Ok(BoundsAction::ClipTo(output.brep)),
         Ok(_) => Ok(BoundsAction::Identity),
@@ -788,7 +888,14 @@ fn identity_subtraction_output(
             output_face_count: host.faces.len(),
             output_shell_count: host.shells.len(),
             empty: host.faces.is_empty(),
+            lump_volumes: lump_volumes(host, tolerance)?,
+            coincidence_adjustments: Vec::new(),
         },
+        provenance: FaceTrace::new(host, &[host, cutter], tolerance).provenance(
+            host,
+            &[host, cutter],
+            tolerance,
+        ),
         brep: host.clone(),
     })
 }
@@ -798,7 +905,7 @@ fn identity_subtraction_output(
 /// merged shell collapses to empty — the caller falls back to sequential
 /// subtraction in either case so the failure mode is unchanged from the
 /// pre-union behavior.
-fn try_union_cutters(cutters: &[Brep], tolerance: f64) -> Option<Brep> {
+fn try_union_cutters(cutters: &[Brep], tolerance: f64, backend: BooleanBackend) -> Option<Brep> {
     if cutters.is_empty() {
         return None;
     }
@@ -808,8 +915,13 @@ fn try_union_cutters(cutters: &[Brep], tolerance: f64) -> Option<Brep> {
     let mut accumulator = first;
 
     for next in iter {
-        match execute_boolean_with_tolerance(&accumulator, next, BooleanOperation::Union, tolerance)
-        {
+        match execute_boolean_with_tolerance(
+            &accumulator,
+            next,
+            BooleanOperation::Union,
+            tolerance,
+            backend,
+        ) {
             Ok(output) => {
                 if output.brep.faces.is_empty() {
                     return None;
@@ -869,7 +981,7 @@ fn detect_operand_kind(brep: &Brep, tolerance: f64) -> Result<BooleanOperandKind
 
 /// Verifies that the operand is a closed shell solid with exactly two face uses
 /// per topological edge.
-fn is_closed_solid_operand(brep: &Brep) -> bool {
+pub(crate) fn is_closed_solid_operand(brep: &Brep) -> bool {
     if brep.faces.is_empty() || brep.edges.is_empty() || brep.shells.is_empty() {
         return false;
     }
@@ -909,6 +1021,7 @@ fn parse_options_json(options_json: Option<String>) -> Result<BooleanOptions, St
 mod tests {
     use super::*;
     use crate::booleans::error::BooleanErrorPhase;
+    use crate::booleans::test_support::volume;
     use crate::operations::extrude::try_extrude_brep_face;
     use crate::primitives::cuboid::OGCuboid;
     use crate::primitives::polygon::OGPolygon;
@@ -1560,7 +1673,7 @@ mod tests {
     /// the caller can fall back. Tested directly to guard the contract.
     #[test]
     fn try_union_cutters_returns_none_for_empty_slice() {
-        let merged = try_union_cutters(&[], 1.0e-6);
+        let merged = try_union_cutters(&[], 1.0e-6, BooleanBackend::Mesh);
         assert!(merged.is_none());
     }
 
@@ -1569,7 +1682,7 @@ mod tests {
     #[test]
     fn try_union_cutters_returns_single_cutter_unchanged() {
         let cutter = build_cuboid(Vector3::new(0.0, 0.0, 0.0), 1.0, 1.0, 1.0);
-        let merged = try_union_cutters(std::slice::from_ref(&cutter), 1.0e-6)
+        let merged = try_union_cutters(std::slice::from_ref(&cutter), 1.0e-6, BooleanBackend::Mesh)
             .expect("single cutter should pass through");
         assert_eq!(merged.faces.len(), cutter.faces.len());
         assert_eq!(merged.shells.len(), cutter.shells.len());
@@ -1748,6 +1861,106 @@ mod tests {
         assert_closed_solid(&output.brep);
     }
 
+    #[test]
+    fn subtraction_provenance_traces_faces_and_classifies_edges() {
+        use crate::booleans::types::EdgeOriginKind;
+
+        let host = build_cuboid(Vector3::new(0.0, 0.0, 0.0), 4.0, 2.0, 2.0);
+        // A pocket sunk 0.75 into the top face.
+        let cutter = build_cuboid(Vector3::new(0.0, 0.75, 0.0), 1.0, 1.0, 1.0);
+        let output = boolean_subtraction(&host, &cutter, BooleanOptions::default())
+            .expect("pocket subtraction should succeed");
+        let brep = &output.brep;
+        let provenance = &output.provenance;
+
+        assert_eq!(provenance.faces.len(), brep.faces.len());
+        assert_eq!(provenance.edges.len(), brep.edges.len());
+        for face in &brep.faces {
+            let origin = provenance.faces[&face.id];
+            let source = [&host, &cutter][origin.operand]
+                .faces
+                .iter()
+                .find(|f| f.id == origin.face_id)
+                .expect("source face exists");
+            // Pieces keep their source's plane; cutter faces flip inward.
+            let alignment = face.normal.dot(&source.normal);
+            let expected = if origin.operand == 0 { 1.0 } else { -1.0 };
+            assert!((alignment - expected).abs() < 1.0e-9);
+        }
+        // Pocket floor and four walls come from the cutter.
+        let from_cutter: std::collections::BTreeSet<u32> = provenance
+            .faces
+            .values()
+            .filter(|origin| origin.operand == 1)
+            .map(|origin| origin.face_id)
+            .collect();
+        assert_eq!(from_cutter.len(), 5);
+
+        let y_of = |edge_id: u32| {
+            let (a, b) = brep.get_edge_endpoints(edge_id).unwrap();
+            (
+                brep.vertices[a as usize].position.y,
+                brep.vertices[b as usize].position.y,
+            )
+        };
+        let rim: Vec<_> = provenance
+            .edges
+            .iter()
+            .filter(|(_, origin)| origin.kind == EdgeOriginKind::Intersection)
+            .map(|(id, _)| y_of(*id))
+            .collect();
+        assert!(rim.len() >= 4);
+        assert!(rim
+            .iter()
+            .all(|(a, b)| (a - 1.0).abs() < 1.0e-9 && (b - 1.0).abs() < 1.0e-9));
+        assert!(provenance.edges.values().any(|origin| {
+            origin.kind == EdgeOriginKind::Operand
+                && origin.operand == Some(0)
+                && origin.edge_id.is_some()
+        }));
+        assert!(provenance
+            .edges
+            .values()
+            .any(|origin| origin.kind == EdgeOriginKind::Internal));
+    }
+
+    #[test]
+    fn provenance_traces_non_convex_faces() {
+        // Two 10 x 1 x 1 bars unioned into an L; its caps are L-shaped and
+        // their vertex average lies outside them.
+        let options = BooleanOptions {
+            backend: BooleanBackend::Native,
+            ..BooleanOptions::default()
+        };
+        let along_x = build_cuboid(Vector3::new(5.0, 0.5, 0.5), 10.0, 1.0, 1.0);
+        let along_y = build_cuboid(Vector3::new(0.5, 5.0, 0.5), 1.0, 10.0, 1.0);
+        let host = boolean_union(&along_x, &along_y, options.clone())
+            .expect("L union")
+            .brep;
+        let cutter = build_cuboid(Vector3::new(7.0, 0.5, 0.5), 0.4, 2.0, 2.0);
+
+        let output = boolean_subtraction(&host, &cutter, options).expect("cut through one arm");
+
+        let brep = &output.brep;
+        assert_eq!(output.provenance.faces.len(), brep.faces.len());
+        assert!(brep.faces.iter().all(|face| matches!(
+            face.surface,
+            Some(crate::brep::SurfaceGeometry::Plane { .. })
+        )));
+        let caps: Vec<_> = brep
+            .faces
+            .iter()
+            .filter(|face| face.normal.z.abs() > 0.5)
+            .filter(|face| brep.get_vertices_by_face_id(face.id).len() == 6)
+            .collect();
+        assert_eq!(caps.len(), 2);
+        for cap in caps {
+            let origin = output.provenance.faces[&cap.id];
+            assert_eq!(origin.operand, 0);
+            assert!(host.faces[origin.face_id as usize].normal.z * cap.normal.z > 0.0);
+        }
+    }
+
     /// Pre-condition: the wall-from-offsets pipeline (centerline →
     /// bilateral offset with bevel → outline polygon → extrude) produces a
     /// closed solid. This mirrors `examples/wall-from-offsets.ts` exactly,
@@ -1982,4 +2195,114 @@ mod tests {
             .map(|d| d.contains("coplanar"))
             .unwrap_or(false));
     }
+
+    fn native_options() -> BooleanOptions {
+        BooleanOptions {
+            backend: BooleanBackend::Native,
+            ..BooleanOptions::default()
+        }
+    }
+
+    #[test]
+    fn native_subtraction_keeps_five_millimetre_reveal() {
+        // 200 mm wall; the pocket stops 5 mm short of the far face and 5 mm
+        // inside the wall's top edge.
+        let host = build_cuboid(Vector3::new(0.0, 0.0, 0.0), 2.0, 1.0, 0.2);
+        let cutter = build_cuboid(Vector3::new(0.0, 0.2475, -0.0525), 0.5, 0.495, 0.295);
+
+        let output =
+            boolean_subtraction(&host, &cutter, native_options()).expect("native subtraction");
+
+        assert_closed_solid(&output.brep);
+        let expected = 2.0 * 1.0 * 0.2 - 0.5 * 0.495 * 0.195;
+        assert!((volume(&output.brep) - expected).abs() < 1.0e-9);
+        assert!(output
+            .brep
+            .vertices
+            .iter()
+            .any(|v| (v.position.z - 0.095).abs() < 1.0e-12));
+        assert!(output
+            .brep
+            .vertices
+            .iter()
+            .any(|v| (v.position.y - 0.495).abs() < 1.0e-12));
+    }
+
+    #[test]
+    fn native_union_and_intersection_of_overlapping_cuboids_are_closed() {
+        let lhs = build_cuboid(Vector3::new(0.0, 0.0, 0.0), 1.0, 1.0, 1.0);
+        let rhs = build_cuboid(Vector3::new(0.5, 0.5, 0.5), 1.0, 1.0, 1.0);
+
+        let union = boolean_union(&lhs, &rhs, native_options()).expect("native union");
+        assert_closed_solid(&union.brep);
+        assert!((volume(&union.brep) - 1.875).abs() < 1.0e-9);
+
+        let intersection =
+            boolean_intersection(&lhs, &rhs, native_options()).expect("native intersection");
+        assert_closed_solid(&intersection.brep);
+        assert!((volume(&intersection.brep) - 0.125).abs() < 1.0e-9);
+    }
+
+    #[test]
+    fn native_union_of_face_touching_cuboids_stays_closed() {
+        let lhs = build_cuboid(Vector3::new(0.0, 0.0, 0.0), 1.0, 1.0, 1.0);
+        let rhs = build_cuboid(Vector3::new(1.0, 0.0, 0.0), 1.0, 1.0, 1.0);
+
+        let output = boolean_union(&lhs, &rhs, native_options()).expect("native union");
+
+        assert_closed_solid(&output.brep);
+        assert_eq!(output.brep.faces.len(), 6);
+    }
+
+    #[test]
+    fn native_backend_is_selected_from_options_json() {
+        let options = parse_options_json(Some(
+            r#"{"tolerance":1e-9,"merge_coplanar_faces":true,"backend":"Native"}"#.to_string(),
+        ))
+        .expect("options");
+        assert_eq!(options.backend, BooleanBackend::Native);
+        assert_eq!(options.working_tolerance_many(&[]), 1.0e-9);
+
+        let defaults = parse_options_json(Some(
+            r#"{"tolerance":null,"merge_coplanar_faces":true}"#.into(),
+        ))
+        .expect("options");
+        assert_eq!(defaults.backend, BooleanBackend::Mesh);
+    }
+
+    #[test]
+    fn native_subtraction_of_sphere_from_cuboid_is_watertight() {
+        let lhs = build_cuboid(Vector3::new(0.0, 0.0, 0.0), 1.6, 1.2, 1.4);
+        let rhs = build_sphere(Vector3::new(0.45, 0.2, 0.1), 0.7);
+
+        let output = boolean_subtraction(&lhs, &rhs, native_options()).expect("native subtraction");
+
+        assert_closed_solid(&output.brep);
+        let mesh = boolean_subtraction(&lhs, &rhs, BooleanOptions::default()).expect("mesh");
+        assert!((volume(&output.brep) - volume(&mesh.brep)).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn native_planar_subtraction_returns_planar_faces() {
+        let lhs = build_polygon(vec![
+            Vector3::new(-1.0, 0.0, -1.0),
+            Vector3::new(1.0, 0.0, -1.0),
+            Vector3::new(1.0, 0.0, 1.0),
+            Vector3::new(-1.0, 0.0, 1.0),
+        ]);
+        let rhs = build_polygon(vec![
+            Vector3::new(-0.995, 0.0, -0.5),
+            Vector3::new(0.5, 0.0, -0.5),
+            Vector3::new(0.5, 0.0, 0.5),
+            Vector3::new(-0.995, 0.0, 0.5),
+        ]);
+
+        let output =
+            boolean_subtraction(&lhs, &rhs, native_options()).expect("native planar subtraction");
+
+        assert!(!output.brep.faces.is_empty());
+        assert!(output.brep.shells.is_empty());
+        assert!(output.brep.validate_topology().is_ok());
+        assert_eq!(output.report.operand_kind, BooleanOperandKind::PlanarFace);
+    }
 }
@@ -0,0 +1,636 @@
+//! Kernel-native boolean for planar BReps (`BooleanBackend::Native`).
+//!
+//! Each operand is cut into convex polygons, each carrying the plane of its
+//! source face, and combined with BSP-tree CSG. There is no hidden snap
+//! window. A vertex is coplanar with a splitting plane only when it lies
+//! within the caller's tolerance of it, so a 5 mm reveal stays a 5 mm reveal.
+//! Vertices are classified with the adaptive predicates in
+//! `booleans::predicates`, so which side of the band a vertex falls on is
+//! decided exactly. Split points are interpolated from plane distances
+//! summed from their exact expansions, and from the same endpoint order
+//! whichever face an edge is walked from, so the two faces sharing an edge
+//! get bit-identical split points and the rebuild stitches them without
+//! cracks.
+
+use std::cmp::Ordering;
+
+use crate::booleans::error::{BooleanError, BooleanErrorKind};
+use crate::booleans::predicates::{plane_distance, plane_side};
+use crate::booleans::solid::{Plane3, Polygon3, Vec3f, Vertex3};
+use crate::booleans::types::BooleanOperation;
+use crate::brep::{Brep, Face};
+use crate::operations::triangulate::triangulate_polygon_with_holes;
+
+const COPLANAR: u8 = 0;
+const FRONT: u8 = 1;
+const BACK: u8 = 2;
+const SPANNING: u8 = 3;
+
+/// Triangulates every face of `brep` into convex pieces that all carry the
+/// face's own plane, oriented along the face normal. Returns the pieces and
+/// the triangle count for the boolean report.
+pub(crate) fn brep_to_native_polygons(
+    brep: &Brep,
+    tolerance: f64,
+) -> Result<(Vec<Polygon3>, usize), BooleanError> {
+    let mut polygons = Vec::new();
+    let mut triangle_count = 0;
+    for face in &brep.faces {
+        let (pieces, triangles) = face_to_native_polygons(brep, face, tolerance)?;
+        polygons.extend(pieces);
+        triangle_count += triangles;
+    }
+    Ok((polygons, triangle_count))
+}
+
+/// The convex pieces of one face; see `brep_to_native_polygons`.
+pub(crate) fn face_to_native_polygons(
+    brep: &Brep,
+    face: &Face,
+    tolerance: f64,
+) -> Result<(Vec<Polygon3>, usize), BooleanError> {
+    face_polygons(brep, face, tolerance, false)
+}
+
+/// Triangulates every face of an open sheet. Planar faces are cut as in
+/// `brep_to_native_polygons`; a curved face is faceted instead, each of its
+/// triangles on its own plane and oriented along the face's Newell normal.
+pub(crate) fn sheet_to_native_polygons(
+    brep: &Brep,
+    tolerance: f64,
+) -> Result<(Vec<Polygon3>, usize), BooleanError> {
+    let mut polygons = Vec::new();
+    let mut triangle_count = 0;
+    for face in &brep.faces {
+        let (pieces, triangles) = face_to_faceted_polygons(brep, face, tolerance)?;
+        polygons.extend(pieces);
+        triangle_count += triangles;
+    }
+    Ok((polygons, triangle_count))
+}
+
+/// The pieces of one face, faceted where it is curved; see
+/// `sheet_to_native_polygons`.
+pub(crate) fn face_to_faceted_polygons(
+    brep: &Brep,
+    face: &Face,
+    tolerance: f64,
+) -> Result<(Vec<Polygon3>, usize), BooleanError> {
+    face_polygons(brep, face, tolerance, true)
+}
+
+fn face_polygons(
+    brep: &Brep,
+    face: &Face,
+    tolerance: f64,
+    facet_curved: bool,
+) -> Result<(Vec<Polygon3>, usize), BooleanError> {
+    let mut polygons = Vec::new();
+    let mut triangle_count = 0;
+
+    let (outer, holes) = brep.get_vertices_and_holes_by_face_id(face.id);
+    if outer.len() < 3 {
+        return Ok((polygons, triangle_count));
+    }
+    let outer: Vec<Vec3f> = outer.iter().map(Vec3f::from_vector3).collect();
+    let plane = face_plane(&outer, Vec3f::from_vector3(&face.normal)).ok_or_else(|| {
+        BooleanError::new(
+            BooleanErrorKind::InvalidOperand,
+            format!("Face {} has a degenerate plane", face.id),
+        )
+    })?;
+    let off_plane = outer
+        .iter()
+        .map(|point| signed_distance(plane, *point).abs())
+        .fold(0.0, f64::max);
+    let curved = off_plane > tolerance;
+    if curved && !facet_curved {
+        return Err(BooleanError::new(
+            BooleanErrorKind::InvalidOperand,
+            format!(
+                "Face {} is not planar within tolerance {:.3e} (vertex {:.3e} off its plane)",
+                face.id, tolerance, off_plane
+            ),
+        ));
+    }
+
+    let outer_vectors: Vec<_> = outer.iter().map(|point| point.to_vector3()).collect();
+    let all_points: Vec<Vec3f> = outer
+        .iter()
+        .copied()
+        .chain(holes.iter().flatten().map(Vec3f::from_vector3))
+        .collect();
+    for triangle in triangulate_polygon_with_holes(&outer_vectors, &holes) {
+        let Some(corners) = triangle
+            .iter()
+            .map(|&index| all_points.get(index).copied())
+            .collect::<Option<Vec<_>>>()
+        else {
+            continue;
+        };
+        triangle_count += 1;
+
+        let area_normal = corners[1].sub(corners[0]).cross(corners[2].sub(corners[0]));
+        // Slivers cover no area; the rebuild closes the T-junction they
+        // would have filled.
+        if area_normal.norm() <= tolerance * tolerance {
+            continue;
+        }
+        let mut vertices: Vec<Vertex3> = corners
+            .into_iter()
+            .map(|position| Vertex3 { position })
+            .collect();
+        if area_normal.dot(plane.normal) < 0.0 {
+            vertices.reverse();
+        }
+        let plane = if curved {
+            let Some(normal) = area_normal.normalized(0.0) else {
+                continue;
+            };
+            let normal = if normal.dot(plane.normal) < 0.0 {
+                normal.scale(-1.0)
+            } else {
+                normal
+            };
+            Plane3 {
+                normal,
+                w: normal.dot(vertices[0].position),
+            }
+        } else {
+            plane
+        };
+        polygons.push(Polygon3 { vertices, plane });
+    }
+
+    Ok((polygons, triangle_count))
+}
+
+/// Splits `polygons` along the boundary of the closed polygon set `solid`.
+/// Returns the pieces outside it and the pieces inside it. Pieces lying on
+/// its boundary count as inside.
+pub(crate) fn partition_by_solid(
+    polygons: Vec<Polygon3>,
+    solid: Vec<Polygon3>,
+    tolerance: f64,
+) -> (Vec<Polygon3>, Vec<Polygon3>) {
+    SolidTree::new(solid, tolerance).partition(polygons)
+}
+
+/// A closed solid's BSP tree, kept to partition many polygon sets against
+/// it.
+pub(crate) struct SolidTree {
+    tree: BspTree,
+}
+
+impl SolidTree {
+    pub(crate) fn new(polygons: Vec<Polygon3>, tolerance: f64) -> Self {
+        Self {
+            tree: BspTree::new(polygons, tolerance),
+        }
+    }
+
+    /// See `partition_by_solid`.
+    pub(crate) fn partition(&self, polygons: Vec<Polygon3>) -> (Vec<Polygon3>, Vec<Polygon3>) {
+        self.tree.partition_polygons(polygons, true)
+    }
+}
+
+/// The pieces of `polygons` strictly inside the closed polygon set `solid`.
+/// Pieces on its boundary count as inside only where they face against it.
+pub(crate) fn pieces_inside_solid(
+    polygons: Vec<Polygon3>,
+    solid: Vec<Polygon3>,
+    tolerance: f64,
+) -> Vec<Polygon3> {
+    BspTree::new(solid, tolerance)
+        .partition_polygons(polygons, false)
+        .1
+}
+
+/// Combines two closed polygon sets with BSP-tree CSG, classifying against
+/// `tolerance`.
+pub(crate) fn execute_native_boolean(
+    lhs: Vec<Polygon3>,
+    rhs: Vec<Polygon3>,
+    operation: BooleanOperation,
+    tolerance: f64,
+) -> Vec<Polygon3> {
+    let mut a = BspTree::new(lhs, tolerance);
+    let mut b = BspTree::new(rhs, tolerance);
+
+    match operation {
+        BooleanOperation::Union => {
+            a.clip_to(&b);
+            b.clip_to(&a);
+            b.invert();
+            b.clip_to(&a);
+            b.invert();
+            a.build(b.all_polygons());
+        }
+        BooleanOperation::Subtraction => {
+            a.invert();
+            a.clip_to(&b);
+            b.clip_to(&a);
+            b.invert();
+            b.clip_to(&a);
+            b.invert();
+            a.build(b.all_polygons());
+            a.invert();
+        }
+        BooleanOperation::Intersection => {
+            a.invert();
+            b.clip_to(&a);
+            b.invert();
+            a.clip_to(&b);
+            b.clip_to(&a);
+            a.build(b.all_polygons());
+            a.invert();
+        }
+    }
+
+    a.all_polygons()
+}
+
+/// A host solid prepared for subtracting cutters one region at a time.
+/// `host - cutter` is the host's pieces outside the cutter (`CutterTree::
+/// clip_host`) plus the cutter's pieces inside the host (`cavity`); cutters
+/// far apart touch disjoint host pieces, so each can be redone alone.
+pub(crate) struct SubtractionHost {
+    inverted: BspTree,
+}
+
+impl SubtractionHost {
+    pub(crate) fn new(polygons: Vec<Polygon3>, tolerance: f64) -> Self {
+        let mut inverted = BspTree::new(polygons, tolerance);
+        inverted.invert();
+        Self { inverted }
+    }
+
+    /// The cutter's side of `host - cutter`: its pieces inside the host,
+    /// facing into the cavity. Pieces flush with a host face are dropped.
+    pub(crate) fn cavity(&self, cutter: Vec<Polygon3>) -> Vec<Polygon3> {
+        let mut inside = self.inverted.clip_polygons(cutter);
+        inside.iter_mut().for_each(flip_polygon);
+        self.inverted.clip_polygons(inside)
+    }
+}
+
+/// A cutter's BSP tree, kept to clip host pieces against it repeatedly.
+pub(crate) struct CutterTree {
+    tree: BspTree,
+}
+
+impl CutterTree {
+    pub(crate) fn new(polygons: Vec<Polygon3>, tolerance: f64) -> Self {
+        Self {
+            tree: BspTree::new(polygons, tolerance),
+        }
+    }
+
+    /// The host's side of `host - cutter` for some host pieces: the parts
+    /// outside the cutter. Host faces flush with a cutter face are removed
+    /// where the cutter covers them.
+    pub(crate) fn clip_host(&self, mut pieces: Vec<Polygon3>) -> Vec<Polygon3> {
+        pieces.iter_mut().for_each(flip_polygon);
+        let mut kept = self.tree.clip_polygons(pieces);
+        kept.iter_mut().for_each(flip_polygon);
+        kept
+    }
+}
+
+/// Volume enclosed by outward-facing polygons (divergence theorem).
+pub(crate) fn enclosed_volume(polygons: &[Polygon3]) -> f64 {
+    polygons
+        .iter()
+        .map(|polygon| {
+            let origin = polygon.vertices[0].position;
+            polygon
+                .vertices
+                .windows(2)
+                .skip(1)
+                .map(|pair| origin.dot(pair[0].position.cross(pair[1].position)) / 6.0)
+                .sum::<f64>()
+        })
+        .sum()
+}
+
+/// Newell plane of a face loop, flipped to agree with `hint` when it is set.
+fn face_plane(points: &[Vec3f], hint: Vec3f) -> Option<Plane3> {
+    let mut normal = Vec3f::new(0.0, 0.0, 0.0);
+    let mut centroid = Vec3f::new(0.0, 0.0, 0.0);
+    for (index, current) in points.iter().enumerate() {
+        let next = points[(index + 1) % points.len()];
+        normal = normal.add(Vec3f::new(
+            (current.y - next.y) * (current.z + next.z),
+            (current.z - next.z) * (current.x + next.x),
+            (current.x - next.x) * (current.y + next.y),
+        ));
+        centroid = centroid.add(*current);
+    }
+    let mut normal = normal.normalized(0.0)?;
+    if normal.dot(hint) < 0.0 {
+        normal = normal.scale(-1.0);
+    }
+    let centroid = centroid.scale(1.0 / points.len() as f64);
+    Some(Plane3 {
+        normal,
+        w: normal.dot(centroid),
+    })
+}
+
+fn signed_distance(plane: Plane3, point: Vec3f) -> f64 {
+    plane.normal.dot(point) - plane.w
+}
+
+fn flip_plane(plane: Plane3) -> Plane3 {
+    Plane3 {
+        normal: plane.normal.scale(-1.0),
+        w: -plane.w,
+    }
+}
+
+fn flip_polygon(polygon: &mut Polygon3) {
+    polygon.vertices.reverse();
+    polygon.plane = flip_plane(polygon.plane);
+}
+
+/// Where `plane` crosses the edge `a`-`b`. The endpoints are ordered
+/// lexicographically first, so the edge yields the same point whichever
+/// face, and so whichever direction, it is walked from.
+fn edge_intersection(plane: Plane3, a: Vec3f, b: Vec3f) -> Vec3f {
+    let key = |p: Vec3f| (p.x, p.y, p.z);
+    let (a, b) = if key(a) <= key(b) { (a, b) } else { (b, a) };
+    let (da, db) = (plane_distance(plane, a), plane_distance(plane, b));
+    a.add(b.sub(a).scale(da / (da - db)))
+}
+
+/// Where a polygon split by a node plane ends up.
+#[derive(Default)]
+struct Split {
+    coplanar_front: Vec<Polygon3>,
+    coplanar_back: Vec<Polygon3>,
+    front: Vec<Polygon3>,
+    back: Vec<Polygon3>,
+}
+
+/// Splits `polygon` by `plane`. Pieces keep the polygon's own plane, so
+/// repeated splits never drift off the source face.
+fn split_polygon(plane: Plane3, polygon: Polygon3, tolerance: f64, split: &mut Split) {
+    let types: Vec<u8> = polygon
+        .vertices
+        .iter()
+        .map(|vertex| {
+            if plane_side(plane, vertex.position, -tolerance) == Ordering::Less {
+                BACK
+            } else if plane_side(plane, vertex.position, tolerance) == Ordering::Greater {
+                FRONT
+            } else {
+                COPLANAR
+            }
+        })
+        .collect();
+
+    match types.iter().fold(COPLANAR, |kind, t| kind | t) {
+        COPLANAR => {
+            if plane.normal.dot(polygon.plane.normal) > 0.0 {
+                split.coplanar_front.push(polygon);
+            } else {
+                split.coplanar_back.push(polygon);
+            }
+        }
+        FRONT => split.front.push(polygon),
+        BACK => split.back.push(polygon),
+        _ => {
+            let count = polygon.vertices.len();
+            let mut front = Vec::with_capacity(count + 1);
+            let mut back = Vec::with_capacity(count + 1);
+            for i in 0..count {
+                let j = (i + 1) % count;
+                let (ti, tj) = (types[i], types[j]);
+                let vi = polygon.vertices[i];
+                if ti != BACK {
+                    front.push(vi);
+                }
+                if ti != FRONT {
+                    back.push(vi);
+                }
+                if ti | tj == SPANNING {
+                    let position =
+                        edge_intersection(plane, vi.position, polygon.vertices[j].position);
+                    front.push(Vertex3 { position });
+                    back.push(Vertex3 { position });
+                }
+            }
+            if front.len() >= 3 {
+                split.front.push(Polygon3 {
+                    vertices: front,
+                    plane: polygon.plane,
+                });
+            }
+            if back.len() >= 3 {
+                split.back.push(Polygon3 {
+                    vertices: back,
+                    plane: polygon.plane,
+                });
+            }
+        }
+    }
+}
+
+struct BspNode {
+    plane: Option<Plane3>,
+    front: Option<usize>,
+    back: Option<usize>,
+    polygons: Vec<Polygon3>,
+}
+
+impl BspNode {
+    fn empty() -> Self {
+        Self {
+            plane: None,
+            front: None,
+            back: None,
+            polygons: Vec::new(),
+        }
+    }
+}
+
+/// BSP tree stored as an arena; node 0 is the root. Traversals are
+/// iterative so deep trees from finely tessellated operands cannot overflow
+/// the stack.
+struct BspTree {
+    nodes: Vec<BspNode>,
+    tolerance: f64,
+}
+
+impl BspTree {
+    fn new(polygons: Vec<Polygon3>, tolerance: f64) -> Self {
+        let mut tree = Self {
+            nodes: vec![BspNode::empty()],
+            tolerance,
+        };
+        tree.build(polygons);
+        tree
+    }
+
+    /// Inserts polygons into the tree, growing nodes where they fall past a
+    /// leaf.
+    fn build(&mut self, polygons: Vec<Polygon3>) {
+        let mut stack = vec![(0, polygons)];
+        while let Some((index, polygons)) = stack.pop() {
+            if polygons.is_empty() {
+                continue;
+            }
+            let plane = *self.nodes[index].plane.get_or_insert(polygons[0].plane);
+            let mut split = Split::default();
+            for polygon in polygons {
+                split_polygon(plane, polygon, self.tolerance, &mut split);
+            }
+            let node = &mut self.nodes[index];
+            node.polygons.append(&mut split.coplanar_front);
+            node.polygons.append(&mut split.coplanar_back);
+
+            for (pieces, is_front) in [(split.front, true), (split.back, false)] {
+                if pieces.is_empty() {
+                    continue;
+                }
+                let existing = if is_front {
+                    self.nodes[index].front
+                } else {
+                    self.nodes[index].back
+                };
+                let child = existing.unwrap_or_else(|| {
+                    self.nodes.push(BspNode::empty());
+                    let child = self.nodes.len() - 1;
+                    if is_front {
+                        self.nodes[index].front = Some(child);
+                    } else {
+                        self.nodes[index].back = Some(child);
+                    }
+                    child
+                });
+                stack.push((child, pieces));
+            }
+        }
+    }
+
+    /// Swaps inside and outside.
+    fn invert(&mut self) {
+        for node in &mut self.nodes {
+            node.polygons.iter_mut().for_each(flip_polygon);
+            node.plane = node.plane.map(flip_plane);
+            std::mem::swap(&mut node.front, &mut node.back);
+        }
+    }
+
+    /// Removes the parts of `polygons` inside this tree's solid.
+    fn clip_polygons(&self, polygons: Vec<Polygon3>) -> Vec<Polygon3> {
+        self.partition_polygons(polygons, false).0
+    }
+
+    /// Splits `polygons` into the pieces outside and inside this tree's
+    /// solid. Pieces coplanar with a node plane follow their orientation,
+    /// as CSG needs, unless `boundary_inside` sends them all inwards.
+    fn partition_polygons(
+        &self,
+        polygons: Vec<Polygon3>,
+        boundary_inside: bool,
+    ) -> (Vec<Polygon3>, Vec<Polygon3>) {
+        let mut outside = Vec::new();
+        let mut inside = Vec::new();
+        let mut stack = vec![(0, polygons)];
+        while let Some((index, polygons)) = stack.pop() {
+            let node = &self.nodes[index];
+            let Some(plane) = node.plane else {
+                outside.extend(polygons);
+                continue;
+            };
+            let mut split = Split::default();
+            for polygon in polygons {
+                split_polygon(plane, polygon, self.tolerance, &mut split);
+            }
+            let mut front = split.front;
+            let mut back = split.back;
+            back.append(&mut split.coplanar_back);
+            if boundary_inside {
+                back.append(&mut split.coplanar_front);
+            } else {
+                front.append(&mut split.coplanar_front);
+            }
+
+            match node.front {
+                Some(child) => stack.push((child, front)),
+                None => outside.extend(front),
+            }
+            match node.back {
+                Some(child) => stack.push((child, back)),
+                None => inside.extend(back),
+            }
+        }
+        (outside, inside)
+    }
+
+    /// Removes the parts of this tree's polygons inside `other`.
+    fn clip_to(&mut self, other: &BspTree) {
+        for node in &mut self.nodes {
+            let polygons = std::mem::take(&mut node.polygons);
+            node.polygons = other.clip_polygons(polygons);
+        }
+    }
+
+    fn all_polygons(&self) -> Vec<Polygon3> {
+        self.nodes
+            .iter()
+            .flat_map(|node| node.polygons.iter().cloned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::booleans::test_support::cuboid;
+    use openmaths::Vector3;
+
+    fn run(lhs: &Brep, rhs: &Brep, operation: BooleanOperation) -> Vec<Polygon3> {
+        let (lhs, _) = brep_to_native_polygons(lhs, 1.0e-9).unwrap();
+        let (rhs, _) = brep_to_native_polygons(rhs, 1.0e-9).unwrap();
+        execute_native_boolean(lhs, rhs, operation, 1.0e-9)
+    }
+
+    #[test]
+    fn native_operations_match_overlapping_cuboid_volumes() {
+        let a = cuboid(Vector3::new(0.0, 0.0, 0.0), 2.0, 2.0, 2.0);
+        let b = cuboid(Vector3::new(1.0, 1.0, 1.0), 2.0, 2.0, 2.0);
+
+        for (operation, expected) in [
+            (BooleanOperation::Union, 15.0),
+            (BooleanOperation::Subtraction, 7.0),
+            (BooleanOperation::Intersection, 1.0),
+        ] {
+            let volume = enclosed_volume(&run(&a, &b, operation));
+            assert!(
+                (volume - expected).abs() < 1.0e-9,
+                "{:?}: volume {} != {}",
+                operation,
+                volume,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn edge_intersection_is_independent_of_edge_direction() {
+        let plane = Plane3 {
+            normal: Vec3f::new(0.6, 0.0, 0.8),
+            w: 0.1234567,
+        };
+        let a = Vec3f::new(-1.3, 0.7, -0.9);
+        let b = Vec3f::new(2.1, -0.4, 1.7);
+        let forward = edge_intersection(plane, a, b);
+        let backward = edge_intersection(plane, b, a);
+        assert_eq!(forward.x.to_bits(), backward.x.to_bits());
+        assert_eq!(forward.y.to_bits(), backward.y.to_bits());
+        assert_eq!(forward.z.to_bits(), backward.z.to_bits());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::booleans::error::{BooleanError, BooleanErrorKind};
+use crate::booleans::native::execute_native_boolean;
 use crate::booleans::solid::{execute_polygon_boolean, Polygon3, Vec3f};
-use crate::booleans::types::BooleanOperation;
+use crate::booleans::types::{BooleanBackend, BooleanOperation};
 use crate::brep::Brep;
 use crate::operations::triangulate::triangulate_polygon_with_holes;
 use openmaths::Vector3;
@@ -86,6 +87,7 @@ pub(crate) fn execute_planar_boolean(
     rhs: &Brep,
     operation: BooleanOperation,
     tolerance: f64,
+    backend: BooleanBackend,
 ) -> Result<(Vec<Polygon3>, usize), BooleanError> {
     let lhs_context = planar_context_from_brep(lhs, tolerance)?;
     let rhs_context = planar_context_from_brep(rhs, tolerance)?;
@@ -133,7 +135,14 @@ pub(crate) fn execute_planar_boolean(
         &mut triangle_count,
     )?;
 
-    let result = execute_polygon_boolean(lhs_polygons, rhs_polygons, operation, tolerance)?;
+    let result = match backend {
+        BooleanBackend::Mesh => {
+            execute_polygon_boolean(lhs_polygons, rhs_polygons, operation, tolerance)?
+        }
+        BooleanBackend::Native => {
+            execute_native_boolean(lhs_polygons, rhs_polygons, operation, tolerance)
+        }
+    };
     let projected = extract_projected_cap_polygons(&result, lhs_context, half_thickness, tolerance);
     Ok((projected, triangle_count))
 }
@@ -0,0 +1,132 @@
+//! Adaptive-precision plane predicates for the native boolean.
+//!
+//! [`plane_side`] decides the sign of `n·p − w − offset` exactly for the
+//! `f64` plane, point and offset it is given. A floating-point filter
+//! settles almost every call; when the rounded value is too close to zero
+//! to trust, the sum is redone as a Shewchuk expansion of error-free
+//! products and sums, whose sign is exact.
+
+use std::cmp::Ordering;
+
+use crate::booleans::solid::{Plane3, Vec3f};
+
+/// Bound on the rounding error of the filtered sum, relative to the sum of
+/// the magnitudes of its terms. Three products and five additions each
+/// round by at most half an ulp; twice that count keeps the filter safe.
+const FILTER_ERROR: f64 = 8.0 * f64::EPSILON;
+
+/// Sign of `plane.normal · point − plane.w − offset`, exact for the given
+/// `f64` inputs.
+pub(crate) fn plane_side(plane: Plane3, point: Vec3f, offset: f64) -> Ordering {
+    let n = plane.normal;
+    let estimate = n.x * point.x + n.y * point.y + n.z * point.z - plane.w - offset;
+    let magnitude = (n.x * point.x).abs()
+        + (n.y * point.y).abs()
+        + (n.z * point.z).abs()
+        + plane.w.abs()
+        + offset.abs();
+    if estimate.abs() > FILTER_ERROR * magnitude {
+        return estimate.partial_cmp(&0.0).unwrap_or(Ordering::Equal);
+    }
+    // The largest component of a nonoverlapping expansion carries its sign.
+    plane_expansion(plane, point, offset)
+        .last()
+        .map_or(Ordering::Equal, |largest| {
+            largest.partial_cmp(&0.0).unwrap_or(Ordering::Equal)
+        })
+}
+
+/// `plane.normal · point − plane.w`, summed from its exact expansion so it
+/// carries no cancellation error.
+pub(crate) fn plane_distance(plane: Plane3, point: Vec3f) -> f64 {
+    plane_expansion(plane, point, 0.0).iter().sum()
+}
+
+/// `n·p − w − offset` as a nonoverlapping expansion, smallest component
+/// first.
+fn plane_expansion(plane: Plane3, point: Vec3f, offset: f64) -> Vec<f64> {
+    let n = plane.normal;
+    let mut expansion = Vec::with_capacity(8);
+    for (a, b) in [(n.x, point.x), (n.y, point.y), (n.z, point.z)] {
+        let (product, error) = two_product(a, b);
+        grow_expansion(&mut expansion, error);
+        grow_expansion(&mut expansion, product);
+    }
+    grow_expansion(&mut expansion, -plane.w);
+    grow_expansion(&mut expansion, -offset);
+    expansion
+}
+
+/// `a + b` as a rounded sum and its exact rounding error.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let b_virtual = sum - a;
+    let a_virtual = sum - b_virtual;
+    (sum, (a - a_virtual) + (b - b_virtual))
+}
+
+/// `a * b` as a rounded product and its exact rounding error.
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let product = a * b;
+    (product, a.mul_add(b, -product))
+}
+
+/// Adds `value` to `expansion`, keeping it nonoverlapping and free of zero
+/// components (Shewchuk's Grow-Expansion).
+fn grow_expansion(expansion: &mut Vec<f64>, value: f64) {
+    let mut carry = value;
+    let mut grown = Vec::with_capacity(expansion.len() + 1);
+    for &component in expansion.iter() {
+        let (sum, error) = two_sum(carry, component);
+        if error != 0.0 {
+            grown.push(error);
+        }
+        carry = sum;
+    }
+    if carry != 0.0 {
+        grown.push(carry);
+    }
+    *expansion = grown;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plane(normal: Vec3f, w: f64) -> Plane3 {
+        Plane3 { normal, w }
+    }
+
+    #[test]
+    fn plane_side_resolves_signs_rounding_hides() {
+        // 0.1 * 3.0 rounds to exactly the plane offset, so the naive
+        // distance is zero although the point lies off the plane.
+        let (a, b) = (0.1_f64, 3.0);
+        let w = a * b;
+        let residual = a.mul_add(b, -w);
+        assert_ne!(residual, 0.0);
+        let point = Vec3f::new(b, 0.0, 0.0);
+        let x_plane = plane(Vec3f::new(a, 0.0, 0.0), w);
+        assert_eq!(a * b - w, 0.0);
+
+        assert_eq!(
+            plane_side(x_plane, point, 0.0),
+            residual.partial_cmp(&0.0).unwrap()
+        );
+        assert_eq!(plane_distance(x_plane, point), residual);
+    }
+
+    #[test]
+    fn plane_side_is_exact_under_cancellation() {
+        // Terms of 1e16 cancel down to a unit difference the rounded sum
+        // loses entirely.
+        let tilted = plane(Vec3f::new(1.0, 1.0, 0.0), 1.0e16);
+        let point = Vec3f::new(1.0e16, 1.0, 0.0);
+        assert_eq!(1.0e16 + 1.0 - 1.0e16, 0.0);
+
+        assert_eq!(plane_side(tilted, point, 0.0), Ordering::Greater);
+        assert_eq!(plane_side(tilted, point, 1.0), Ordering::Equal);
+        assert_eq!(plane_side(tilted, point, 2.0), Ordering::Less);
+        assert_eq!(plane_distance(tilted, point), 1.0);
+    }
+}
@@ -0,0 +1,315 @@
+//! Face and edge history of boolean output.
+//!
+//! The boolean pipelines rebuild their result from scratch, so output ids say
+//! nothing about the operands. Every output face is a piece of exactly one
+//! operand face, though, so it is traced back geometrically: coplanar with
+//! that face and with an interior point inside its outline. Edges are then
+//! classified from the faces on either side.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::booleans::solid::Vec3f;
+use crate::booleans::types::{BooleanProvenance, EdgeOrigin, EdgeOriginKind, FaceOrigin};
+use crate::brep::{Brep, Face, SurfaceGeometry};
+use crate::operations::triangulate::triangulate_polygon_with_holes;
+
+/// A face must match an operand face's normal this closely to be a piece of
+/// it.
+pub(crate) const PLANE_ALIGNMENT_COS: f64 = 1.0 - 1.0e-6;
+
+/// An operand face with its surface, flattened for containment tests.
+pub(crate) struct SourceFace {
+    pub(crate) operand: usize,
+    pub(crate) face_id: u32,
+    /// The face's analytic surface, or the plane implied by its loop.
+    pub(crate) surface: SurfaceGeometry,
+    normal: Vec3f,
+    origin: Vec3f,
+    drop_axis: usize,
+    outline: Vec<[f64; 2]>,
+    edges: Vec<u32>,
+}
+
+impl SourceFace {
+    /// Whether a face with unit `normal` and a point `inside` it is a piece
+    /// of this one: coplanar with it, the point inside its outline.
+    fn contains(&self, normal: Vec3f, inside: Vec3f, tolerance: f64) -> bool {
+        normal.dot(self.normal).abs() >= PLANE_ALIGNMENT_COS
+            && inside.sub(self.origin).dot(self.normal).abs() <= tolerance
+            && point_in_outline(flatten(inside, self.drop_axis), &self.outline)
+    }
+}
+
+/// The operand face each output face was cut from, by output face index.
+pub(crate) struct FaceTrace {
+    sources: Vec<SourceFace>,
+    traced: Vec<Option<usize>>,
+}
+
+impl FaceTrace {
+    /// Traces every face of `brep` to a face of `operands`, numbered by
+    /// their position in the slice. Earlier operands win where faces of
+    /// several coincide.
+    pub(crate) fn new(brep: &Brep, operands: &[&Brep], tolerance: f64) -> Self {
+        let all: Vec<usize> = (0..operands.len()).collect();
+        Self::within(brep, operands, tolerance, |_| &all)
+    }
+
+    /// As `new`, but traces the face at each index only to the faces of the
+    /// operands `candidates` lists for it, in the order listed.
+    pub(crate) fn within<'c>(
+        brep: &Brep,
+        operands: &[&Brep],
+        tolerance: f64,
+        candidates: impl Fn(usize) -> &'c [usize],
+    ) -> Self {
+        let mut sources = Vec::new();
+        let mut ranges = Vec::with_capacity(operands.len());
+        for (operand, input) in operands.iter().enumerate() {
+            let start = sources.len();
+            sources.extend(
+                input
+                    .faces
+                    .iter()
+                    .filter_map(|face| source_face(operand, input, face)),
+            );
+            ranges.push(start..sources.len());
+        }
+
+        let traced = brep
+            .faces
+            .iter()
+            .enumerate()
+            .map(|(index, face)| {
+                let normal = Vec3f::from_vector3(&face.normal).normalized(0.0)?;
+                let inside = interior_point(brep, face, tolerance)?;
+                candidates(index).iter().find_map(|&operand| {
+                    ranges
+                        .get(operand)?
+                        .clone()
+                        .find(|&source| sources[source].contains(normal, inside, tolerance))
+                })
+            })
+            .collect();
+
+        Self { sources, traced }
+    }
+
+    /// The operand face the output face at `face_index` was cut from.
+    pub(crate) fn source(&self, face_index: usize) -> Option<&SourceFace> {
+        self.traced
+            .get(face_index)
+            .copied()
+            .flatten()
+            .map(|index| &self.sources[index])
+    }
+
+    /// Face origins plus a classification of every output edge.
+    pub(crate) fn provenance(
+        &self,
+        brep: &Brep,
+        operands: &[&Brep],
+        tolerance: f64,
+    ) -> BooleanProvenance {
+        let faces: BTreeMap<u32, FaceOrigin> = brep
+            .faces
+            .iter()
+            .enumerate()
+            .filter_map(|(index, face)| {
+                let source = self.source(index)?;
+                Some((
+                    face.id,
+                    FaceOrigin {
+                        operand: source.operand,
+                        face_id: source.face_id,
+                    },
+                ))
+            })
+            .collect();
+
+        let face_index: HashMap<u32, usize> = brep
+            .faces
+            .iter()
+            .enumerate()
+            .map(|(index, face)| (face.id, index))
+            .collect();
+        let mut edges = BTreeMap::new();
+        for edge in &brep.edges {
+            let Some((from, to)) = brep.get_edge_endpoints(edge.id) else {
+                continue;
+            };
+            let side = |halfedge: Option<u32>| {
+                let face = brep.halfedges.get(halfedge? as usize)?.face?;
+                face_index.get(&face).copied()
+            };
+            let twin = edge
+                .twin_halfedge
+                .or_else(|| brep.halfedges.get(edge.halfedge as usize)?.twin);
+            let sides: Vec<Option<usize>> = [side(Some(edge.halfedge)), side(twin)]
+                .into_iter()
+                .flatten()
+                .map(|index| self.traced[index])
+                .collect();
+            let segment = (
+                Vec3f::from_vector3(&brep.vertices[from as usize].position),
+                Vec3f::from_vector3(&brep.vertices[to as usize].position),
+            );
+            edges.insert(
+                edge.id,
+                self.edge_origin(&sides, segment, operands, tolerance),
+            );
+        }
+
+        BooleanProvenance { faces, edges }
+    }
+
+    /// Classifies an output edge from the traced sources of the faces beside
+    /// it and the operand edge, if any, it lies on.
+    fn edge_origin(
+        &self,
+        sides: &[Option<usize>],
+        (start, end): (Vec3f, Vec3f),
+        operands: &[&Brep],
+        tolerance: f64,
+    ) -> EdgeOrigin {
+        let sources: Vec<&SourceFace> = sides
+            .iter()
+            .flatten()
+            .map(|&index| &self.sources[index])
+            .collect();
+        if let [a, b] = sides {
+            if a.is_some() && a == b {
+                return EdgeOrigin {
+                    kind: EdgeOriginKind::Internal,
+                    operand: sources.first().map(|source| source.operand),
+                    edge_id: None,
+                };
+            }
+        }
+
+        let on_edge = sources.iter().find_map(|source| {
+            let operand = operands.get(source.operand)?;
+            source.edges.iter().find_map(|&edge_id| {
+                let (a, b) = operand.get_edge_endpoints(edge_id)?;
+                let a = Vec3f::from_vector3(&operand.vertices.get(a as usize)?.position);
+                let b = Vec3f::from_vector3(&operand.vertices.get(b as usize)?.position);
+                (point_on_segment(start, a, b, tolerance) && point_on_segment(end, a, b, tolerance))
+                    .then_some((source.operand, edge_id))
+            })
+        });
+        let single_operand = sources
+            .iter()
+            .all(|source| Some(source.operand) == sources.first().map(|s| s.operand));
+        match on_edge {
+            Some((operand, edge_id)) if single_operand && sources[0].operand == operand => {
+                EdgeOrigin {
+                    kind: EdgeOriginKind::Operand,
+                    operand: Some(operand),
+                    edge_id: Some(edge_id),
+                }
+            }
+            _ => EdgeOrigin {
+                kind: EdgeOriginKind::Intersection,
+                operand: on_edge.map(|(operand, _)| operand),
+                edge_id: on_edge.map(|(_, edge_id)| edge_id),
+            },
+        }
+    }
+}
+
+/// Builds the containment record of one operand face; `None` for faces with
+/// a degenerate normal or loop.
+fn source_face(operand: usize, input: &Brep, face: &Face) -> Option<SourceFace> {
+    let normal = Vec3f::from_vector3(&face.normal).normalized(0.0)?;
+    let points = input.get_vertices_by_face_id(face.id);
+    let origin = Vec3f::from_vector3(points.first()?);
+    let drop_axis = dominant_axis(normal);
+    let edges = std::iter::once(face.outer_loop)
+        .chain(face.inner_loops.iter().copied())
+        .filter_map(|loop_id| input.get_loop_halfedges(loop_id).ok())
+        .flatten()
+        .filter_map(|halfedge| input.halfedges.get(halfedge as usize))
+        .map(|halfedge| halfedge.edge)
+        .collect();
+    Some(SourceFace {
+        operand,
+        face_id: face.id,
+        surface: face.surface.clone().unwrap_or(SurfaceGeometry::Plane {
+            origin: origin.to_vector3(),
+            normal: face.normal,
+        }),
+        normal,
+        origin,
+        drop_axis,
+        outline: points
+            .iter()
+            .map(|p| flatten(Vec3f::from_vector3(p), drop_axis))
+            .collect(),
+        edges,
+    })
+}
+
+/// A point strictly inside `face`: the centroid of its first triangle with
+/// area. The loop average can fall outside a non-convex face, or in a hole.
+fn interior_point(brep: &Brep, face: &Face, tolerance: f64) -> Option<Vec3f> {
+    let (outer, holes) = brep.get_vertices_and_holes_by_face_id(face.id);
+    if outer.len() < 3 {
+        return None;
+    }
+    let points: Vec<Vec3f> = outer
+        .iter()
+        .chain(holes.iter().flatten())
+        .map(Vec3f::from_vector3)
+        .collect();
+    triangulate_polygon_with_holes(&outer, &holes)
+        .into_iter()
+        .find_map(|[a, b, c]| {
+            let (a, b, c) = (*points.get(a)?, *points.get(b)?, *points.get(c)?);
+            (b.sub(a).cross(c.sub(a)).norm() > tolerance * tolerance)
+                .then(|| a.add(b).add(c).scale(1.0 / 3.0))
+        })
+}
+
+fn dominant_axis(normal: Vec3f) -> usize {
+    let (x, y, z) = (normal.x.abs(), normal.y.abs(), normal.z.abs());
+    if x >= y && x >= z {
+        0
+    } else if y >= z {
+        1
+    } else {
+        2
+    }
+}
+
+/// Drops the coordinate along `axis`, projecting onto a coordinate plane.
+fn flatten(point: Vec3f, axis: usize) -> [f64; 2] {
+    match axis {
+        0 => [point.y, point.z],
+        1 => [point.z, point.x],
+        _ => [point.x, point.y],
+    }
+}
+
+fn point_in_outline(point: [f64; 2], outline: &[[f64; 2]]) -> bool {
+    let mut inside = false;
+    for (index, a) in outline.iter().enumerate() {
+        let b = outline[(index + 1) % outline.len()];
+        if (a[1] > point[1]) != (b[1] > point[1]) {
+            let x = a[0] + (point[1] - a[1]) / (b[1] - a[1]) * (b[0] - a[0]);
+            if point[0] < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+fn point_on_segment(point: Vec3f, start: Vec3f, end: Vec3f, tolerance: f64) -> bool {
+    let direction = end.sub(start);
+    let length_sq = direction.norm_sq();
+    if length_sq <= tolerance * tolerance {
+        return point.sub(start).norm() <= tolerance;
+    }
+    let t = (point.sub(start).dot(direction) / length_sq).clamp(0.0, 1.0);
+    point.sub(start.add(direction.scale(t))).norm() <= tolerance
+}
@@ -3,7 +3,7 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use uuid::Uuid;
 
 use crate::booleans::error::{BooleanError, BooleanErrorKind};
-use crate::booleans::solid::{Polygon3, TriangleMesh, Vec3f};
+use crate::booleans::solid::{Polygon3, TriangleMesh, Vec3f, Vertex3};
 use crate::brep::{Brep, BrepBuilder, Shell};
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
@@ -100,10 +100,43 @@ pub(crate) fn build_brep_from_polygons(
 ) -> Result<Brep, BooleanError> {
     let polygons = deduplicate_polygons(split_t_junctions(polygons, tolerance), tolerance);
     let rebuilt_faces = merge_coplanar_faces(&polygons, tolerance)?;
+    let faces = rebuilt_faces.into_iter().map(|face| (face, 0)).collect();
+    assemble_brep(faces, tolerance, create_shells).map(|(brep, _)| brep)
+}
+
+/// Rebuilds a BRep from polygon groups that stay separate faces even where
+/// they are coplanar and adjacent, such as the pieces of an imprinted face.
+/// Returns the BRep and the group index of each of its faces, in face order.
+pub(crate) fn build_brep_from_polygon_groups(
+    groups: &[Vec<Polygon3>],
+    tolerance: f64,
+    create_shells: bool,
+) -> Result<(Brep, Vec<usize>), BooleanError> {
+    // T-junctions are split across all groups so neighbouring groups share
+    // their edges; the split keeps polygon order.
+    let mut split = split_t_junctions(&groups.concat(), tolerance).into_iter();
+    let mut faces = Vec::new();
+    for (index, group) in groups.iter().enumerate() {
+        let pieces: Vec<Polygon3> = split.by_ref().take(group.len()).collect();
+        for face in merge_coplanar_faces(&pieces, tolerance)? {
+            faces.push((face, index));
+        }
+    }
+    assemble_brep(faces, tolerance, create_shells)
+}
+
+/// Welds merged faces into a BRep, optionally grouping them into shells.
+/// Faces carry a caller tag, returned for each face that survives.
+fn assemble_brep(
+    faces: Vec<(RebuiltFace, usize)>,
+    tolerance: f64,
+    create_shells: bool,
+) -> Result<(Brep, Vec<usize>), BooleanError> {
     let mut builder = BrepBuilder::new(Uuid::new_v4());
     let mut vertex_map: HashMap<QuantizedPoint, u32> = HashMap::new();
+    let mut tags = Vec::new();
 
-    for rebuilt_face in rebuilt_faces {
+    for (rebuilt_face, tag) in faces {
         let outer_indices = collect_face_indices(
             &mut builder,
             &mut vertex_map,
@@ -137,6 +170,7 @@ pub(crate) fn build_brep_from_polygons(
                     format!("Failed to rebuild boolean face: {}", error),
                 )
             })?;
+        tags.push(tag);
     }
 
     let mut brep = builder.build().map_err(|error| {
@@ -157,13 +191,13 @@ pub(crate) fn build_brep_from_polygons(
     }
 
     tag_planar_surfaces(&mut brep);
-    Ok(brep)
+    Ok((brep, tags))
 }
 
 /// D4/D1: every face emerging from the boolean engine is planar (merged
 /// coplanar polygons or triangles). Re-attach an analytic `Plane` surface so
 /// boolean results also export as analytic planes (D9) rather than bare facets.
-/// Re-detecting curved surfaces (cylinders) from the facet soup is a follow-on.
+/// Operand surfaces and circles are restored afterwards by `booleans::analytic`.
 fn tag_planar_surfaces(brep: &mut Brep) {
     use crate::brep::SurfaceGeometry;
     // Compute a per-face origin (a loop vertex) under an immutable borrow first,
@@ -434,14 +468,19 @@ fn split_t_junctions(polygons: &[Polygon3], tolerance: f64) -> Vec<Polygon3> {
                 }
             }
 
-            let Some(mut rebuilt) = Polygon3::from_positions(positions, tolerance) else {
+            if positions.len() == polygon.vertices.len() {
                 return polygon.clone();
-            };
+            }
 
-            if rebuilt.plane.normal.dot(polygon.plane.normal) < 0.0 {
-                rebuilt = rebuilt.flipped();
+            // Build the split loop directly: `Polygon3::new` would drop the
+            // inserted points again as collinear.
+            Polygon3 {
+                vertices: positions
+                    .into_iter()
+                    .map(|position| Vertex3 { position })
+                    .collect(),
+                plane: polygon.plane,
             }
-            rebuilt
         })
         .collect()
 }
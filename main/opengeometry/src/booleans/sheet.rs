@@ -0,0 +1,589 @@
+//! Mixed sheet/solid booleans.
+//!
+//! The regular booleans want two closed solids or two coplanar faces. Here
+//! one operand is an open sheet (a terrain or roof surface, a freeform
+//! patch) and the other a closed solid, both on the native BSP kernel:
+//!
+//! * `trim_sheet` / `split_sheet` cut the sheet along the solid's boundary
+//!   and keep the part inside, the part outside, or both.
+//! * `split_solid_by_sheet` cuts the solid in two along the sheet. The sheet
+//!   is swept backwards, against its mean normal, past the far side of the
+//!   solid; that closed body is intersected with and subtracted from the
+//!   solid. This needs the sheet to be single-valued along its mean normal
+//!   and to reach past the solid on every side.
+
+use std::collections::HashMap;
+
+use wasm_bindgen::prelude::*;
+
+use super::{finish_output, is_closed_solid_operand, parse_options_json, OGBooleanResult};
+use crate::booleans::error::{BooleanError, BooleanErrorKind};
+use crate::booleans::native::{
+    brep_to_native_polygons, execute_native_boolean, partition_by_solid, pieces_inside_solid,
+    sheet_to_native_polygons,
+};
+use crate::booleans::rebuild::build_brep_from_polygons;
+use crate::booleans::solid::{Plane3, Polygon3, Vec3f, Vertex3};
+use crate::booleans::types::{
+    BooleanOperandKind, BooleanOperation, BooleanOptions, BooleanOutput, SheetSplitOutput,
+    SheetTrimSide, SolidSplitOutput,
+};
+use crate::brep::Brep;
+
+/// A sheet facet may lean this close to perpendicular to the sweep direction
+/// before the sheet counts as folding back over itself.
+const MIN_SWEEP_COS: f64 = 1.0e-6;
+
+/// Keeps the part of `sheet` inside `solid` (on its surface included), or the
+/// part outside it. The report's `operation` is `Intersection` or
+/// `Subtraction` to match.
+pub fn trim_sheet(
+    sheet: &Brep,
+    solid: &Brep,
+    side: SheetTrimSide,
+    options: BooleanOptions,
+) -> Result<BooleanOutput, BooleanError> {
+    partition_sheet(sheet, solid, &options)?.output(sheet, solid, side)
+}
+
+/// Splits `sheet` along the boundary of `solid` into the part inside it and
+/// the part outside it.
+pub fn split_sheet(
+    sheet: &Brep,
+    solid: &Brep,
+    options: BooleanOptions,
+) -> Result<SheetSplitOutput, BooleanError> {
+    let partition = partition_sheet(sheet, solid, &options)?;
+    Ok(SheetSplitOutput {
+        inside: partition.output(sheet, solid, SheetTrimSide::Inside)?,
+        outside: partition.output(sheet, solid, SheetTrimSide::Outside)?,
+    })
+}
+
+/// Cuts `solid` in two along `sheet`. Fails with `InvalidOperand` when the
+/// sheet folds back over its mean normal or its boundary passes through the
+/// solid, and with `EmptyResult` when it misses the solid altogether.
+pub fn split_solid_by_sheet(
+    solid: &Brep,
+    sheet: &Brep,
+    options: BooleanOptions,
+) -> Result<SolidSplitOutput, BooleanError> {
+    solid.validate_topology().map_err(BooleanError::from)?;
+    sheet.validate_topology().map_err(BooleanError::from)?;
+    check_solid_operand(solid)?;
+    check_sheet_operand(sheet)?;
+
+    let tolerance = options.working_tolerance_many(&[solid, sheet]);
+    let (solid_polygons, solid_triangles) = brep_to_native_polygons(solid, tolerance)?;
+    let (sheet_polygons, sheet_triangles) = sheet_to_native_polygons(sheet, tolerance)?;
+    let input_triangle_count = solid_triangles + sheet_triangles;
+
+    let diagonal = bounds_diagonal(solid_polygons.iter().chain(&sheet_polygons));
+    let direction = sweep_direction(&sheet_polygons)?;
+    let (swept, walls) = sweep_sheet(&sheet_polygons, direction, 2.0 * diagonal + 1.0);
+
+    let leak: f64 = pieces_inside_solid(walls, solid_polygons.clone(), tolerance)
+        .iter()
+        .map(polygon_area)
+        .sum();
+    if leak > tolerance * diagonal {
+        return Err(BooleanError::new(
+            BooleanErrorKind::InvalidOperand,
+            "Sheet boundary passes through the solid; the sheet must extend past it on every side",
+        ));
+    }
+    if pieces_inside_solid(sheet_polygons, solid_polygons.clone(), tolerance).is_empty() {
+        return Err(BooleanError::new(
+            BooleanErrorKind::EmptyResult,
+            "Sheet does not cross the solid",
+        ));
+    }
+
+    let half = |operation: BooleanOperation| {
+        let polygons =
+            execute_native_boolean(solid_polygons.clone(), swept.clone(), operation, tolerance);
+        let brep = build_brep_from_polygons(&polygons, tolerance, true)?;
+        if !brep.faces.is_empty() && !is_closed_solid_operand(&brep) {
+            return Err(BooleanError::new(
+                BooleanErrorKind::TopologyError,
+                "Solid split by sheet is not watertight",
+            ));
+        }
+        finish_output(
+            brep,
+            solid,
+            sheet,
+            operation,
+            BooleanOperandKind::ClosedSolid,
+            input_triangle_count,
+            tolerance,
+        )
+    };
+    Ok(SolidSplitOutput {
+        front: half(BooleanOperation::Subtraction)?,
+        back: half(BooleanOperation::Intersection)?,
+    })
+}
+
+/// Sheet pieces on either side of the solid's boundary.
+struct SheetPartition {
+    inside: Vec<Polygon3>,
+    outside: Vec<Polygon3>,
+    input_triangle_count: usize,
+    tolerance: f64,
+}
+
+impl SheetPartition {
+    fn output(
+        &self,
+        sheet: &Brep,
+        solid: &Brep,
+        side: SheetTrimSide,
+    ) -> Result<BooleanOutput, BooleanError> {
+        let (polygons, operation) = match side {
+            SheetTrimSide::Inside => (&self.inside, BooleanOperation::Intersection),
+            SheetTrimSide::Outside => (&self.outside, BooleanOperation::Subtraction),
+        };
+        let brep = build_brep_from_polygons(polygons, self.tolerance, !sheet.shells.is_empty())?;
+        finish_output(
+            brep,
+            sheet,
+            solid,
+            operation,
+            BooleanOperandKind::OpenSheet,
+            self.input_triangle_count,
+            self.tolerance,
+        )
+    }
+}
+
+fn partition_sheet(
+    sheet: &Brep,
+    solid: &Brep,
+    options: &BooleanOptions,
+) -> Result<SheetPartition, BooleanError> {
+    sheet.validate_topology().map_err(BooleanError::from)?;
+    solid.validate_topology().map_err(BooleanError::from)?;
+    check_sheet_operand(sheet)?;
+    check_solid_operand(solid)?;
+
+    let tolerance = options.working_tolerance_many(&[sheet, solid]);
+    let (sheet_polygons, sheet_triangles) = sheet_to_native_polygons(sheet, tolerance)?;
+    let (solid_polygons, solid_triangles) = brep_to_native_polygons(solid, tolerance)?;
+    let (outside, inside) = partition_by_solid(sheet_polygons, solid_polygons, tolerance);
+    Ok(SheetPartition {
+        inside,
+        outside,
+        input_triangle_count: sheet_triangles + solid_triangles,
+        tolerance,
+    })
+}
+
+fn check_sheet_operand(sheet: &Brep) -> Result<(), BooleanError> {
+    if sheet.faces.is_empty() {
+        let message = if sheet.wires.is_empty() {
+            "Sheet operands require at least one face"
+        } else {
+            "Sheet operands do not support wire-only BReps"
+        };
+        return Err(BooleanError::new(
+            BooleanErrorKind::UnsupportedOperandKind,
+            message,
+        ));
+    }
+    if is_closed_solid_operand(sheet) {
+        return Err(BooleanError::new(
+            BooleanErrorKind::UnsupportedOperandKind,
+            "Sheet operand is a closed solid; use the solid booleans instead",
+        ));
+    }
+    Ok(())
+}
+
+fn check_solid_operand(solid: &Brep) -> Result<(), BooleanError> {
+    if is_closed_solid_operand(solid) {
+        return Ok(());
+    }
+    Err(BooleanError::new(
+        BooleanErrorKind::UnsupportedOperandKind,
+        "Sheet/solid booleans require the solid operand to be a closed solid",
+    ))
+}
+
+/// Area-weighted mean normal of the sheet. Every facet must face along it,
+/// or the swept body would overlap itself.
+fn sweep_direction(polygons: &[Polygon3]) -> Result<Vec3f, BooleanError> {
+    let sum = polygons
+        .iter()
+        .fold(Vec3f::new(0.0, 0.0, 0.0), |sum, polygon| {
+            sum.add(polygon.plane.normal.scale(polygon_area(polygon)))
+        });
+    let direction = sum.normalized(0.0).ok_or_else(|| {
+        BooleanError::new(
+            BooleanErrorKind::InvalidOperand,
+            "Sheet has no mean normal to split along",
+        )
+    })?;
+    if polygons
+        .iter()
+        .any(|polygon| polygon.plane.normal.dot(direction) <= MIN_SWEEP_COS)
+    {
+        return Err(BooleanError::new(
+            BooleanErrorKind::InvalidOperand,
+            "Sheet folds back over its mean normal and cannot split a solid",
+        ));
+    }
+    Ok(direction)
+}
+
+/// Sweeps the sheet `depth` against `direction` into a closed polygon set:
+/// the sheet itself, a reversed copy at the far end, and a wall under every
+/// boundary edge. Returns the body and, separately, its walls.
+fn sweep_sheet(
+    polygons: &[Polygon3],
+    direction: Vec3f,
+    depth: f64,
+) -> (Vec<Polygon3>, Vec<Polygon3>) {
+    let offset = direction.scale(-depth);
+    let key = |p: Vec3f| (p.x.to_bits(), p.y.to_bits(), p.z.to_bits());
+
+    // Boundary edges are used once; interior ones once each way.
+    let mut edge_uses: HashMap<_, usize> = HashMap::new();
+    for polygon in polygons {
+        for (a, b) in polygon_edges(polygon) {
+            let (ka, kb) = (key(a), key(b));
+            *edge_uses
+                .entry(if ka <= kb { (ka, kb) } else { (kb, ka) })
+                .or_insert(0) += 1;
+        }
+    }
+
+    let mut walls = Vec::new();
+    for polygon in polygons {
+        for (a, b) in polygon_edges(polygon) {
+            let (ka, kb) = (key(a), key(b));
+            if edge_uses[&if ka <= kb { (ka, kb) } else { (kb, ka) }] != 1 {
+                continue;
+            }
+            let Some(normal) = b.sub(a).cross(direction).normalized(0.0) else {
+                continue;
+            };
+            walls.push(Polygon3 {
+                vertices: [a, a.add(offset), b.add(offset), b]
+                    .into_iter()
+                    .map(|position| Vertex3 { position })
+                    .collect(),
+                plane: Plane3 {
+                    normal,
+                    w: normal.dot(a),
+                },
+            });
+        }
+    }
+
+    let mut swept: Vec<Polygon3> = polygons.to_vec();
+    swept.extend(polygons.iter().map(|polygon| {
+        let mut far = polygon.flipped();
+        for vertex in &mut far.vertices {
+            vertex.position = vertex.position.add(offset);
+        }
+        far.plane.w = far.plane.normal.dot(far.vertices[0].position);
+        far
+    }));
+    swept.extend(walls.iter().cloned());
+    (swept, walls)
+}
+
+fn polygon_edges(polygon: &Polygon3) -> impl Iterator<Item = (Vec3f, Vec3f)> + '_ {
+    let count = polygon.vertices.len();
+    (0..count).map(move |i| {
+        (
+            polygon.vertices[i].position,
+            polygon.vertices[(i + 1) % count].position,
+        )
+    })
+}
+
+fn polygon_area(polygon: &Polygon3) -> f64 {
+    let origin = polygon.vertices[0].position;
+    (1..polygon.vertices.len().saturating_sub(1))
+        .map(|i| {
+            let b = polygon.vertices[i].position.sub(origin);
+            let c = polygon.vertices[i + 1].position.sub(origin);
+            b.cross(c).norm() * 0.5
+        })
+        .sum()
+}
+
+fn bounds_diagonal<'a>(polygons: impl Iterator<Item = &'a Polygon3>) -> f64 {
+    let mut min = Vec3f::new(f64::INFINITY, f64::INFINITY, f64::INFINITY);
+    let mut max = Vec3f::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for vertex in polygons.flat_map(|polygon| &polygon.vertices) {
+        let p = vertex.position;
+        min = Vec3f::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+        max = Vec3f::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+    }
+    if min.x.is_finite() {
+        max.sub(min).norm()
+    } else {
+        0.0
+    }
+}
+
+/// Both halves of a sheet/solid split, returned across the wasm boundary.
+#[wasm_bindgen]
+pub struct OGBooleanSplitResult {
+    first: OGBooleanResult,
+    second: OGBooleanResult,
+}
+
+#[wasm_bindgen]
+impl OGBooleanSplitResult {
+    /// `inside` of a sheet split, `front` of a solid split.
+    #[wasm_bindgen(getter)]
+    pub fn first(&self) -> OGBooleanResult {
+        self.first.clone()
+    }
+
+    /// `outside` of a sheet split, `back` of a solid split.
+    #[wasm_bindgen(getter)]
+    pub fn second(&self) -> OGBooleanResult {
+        self.second.clone()
+    }
+}
+
+fn parse_operands(
+    sheet_brep_serialized: &str,
+    solid_brep_serialized: &str,
+) -> Result<(Brep, Brep), JsValue> {
+    let sheet: Brep = serde_json::from_str(sheet_brep_serialized).map_err(|error| {
+        JsValue::from_str(&format!("Invalid sheet BRep JSON payload: {}", error))
+    })?;
+    let solid: Brep = serde_json::from_str(solid_brep_serialized).map_err(|error| {
+        JsValue::from_str(&format!("Invalid solid BRep JSON payload: {}", error))
+    })?;
+    Ok((sheet, solid))
+}
+
+/// Wasm entry point for `trim_sheet`. `keep` is `"Inside"` or `"Outside"`.
+#[wasm_bindgen(js_name = booleanTrimSheet)]
+pub fn trim_sheet_wasm(
+    sheet_brep_serialized: String,
+    solid_brep_serialized: String,
+    keep: String,
+    options_json: Option<String>,
+) -> Result<OGBooleanResult, JsValue> {
+    let (sheet, solid) = parse_operands(&sheet_brep_serialized, &solid_brep_serialized)?;
+    let side: SheetTrimSide = serde_json::from_value(serde_json::Value::String(keep))
+        .map_err(|error| JsValue::from_str(&format!("Invalid sheet trim side: {}", error)))?;
+    let options = parse_options_json(options_json).map_err(|error| JsValue::from_str(&error))?;
+    let output = trim_sheet(&sheet, &solid, side, options)
+        .map_err(|error| JsValue::from_str(&error.to_wasm_json()))?;
+    OGBooleanResult::from_output(output).map_err(|error| JsValue::from_str(&error))
+}
+
+/// Wasm entry point for `split_sheet`: `first` is inside, `second` outside.
+#[wasm_bindgen(js_name = booleanSplitSheet)]
+pub fn split_sheet_wasm(
+    sheet_brep_serialized: String,
+    solid_brep_serialized: String,
+    options_json: Option<String>,
+) -> Result<OGBooleanSplitResult, JsValue> {
+    let (sheet, solid) = parse_operands(&sheet_brep_serialized, &solid_brep_serialized)?;
+    let options = parse_options_json(options_json).map_err(|error| JsValue::from_str(&error))?;
+    let output = split_sheet(&sheet, &solid, options)
+        .map_err(|error| JsValue::from_str(&error.to_wasm_json()))?;
+    Ok(OGBooleanSplitResult {
+        first: OGBooleanResult::from_output(output.inside).map_err(|e| JsValue::from_str(&e))?,
+        second: OGBooleanResult::from_output(output.outside).map_err(|e| JsValue::from_str(&e))?,
+    })
+}
+
+/// Wasm entry point for `split_solid_by_sheet`: `first` is front, `second`
+/// back.
+#[wasm_bindgen(js_name = booleanSplitSolidBySheet)]
+pub fn split_solid_by_sheet_wasm(
+    solid_brep_serialized: String,
+    sheet_brep_serialized: String,
+    options_json: Option<String>,
+) -> Result<OGBooleanSplitResult, JsValue> {
+    let (sheet, solid) = parse_operands(&sheet_brep_serialized, &solid_brep_serialized)?;
+    let options = parse_options_json(options_json).map_err(|error| JsValue::from_str(&error))?;
+    let output = split_solid_by_sheet(&solid, &sheet, options)
+        .map_err(|error| JsValue::from_str(&error.to_wasm_json()))?;
+    Ok(OGBooleanSplitResult {
+        first: OGBooleanResult::from_output(output.front).map_err(|e| JsValue::from_str(&e))?,
+        second: OGBooleanResult::from_output(output.back).map_err(|e| JsValue::from_str(&e))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::booleans::test_support::{cuboid, volume};
+    use crate::brep::BrepBuilder;
+    use openmaths::Vector3;
+
+    /// Gable roof over x, z in [-half, half]: ridge along z at y = 1.5, eaves
+    /// falling 0.5 per unit of x. Normals point up.
+    fn gable_roof(half: f64) -> Brep {
+        let eave = 1.5 - 0.5 * half;
+        let mut builder = BrepBuilder::new(uuid::Uuid::new_v4());
+        let ids = builder.add_vertices(&[
+            Vector3::new(-half, eave, -half),
+            Vector3::new(-half, eave, half),
+            Vector3::new(0.0, 1.5, half),
+            Vector3::new(0.0, 1.5, -half),
+            Vector3::new(half, eave, half),
+            Vector3::new(half, eave, -half),
+        ]);
+        builder
+            .add_face(&[ids[0], ids[1], ids[2], ids[3]], &[])
+            .unwrap();
+        builder
+            .add_face(&[ids[3], ids[2], ids[4], ids[5]], &[])
+            .unwrap();
+        builder.add_shell_from_all_faces(false).unwrap();
+        builder.build().unwrap()
+    }
+
+    fn area(brep: &Brep) -> f64 {
+        let (polygons, _) = sheet_to_native_polygons(brep, 1.0e-9).unwrap();
+        polygons.iter().map(polygon_area).sum()
+    }
+
+    #[test]
+    fn split_sheet_divides_roof_along_box_boundary() {
+        let roof = gable_roof(2.0);
+        let block = cuboid(Vector3::new(0.0, 1.0, 0.0), 2.0, 2.0, 2.0);
+        let slope = 1.25_f64.sqrt();
+
+        let output = split_sheet(&roof, &block, BooleanOptions::default()).expect("split");
+
+        assert!((area(&output.inside.brep) - 4.0 * slope).abs() < 1.0e-9);
+        assert!((area(&output.outside.brep) - 12.0 * slope).abs() < 1.0e-9);
+        assert_eq!(
+            output.inside.report.operand_kind,
+            BooleanOperandKind::OpenSheet
+        );
+        assert_eq!(
+            output.inside.report.operation,
+            BooleanOperation::Intersection
+        );
+        assert_eq!(
+            output.outside.report.operation,
+            BooleanOperation::Subtraction
+        );
+        assert!(!is_closed_solid_operand(&output.outside.brep));
+        assert!(output
+            .inside
+            .provenance
+            .faces
+            .values()
+            .all(|origin| origin.operand == 0));
+
+        let kept = trim_sheet(
+            &roof,
+            &block,
+            SheetTrimSide::Inside,
+            BooleanOptions::default(),
+        )
+        .expect("trim");
+        assert_eq!(kept.brep.faces.len(), output.inside.brep.faces.len());
+    }
+
+    #[test]
+    fn split_solid_by_sheet_caps_both_halves_with_the_roof() {
+        let roof = gable_roof(2.0);
+        let block = cuboid(Vector3::new(0.0, 1.0, 0.0), 2.0, 2.0, 2.0);
+
+        let output = split_solid_by_sheet(&block, &roof, BooleanOptions::default()).expect("split");
+
+        assert!(is_closed_solid_operand(&output.front.brep));
+        assert!(is_closed_solid_operand(&output.back.brep));
+        // Under the roof: 2 deep, 1.5 - 0.5|x| high over x in [-1, 1].
+        assert!((volume(&output.back.brep) - 5.0).abs() < 1.0e-9);
+        assert!((volume(&output.front.brep) - 3.0).abs() < 1.0e-9);
+        assert!(output
+            .back
+            .provenance
+            .faces
+            .values()
+            .any(|origin| origin.operand == 1));
+    }
+
+    #[test]
+    fn curved_sheet_faces_are_faceted() {
+        // One warped quad: no single plane fits it.
+        let mut builder = BrepBuilder::new(uuid::Uuid::new_v4());
+        let ids = builder.add_vertices(&[
+            Vector3::new(-2.0, 1.0, -2.0),
+            Vector3::new(-2.0, 1.4, 2.0),
+            Vector3::new(2.0, 1.0, 2.0),
+            Vector3::new(2.0, 1.4, -2.0),
+        ]);
+        builder.add_face(&ids, &[]).unwrap();
+        let terrain = builder.build().unwrap();
+        let block = cuboid(Vector3::new(0.0, 1.0, 0.0), 2.0, 2.0, 2.0);
+
+        let output = split_sheet(&terrain, &block, BooleanOptions::default()).expect("split");
+
+        let total = area(&output.inside.brep) + area(&output.outside.brep);
+        assert!((total - area(&terrain)).abs() < 1.0e-9);
+        assert!(!output.inside.brep.faces.is_empty());
+        assert!(split_solid_by_sheet(&block, &terrain, BooleanOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn sheet_operations_reject_unusable_operands() {
+        let block = cuboid(Vector3::new(0.0, 1.0, 0.0), 2.0, 2.0, 2.0);
+        let kind = |result: Result<SolidSplitOutput, BooleanError>| {
+            result.err().expect("operation should fail").kind().clone()
+        };
+
+        // Too small to cut through: its edges run inside the block.
+        assert_eq!(
+            kind(split_solid_by_sheet(
+                &block,
+                &gable_roof(0.5),
+                BooleanOptions::default()
+            )),
+            BooleanErrorKind::InvalidOperand
+        );
+        let far_roof = {
+            let mut roof = gable_roof(2.0);
+            for vertex in &mut roof.vertices {
+                vertex.position.y += 10.0;
+            }
+            roof
+        };
+        assert_eq!(
+            kind(split_solid_by_sheet(
+                &block,
+                &far_roof,
+                BooleanOptions::default()
+            )),
+            BooleanErrorKind::EmptyResult
+        );
+        assert_eq!(
+            kind(split_solid_by_sheet(
+                &block,
+                &block,
+                BooleanOptions::default()
+            )),
+            BooleanErrorKind::UnsupportedOperandKind
+        );
+        assert_eq!(
+            *trim_sheet(
+                &block,
+                &block,
+                SheetTrimSide::Inside,
+                BooleanOptions::default()
+            )
+            .err()
+            .expect("solid sheets are rejected")
+            .kind(),
+            BooleanErrorKind::UnsupportedOperandKind
+        );
+    }
+}
@@ -0,0 +1,20 @@
+//! Fixtures shared by the boolean and clash unit tests.
+
+use openmaths::Vector3;
+
+use crate::booleans::native::{enclosed_volume, sheet_to_native_polygons};
+use crate::brep::Brep;
+use crate::primitives::cuboid::OGCuboid;
+
+pub(crate) fn cuboid(center: Vector3, width: f64, height: f64, depth: f64) -> Brep {
+    let mut cuboid = OGCuboid::new("boolean-test".to_string());
+    cuboid.set_config(center, width, height, depth).unwrap();
+    cuboid.world_brep()
+}
+
+/// Volume enclosed by the closed shells of `brep`; curved faces count by
+/// their facets.
+pub(crate) fn volume(brep: &Brep) -> f64 {
+    let (polygons, _) = sheet_to_native_polygons(brep, 1.0e-9).unwrap();
+    enclosed_volume(&polygons)
+}
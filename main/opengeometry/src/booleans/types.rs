@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use openmaths::Vector3;
 use serde::{Deserialize, Serialize};
 
@@ -14,26 +16,41 @@ pub enum BooleanOperation {
 pub enum BooleanOperandKind {
     ClosedSolid,
     PlanarFace,
+    /// An open surface: a terrain or roof sheet, a freeform patch. Only the
+    /// sheet/solid operations in `booleans::sheet` accept it.
+    OpenSheet,
+}
+
+/// Which part of a sheet `trim_sheet` keeps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SheetTrimSide {
+    /// The part inside the solid or on its surface.
+    Inside,
+    /// The part outside the solid.
+    Outside,
 }
 
 /// Tunable parameters for the boolean pipeline.
 ///
 /// `tolerance` is in **model units** (typically meters in OpenGeometry-using
 /// apps). When `None`, the kernel auto-scales it to `operands_diagonal * 1e-8`
-/// (clamped to `1e-9` floor) and clamped further to `1e-6` inside
-/// `boolean_subtraction_many`. This value drives the kernel-side polygon /
+/// (clamped to `1e-9` floor) and, on the mesh backend, clamped further to
+/// `1e-6` (see `working_tolerance_many`). This value drives the kernel-side polygon /
 /// face math (vertex welding in `solid::weld_position`, plane coincidence in
 /// `detect_coincident_faces`, AABB enforcement in
 /// `enforce_host_bounds_for_subtraction`).
 ///
-/// **Important: this tolerance does NOT control boolmesh's internal welding
-/// snap window.** Boolmesh has its own snap that treats faces within ~1 mm
-/// (in some configurations) as coincident. If your cutter's face is between
-/// roughly 0.001 m and 0.01 m offset from a host face, you may hit the
+/// **Important: on the default `BooleanBackend::Mesh` this tolerance does NOT
+/// control boolmesh's internal welding snap window.** Boolmesh has its own
+/// snap that treats faces within ~1 mm (in some configurations) as
+/// coincident. If your cutter's face is between roughly 0.001 m and 0.01 m
+/// offset from a host face, you may hit the
 /// `BooleanErrorKind::DegenerateTriangle` / `CoincidentFaces` error path
 /// regardless of what `tolerance` you pass. See
 /// `knowledge/boolean-tolerance-guide.md` for the empirical thresholds and
-/// the recommended cutter-overshoot formula.
+/// the recommended cutter-overshoot formula, or set `resolve_coincidence` to
+/// have the kernel apply it. `BooleanBackend::Native` has no such window: it
+/// classifies and splits against `tolerance` alone.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BooleanOptions {
     /// Caller-supplied tolerance in model units. `None` → auto-scale.
@@ -41,6 +58,28 @@ pub struct BooleanOptions {
     /// Whether the planar pipeline merges coplanar adjacent faces in its
     /// output. Defaults to `true`.
     pub merge_coplanar_faces: bool,
+    /// Kernel that evaluates closed-solid and planar booleans. Defaults to
+    /// `BooleanBackend::Mesh`.
+    #[serde(default)]
+    pub backend: BooleanBackend,
+    /// Subtraction only: push closed-solid cutter faces that lie on a
+    /// same-facing host face outward past it before the boolean, so cutters
+    /// authored flush with a wall cut through. Each push is listed in
+    /// `BooleanReport::coincidence_adjustments`. Defaults to `false`.
+    #[serde(default)]
+    pub resolve_coincidence: bool,
+}
+
+/// Boolean kernel selected through `BooleanOptions::backend`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BooleanBackend {
+    /// The boolmesh triangle-mesh kernel.
+    #[default]
+    Mesh,
+    /// The kernel-native BSP boolean in `booleans::native`. Vertices are
+    /// classified against `BooleanOptions::tolerance` with exact adaptive
+    /// predicates, and there is no hidden snap window.
+    Native,
 }
 
 impl Default for BooleanOptions {
@@ -48,6 +87,8 @@ impl Default for BooleanOptions {
         Self {
             tolerance: None,
             merge_coplanar_faces: true,
+            backend: BooleanBackend::Mesh,
+            resolve_coincidence: false,
         }
     }
 }
@@ -59,6 +100,16 @@ impl BooleanOptions {
         self.resolve_tolerance_many(&[lhs, rhs])
     }
 
+    /// The tolerance the selected backend works at: the resolved tolerance,
+    /// clamped to `1e-6` for the mesh backend, which resolves nothing finer.
+    pub fn working_tolerance_many(&self, operands: &[&Brep]) -> f64 {
+        let tolerance = self.resolve_tolerance_many(operands);
+        match self.backend {
+            BooleanBackend::Mesh => tolerance.max(1.0e-6),
+            BooleanBackend::Native => tolerance,
+        }
+    }
+
     /// Computes the working tolerance from the combined bounds of multiple
     /// operands when the caller does not supply one explicitly.
     pub fn resolve_tolerance_many(&self, operands: &[&Brep]) -> f64 {
@@ -99,10 +150,104 @@ pub struct BooleanReport {
     pub output_face_count: usize,
     pub output_shell_count: usize,
     pub empty: bool,
+    /// Net volume of each separate solid in the output, in
+    /// `lumps::find_lumps` order; empty for outputs without closed shells.
+    /// Lets callers drop slivers.
+    #[serde(default)]
+    pub lump_volumes: Vec<f64>,
+    /// Cutter faces moved by `BooleanOptions::resolve_coincidence`.
+    #[serde(default)]
+    pub coincidence_adjustments: Vec<CoincidenceAdjustment>,
+}
+
+/// A cutter face pushed off a host face before a subtraction. `operand` is
+/// numbered as in `FaceOrigin`, so the first cutter is `1`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CoincidenceAdjustment {
+    pub operand: usize,
+    pub face_id: u32,
+    pub host_face_id: u32,
+    /// Distance the face moved along its outward normal.
+    pub offset: f64,
+}
+
+/// Operand face an output face was cut from. Operands are numbered in call
+/// order: `0` is the left-hand operand or host, the right-hand operand or
+/// cutters follow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FaceOrigin {
+    pub operand: usize,
+    pub face_id: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EdgeOriginKind {
+    /// Lies on an edge of one operand, between faces of that operand.
+    Operand,
+    /// New edge where faces of different operands meet, or where the result
+    /// is trimmed by another operand.
+    Intersection,
+    /// Divides pieces of a single operand face; not a feature edge.
+    Internal,
+}
+
+/// How an output edge arose. `operand` and `edge_id` name the operand edge
+/// the output edge lies on, when there is one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeOrigin {
+    pub kind: EdgeOriginKind,
+    pub operand: Option<usize>,
+    pub edge_id: Option<u32>,
+}
+
+/// History of a boolean result keyed by output face and edge id. Faces that
+/// could not be traced to an operand face are absent.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BooleanProvenance {
+    pub faces: BTreeMap<u32, FaceOrigin>,
+    pub edges: BTreeMap<u32, EdgeOrigin>,
 }
 
 #[derive(Clone)]
 pub struct BooleanOutput {
     pub brep: Brep,
     pub report: BooleanReport,
+    pub provenance: BooleanProvenance,
+}
+
+/// Report of an imprint, keyed by host face id and output face id.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ImprintReport {
+    pub host_face_count: usize,
+    pub output_face_count: usize,
+    /// Host faces the tool's boundary crossed, each with the output faces it
+    /// was split into.
+    pub split_faces: BTreeMap<u32, Vec<u32>>,
+    /// Output faces lying inside the tool or on its surface: the imprinted
+    /// regions.
+    pub imprinted_face_ids: Vec<u32>,
+    /// Host face each output face is a piece of.
+    pub face_sources: BTreeMap<u32, u32>,
+}
+
+#[derive(Clone)]
+pub struct ImprintOutput {
+    pub brep: Brep,
+    pub report: ImprintReport,
+}
+
+/// A sheet split along a solid's boundary. Both halves are open sheets.
+#[derive(Clone)]
+pub struct SheetSplitOutput {
+    pub inside: BooleanOutput,
+    pub outside: BooleanOutput,
+}
+
+/// A solid cut in two by a sheet. `front` lies on the side the sheet's
+/// normals point to, `back` on the other; both are closed solids capped by
+/// the sheet.
+#[derive(Clone)]
+pub struct SolidSplitOutput {
+    pub front: BooleanOutput,
+    pub back: BooleanOutput,
 }
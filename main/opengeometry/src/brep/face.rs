@@ -14,6 +14,17 @@ pub struct Face {
     /// polygon whose plane is implied by its loop. Defaulted for v1 back-compat.
     #[serde(default)]
     pub surface: Option<SurfaceGeometry>,
+    /// What the face represents beyond plain model boundary, e.g. the cap
+    /// left by a split so drawings can hatch it. `None` ⇒ an ordinary face.
+    #[serde(default)]
+    pub role: Option<FaceRole>,
+}
+
+/// Semantic role attached to a face by the operation that created it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FaceRole {
+    /// Planar cap closing a solid where a split or section plane cut it.
+    SectionCap,
 }
 
 impl Face {
@@ -31,6 +42,7 @@ impl Face {
             inner_loops,
             shell_ref,
             surface: None,
+            role: None,
         }
     }
 
@@ -167,6 +167,8 @@ pub enum SurfaceGeometry {
         radius: f64,
         height: f64,
     },
+    /// A (possibly partial) spherical face centred on `center`.
+    Sphere { center: Vector3, radius: f64 },
 }
 
 impl SurfaceGeometry {
@@ -174,6 +176,7 @@ impl SurfaceGeometry {
         match self {
             SurfaceGeometry::Plane { .. } => "plane",
             SurfaceGeometry::Cylinder { .. } => "cylinder",
+            SurfaceGeometry::Sphere { .. } => "sphere",
         }
     }
 
@@ -200,6 +203,10 @@ impl SurfaceGeometry {
                 radius: radius * scale,
                 height: height * scale,
             },
+            SurfaceGeometry::Sphere { center, radius } => SurfaceGeometry::Sphere {
+                center: transform_point(*center),
+                radius: radius * scale,
+            },
         }
     }
 }
@@ -26,7 +26,7 @@ use crate::{
 pub use builder::BrepBuilder;
 pub use edge::Edge;
 pub use error::{BrepError, BrepErrorKind};
-pub use face::Face;
+pub use face::{Face, FaceRole};
 pub use geometry::{CurveGeometry, SurfaceGeometry};
 pub use halfedge::HalfEdge;
 pub use r#loop::Loop;
@@ -179,28 +179,28 @@ impl Brep {
     }
 
     pub fn bounds_center(&self) -> Option<Vector3> {
-        let first_vertex = self.vertices.first()?;
-        let mut min_x = first_vertex.position.x;
-        let mut min_y = first_vertex.position.y;
-        let mut min_z = first_vertex.position.z;
-        let mut max_x = first_vertex.position.x;
-        let mut max_y = first_vertex.position.y;
-        let mut max_z = first_vertex.position.z;
+        let (min, max) = self.bounds()?;
+        Some(Vector3::new(
+            (min.x + max.x) * 0.5,
+            (min.y + max.y) * 0.5,
+            (min.z + max.z) * 0.5,
+        ))
+    }
 
+    /// Axis-aligned bounds `(min, max)` of the vertices.
+    pub fn bounds(&self) -> Option<(Vector3, Vector3)> {
+        let first_vertex = self.vertices.first()?;
+        let mut min = first_vertex.position;
+        let mut max = first_vertex.position;
         for vertex in &self.vertices[1..] {
-            min_x = min_x.min(vertex.position.x);
-            min_y = min_y.min(vertex.position.y);
-            min_z = min_z.min(vertex.position.z);
-            max_x = max_x.max(vertex.position.x);
-            max_y = max_y.max(vertex.position.y);
-            max_z = max_z.max(vertex.position.z);
+            min.x = min.x.min(vertex.position.x);
+            min.y = min.y.min(vertex.position.y);
+            min.z = min.z.min(vertex.position.z);
+            max.x = max.x.max(vertex.position.x);
+            max.y = max.y.max(vertex.position.y);
+            max.z = max.z.max(vertex.position.z);
         }
-
-        Some(Vector3::new(
-            (min_x + max_x) * 0.5,
-            (min_y + max_y) * 0.5,
-            (min_z + max_z) * 0.5,
-        ))
+        Some((min, max))
     }
 
     fn transform_cache_key(&self, placement: &Placement3D) -> TransformCacheKey {
@@ -1,6 +1,9 @@
 use wasm_bindgen::prelude::JsValue;
 
-use super::edits::collect_closed_quad_edge_ring;
+use super::edits::{
+    collect_closed_quad_edge_ring, resolve_chamfer_edge_topology,
+    resolve_chamfer_edge_topology_with, VertexFaces,
+};
 use super::inspection::incident_faces_for_edge;
 use super::validation::normalized;
 use super::{EditCapabilities, FeatureEditCapabilities, OGFreeformGeometry};
@@ -10,6 +13,7 @@ impl OGFreeformGeometry {
         let supports_edge_topology_edits = self.supports_any_edge_topology_edits();
         let supports_face_cut = self.supports_any_face_cut();
         let supports_loop_cut = self.supports_any_loop_cut();
+        let supports_chamfer = self.supports_any_chamfer();
         let supports_vertex_removal = self.supports_single_face_topology_edits();
         let can_remove_vertex = supports_vertex_removal && self.single_face_vertex_count() > 3;
 
@@ -37,6 +41,12 @@ impl OGFreeformGeometry {
                     .to_string(),
             );
         }
+        if !supports_chamfer {
+            reasons.push(
+                "chamferEdge currently requires a convex edge between planar faces whose endpoints join exactly three faces"
+                    .to_string(),
+            );
+        }
 
         EditCapabilities {
             can_push_pull_face: !self.local_brep.faces.is_empty(),
@@ -53,6 +63,7 @@ impl OGFreeformGeometry {
             can_remove_vertex,
             can_split_edge: supports_edge_topology_edits,
             can_loop_cut: supports_loop_cut,
+            can_chamfer_edge: supports_chamfer,
             reasons,
         }
     }
@@ -99,6 +110,7 @@ impl OGFreeformGeometry {
             can_remove_vertex: false,
             can_split_edge: false,
             can_loop_cut: false,
+            can_chamfer_edge: false,
             reasons,
         })
     }
@@ -116,6 +128,7 @@ impl OGFreeformGeometry {
 
         let supports_topology_edits = self.supports_rebuildable_edge_topology_edits(edge_id);
         let supports_loop_cut = self.supports_loop_cut_from_edge(edge_id);
+        let supports_chamfer = self.supports_chamfer_on_edge(edge_id);
         let mut reasons = Vec::new();
         if !supports_topology_edits {
             reasons.push(
@@ -128,6 +141,12 @@ impl OGFreeformGeometry {
                     .to_string(),
             );
         }
+        if !supports_chamfer {
+            reasons.push(
+                "chamferEdge currently requires a convex edge between planar faces whose endpoints join exactly three faces"
+                    .to_string(),
+            );
+        }
 
         Ok(FeatureEditCapabilities {
             domain: "edge".to_string(),
@@ -142,6 +161,7 @@ impl OGFreeformGeometry {
             can_remove_vertex: false,
             can_split_edge: supports_topology_edits,
             can_loop_cut: supports_loop_cut,
+            can_chamfer_edge: supports_chamfer,
             reasons,
         })
     }
@@ -185,6 +205,7 @@ impl OGFreeformGeometry {
             can_remove_vertex,
             can_split_edge: false,
             can_loop_cut: false,
+            can_chamfer_edge: false,
             reasons,
         })
     }
@@ -274,6 +295,17 @@ impl OGFreeformGeometry {
         collect_closed_quad_edge_ring(&self.local_brep, edge_id).is_ok()
     }
 
+    pub(super) fn supports_any_chamfer(&self) -> bool {
+        let vertex_faces = VertexFaces::new(&self.local_brep);
+        self.local_brep.edges.iter().any(|edge| {
+            resolve_chamfer_edge_topology_with(&self.local_brep, edge.id, &vertex_faces).is_ok()
+        })
+    }
+
+    pub(super) fn supports_chamfer_on_edge(&self, edge_id: u32) -> bool {
+        resolve_chamfer_edge_topology(&self.local_brep, edge_id).is_ok()
+    }
+
     fn single_face_vertex_count(&self) -> usize {
         self.local_brep
             .faces
@@ -1,3 +1,4 @@
+mod chamfer;
 mod edge_insert;
 mod extrusion;
 mod face_cut;
@@ -6,4 +7,7 @@ mod topology;
 mod translation;
 mod vertex_remove;
 
+pub(in crate::editor) use chamfer::{
+    resolve_chamfer_edge_topology, resolve_chamfer_edge_topology_with, ChamferSpec, VertexFaces,
+};
 pub(in crate::editor) use loop_cut::collect_closed_quad_edge_ring;
@@ -0,0 +1,582 @@
+use std::collections::{HashMap, HashSet};
+
+use openmaths::Vector3;
+
+use crate::brep::{Brep, BrepBuilder};
+
+use super::super::inspection::incident_faces_for_edge;
+use super::super::validation::normalized;
+use super::super::{
+    BrepDiagnostic, EditEffect, OGFreeformGeometry, TopologyChangeJournal, GEOMETRY_EPSILON,
+};
+use super::topology::undirected_edge_key;
+
+/// How the chamfer setback is measured on the two faces adjacent to the edge.
+/// The "first" face is the face of the edge's primary halfedge, the "second"
+/// face the face of its twin.
+#[derive(Clone, Copy, Debug)]
+pub(in crate::editor) enum ChamferSpec {
+    /// Equal setback on both faces.
+    Distance(f64),
+    /// Independent setbacks on the first and second face.
+    TwoDistances(f64, f64),
+    /// Setback on the first face plus the angle (radians) between the first
+    /// face and the chamfer face.
+    DistanceAngle(f64, f64),
+}
+
+/// Per-endpoint topology of a chamfer candidate: the neighbouring vertex the
+/// endpoint slides toward on each adjacent face, and the third face that
+/// closes the corner.
+#[derive(Clone)]
+struct ChamferCorner {
+    vertex_id: u32,
+    first_neighbor: u32,
+    second_neighbor: u32,
+    corner_face_id: u32,
+}
+
+#[derive(Clone)]
+pub(in crate::editor) struct ChamferEdgeTopology {
+    first_face_id: u32,
+    second_face_id: u32,
+    from_id: u32,
+    to_id: u32,
+    first_inward: Vector3,
+    second_inward: Vector3,
+    corners: [ChamferCorner; 2],
+}
+
+impl OGFreeformGeometry {
+    pub(in crate::editor) fn chamfer_edge_internal(
+        &mut self,
+        edge_id: u32,
+        spec: ChamferSpec,
+    ) -> Result<EditEffect, BrepDiagnostic> {
+        let old_brep = self.local_brep.clone();
+        let topology = resolve_chamfer_edge_topology(&old_brep, edge_id)?;
+        let (first_distance, second_distance) = resolve_chamfer_distances(&topology, spec)
+            .map_err(|message| {
+                BrepDiagnostic::error("invalid_parameter", message)
+                    .with_domain("edge", Some(edge_id))
+            })?;
+
+        let mut positions = old_brep
+            .vertices
+            .iter()
+            .map(|vertex| vertex.position)
+            .collect::<Vec<_>>();
+
+        // Each endpoint splits in two: the original id slides along the first
+        // face and a new vertex slides along the second face.
+        let mut split_vertex_by_old = HashMap::new();
+        for corner in &topology.corners {
+            let origin = positions[corner.vertex_id as usize];
+            let first_position = slide_toward_neighbor(
+                &old_brep,
+                origin,
+                corner.first_neighbor,
+                topology.first_inward,
+                first_distance,
+            )
+            .map_err(|message| {
+                BrepDiagnostic::error("chamfer_failed", message).with_domain("edge", Some(edge_id))
+            })?;
+            let second_position = slide_toward_neighbor(
+                &old_brep,
+                origin,
+                corner.second_neighbor,
+                topology.second_inward,
+                second_distance,
+            )
+            .map_err(|message| {
+                BrepDiagnostic::error("chamfer_failed", message).with_domain("edge", Some(edge_id))
+            })?;
+
+            positions[corner.vertex_id as usize] = first_position;
+            let split_vertex_id = positions.len() as u32;
+            positions.push(second_position);
+            split_vertex_by_old.insert(corner.vertex_id, split_vertex_id);
+        }
+
+        let corner_by_face = topology
+            .corners
+            .iter()
+            .map(|corner| (corner.corner_face_id, corner))
+            .collect::<HashMap<_, _>>();
+
+        let mut rebuilt_faces = Vec::with_capacity(old_brep.faces.len() + 1);
+        for face in &old_brep.faces {
+            let rebuild_loop = |loop_id: u32| -> Vec<u32> {
+                let loop_vertices = old_brep.get_loop_vertex_indices(loop_id);
+                if face.id == topology.second_face_id {
+                    return loop_vertices
+                        .iter()
+                        .map(|vertex_id| {
+                            split_vertex_by_old
+                                .get(vertex_id)
+                                .copied()
+                                .unwrap_or(*vertex_id)
+                        })
+                        .collect();
+                }
+
+                match corner_by_face.get(&face.id) {
+                    Some(corner) => split_corner_in_loop(
+                        &loop_vertices,
+                        corner,
+                        split_vertex_by_old[&corner.vertex_id],
+                    ),
+                    None => loop_vertices,
+                }
+            };
+
+            let outer = rebuild_loop(face.outer_loop);
+            let holes = face
+                .inner_loops
+                .iter()
+                .map(|loop_id| rebuild_loop(*loop_id))
+                .collect::<Vec<_>>();
+            rebuilt_faces.push((outer, holes));
+        }
+
+        let from_split = split_vertex_by_old[&topology.from_id];
+        let to_split = split_vertex_by_old[&topology.to_id];
+        let chamfer_loop = vec![topology.to_id, topology.from_id, from_split, to_split];
+
+        let mut builder = BrepBuilder::new(self.local_brep.id);
+        builder.add_vertices(&positions);
+
+        for (outer, holes) in &rebuilt_faces {
+            builder.add_face(outer, holes).map_err(|error| {
+                BrepDiagnostic::error(
+                    "chamfer_failed",
+                    format!("Failed to rebuild face during chamfer: {}", error),
+                )
+                .with_domain("edge", Some(edge_id))
+            })?;
+        }
+
+        let chamfer_face_id = builder.add_face(&chamfer_loop, &[]).map_err(|error| {
+            BrepDiagnostic::error(
+                "chamfer_failed",
+                format!("Failed to build chamfer face: {}", error),
+            )
+            .with_domain("edge", Some(edge_id))
+        })?;
+
+        for wire in &old_brep.wires {
+            let wire_vertices = old_brep.get_wire_vertex_indices(wire.id);
+            builder
+                .add_wire(&wire_vertices, wire.is_closed)
+                .map_err(|error| {
+                    BrepDiagnostic::error(
+                        "chamfer_failed",
+                        format!("Failed to rebuild wire after chamfer: {}", error),
+                    )
+                    .with_domain("edge", Some(edge_id))
+                })?;
+        }
+
+        for shell in &old_brep.shells {
+            let mut shell_faces = shell.faces.clone();
+            if shell_faces.contains(&topology.first_face_id) {
+                shell_faces.push(chamfer_face_id);
+            }
+
+            builder
+                .add_shell(&shell_faces, shell.is_closed)
+                .map_err(|error| {
+                    BrepDiagnostic::error(
+                        "chamfer_failed",
+                        format!("Failed to rebuild shell after chamfer: {}", error),
+                    )
+                    .with_domain("edge", Some(edge_id))
+                })?;
+        }
+
+        self.local_brep = builder.build().map_err(|error| {
+            BrepDiagnostic::error(
+                "chamfer_failed",
+                format!("Failed to finalize chamfer: {}", error),
+            )
+            .with_domain("edge", Some(edge_id))
+        })?;
+
+        let mut journal = TopologyChangeJournal::default();
+        journal.faces.add_created(chamfer_face_id);
+        for (old_vertex_id, split_vertex_id) in &split_vertex_by_old {
+            journal
+                .vertices
+                .map(*old_vertex_id, vec![*old_vertex_id, *split_vertex_id]);
+            journal.vertices.add_created(*split_vertex_id);
+        }
+
+        let edge_lookup = self.edge_lookup_by_endpoints();
+        let remap_vertex_on_second_face = |vertex_id: u32, other_id: u32| -> u32 {
+            let other_is_second_neighbor = topology
+                .corners
+                .iter()
+                .any(|corner| corner.vertex_id == vertex_id && corner.second_neighbor == other_id);
+            if other_is_second_neighbor {
+                split_vertex_by_old[&vertex_id]
+            } else {
+                vertex_id
+            }
+        };
+
+        let mut changed_edges = HashSet::new();
+        for edge in &old_brep.edges {
+            let Some((from, to)) = old_brep.get_edge_endpoints(edge.id) else {
+                continue;
+            };
+
+            let mapped = if edge.id == edge_id {
+                [
+                    undirected_edge_key(topology.from_id, topology.to_id),
+                    undirected_edge_key(from_split, to_split),
+                ]
+                .iter()
+                .filter_map(|key| edge_lookup.get(key).copied())
+                .collect::<Vec<_>>()
+            } else {
+                let key = undirected_edge_key(
+                    remap_vertex_on_second_face(from, to),
+                    remap_vertex_on_second_face(to, from),
+                );
+                edge_lookup
+                    .get(&key)
+                    .copied()
+                    .map(|id| vec![id])
+                    .unwrap_or_default()
+            };
+
+            let touches_edge_endpoint = [from, to]
+                .iter()
+                .any(|id| *id == topology.from_id || *id == topology.to_id);
+            if touches_edge_endpoint {
+                changed_edges.extend(mapped.iter().copied());
+            }
+            journal.edges.map(edge.id, mapped);
+        }
+
+        for (vertex_id, split_vertex_id) in &split_vertex_by_old {
+            let key = undirected_edge_key(*vertex_id, *split_vertex_id);
+            if let Some(new_edge_id) = edge_lookup.get(&key).copied() {
+                journal.edges.add_created(new_edge_id);
+                changed_edges.insert(new_edge_id);
+            }
+        }
+
+        let mut changed_faces = vec![
+            topology.first_face_id,
+            topology.second_face_id,
+            chamfer_face_id,
+        ];
+        changed_faces.extend(topology.corners.iter().map(|corner| corner.corner_face_id));
+        changed_faces.sort_unstable();
+        changed_faces.dedup();
+
+        let mut changed_edges = changed_edges.into_iter().collect::<Vec<_>>();
+        changed_edges.sort_unstable();
+
+        let mut changed_vertices = vec![topology.from_id, topology.to_id, from_split, to_split];
+        changed_vertices.sort_unstable();
+
+        Ok(EditEffect {
+            diagnostics: vec![BrepDiagnostic::info(
+                "edge_chamfered",
+                format!(
+                    "Chamfered edge {} with setbacks {:.6} / {:.6}",
+                    edge_id, first_distance, second_distance
+                ),
+            )
+            .with_domain("edge", Some(edge_id))],
+            topology_journal: Some(journal),
+            changed_faces,
+            changed_edges,
+            changed_vertices,
+        })
+    }
+}
+
+/// Faces around each vertex, gathered in one pass over the halfedges so
+/// that many edges can be checked without rescanning the solid.
+pub(in crate::editor) struct VertexFaces(HashMap<u32, Vec<u32>>);
+
+impl VertexFaces {
+    pub(in crate::editor) fn new(brep: &Brep) -> Self {
+        let mut faces: HashMap<u32, Vec<u32>> = HashMap::new();
+        for halfedge in &brep.halfedges {
+            if let Some(face_id) = halfedge.face {
+                let around = faces.entry(halfedge.from).or_default();
+                if !around.contains(&face_id) {
+                    around.push(face_id);
+                }
+            }
+        }
+        Self(faces)
+    }
+
+    fn around(&self, vertex_id: u32) -> &[u32] {
+        self.0.get(&vertex_id).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Resolves the faces, endpoint corners and in-face setback directions needed
+/// to chamfer `edge_id`. Only convex edges between two planar faces whose
+/// endpoints each join exactly three faces are supported.
+pub(in crate::editor) fn resolve_chamfer_edge_topology(
+    brep: &Brep,
+    edge_id: u32,
+) -> Result<ChamferEdgeTopology, BrepDiagnostic> {
+    resolve_chamfer_edge_topology_with(brep, edge_id, &VertexFaces::new(brep))
+}
+
+/// [`resolve_chamfer_edge_topology`] with the vertex faces gathered once by
+/// the caller.
+pub(in crate::editor) fn resolve_chamfer_edge_topology_with(
+    brep: &Brep,
+    edge_id: u32,
+    vertex_faces: &VertexFaces,
+) -> Result<ChamferEdgeTopology, BrepDiagnostic> {
+    let unsupported = |message: &str| {
+        BrepDiagnostic::error("unsupported_topology", message.to_string())
+            .with_domain("edge", Some(edge_id))
+    };
+
+    let edge = brep
+        .edges
+        .get(edge_id as usize)
+        .ok_or_else(|| {
+            BrepDiagnostic::error("missing_edge", format!("Edge {} does not exist", edge_id))
+                .with_domain("edge", Some(edge_id))
+        })?
+        .clone();
+
+    if incident_faces_for_edge(brep, edge_id).len() != 2 {
+        return Err(unsupported(
+            "chamferEdge requires an edge shared by exactly two faces",
+        ));
+    }
+
+    let Some(first_halfedge) = brep.halfedges.get(edge.halfedge as usize) else {
+        return Err(unsupported(
+            "chamferEdge edge references a missing halfedge",
+        ));
+    };
+    let Some(second_halfedge) = edge
+        .twin_halfedge
+        .and_then(|halfedge_id| brep.halfedges.get(halfedge_id as usize))
+    else {
+        return Err(unsupported("chamferEdge edge has no twin halfedge"));
+    };
+
+    let (Some(first_face_id), Some(second_face_id)) = (first_halfedge.face, second_halfedge.face)
+    else {
+        return Err(unsupported(
+            "chamferEdge requires an edge shared by exactly two faces",
+        ));
+    };
+    let (Some(first_loop), Some(second_loop)) = (first_halfedge.loop_ref, second_halfedge.loop_ref)
+    else {
+        return Err(unsupported("chamferEdge edge is not part of a face loop"));
+    };
+
+    let from_id = first_halfedge.from;
+    let to_id = first_halfedge.to;
+    let from = brep.vertices[from_id as usize].position;
+    let to = brep.vertices[to_id as usize].position;
+    let direction = Vector3::new(to.x - from.x, to.y - from.y, to.z - from.z);
+
+    let first_normal = normalized(brep.faces[first_face_id as usize].normal)
+        .ok_or_else(|| unsupported("chamferEdge requires planar faces with valid normals"))?;
+    let second_normal = normalized(brep.faces[second_face_id as usize].normal)
+        .ok_or_else(|| unsupported("chamferEdge requires planar faces with valid normals"))?;
+
+    // Face loops wind counter-clockwise about their normal, so the face
+    // interior lies to the left of each halfedge: normal × direction.
+    let first_inward = normalized(first_normal.cross(&direction))
+        .ok_or_else(|| unsupported("chamferEdge edge is degenerate"))?;
+    let second_inward =
+        normalized(second_normal.cross(&Vector3::new(-direction.x, -direction.y, -direction.z)))
+            .ok_or_else(|| unsupported("chamferEdge edge is degenerate"))?;
+
+    if second_inward.dot(&first_normal) > -GEOMETRY_EPSILON {
+        return Err(unsupported(
+            "chamferEdge currently supports convex edges between non-coplanar faces",
+        ));
+    }
+
+    let first_loop_vertices = brep.get_loop_vertex_indices(first_loop);
+    let second_loop_vertices = brep.get_loop_vertex_indices(second_loop);
+
+    let mut corners = Vec::with_capacity(2);
+    for vertex_id in [from_id, to_id] {
+        let other_id = if vertex_id == from_id { to_id } else { from_id };
+        let first_neighbor = loop_neighbor_excluding(&first_loop_vertices, vertex_id, other_id)
+            .ok_or_else(|| unsupported("chamferEdge could not resolve the first face corner"))?;
+        let second_neighbor = loop_neighbor_excluding(&second_loop_vertices, vertex_id, other_id)
+            .ok_or_else(|| {
+            unsupported("chamferEdge could not resolve the second face corner")
+        })?;
+
+        let corner_faces = vertex_faces.around(vertex_id);
+        if corner_faces.len() != 3 {
+            return Err(unsupported(
+                "chamferEdge currently requires both edge endpoints to join exactly three faces",
+            ));
+        }
+
+        let Some(corner_face_id) = corner_faces
+            .iter()
+            .copied()
+            .find(|face_id| *face_id != first_face_id && *face_id != second_face_id)
+        else {
+            return Err(unsupported(
+                "chamferEdge could not resolve the face closing the edge corner",
+            ));
+        };
+
+        corners.push(ChamferCorner {
+            vertex_id,
+            first_neighbor,
+            second_neighbor,
+            corner_face_id,
+        });
+    }
+
+    let [from_corner, to_corner]: [ChamferCorner; 2] = corners
+        .try_into()
+        .map_err(|_| unsupported("chamferEdge could not resolve edge corners"))?;
+
+    if from_corner.corner_face_id == to_corner.corner_face_id {
+        return Err(unsupported(
+            "chamferEdge does not support edges whose corners share the same closing face",
+        ));
+    }
+
+    Ok(ChamferEdgeTopology {
+        first_face_id,
+        second_face_id,
+        from_id,
+        to_id,
+        first_inward,
+        second_inward,
+        corners: [from_corner, to_corner],
+    })
+}
+
+fn resolve_chamfer_distances(
+    topology: &ChamferEdgeTopology,
+    spec: ChamferSpec,
+) -> Result<(f64, f64), String> {
+    let (first, second) = match spec {
+        ChamferSpec::Distance(distance) => (distance, distance),
+        ChamferSpec::TwoDistances(first, second) => (first, second),
+        ChamferSpec::DistanceAngle(distance, angle) => {
+            if !angle.is_finite() || angle <= GEOMETRY_EPSILON {
+                return Err("chamferEdge angle must be a finite positive number".to_string());
+            }
+
+            // Cross-section triangle: the setbacks meet at the edge with the
+            // interior face angle between them; the law of sines gives the
+            // second setback from the first and the chamfer angle.
+            let interior_angle = topology
+                .first_inward
+                .dot(&topology.second_inward)
+                .clamp(-1.0, 1.0)
+                .acos();
+            let opposite_angle = std::f64::consts::PI - interior_angle - angle;
+            if opposite_angle <= GEOMETRY_EPSILON {
+                return Err(format!(
+                    "chamferEdge angle must be smaller than {:.6} rad for this edge",
+                    std::f64::consts::PI - interior_angle
+                ));
+            }
+
+            (distance, distance * angle.sin() / opposite_angle.sin())
+        }
+    };
+
+    for value in [first, second] {
+        if !value.is_finite() || value <= GEOMETRY_EPSILON {
+            return Err("chamferEdge distances must be finite positive numbers".to_string());
+        }
+    }
+
+    Ok((first, second))
+}
+
+/// Moves `origin` along the edge toward `neighbor_id` until it sits `distance`
+/// away from the chamfered edge, measured along `inward`.
+fn slide_toward_neighbor(
+    brep: &Brep,
+    origin: Vector3,
+    neighbor_id: u32,
+    inward: Vector3,
+    distance: f64,
+) -> Result<Vector3, String> {
+    let neighbor = brep.vertices[neighbor_id as usize].position;
+    let along = Vector3::new(
+        neighbor.x - origin.x,
+        neighbor.y - origin.y,
+        neighbor.z - origin.z,
+    );
+    let reach = along.dot(&inward);
+    if reach <= GEOMETRY_EPSILON {
+        return Err("chamferEdge adjacent edge does not run away from the chamfered edge".into());
+    }
+
+    let t = distance / reach;
+    if t >= 1.0 - GEOMETRY_EPSILON {
+        return Err(format!(
+            "chamferEdge distance {:.6} exceeds the adjacent edge length",
+            distance
+        ));
+    }
+
+    Ok(Vector3::new(
+        origin.x + along.x * t,
+        origin.y + along.y * t,
+        origin.z + along.z * t,
+    ))
+}
+
+/// Replaces the corner vertex in the closing face loop with the two vertices
+/// it split into, ordered so the loop keeps its winding.
+fn split_corner_in_loop(
+    loop_vertices: &[u32],
+    corner: &ChamferCorner,
+    split_vertex_id: u32,
+) -> Vec<u32> {
+    let mut output = Vec::with_capacity(loop_vertices.len() + 1);
+    for index in 0..loop_vertices.len() {
+        let vertex_id = loop_vertices[index];
+        if vertex_id != corner.vertex_id {
+            output.push(vertex_id);
+            continue;
+        }
+
+        let previous = loop_vertices[(index + loop_vertices.len() - 1) % loop_vertices.len()];
+        if previous == corner.first_neighbor {
+            output.push(corner.vertex_id);
+            output.push(split_vertex_id);
+        } else {
+            output.push(split_vertex_id);
+            output.push(corner.vertex_id);
+        }
+    }
+    output
+}
+
+fn loop_neighbor_excluding(loop_vertices: &[u32], vertex_id: u32, excluded: u32) -> Option<u32> {
+    let index = loop_vertices.iter().position(|id| *id == vertex_id)?;
+    let count = loop_vertices.len();
+    [
+        loop_vertices[(index + count - 1) % count],
+        loop_vertices[(index + 1) % count],
+    ]
+    .into_iter()
+    .find(|candidate| *candidate != excluded)
+}
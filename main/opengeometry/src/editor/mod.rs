@@ -20,6 +20,7 @@ use wasm_bindgen::prelude::*;
 
 use crate::freeform::{OGFreeformGeometry, ObjectTransformation};
 
+use edits::ChamferSpec;
 use remap::{build_topology_remap, topology_changed, TopologySnapshot};
 use validation::validate_geometry;
 
@@ -59,6 +60,8 @@ struct EditOperationOptions {
     preserve_coplanarity: bool,
     constraint_frame: String,
     open_surface_mode: bool,
+    chamfer_second_distance: Option<f64>,
+    chamfer_angle: Option<f64>,
 }
 
 impl Default for EditOperationOptions {
@@ -70,6 +73,8 @@ impl Default for EditOperationOptions {
             preserve_coplanarity: false,
             constraint_frame: "local".to_string(),
             open_surface_mode: false,
+            chamfer_second_distance: None,
+            chamfer_angle: None,
         }
     }
 }
@@ -339,6 +344,34 @@ impl OGFreeformEditor {
         })
     }
 
+    /// Chamfers an edge with a planar bevel face. `distance` is the setback on
+    /// the edge's first face; `chamferSecondDistance` sets an independent
+    /// setback on the second face, or `chamferAngle` (radians, measured from the
+    /// first face) derives it.
+    #[wasm_bindgen(js_name = chamferEdge)]
+    pub fn chamfer_edge(
+        &self,
+        geometry: &mut OGFreeformGeometry,
+        edge_id: u32,
+        distance: f64,
+        options_json: Option<String>,
+    ) -> Result<String, JsValue> {
+        let options = Self::parse_edit_operation_options(options_json)?;
+        let spec = match (options.chamfer_second_distance, options.chamfer_angle) {
+            (None, None) => Ok(ChamferSpec::Distance(distance)),
+            (Some(second), None) => Ok(ChamferSpec::TwoDistances(distance, second)),
+            (None, Some(angle)) => Ok(ChamferSpec::DistanceAngle(distance, angle)),
+            (Some(_), Some(_)) => Err(BrepDiagnostic::error(
+                "invalid_parameter",
+                "chamferEdge accepts either chamferSecondDistance or chamferAngle, not both",
+            )
+            .with_domain("edge", Some(edge_id))),
+        };
+        self.apply_edit(geometry, &options, |entity| {
+            entity.chamfer_edge_internal(edge_id, spec.clone()?)
+        })
+    }
+
     #[wasm_bindgen(js_name = removeVertex)]
     pub fn remove_vertex(
         &self,
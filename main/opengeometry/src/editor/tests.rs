@@ -170,6 +170,20 @@ fn cut_face(
     )
 }
 
+fn chamfer_edge(
+    editor: &OGFreeformEditor,
+    entity: &mut OGFreeformGeometry,
+    edge_id: u32,
+    distance: f64,
+    options: Option<&str>,
+) -> FreeformEditResult {
+    parse_edit_result(
+        &editor
+            .chamfer_edge(entity, edge_id, distance, options.map(str::to_string))
+            .expect("chamfer edge payload"),
+    )
+}
+
 fn assert_vec3_close(actual: Vector3, expected: Vector3) {
     assert!((actual.x - expected.x).abs() < 1.0e-9);
     assert!((actual.y - expected.y).abs() < 1.0e-9);
@@ -266,6 +280,28 @@ fn opposite_vertical_face_edges(
     (left_edge, right_edge)
 }
 
+fn create_freeform_cuboid_entity(id: &str) -> OGFreeformGeometry {
+    let mut cuboid = OGCuboid::new(format!("{}-source", id));
+    cuboid
+        .set_config(Vector3::new(0.0, 0.0, 0.0), 2.0, 2.0, 2.0)
+        .expect("cuboid config");
+
+    OGFreeformGeometry::new(id.to_string(), cuboid.get_local_brep_serialized())
+        .expect("freeform cuboid")
+}
+
+fn face_width_across_edge(entity: &OGFreeformGeometry, face_id: u32) -> f64 {
+    let vertices = entity.local_brep.get_vertices_by_face_id(face_id);
+    let mut shortest = f64::INFINITY;
+    for index in 0..vertices.len() {
+        let a = vertices[index];
+        let b = vertices[(index + 1) % vertices.len()];
+        let length = ((b.x - a.x).powi(2) + (b.y - a.y).powi(2) + (b.z - a.z).powi(2)).sqrt();
+        shortest = shortest.min(length);
+    }
+    shortest
+}
+
 fn remap_entry_for(entries: &[TopologyRemapEntry], old_id: u32) -> &TopologyRemapEntry {
     entries
         .iter()
@@ -874,3 +910,105 @@ fn topology_remap_builder_supports_split_merge_and_deleted_statuses() {
     assert_eq!(deleted_entry.primary_id, None);
     assert!(deleted_entry.new_ids.is_empty());
 }
+
+#[test]
+fn converted_cuboid_chamfer_edge_inserts_planar_face_and_splits_endpoints() {
+    let editor = create_editor();
+    let mut entity = create_freeform_cuboid_entity("entity-cuboid-chamfer");
+
+    let edge_id = vertical_edge_id(&entity);
+    assert!(entity_caps(&editor, &entity).can_chamfer_edge);
+    assert!(edge_caps(&editor, &entity, edge_id).can_chamfer_edge);
+    assert!(!face_caps(&editor, &entity, 0).can_chamfer_edge);
+
+    let result = chamfer_edge(&editor, &mut entity, edge_id, 0.25, None);
+
+    assert!(result.validity.ok);
+    assert!(result.topology_changed);
+    assert_eq!(entity.local_brep.faces.len(), 7);
+    assert_eq!(entity.local_brep.vertices.len(), 10);
+    assert_eq!(entity.local_brep.shells.len(), 1);
+    assert!(entity.local_brep.shells[0].is_closed);
+
+    let remap = result.topology_remap.expect("topology remap");
+    assert_eq!(remap.created_ids.faces.len(), 1);
+    assert_eq!(remap.created_ids.vertices.len(), 2);
+    assert_eq!(remap.created_ids.edges.len(), 2);
+    assert_eq!(
+        remap_entry_for(&remap.edges, edge_id).status,
+        TopologyRemapStatus::Split
+    );
+    assert_eq!(
+        remap
+            .vertices
+            .iter()
+            .filter(|entry| entry.status == TopologyRemapStatus::Split)
+            .count(),
+        2
+    );
+
+    let chamfer_face_id = remap.created_ids.faces[0];
+    let chamfer_width = face_width_across_edge(&entity, chamfer_face_id);
+    assert!((chamfer_width - 0.25 * std::f64::consts::SQRT_2).abs() < 1.0e-9);
+
+    let chamfer_normal = face_info(&editor, &entity, chamfer_face_id).normal;
+    assert!(chamfer_normal.y.abs() < 1.0e-9);
+    assert!((chamfer_normal.x.abs() - chamfer_normal.z.abs()).abs() < 1.0e-9);
+}
+
+#[test]
+fn chamfer_edge_supports_two_distance_and_distance_angle_modes() {
+    let editor = create_editor();
+
+    let mut two_distance = create_freeform_cuboid_entity("entity-chamfer-two-distance");
+    let edge_id = vertical_edge_id(&two_distance);
+    let result = chamfer_edge(
+        &editor,
+        &mut two_distance,
+        edge_id,
+        0.3,
+        Some(r#"{"chamferSecondDistance":0.4}"#),
+    );
+    assert!(result.validity.ok);
+    let created_face = result.topology_remap.expect("remap").created_ids.faces[0];
+    assert!((face_width_across_edge(&two_distance, created_face) - 0.5).abs() < 1.0e-9);
+
+    let mut distance_angle = create_freeform_cuboid_entity("entity-chamfer-distance-angle");
+    let edge_id = vertical_edge_id(&distance_angle);
+    let angle = std::f64::consts::FRAC_PI_6;
+    let options = format!(r#"{{"chamferAngle":{}}}"#, angle);
+    let result = chamfer_edge(&editor, &mut distance_angle, edge_id, 0.3, Some(&options));
+    assert!(result.validity.ok);
+    let created_face = result.topology_remap.expect("remap").created_ids.faces[0];
+    let expected_second = 0.3 * angle.tan();
+    let expected_width = (0.3_f64.powi(2) + expected_second.powi(2)).sqrt();
+    assert!(
+        (face_width_across_edge(&distance_angle, created_face) - expected_width).abs() < 1.0e-9
+    );
+}
+
+#[test]
+fn chamfer_edge_rejects_setback_longer_than_adjacent_edge() {
+    let editor = create_editor();
+    let mut entity = create_freeform_cuboid_entity("entity-chamfer-too-large");
+    let edge_id = vertical_edge_id(&entity);
+    let before = serde_json::to_string(&entity.local_brep).expect("brep json");
+
+    let result = chamfer_edge(&editor, &mut entity, edge_id, 2.5, None);
+
+    assert!(!result.validity.ok);
+    assert!(result
+        .validity
+        .diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.code == "chamfer_failed"));
+    assert_eq!(
+        serde_json::to_string(&entity.local_brep).expect("brep json"),
+        before
+    );
+
+    let mut polygon = create_freeform_polygon_entity("entity-chamfer-open-surface");
+    assert!(!edge_caps(&editor, &polygon, 0).can_chamfer_edge);
+    let result = chamfer_edge(&editor, &mut polygon, 0, 0.1, None);
+    assert!(!result.validity.ok);
+}
@@ -202,6 +202,7 @@ pub struct EditCapabilities {
     pub can_remove_vertex: bool,
     pub can_split_edge: bool,
     pub can_loop_cut: bool,
+    pub can_chamfer_edge: bool,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub reasons: Vec<String>,
 }
@@ -220,6 +221,7 @@ pub struct FeatureEditCapabilities {
     pub can_remove_vertex: bool,
     pub can_split_edge: bool,
     pub can_loop_cut: bool,
+    pub can_chamfer_edge: bool,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub reasons: Vec<String>,
 }
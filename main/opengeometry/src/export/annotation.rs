@@ -0,0 +1,1216 @@
+//! Dimensions and annotations for 2D drawings.
+//!
+//! An [`Annotation`] is stored semantically — the measured points in scene
+//! coordinates — and is only laid out into lines, arrowheads and text by
+//! [`Annotation::layout`] once the exporter knows the drawing scale, so arrow
+//! sizes and text heights stay constant in paper millimetres. Every exporter
+//! renders the same [`AnnotationGraphics`].
+
+use std::f64::consts::{FRAC_PI_2, PI, TAU};
+
+use openmaths::Vector3;
+use serde::{Deserialize, Serialize};
+
+use crate::export::projection::{project_point, CameraParameters, Scene2D, Segment2D, Vec2};
+use crate::units::LengthUnit;
+
+const EPSILON: f64 = 1.0e-12;
+/// Helvetica cap height as a fraction of the em. Text heights are cap
+/// heights (ISO 3098), so a 3.5 mm text uses a 3.5 / 0.718 mm font.
+pub const CAP_HEIGHT_PER_EM: f64 = 0.718;
+/// Arrows flip outside the extension lines below this many arrow lengths.
+const ARROWS_INSIDE_MIN_LENGTH: f64 = 2.5;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DimensionStandard {
+    /// ISO 129: text above the dimension line, aligned with it.
+    #[default]
+    Iso,
+    /// ASME Y14.5: horizontal text set in a break in the dimension line.
+    Ansi,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArrowheadStyle {
+    #[default]
+    ClosedFilled,
+    /// 30° open arrow.
+    Open,
+    /// 45° architectural tick.
+    Oblique,
+    Dot,
+}
+
+/// Drafting settings for dimensions. Sizes are paper millimetres.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DimensionStyle {
+    pub standard: DimensionStandard,
+    pub arrowhead: ArrowheadStyle,
+    pub arrow_size_mm: f64,
+    /// Cap height of dimension text.
+    pub text_height_mm: f64,
+    /// Clearance between text and its dimension line, and between an
+    /// extension line and the feature it starts from.
+    pub gap_mm: f64,
+    /// How far extension lines run past the dimension line.
+    pub extension_overshoot_mm: f64,
+    /// Decimal places of length values.
+    pub precision: usize,
+    /// Decimal places of angles, in degrees.
+    pub angular_precision: usize,
+    pub suppress_trailing_zeros: bool,
+    /// Append the unit symbol ("mm", "in", …) to length values.
+    pub show_unit: bool,
+    /// Unit lengths are displayed in.
+    pub unit: LengthUnit,
+    /// Unit of the scene coordinates; projected views are in metres.
+    pub scene_unit: LengthUnit,
+}
+
+impl Default for DimensionStyle {
+    fn default() -> Self {
+        Self::iso()
+    }
+}
+
+impl DimensionStyle {
+    pub fn iso() -> Self {
+        Self {
+            standard: DimensionStandard::Iso,
+            arrowhead: ArrowheadStyle::ClosedFilled,
+            arrow_size_mm: 3.0,
+            text_height_mm: 3.5,
+            gap_mm: 1.0,
+            extension_overshoot_mm: 2.0,
+            precision: 2,
+            angular_precision: 0,
+            suppress_trailing_zeros: true,
+            show_unit: false,
+            unit: LengthUnit::Millimetre,
+            scene_unit: LengthUnit::Metre,
+        }
+    }
+
+    pub fn ansi() -> Self {
+        Self {
+            standard: DimensionStandard::Ansi,
+            arrowhead: ArrowheadStyle::ClosedFilled,
+            arrow_size_mm: 3.175,
+            text_height_mm: 3.175,
+            gap_mm: 1.5875,
+            extension_overshoot_mm: 3.175,
+            precision: 3,
+            angular_precision: 0,
+            suppress_trailing_zeros: false,
+            show_unit: false,
+            unit: LengthUnit::Inch,
+            scene_unit: LengthUnit::Metre,
+        }
+    }
+
+    /// Formats a scene-space length in the display unit.
+    pub fn format_length(&self, length: f64) -> String {
+        let value = length * self.scene_unit.conversion_factor_to(self.unit);
+        let mut text = self.format_number(value, self.precision);
+        if self.show_unit {
+            text.push(' ');
+            text.push_str(self.unit.symbol());
+        }
+        text
+    }
+
+    /// Formats an angle given in radians as degrees.
+    pub fn format_angle(&self, radians: f64) -> String {
+        let mut text = self.format_number(radians.to_degrees(), self.angular_precision);
+        text.push('°');
+        text
+    }
+
+    fn format_number(&self, value: f64, precision: usize) -> String {
+        let mut text = format!("{:.*}", precision, value);
+        if self.suppress_trailing_zeros && text.contains('.') {
+            text.truncate(text.trim_end_matches('0').trim_end_matches('.').len());
+        }
+        if text == "-0" {
+            text = "0".to_string();
+        }
+        text
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinearOrientation {
+    #[default]
+    Horizontal,
+    Vertical,
+    /// Parallel to the line through both points.
+    Aligned,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrdinateAxis {
+    /// Measures x from the datum; the leader runs vertically.
+    #[default]
+    X,
+    /// Measures y from the datum; the leader runs horizontally.
+    Y,
+}
+
+/// Distance between two points along a horizontal, vertical or aligned
+/// direction. The dimension line lies `offset` scene units from `p1`, along
+/// the left-hand normal of the measuring direction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LinearDimension {
+    pub p1: Vec2,
+    pub p2: Vec2,
+    #[serde(default)]
+    pub orientation: LinearOrientation,
+    pub offset: f64,
+    /// Replaces the measured value; `<>` inside it stands for the value.
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+/// Angle swept counter-clockwise from the ray `center → start` to the ray
+/// `center → end`, drawn as an arc of `radius`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AngularDimension {
+    pub center: Vec2,
+    pub start: Vec2,
+    pub end: Vec2,
+    pub radius: f64,
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+/// Radius (or diameter) of a circle, drawn along the direction `angle`
+/// (radians) from its centre.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RadialDimension {
+    pub center: Vec2,
+    pub radius: f64,
+    pub angle: f64,
+    #[serde(default)]
+    pub diameter: bool,
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+/// Coordinate of `point` relative to the datum `origin` along one axis,
+/// written at the end of a leader `leader_length` long (signed).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OrdinateDimension {
+    pub origin: Vec2,
+    pub point: Vec2,
+    #[serde(default)]
+    pub axis: OrdinateAxis,
+    pub leader_length: f64,
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+/// Note with an arrow at `points[0]` and the text after the last point.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Leader {
+    pub points: Vec<Vec2>,
+    pub text: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// Free text anchored on its baseline.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TextNote {
+    pub position: Vec2,
+    pub text: String,
+    /// Cap height; the dimension style's text height when absent.
+    #[serde(default)]
+    pub height_mm: Option<f64>,
+    /// Radians, counter-clockwise.
+    #[serde(default)]
+    pub rotation: f64,
+    #[serde(default)]
+    pub align: TextAlign,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Annotation {
+    Linear(LinearDimension),
+    Angular(AngularDimension),
+    Radial(RadialDimension),
+    Ordinate(OrdinateDimension),
+    Leader(Leader),
+    Text(TextNote),
+}
+
+/// One line of annotation text, anchored on its baseline. `height` is the
+/// cap height in scene units.
+#[derive(Clone, Debug)]
+pub struct AnnotationText {
+    pub text: String,
+    pub position: Vec2,
+    pub height: f64,
+    pub rotation: f64,
+    pub align: TextAlign,
+}
+
+/// An annotation laid out at a given scale, in scene units.
+#[derive(Clone, Debug, Default)]
+pub struct AnnotationGraphics {
+    /// Dimension, extension and leader lines, and open arrowheads.
+    pub lines: Vec<Segment2D>,
+    /// Filled arrowheads, as closed polygons.
+    pub solids: Vec<Vec<Vec2>>,
+    pub texts: Vec<AnnotationText>,
+}
+
+impl Annotation {
+    /// Lays the annotation out for a drawing with `units_per_mm` scene units
+    /// per paper millimetre.
+    pub fn layout(&self, style: &DimensionStyle, units_per_mm: f64) -> AnnotationGraphics {
+        let mut pen = Pen {
+            style,
+            scale: units_per_mm.max(EPSILON),
+            out: AnnotationGraphics::default(),
+        };
+        match self {
+            Annotation::Linear(dimension) => pen.linear(dimension),
+            Annotation::Angular(dimension) => pen.angular(dimension),
+            Annotation::Radial(dimension) => pen.radial(dimension),
+            Annotation::Ordinate(dimension) => pen.ordinate(dimension),
+            Annotation::Leader(leader) => pen.leader(leader),
+            Annotation::Text(note) => pen.out.texts.push(AnnotationText {
+                text: note.text.clone(),
+                position: note.position,
+                height: note.height_mm.unwrap_or(style.text_height_mm) * pen.scale,
+                rotation: note.rotation,
+                align: note.align,
+            }),
+        }
+        pen.out
+    }
+
+    /// Scene points the annotation is defined by, for drawing extents.
+    pub fn reference_points(&self) -> Vec<Vec2> {
+        match self {
+            Annotation::Linear(d) => {
+                let (q1, q2) = d.dimension_line().unwrap_or((d.p1, d.p2));
+                vec![d.p1, d.p2, q1, q2]
+            }
+            Annotation::Angular(d) => {
+                let reach = |p: Vec2| {
+                    let dir = normalized(sub(p, d.center)).unwrap_or(Vec2::new(0.0, 0.0));
+                    add(d.center, scale(dir, d.radius))
+                };
+                vec![d.center, d.start, d.end, reach(d.start), reach(d.end)]
+            }
+            Annotation::Radial(d) => {
+                let dir = Vec2::new(d.angle.cos(), d.angle.sin());
+                vec![
+                    add(d.center, scale(dir, d.radius)),
+                    sub(d.center, scale(dir, d.radius)),
+                ]
+            }
+            Annotation::Ordinate(d) => vec![d.point, d.ordinate_leader().1],
+            Annotation::Leader(leader) => leader.points.clone(),
+            Annotation::Text(note) => vec![note.position],
+        }
+    }
+}
+
+impl LinearDimension {
+    /// Dimension of a projected straight segment.
+    pub fn from_segment(
+        segment: &Segment2D,
+        orientation: LinearOrientation,
+        offset: f64,
+    ) -> Option<Self> {
+        let Segment2D::Line { start, end } = segment else {
+            return None;
+        };
+        Some(Self {
+            p1: *start,
+            p2: *end,
+            orientation,
+            offset,
+            text: None,
+        })
+    }
+
+    /// The measuring direction, or `None` for coincident aligned points.
+    fn direction(&self) -> Option<Vec2> {
+        match self.orientation {
+            LinearOrientation::Horizontal => Some(Vec2::new(1.0, 0.0)),
+            LinearOrientation::Vertical => Some(Vec2::new(0.0, 1.0)),
+            LinearOrientation::Aligned => normalized(sub(self.p2, self.p1)),
+        }
+    }
+
+    /// Ends of the dimension line.
+    fn dimension_line(&self) -> Option<(Vec2, Vec2)> {
+        let u = self.direction()?;
+        let q1 = add(self.p1, scale(perp(u), self.offset));
+        Some((q1, add(q1, scale(u, dot(sub(self.p2, self.p1), u)))))
+    }
+
+    /// Measured distance in scene units.
+    pub fn measurement(&self) -> f64 {
+        self.direction()
+            .map(|u| dot(sub(self.p2, self.p1), u).abs())
+            .unwrap_or(0.0)
+    }
+}
+
+impl AngularDimension {
+    /// Dimension of the angle between two projected straight segments, at
+    /// the intersection of their supporting lines. The rays point towards
+    /// each segment's far end, so the angle spans the corner they form.
+    pub fn between_segments(a: &Segment2D, b: &Segment2D, radius: f64) -> Option<Self> {
+        let (Segment2D::Line { start: a0, end: a1 }, Segment2D::Line { start: b0, end: b1 }) =
+            (a, b)
+        else {
+            return None;
+        };
+        let (da, db) = (sub(*a1, *a0), sub(*b1, *b0));
+        let denominator = cross(da, db);
+        if denominator.abs() <= EPSILON {
+            return None;
+        }
+        let t = cross(sub(*b0, *a0), db) / denominator;
+        let center = add(*a0, scale(da, t));
+        let far = |p: Vec2, q: Vec2| {
+            if length(sub(p, center)) >= length(sub(q, center)) {
+                p
+            } else {
+                q
+            }
+        };
+        let (mut start, mut end) = (far(*a0, *a1), far(*b0, *b1));
+        // Measure the smaller, interior angle.
+        if cross(sub(start, center), sub(end, center)) < 0.0 {
+            std::mem::swap(&mut start, &mut end);
+        }
+        Some(Self {
+            center,
+            start,
+            end,
+            radius,
+            text: None,
+        })
+    }
+
+    /// Swept angle in radians, in `[0, 2π)`.
+    pub fn measurement(&self) -> f64 {
+        let (a, b) = (sub(self.start, self.center), sub(self.end, self.center));
+        (b.y.atan2(b.x) - a.y.atan2(a.x)).rem_euclid(TAU)
+    }
+}
+
+impl RadialDimension {
+    /// Dimension of a projected arc (or circular ellipse), drawn through the
+    /// middle of the arc.
+    pub fn from_segment(segment: &Segment2D, diameter: bool) -> Option<Self> {
+        let (center, radius, start_angle, end_angle) = match *segment {
+            Segment2D::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+            } => (center, radius, start_angle, end_angle),
+            Segment2D::Ellipse {
+                center,
+                rx,
+                ry,
+                rotation,
+                start_angle,
+                end_angle,
+            } if (rx - ry).abs() <= rx.abs() * 1.0e-9 => {
+                (center, rx, start_angle + rotation, end_angle + rotation)
+            }
+            _ => return None,
+        };
+        Some(Self {
+            center,
+            radius,
+            angle: 0.5 * (start_angle + end_angle),
+            diameter,
+            text: None,
+        })
+    }
+}
+
+impl OrdinateDimension {
+    /// Signed coordinate of the point from the datum, in scene units.
+    pub fn measurement(&self) -> f64 {
+        match self.axis {
+            OrdinateAxis::X => self.point.x - self.origin.x,
+            OrdinateAxis::Y => self.point.y - self.origin.y,
+        }
+    }
+
+    /// Unit leader direction and leader end.
+    fn ordinate_leader(&self) -> (Vec2, Vec2) {
+        let sign = if self.leader_length < 0.0 { -1.0 } else { 1.0 };
+        let direction = match self.axis {
+            OrdinateAxis::X => Vec2::new(0.0, sign),
+            OrdinateAxis::Y => Vec2::new(sign, 0.0),
+        };
+        (
+            direction,
+            add(self.point, scale(direction, self.leader_length.abs())),
+        )
+    }
+}
+
+/// Overall horizontal and vertical dimensions of a scene's geometry, placed
+/// `offset` scene units below and to the right of its extents.
+pub fn overall_dimensions(scene: &Scene2D, offset: f64) -> Vec<Annotation> {
+    let Some((lo, hi)) = scene.geometry_bounding_box() else {
+        return Vec::new();
+    };
+    vec![
+        Annotation::Linear(LinearDimension {
+            p1: lo,
+            p2: Vec2::new(hi.x, lo.y),
+            orientation: LinearOrientation::Horizontal,
+            offset: -offset,
+            text: None,
+        }),
+        Annotation::Linear(LinearDimension {
+            p1: Vec2::new(hi.x, lo.y),
+            p2: hi,
+            orientation: LinearOrientation::Vertical,
+            offset: -offset,
+            text: None,
+        }),
+    ]
+}
+
+/// A dimension given by 3D points on the model, placed in a view by
+/// projecting them with the view's camera. Lengths are measured in the
+/// drawing, so they are true lengths only for features parallel to the
+/// view plane of an orthographic view.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ModelDimension {
+    Linear {
+        p1: Vector3,
+        p2: Vector3,
+        #[serde(default)]
+        orientation: LinearOrientation,
+        offset: f64,
+        #[serde(default)]
+        text: Option<String>,
+    },
+    Angular {
+        center: Vector3,
+        start: Vector3,
+        end: Vector3,
+        radius: f64,
+        #[serde(default)]
+        text: Option<String>,
+    },
+    /// `point` is any point on the circle; it fixes both radius and the
+    /// direction the dimension is drawn in.
+    Radial {
+        center: Vector3,
+        point: Vector3,
+        #[serde(default)]
+        diameter: bool,
+        #[serde(default)]
+        text: Option<String>,
+    },
+    Ordinate {
+        origin: Vector3,
+        point: Vector3,
+        #[serde(default)]
+        axis: OrdinateAxis,
+        leader_length: f64,
+        #[serde(default)]
+        text: Option<String>,
+    },
+    Leader {
+        points: Vec<Vector3>,
+        text: String,
+    },
+}
+
+impl ModelDimension {
+    /// The annotation in the view of `camera`, or `None` if a point lies
+    /// behind the camera.
+    pub fn project(&self, camera: &CameraParameters) -> Option<Annotation> {
+        let at = |p: &Vector3| project_point(p, camera);
+        Some(match self {
+            ModelDimension::Linear {
+                p1,
+                p2,
+                orientation,
+                offset,
+                text,
+            } => Annotation::Linear(LinearDimension {
+                p1: at(p1)?,
+                p2: at(p2)?,
+                orientation: *orientation,
+                offset: *offset,
+                text: text.clone(),
+            }),
+            ModelDimension::Angular {
+                center,
+                start,
+                end,
+                radius,
+                text,
+            } => Annotation::Angular(AngularDimension {
+                center: at(center)?,
+                start: at(start)?,
+                end: at(end)?,
+                radius: *radius,
+                text: text.clone(),
+            }),
+            ModelDimension::Radial {
+                center,
+                point,
+                diameter,
+                text,
+            } => {
+                let (center, point) = (at(center)?, at(point)?);
+                let spoke = sub(point, center);
+                Annotation::Radial(RadialDimension {
+                    center,
+                    radius: length(spoke),
+                    angle: spoke.y.atan2(spoke.x),
+                    diameter: *diameter,
+                    text: text.clone(),
+                })
+            }
+            ModelDimension::Ordinate {
+                origin,
+                point,
+                axis,
+                leader_length,
+                text,
+            } => Annotation::Ordinate(OrdinateDimension {
+                origin: at(origin)?,
+                point: at(point)?,
+                axis: *axis,
+                leader_length: *leader_length,
+                text: text.clone(),
+            }),
+            ModelDimension::Leader { points, text } => Annotation::Leader(Leader {
+                points: points.iter().map(at).collect::<Option<_>>()?,
+                text: text.clone(),
+            }),
+        })
+    }
+}
+
+/// Approximate advance width of `text` in Helvetica at cap height
+/// `cap_height`, used to centre and clear dimension text consistently
+/// across exporters.
+pub fn text_width(text: &str, cap_height: f64) -> f64 {
+    let ems: f64 = text
+        .chars()
+        .map(|ch| match ch {
+            '0'..='9' | '+' | '±' | '$' => 0.556,
+            '.' | ',' | ' ' | '/' | ':' | 'i' | 'j' | 'l' => 0.278,
+            '-' | '(' | ')' | '°' | 'r' | 't' | 'f' => 0.333,
+            'Ø' | 'M' | 'm' | 'W' => 0.833,
+            'A'..='Z' => 0.667,
+            _ => 0.556,
+        })
+        .sum();
+    ems * cap_height / CAP_HEIGHT_PER_EM
+}
+
+/// Replaces `<>` in an override with the measured value.
+fn dimension_text(text: &Option<String>, measured: String) -> String {
+    match text {
+        Some(text) => text.replace("<>", &measured),
+        None => measured,
+    }
+}
+
+/// Folds an angle into `(-π/2, π/2]` so rotated text never reads upside down.
+fn readable(angle: f64) -> f64 {
+    let angle = (angle + PI).rem_euclid(TAU) - PI;
+    if angle > FRAC_PI_2 + EPSILON {
+        angle - PI
+    } else if angle <= -FRAC_PI_2 + EPSILON {
+        angle + PI
+    } else {
+        angle
+    }
+}
+
+struct Pen<'a> {
+    style: &'a DimensionStyle,
+    /// Scene units per paper millimetre.
+    scale: f64,
+    out: AnnotationGraphics,
+}
+
+impl Pen<'_> {
+    fn mm(&self, value: f64) -> f64 {
+        value * self.scale
+    }
+
+    fn line(&mut self, start: Vec2, end: Vec2) {
+        if length(sub(end, start)) > EPSILON {
+            self.out.lines.push(Segment2D::Line { start, end });
+        }
+    }
+
+    /// Arrowhead with its tip at `tip`, pointing along unit `direction`.
+    fn arrow(&mut self, tip: Vec2, direction: Vec2) {
+        let size = self.mm(self.style.arrow_size_mm);
+        let side = perp(direction);
+        let back = sub(tip, scale(direction, size));
+        match self.style.arrowhead {
+            ArrowheadStyle::ClosedFilled => self.out.solids.push(vec![
+                tip,
+                add(back, scale(side, size / 6.0)),
+                sub(back, scale(side, size / 6.0)),
+            ]),
+            ArrowheadStyle::Open => {
+                let spread = size * 15f64.to_radians().tan();
+                self.line(add(back, scale(side, spread)), tip);
+                self.line(tip, sub(back, scale(side, spread)));
+            }
+            ArrowheadStyle::Oblique => {
+                let tick = scale(add(direction, side), 0.5 * size / 2f64.sqrt());
+                self.line(sub(tip, tick), add(tip, tick));
+            }
+            ArrowheadStyle::Dot => {
+                let radius = 0.25 * size;
+                self.out.solids.push(
+                    (0..16)
+                        .map(|i| {
+                            let a = i as f64 * TAU / 16.0;
+                            add(tip, Vec2::new(radius * a.cos(), radius * a.sin()))
+                        })
+                        .collect(),
+                );
+            }
+        }
+    }
+
+    fn text(&mut self, text: String, position: Vec2, rotation: f64, align: TextAlign) {
+        self.out.texts.push(AnnotationText {
+            text,
+            position,
+            height: self.mm(self.style.text_height_mm),
+            rotation,
+            align,
+        });
+    }
+
+    fn text_height(&self) -> f64 {
+        self.mm(self.style.text_height_mm)
+    }
+
+    /// Extension line from a feature point towards (and past) the
+    /// dimension line.
+    fn extension(&mut self, from: Vec2, to: Vec2) {
+        let gap = self.mm(self.style.gap_mm);
+        let reach = sub(to, from);
+        if let Some(direction) = normalized(reach).filter(|_| length(reach) > gap) {
+            let overshoot = self.mm(self.style.extension_overshoot_mm);
+            self.line(
+                add(from, scale(direction, gap)),
+                add(to, scale(direction, overshoot)),
+            );
+        }
+    }
+
+    /// Text set along the dimension direction `direction` at `mid`: above
+    /// the line (ISO) or horizontal in a break in it (ANSI). Returns the
+    /// half-length of the break to leave in the line, if any.
+    fn dimension_text(&mut self, text: String, mid: Vec2, direction: Vec2, room: f64) -> f64 {
+        let gap = self.mm(self.style.gap_mm);
+        let height = self.text_height();
+        if self.style.standard == DimensionStandard::Ansi {
+            let half_width = 0.5 * text_width(&text, height) + gap;
+            let half_height = 0.5 * height + gap;
+            let mut clearance = f64::INFINITY;
+            if direction.x.abs() > EPSILON {
+                clearance = clearance.min(half_width / direction.x.abs());
+            }
+            if direction.y.abs() > EPSILON {
+                clearance = clearance.min(half_height / direction.y.abs());
+            }
+            if 2.0 * clearance < room {
+                self.text(
+                    text,
+                    sub(mid, Vec2::new(0.0, 0.5 * height)),
+                    0.0,
+                    TextAlign::Center,
+                );
+                return clearance;
+            }
+            // No room for a break: sit the text above the line instead.
+            let lift = 0.5 * height + gap + half_width * direction.y.abs();
+            self.text(
+                text,
+                add(mid, Vec2::new(0.0, lift - 0.5 * height)),
+                0.0,
+                TextAlign::Center,
+            );
+            return 0.0;
+        }
+        let rotation = readable(direction.y.atan2(direction.x));
+        let up = Vec2::new(-rotation.sin(), rotation.cos());
+        self.text(text, add(mid, scale(up, gap)), rotation, TextAlign::Center);
+        0.0
+    }
+
+    fn linear(&mut self, dimension: &LinearDimension) {
+        let Some((q1, q2)) = dimension.dimension_line() else {
+            return;
+        };
+        let Some(e) = normalized(sub(q2, q1)) else {
+            return;
+        };
+        self.extension(dimension.p1, q1);
+        self.extension(dimension.p2, q2);
+
+        let arrow = self.mm(self.style.arrow_size_mm);
+        let span = length(sub(q2, q1));
+        let inside = span >= ARROWS_INSIDE_MIN_LENGTH * arrow;
+        let (l0, l1) = if inside {
+            self.arrow(q1, scale(e, -1.0));
+            self.arrow(q2, e);
+            (q1, q2)
+        } else {
+            self.arrow(q1, e);
+            self.arrow(q2, scale(e, -1.0));
+            (
+                sub(q1, scale(e, 2.0 * arrow)),
+                add(q2, scale(e, 2.0 * arrow)),
+            )
+        };
+
+        let mid = scale(add(q1, q2), 0.5);
+        let text = dimension_text(
+            &dimension.text,
+            self.style.format_length(dimension.measurement()),
+        );
+        let room = if inside { span - 2.0 * arrow } else { 0.0 };
+        let clearance = self.dimension_text(text, mid, e, room);
+        if clearance > 0.0 {
+            self.line(l0, sub(mid, scale(e, clearance)));
+            self.line(add(mid, scale(e, clearance)), l1);
+        } else {
+            self.line(l0, l1);
+        }
+    }
+
+    fn angular(&mut self, dimension: &AngularDimension) {
+        let radius = dimension.radius.abs();
+        let (Some(da), Some(db)) = (
+            normalized(sub(dimension.start, dimension.center)),
+            normalized(sub(dimension.end, dimension.center)),
+        ) else {
+            return;
+        };
+        let sweep = dimension.measurement();
+        if radius <= EPSILON || sweep <= EPSILON {
+            return;
+        }
+        let start_angle = da.y.atan2(da.x);
+        let end_angle = start_angle + sweep;
+        let at = |angle: f64| {
+            add(
+                dimension.center,
+                Vec2::new(radius * angle.cos(), radius * angle.sin()),
+            )
+        };
+        let tangent = |angle: f64| Vec2::new(-angle.sin(), angle.cos());
+
+        for (point, direction) in [(dimension.start, da), (dimension.end, db)] {
+            let reach = length(sub(point, dimension.center));
+            if radius > reach {
+                self.extension(point, add(dimension.center, scale(direction, radius)));
+            }
+        }
+
+        self.out.lines.push(Segment2D::Arc {
+            center: dimension.center,
+            radius,
+            start_angle,
+            end_angle,
+        });
+        let arrow = self.mm(self.style.arrow_size_mm);
+        if radius * sweep >= ARROWS_INSIDE_MIN_LENGTH * arrow {
+            self.arrow(at(start_angle), scale(tangent(start_angle), -1.0));
+            self.arrow(at(end_angle), tangent(end_angle));
+        } else {
+            self.arrow(at(start_angle), tangent(start_angle));
+            self.arrow(at(end_angle), scale(tangent(end_angle), -1.0));
+        }
+
+        let middle = start_angle + 0.5 * sweep;
+        let radial = Vec2::new(middle.cos(), middle.sin());
+        let text = dimension_text(&dimension.text, self.style.format_angle(sweep));
+        let (gap, height) = (self.mm(self.style.gap_mm), self.text_height());
+        if self.style.standard == DimensionStandard::Ansi {
+            let half_width = 0.5 * text_width(&text, height);
+            let reach = gap + radial.x.abs() * half_width + radial.y.abs() * 0.5 * height;
+            let centre = add(at(middle), scale(radial, reach));
+            self.text(
+                text,
+                sub(centre, Vec2::new(0.0, 0.5 * height)),
+                0.0,
+                TextAlign::Center,
+            );
+        } else {
+            let rotation = readable(middle + FRAC_PI_2);
+            let up = Vec2::new(-rotation.sin(), rotation.cos());
+            // Keep the text outside the arc whichever way it reads.
+            let lift = if dot(up, radial) >= 0.0 {
+                gap
+            } else {
+                gap + height
+            };
+            self.text(
+                text,
+                add(at(middle), scale(radial, lift)),
+                rotation,
+                TextAlign::Center,
+            );
+        }
+    }
+
+    fn radial(&mut self, dimension: &RadialDimension) {
+        let radius = dimension.radius.abs();
+        if radius <= EPSILON {
+            return;
+        }
+        let direction = Vec2::new(dimension.angle.cos(), dimension.angle.sin());
+        let tip = add(dimension.center, scale(direction, radius));
+        let (measured, prefix, tail) = if dimension.diameter {
+            (
+                2.0 * radius,
+                "Ø",
+                sub(dimension.center, scale(direction, radius)),
+            )
+        } else {
+            (radius, "R", dimension.center)
+        };
+        let text = dimension_text(
+            &dimension.text,
+            format!("{prefix}{}", self.style.format_length(measured)),
+        );
+
+        self.arrow(tip, direction);
+        if dimension.diameter {
+            self.arrow(tail, scale(direction, -1.0));
+        }
+
+        if self.style.standard == DimensionStandard::Ansi {
+            // Leader out through the arc to a horizontal landing.
+            let arrow = self.mm(self.style.arrow_size_mm);
+            let (gap, height) = (self.mm(self.style.gap_mm), self.text_height());
+            let elbow = add(tip, scale(direction, 2.0 * arrow));
+            let side = if direction.x < 0.0 { -1.0 } else { 1.0 };
+            let landing = add(elbow, Vec2::new(side * arrow, 0.0));
+            self.line(tail, elbow);
+            self.line(elbow, landing);
+            let align = if side > 0.0 {
+                TextAlign::Left
+            } else {
+                TextAlign::Right
+            };
+            self.text(
+                text,
+                add(landing, Vec2::new(side * gap, -0.5 * height)),
+                0.0,
+                align,
+            );
+        } else {
+            self.line(tail, tip);
+            let gap = self.mm(self.style.gap_mm);
+            let rotation = readable(dimension.angle);
+            let up = Vec2::new(-rotation.sin(), rotation.cos());
+            let mid = scale(add(tail, tip), 0.5);
+            self.text(text, add(mid, scale(up, gap)), rotation, TextAlign::Center);
+        }
+    }
+
+    fn ordinate(&mut self, dimension: &OrdinateDimension) {
+        let (direction, end) = dimension.ordinate_leader();
+        let gap = self.mm(self.style.gap_mm);
+        let height = self.text_height();
+        self.line(add(dimension.point, scale(direction, gap)), end);
+
+        let text = dimension_text(
+            &dimension.text,
+            self.style.format_length(dimension.measurement()),
+        );
+        let rotation = readable(direction.y.atan2(direction.x));
+        let reading = Vec2::new(rotation.cos(), rotation.sin());
+        let up = Vec2::new(-rotation.sin(), rotation.cos());
+        let align = if dot(reading, direction) > 0.0 {
+            TextAlign::Left
+        } else {
+            TextAlign::Right
+        };
+        // Centre the text on the leader line.
+        let anchor = sub(add(end, scale(direction, gap)), scale(up, 0.5 * height));
+        self.text(text, anchor, rotation, align);
+    }
+
+    fn leader(&mut self, leader: &Leader) {
+        let [first, second, ..] = leader.points.as_slice() else {
+            return;
+        };
+        if let Some(direction) = normalized(sub(*first, *second)) {
+            self.arrow(*first, direction);
+        }
+        for pair in leader.points.windows(2) {
+            self.line(pair[0], pair[1]);
+        }
+
+        let last = leader.points[leader.points.len() - 1];
+        let previous = leader.points[leader.points.len() - 2];
+        let side = if last.x < previous.x { -1.0 } else { 1.0 };
+        let (arrow, gap, height) = (
+            self.mm(self.style.arrow_size_mm),
+            self.mm(self.style.gap_mm),
+            self.text_height(),
+        );
+        let landing = add(last, Vec2::new(side * arrow, 0.0));
+        self.line(last, landing);
+        let align = if side > 0.0 {
+            TextAlign::Left
+        } else {
+            TextAlign::Right
+        };
+        self.text(
+            leader.text.clone(),
+            add(landing, Vec2::new(side * gap, -0.5 * height)),
+            0.0,
+            align,
+        );
+    }
+}
+
+fn add(a: Vec2, b: Vec2) -> Vec2 {
+    Vec2::new(a.x + b.x, a.y + b.y)
+}
+
+fn sub(a: Vec2, b: Vec2) -> Vec2 {
+    Vec2::new(a.x - b.x, a.y - b.y)
+}
+
+fn scale(v: Vec2, s: f64) -> Vec2 {
+    Vec2::new(v.x * s, v.y * s)
+}
+
+fn dot(a: Vec2, b: Vec2) -> f64 {
+    a.x * b.x + a.y * b.y
+}
+
+fn cross(a: Vec2, b: Vec2) -> f64 {
+    a.x * b.y - a.y * b.x
+}
+
+fn perp(v: Vec2) -> Vec2 {
+    Vec2::new(-v.y, v.x)
+}
+
+fn length(v: Vec2) -> f64 {
+    v.x.hypot(v.y)
+}
+
+fn normalized(v: Vec2) -> Option<Vec2> {
+    let len = length(v);
+    (len > EPSILON).then(|| scale(v, 1.0 / len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Scene in metres drawn at 1:1.
+    const ONE_TO_ONE: f64 = 0.001;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1.0e-9
+    }
+
+    fn horizontal(p2: Vec2, offset: f64) -> Annotation {
+        Annotation::Linear(LinearDimension {
+            p1: Vec2::new(0.0, 0.0),
+            p2,
+            orientation: LinearOrientation::Horizontal,
+            offset,
+            text: None,
+        })
+    }
+
+    #[test]
+    fn lengths_and_angles_format_per_style() {
+        let iso = DimensionStyle::iso();
+        assert_eq!(iso.format_length(1.25), "1250");
+        assert_eq!(iso.format_length(0.0125), "12.5");
+        assert_eq!(iso.format_angle(FRAC_PI_2), "90°");
+
+        let ansi = DimensionStyle::ansi();
+        assert_eq!(ansi.format_length(0.0254), "1.000");
+        let with_unit = DimensionStyle {
+            show_unit: true,
+            unit: LengthUnit::Metre,
+            ..DimensionStyle::iso()
+        };
+        assert_eq!(with_unit.format_length(2.5), "2.5 m");
+    }
+
+    #[test]
+    fn iso_linear_dimension_sets_text_above_an_unbroken_line() {
+        let style = DimensionStyle::iso();
+        let graphics = horizontal(Vec2::new(1.0, 0.2), -0.1).layout(&style, ONE_TO_ONE);
+
+        // Two extension lines and one dimension line between the arrows.
+        assert_eq!(graphics.lines.len(), 3);
+        assert_eq!(graphics.solids.len(), 2);
+        let Segment2D::Line { start, end } = graphics.lines[2] else {
+            panic!("dimension line is straight");
+        };
+        assert!(close(start.y, -0.1) && close(end.y, -0.1));
+        assert!(close(start.x, 0.0) && close(end.x, 1.0));
+        // The second extension line climbs from p2 past the dimension line.
+        let Segment2D::Line { start, end } = graphics.lines[1] else {
+            panic!("extension line is straight");
+        };
+        assert!(close(start.y, 0.2 - 0.001) && close(end.y, -0.1 - 0.002));
+
+        let text = &graphics.texts[0];
+        assert_eq!(text.text, "1000");
+        assert_eq!(text.align, TextAlign::Center);
+        assert!(close(text.position.x, 0.5) && close(text.position.y, -0.1 + 0.001));
+        assert!(close(text.height, 0.0035) && close(text.rotation, 0.0));
+    }
+
+    #[test]
+    fn ansi_text_breaks_the_line_and_short_dimensions_flip_arrows() {
+        let style = DimensionStyle::ansi();
+        let graphics = horizontal(Vec2::new(0.1, 0.0), 0.02).layout(&style, ONE_TO_ONE);
+        assert_eq!(graphics.lines.len(), 4, "dimension line split around text");
+        assert!(close(graphics.texts[0].rotation, 0.0));
+        assert_eq!(graphics.texts[0].text, "3.937");
+
+        // 5 mm is under 2.5 arrow lengths: arrows point inwards from outside.
+        let graphics =
+            horizontal(Vec2::new(0.005, 0.0), 0.01).layout(&DimensionStyle::iso(), ONE_TO_ONE);
+        let [first, _] = [&graphics.solids[0], &graphics.solids[1]];
+        assert!(close(first[0].x, 0.0));
+        assert!(first[1].x < 0.0, "arrow at p1 lies outside, pointing in");
+    }
+
+    #[test]
+    fn vertical_aligned_text_reads_from_the_right() {
+        let dimension = Annotation::Linear(LinearDimension {
+            p1: Vec2::new(0.0, 1.0),
+            p2: Vec2::new(0.0, 0.0),
+            orientation: LinearOrientation::Aligned,
+            offset: 0.1,
+            text: Some("<> TYP".to_string()),
+        });
+        let graphics = dimension.layout(&DimensionStyle::iso(), ONE_TO_ONE);
+        let text = &graphics.texts[0];
+        assert_eq!(text.text, "1000 TYP");
+        assert!(close(text.rotation, FRAC_PI_2));
+    }
+
+    #[test]
+    fn dimensions_derive_from_projected_geometry() {
+        let a = Segment2D::Line {
+            start: Vec2::new(0.0, 0.0),
+            end: Vec2::new(1.0, 0.0),
+        };
+        let b = Segment2D::Line {
+            start: Vec2::new(0.0, 1.0),
+            end: Vec2::new(0.0, 0.0),
+        };
+        let angle = AngularDimension::between_segments(&a, &b, 0.3).unwrap();
+        assert!(close(angle.center.x, 0.0) && close(angle.center.y, 0.0));
+        assert!(close(angle.measurement(), FRAC_PI_2));
+        let graphics = Annotation::Angular(angle).layout(&DimensionStyle::iso(), ONE_TO_ONE);
+        assert!(matches!(graphics.lines[0], Segment2D::Arc { .. }));
+        assert_eq!(graphics.texts[0].text, "90°");
+
+        let arc = Segment2D::Arc {
+            center: Vec2::new(1.0, 1.0),
+            radius: 0.25,
+            start_angle: 0.0,
+            end_angle: PI,
+        };
+        let radial = RadialDimension::from_segment(&arc, false).unwrap();
+        assert!(close(radial.angle, FRAC_PI_2));
+        let graphics = Annotation::Radial(radial).layout(&DimensionStyle::iso(), ONE_TO_ONE);
+        assert_eq!(graphics.texts[0].text, "R250");
+        let diameter = RadialDimension::from_segment(&arc, true).unwrap();
+        let graphics = Annotation::Radial(diameter).layout(&DimensionStyle::iso(), ONE_TO_ONE);
+        assert_eq!(graphics.texts[0].text, "Ø500");
+        assert_eq!(graphics.solids.len(), 2);
+
+        let mut scene = Scene2D::new();
+        for segment in [a, b] {
+            scene.add_segment(crate::export::projection::ClassifiedSegment {
+                geometry: segment,
+                class: crate::export::projection::EdgeClass::VisibleOutline,
+                layer: None,
+                source_entity_id: None,
+            });
+        }
+        let overall = overall_dimensions(&scene, 0.1);
+        assert_eq!(overall.len(), 2);
+        let Annotation::Linear(width) = &overall[0] else {
+            panic!("overall dimensions are linear");
+        };
+        assert!(close(width.measurement(), 1.0));
+    }
+
+    #[test]
+    fn ordinate_and_model_dimensions() {
+        let ordinate = OrdinateDimension {
+            origin: Vec2::new(0.0, 0.0),
+            point: Vec2::new(0.4, 0.3),
+            axis: OrdinateAxis::Y,
+            leader_length: -0.05,
+            text: None,
+        };
+        assert!(close(ordinate.measurement(), 0.3));
+        let graphics = Annotation::Ordinate(ordinate).layout(&DimensionStyle::iso(), ONE_TO_ONE);
+        assert_eq!(graphics.texts[0].text, "300");
+        assert_eq!(graphics.texts[0].align, TextAlign::Right);
+        assert!(graphics.texts[0].position.x < 0.35);
+
+        // Looking down -z: world x and y map straight onto the view.
+        let camera = CameraParameters {
+            position: Vector3::new(0.0, 0.0, 10.0),
+            target: Vector3::new(0.0, 0.0, 0.0),
+            up: Vector3::new(0.0, 1.0, 0.0),
+            ..CameraParameters::default()
+        };
+        let model = ModelDimension::Radial {
+            center: Vector3::new(1.0, 1.0, 0.0),
+            point: Vector3::new(1.0, 1.5, 0.0),
+            diameter: false,
+            text: None,
+        };
+        let Some(Annotation::Radial(radial)) = model.project(&camera) else {
+            panic!("radial dimension projects");
+        };
+        assert!(close(radial.radius, 0.5) && close(radial.angle, FRAC_PI_2));
+        assert!(close(radial.center.x, 1.0) && close(radial.center.y, 1.0));
+    }
+}
@@ -0,0 +1,489 @@
+//! DXF export of 2D drawings.
+//!
+//! Geometry is written to model space in millimetres with arcs and ellipses
+//! kept exact. Edges go on their AIA layer, or a layer named after their
+//! edge class, with ISO 128 lineweights; hidden edges use a `HIDDEN`
+//! linetype. Annotations are laid out for the plot scale and exploded into
+//! lines, solid arrowheads and text on a `DIMENSIONS` layer.
+
+use std::fmt;
+
+use dxf::entities::{Arc, Ellipse, Entity, EntityType, Line, LwPolyline, Solid, Text};
+use dxf::enums::{AcadVersion, HorizontalTextJustification, Units};
+use dxf::tables::{Layer, LineType};
+use dxf::{Color, Drawing, LwPolylineVertex, Point, Vector};
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+use crate::export::annotation::TextAlign;
+use crate::export::hatch::{hatch_lines, HATCH_LINE_WIDTH_MM};
+use crate::export::projection::{EdgeClass, Scene2D, Segment2D, Vec2, THIN_LINE_WIDTH_MM};
+use crate::export::sheet::Sheet;
+use crate::units::LengthUnit;
+
+const DIMENSION_LAYER: &str = "DIMENSIONS";
+const HATCH_LAYER: &str = "HATCH";
+const HIDDEN_LINE_TYPE: &str = "HIDDEN";
+/// Paper the drawing is fitted to when no plot scale is given (A4
+/// landscape less 10 mm margins).
+const FIT_WIDTH_MM: f64 = 277.0;
+const FIT_HEIGHT_MM: f64 = 190.0;
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct DxfExportConfig {
+    /// Plot scale denominator `N` of a 1:N drawing. Sizes annotation text,
+    /// arrows and hatch spacing; `None` fits the drawing to an A4 sheet.
+    pub scale_denominator: Option<f64>,
+}
+
+#[derive(Debug)]
+pub enum DxfExportError {
+    EmptyScene,
+    InvalidConfig(String),
+    Write(String),
+}
+
+impl fmt::Display for DxfExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DxfExportError::EmptyScene => write!(f, "Cannot export empty scene"),
+            DxfExportError::InvalidConfig(message) => write!(f, "Invalid config: {}", message),
+            DxfExportError::Write(message) => write!(f, "DXF write error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for DxfExportError {}
+
+/// Wasm entry point: render a serialized [`Scene2D`] as ASCII DXF.
+/// `config_json` may be empty to fit the drawing to A4.
+#[wasm_bindgen(js_name = exportScene2DToDxf)]
+pub fn export_scene_to_dxf_wasm(
+    scene_json: String,
+    config_json: String,
+) -> Result<String, JsValue> {
+    let scene: Scene2D = serde_json::from_str(&scene_json)
+        .map_err(|e| JsValue::from_str(&format!("Invalid Scene2D JSON: {}", e)))?;
+    let config = if config_json.trim().is_empty() {
+        DxfExportConfig::default()
+    } else {
+        serde_json::from_str(&config_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid DXF config JSON: {}", e)))?
+    };
+    let bytes = export_scene_to_dxf_bytes(&scene, &config)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    String::from_utf8(bytes).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Wasm entry point: render a serialized [`Sheet`] as ASCII DXF.
+#[wasm_bindgen(js_name = exportSheetToDxf)]
+pub fn export_sheet_to_dxf_wasm(sheet_json: String) -> Result<String, JsValue> {
+    let sheet: Sheet = serde_json::from_str(&sheet_json)
+        .map_err(|e| JsValue::from_str(&format!("Invalid sheet JSON: {}", e)))?;
+    let bytes = export_sheet_to_dxf_bytes(&sheet).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    String::from_utf8(bytes).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Renders a sheet in paper millimetres at 1:1.
+pub fn export_sheet_to_dxf_bytes(sheet: &Sheet) -> Result<Vec<u8>, DxfExportError> {
+    let config = DxfExportConfig {
+        scale_denominator: Some(1.0),
+    };
+    export_scene_to_dxf_bytes(&sheet.compose(), &config)
+}
+
+/// Renders a scene as an ASCII DXF (AutoCAD 2000) file.
+pub fn export_scene_to_dxf_bytes(
+    scene: &Scene2D,
+    config: &DxfExportConfig,
+) -> Result<Vec<u8>, DxfExportError> {
+    if scene.is_empty() {
+        return Err(DxfExportError::EmptyScene);
+    }
+    // Millimetres per scene unit.
+    let to_mm = scene
+        .dimension_style
+        .scene_unit
+        .conversion_factor_to(LengthUnit::Millimetre);
+    let model_mm_per_paper_mm = match config.scale_denominator {
+        Some(denominator) if denominator.is_finite() && denominator > 0.0 => denominator,
+        Some(denominator) => {
+            return Err(DxfExportError::InvalidConfig(format!(
+                "scale denominator must be positive, got {}",
+                denominator
+            )))
+        }
+        None => scene
+            .bounding_box()
+            .map(|(lo, hi)| {
+                let (width, height) = ((hi.x - lo.x) * to_mm, (hi.y - lo.y) * to_mm);
+                (width / FIT_WIDTH_MM).max(height / FIT_HEIGHT_MM)
+            })
+            .filter(|ratio| *ratio > 0.0)
+            .unwrap_or(1.0),
+    };
+
+    let mut writer = DxfWriter {
+        drawing: Drawing::new(),
+        to_mm,
+    };
+    writer.drawing.header.version = AcadVersion::R2000;
+    writer.drawing.header.default_drawing_units = Units::Millimeters;
+    writer.drawing.add_line_type(LineType {
+        name: HIDDEN_LINE_TYPE.to_string(),
+        description: "Hidden __ __ __".to_string(),
+        element_count: 2,
+        total_pattern_length: 4.5,
+        dash_dot_space_lengths: vec![3.0, -1.5],
+        ..Default::default()
+    });
+
+    for fill in &scene.fills {
+        let layer = fill.layer.as_deref().unwrap_or(HATCH_LAYER);
+        writer.ensure_layer(layer);
+        let rings: Vec<Vec<Vec2>> = fill
+            .boundary
+            .iter()
+            .filter(|ring| ring.len() >= 3)
+            .map(|ring| ring.iter().map(|p| writer.map(*p)).collect())
+            .collect();
+        if fill.pattern.is_solid() {
+            // Solid regions are outlined; DXF SOLIDs cannot express holes.
+            for ring in &rings {
+                writer.add_polyline(ring, true, layer, HATCH_LINE_WIDTH_MM, false);
+            }
+            continue;
+        }
+        for segment in hatch_lines(&rings, fill.pattern, model_mm_per_paper_mm) {
+            if let Segment2D::Line { start, end } = segment {
+                writer.add(
+                    EntityType::Line(Line::new(point(start), point(end))),
+                    layer,
+                    HATCH_LINE_WIDTH_MM,
+                    false,
+                );
+            }
+        }
+    }
+
+    for segment in &scene.segments {
+        let layer = segment
+            .layer
+            .clone()
+            .unwrap_or_else(|| segment.class.as_str().to_string());
+        writer.ensure_layer(&layer);
+        writer.add_segment(
+            &segment.geometry,
+            &layer,
+            segment.class.line_width_mm(),
+            segment.class == EdgeClass::Hidden,
+        );
+    }
+
+    if !scene.annotations.is_empty() {
+        writer.ensure_layer(DIMENSION_LAYER);
+        let units_per_mm = model_mm_per_paper_mm / to_mm;
+        for annotation in &scene.annotations {
+            let graphics = annotation.layout(&scene.dimension_style, units_per_mm);
+            for line in &graphics.lines {
+                writer.add_segment(line, DIMENSION_LAYER, THIN_LINE_WIDTH_MM, false);
+            }
+            for solid in &graphics.solids {
+                writer.add_solid(solid);
+            }
+            for text in &graphics.texts {
+                let anchor = point(writer.map(text.position));
+                let horizontal_text_justification = match text.align {
+                    TextAlign::Left => HorizontalTextJustification::Left,
+                    TextAlign::Center => HorizontalTextJustification::Center,
+                    TextAlign::Right => HorizontalTextJustification::Right,
+                };
+                writer.add(
+                    EntityType::Text(Text {
+                        location: anchor.clone(),
+                        second_alignment_point: anchor,
+                        text_height: text.height * to_mm,
+                        value: text.text.clone(),
+                        rotation: text.rotation.to_degrees(),
+                        horizontal_text_justification,
+                        ..Default::default()
+                    }),
+                    DIMENSION_LAYER,
+                    THIN_LINE_WIDTH_MM,
+                    false,
+                );
+            }
+        }
+    }
+
+    let mut bytes = Vec::new();
+    writer
+        .drawing
+        .save(&mut bytes)
+        .map_err(|e| DxfExportError::Write(e.to_string()))?;
+    Ok(bytes)
+}
+
+struct DxfWriter {
+    drawing: Drawing,
+    to_mm: f64,
+}
+
+impl DxfWriter {
+    fn map(&self, p: Vec2) -> Vec2 {
+        Vec2::new(p.x * self.to_mm, p.y * self.to_mm)
+    }
+
+    fn ensure_layer(&mut self, name: &str) {
+        if self.drawing.layers().any(|layer| layer.name == name) {
+            return;
+        }
+        self.drawing.add_layer(Layer {
+            name: name.to_string(),
+            color: Color::from_index(7),
+            ..Default::default()
+        });
+    }
+
+    fn add(&mut self, specific: EntityType, layer: &str, width_mm: f64, hidden: bool) {
+        let mut entity = Entity::new(specific);
+        entity.common.layer = layer.to_string();
+        // Lineweights are stored in hundredths of a millimetre.
+        entity.common.lineweight_enum_value = (width_mm * 100.0).round() as i16;
+        if hidden {
+            entity.common.line_type_name = HIDDEN_LINE_TYPE.to_string();
+        }
+        self.drawing.add_entity(entity);
+    }
+
+    fn add_polyline(
+        &mut self,
+        points: &[Vec2],
+        closed: bool,
+        layer: &str,
+        width: f64,
+        hidden: bool,
+    ) {
+        let polyline = LwPolyline {
+            flags: i32::from(closed),
+            vertices: points
+                .iter()
+                .map(|p| LwPolylineVertex {
+                    x: p.x,
+                    y: p.y,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        self.add(EntityType::LwPolyline(polyline), layer, width, hidden);
+    }
+
+    fn add_segment(&mut self, segment: &Segment2D, layer: &str, width: f64, hidden: bool) {
+        let specific = match *segment {
+            Segment2D::Line { start, end } => {
+                EntityType::Line(Line::new(point(self.map(start)), point(self.map(end))))
+            }
+            Segment2D::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+            } => {
+                // DXF arcs run counter-clockwise from start to end.
+                let (from, to) = ordered(start_angle, end_angle);
+                EntityType::Arc(Arc::new(
+                    point(self.map(center)),
+                    radius * self.to_mm,
+                    from.to_degrees(),
+                    to.to_degrees(),
+                ))
+            }
+            Segment2D::Ellipse {
+                center,
+                rx,
+                ry,
+                rotation,
+                start_angle,
+                end_angle,
+            } => {
+                let (from, to) = ordered(start_angle, end_angle);
+                // The major axis must be the longer one; rotating it a
+                // quarter turn shifts the parameters back by as much.
+                let (major, minor, axis_angle, shift) = if rx >= ry {
+                    (rx, ry, rotation, 0.0)
+                } else {
+                    let quarter = std::f64::consts::FRAC_PI_2;
+                    (ry, rx, rotation + quarter, quarter)
+                };
+                if major <= 0.0 {
+                    return;
+                }
+                let major = major * self.to_mm;
+                EntityType::Ellipse(Ellipse {
+                    center: point(self.map(center)),
+                    major_axis: Vector::new(
+                        major * axis_angle.cos(),
+                        major * axis_angle.sin(),
+                        0.0,
+                    ),
+                    normal: Vector::z_axis(),
+                    minor_axis_ratio: minor * self.to_mm / major,
+                    start_parameter: from - shift,
+                    end_parameter: to - shift,
+                })
+            }
+            Segment2D::CubicBezier { .. } => {
+                let points: Vec<Vec2> =
+                    segment.flatten().into_iter().map(|p| self.map(p)).collect();
+                self.add_polyline(&points, false, layer, width, hidden);
+                return;
+            }
+        };
+        self.add(specific, layer, width, hidden);
+    }
+
+    /// Fills a convex polygon with a fan of SOLID entities.
+    fn add_solid(&mut self, polygon: &[Vec2]) {
+        let points: Vec<Point> = polygon.iter().map(|p| point(self.map(*p))).collect();
+        for index in 1..points.len().saturating_sub(1) {
+            // SOLID corners are ordered 1-2-4-3; a triangle repeats its last.
+            let solid = Solid::new(
+                points[0].clone(),
+                points[index].clone(),
+                points[index + 1].clone(),
+                points[index + 1].clone(),
+            );
+            self.add(EntityType::Solid(solid), DIMENSION_LAYER, 0.0, false);
+        }
+    }
+}
+
+fn point(p: Vec2) -> Point {
+    Point::new(p.x, p.y, 0.0)
+}
+
+fn ordered(start: f64, end: f64) -> (f64, f64) {
+    if end < start {
+        (end, start)
+    } else {
+        (start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::annotation::{Annotation, LinearDimension, LinearOrientation};
+    use crate::export::projection::ClassifiedSegment;
+
+    fn reload(bytes: &[u8]) -> Drawing {
+        Drawing::load(&mut std::io::Cursor::new(bytes)).expect("written DXF reloads")
+    }
+
+    #[test]
+    fn segments_keep_exact_arcs_and_layers() {
+        let mut scene = Scene2D::with_name("plate");
+        scene.add_segment(ClassifiedSegment {
+            geometry: Segment2D::Line {
+                start: Vec2::new(0.0, 0.0),
+                end: Vec2::new(0.2, 0.0),
+            },
+            class: EdgeClass::VisibleOutline,
+            layer: Some("A-WALL".to_string()),
+            source_entity_id: None,
+        });
+        scene.add_segment(ClassifiedSegment {
+            geometry: Segment2D::Arc {
+                center: Vec2::new(0.1, 0.1),
+                radius: 0.05,
+                start_angle: std::f64::consts::PI,
+                end_angle: 0.0,
+            },
+            class: EdgeClass::Hidden,
+            layer: None,
+            source_entity_id: None,
+        });
+        let drawing =
+            reload(&export_scene_to_dxf_bytes(&scene, &DxfExportConfig::default()).unwrap());
+
+        let entities: Vec<&Entity> = drawing.entities().collect();
+        assert_eq!(entities.len(), 2);
+        let EntityType::Line(line) = &entities[0].specific else {
+            panic!("expected a line");
+        };
+        assert!((line.p2.x - 200.0).abs() < 1e-9);
+        assert_eq!(entities[0].common.layer, "A-WALL");
+        assert_eq!(entities[0].common.lineweight_enum_value, 50);
+
+        let EntityType::Arc(arc) = &entities[1].specific else {
+            panic!("expected an arc");
+        };
+        assert!((arc.radius - 50.0).abs() < 1e-9);
+        assert!((arc.start_angle - 0.0).abs() < 1e-9 && (arc.end_angle - 180.0).abs() < 1e-9);
+        assert_eq!(entities[1].common.line_type_name, HIDDEN_LINE_TYPE);
+        assert!(drawing.layers().any(|layer| layer.name == "Hidden"));
+    }
+
+    #[test]
+    fn dimensions_are_sized_for_the_plot_scale() {
+        let mut scene = Scene2D::new();
+        scene.add_annotation(Annotation::Linear(LinearDimension {
+            p1: Vec2::new(0.0, 0.0),
+            p2: Vec2::new(1.0, 0.0),
+            orientation: LinearOrientation::Horizontal,
+            offset: -0.2,
+            text: None,
+        }));
+        let config = DxfExportConfig {
+            scale_denominator: Some(20.0),
+        };
+        let drawing = reload(&export_scene_to_dxf_bytes(&scene, &config).unwrap());
+
+        let texts: Vec<&Text> = drawing
+            .entities()
+            .filter_map(|entity| match &entity.specific {
+                EntityType::Text(text) => Some(text),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(texts.len(), 1);
+        assert_eq!(texts[0].value, "1000");
+        // 3.5 mm on paper at 1:20.
+        assert!((texts[0].text_height - 70.0).abs() < 1e-9);
+        let solids = drawing
+            .entities()
+            .filter(|entity| matches!(entity.specific, EntityType::Solid(_)))
+            .count();
+        assert_eq!(solids, 2);
+
+        assert!(matches!(
+            export_scene_to_dxf_bytes(
+                &scene,
+                &DxfExportConfig {
+                    scale_denominator: Some(0.0)
+                }
+            ),
+            Err(DxfExportError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn sheets_are_written_in_paper_millimetres() {
+        use crate::export::sheet::{PaperSize, Sheet};
+
+        let sheet = Sheet::new(PaperSize::iso_a(4).unwrap());
+        let drawing = reload(&export_sheet_to_dxf_bytes(&sheet).unwrap());
+        let frame_right = drawing
+            .entities()
+            .filter(|entity| entity.common.layer == "SHEET")
+            .filter_map(|entity| match &entity.specific {
+                EntityType::Line(line) => Some(line.p1.x.max(line.p2.x)),
+                _ => None,
+            })
+            .fold(f64::NEG_INFINITY, f64::max);
+        // Frame at 287 mm plus 5 mm grid ticks.
+        assert!((frame_right - 292.0).abs() < 1e-9);
+    }
+}
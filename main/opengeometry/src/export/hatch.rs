@@ -0,0 +1,294 @@
+//! Hatch patterns for section cut regions.
+//!
+//! A [`HatchFill`] carries the projected boundary of a cut region; the pattern
+//! is only expanded into line work by the exporter, in paper millimetres, so
+//! hatch spacing stays constant whatever the drawing scale.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::export::projection::{Segment2D, Vec2};
+
+const EPSILON: f64 = 1.0e-12;
+/// Pen for hatch lines: ISO 128's narrowest width, so hatching reads lighter
+/// than the thin dimension lines around it.
+pub const HATCH_LINE_WIDTH_MM: f64 = 0.13;
+
+/// Hatch pattern applied to a section cut region.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HatchPattern {
+    /// ANSI31 general-purpose / iron: 45° lines at 3.175 mm (1/8").
+    #[default]
+    Ansi31,
+    /// Concrete: crossed dashed diagonals.
+    Concrete,
+    /// Batt insulation: crossed 60° lines forming a zig-zag lattice.
+    Insulation,
+    /// Solid (poché) fill.
+    SolidFill,
+}
+
+/// One family of parallel hatch lines, in paper millimetres.
+#[derive(Clone, Copy, Debug)]
+pub struct HatchFamily {
+    pub angle_deg: f64,
+    pub spacing_mm: f64,
+    /// Perpendicular shift of the family so crossed families interleave.
+    pub offset_mm: f64,
+    /// `(dash, gap)` lengths; `None` ⇒ continuous lines.
+    pub dash_mm: Option<(f64, f64)>,
+}
+
+const ANSI31_FAMILIES: [HatchFamily; 1] = [HatchFamily {
+    angle_deg: 45.0,
+    spacing_mm: 3.175,
+    offset_mm: 0.0,
+    dash_mm: None,
+}];
+
+const CONCRETE_FAMILIES: [HatchFamily; 2] = [
+    HatchFamily {
+        angle_deg: 45.0,
+        spacing_mm: 5.0,
+        offset_mm: 0.0,
+        dash_mm: Some((2.5, 2.5)),
+    },
+    HatchFamily {
+        angle_deg: 135.0,
+        spacing_mm: 5.0,
+        offset_mm: 2.5,
+        dash_mm: Some((1.0, 4.0)),
+    },
+];
+
+const INSULATION_FAMILIES: [HatchFamily; 2] = [
+    HatchFamily {
+        angle_deg: 60.0,
+        spacing_mm: 4.0,
+        offset_mm: 0.0,
+        dash_mm: None,
+    },
+    HatchFamily {
+        angle_deg: 120.0,
+        spacing_mm: 4.0,
+        offset_mm: 0.0,
+        dash_mm: None,
+    },
+];
+
+impl HatchPattern {
+    /// Line families making up the pattern; empty for [`HatchPattern::SolidFill`].
+    pub fn families(self) -> &'static [HatchFamily] {
+        match self {
+            HatchPattern::Ansi31 => &ANSI31_FAMILIES,
+            HatchPattern::Concrete => &CONCRETE_FAMILIES,
+            HatchPattern::Insulation => &INSULATION_FAMILIES,
+            HatchPattern::SolidFill => &[],
+        }
+    }
+
+    pub fn is_solid(self) -> bool {
+        matches!(self, HatchPattern::SolidFill)
+    }
+}
+
+/// A projected section cut region to be hatched. `boundary[0]` is the outer
+/// loop, the rest are holes; filling uses the even-odd rule.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HatchFill {
+    pub boundary: Vec<Vec<Vec2>>,
+    pub pattern: HatchPattern,
+    /// AIA/NCS layer code of the originating entity.
+    pub layer: Option<String>,
+    pub source_entity_id: Option<String>,
+}
+
+/// Per-view hatch assignment. Entity kind wins over layer, layer over the
+/// default.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct HatchConfig {
+    #[serde(default)]
+    pub default_pattern: HatchPattern,
+    /// Keyed by `OGEntityKind` string, case-insensitive.
+    #[serde(default)]
+    pub by_kind: HashMap<String, HatchPattern>,
+    /// Keyed by AIA/NCS layer code (e.g. "A-WALL").
+    #[serde(default)]
+    pub by_layer: HashMap<String, HatchPattern>,
+}
+
+impl HatchConfig {
+    pub fn resolve(&self, kind: &str, layer: Option<&str>) -> HatchPattern {
+        let kind = kind.trim().to_lowercase();
+        self.by_kind
+            .iter()
+            .find(|(key, _)| key.trim().to_lowercase() == kind)
+            .map(|(_, pattern)| *pattern)
+            .or_else(|| layer.and_then(|layer| self.by_layer.get(layer).copied()))
+            .unwrap_or(self.default_pattern)
+    }
+}
+
+/// Expands `pattern` into line segments clipped to `rings` (even-odd), with
+/// `units_per_mm` drawing units per millimetre of pattern spacing.
+pub fn hatch_lines(
+    rings: &[Vec<Vec2>],
+    pattern: HatchPattern,
+    units_per_mm: f64,
+) -> Vec<Segment2D> {
+    let mut lines = Vec::new();
+    if units_per_mm <= EPSILON {
+        return lines;
+    }
+
+    for family in pattern.families() {
+        let angle = family.angle_deg.to_radians();
+        let direction = Vec2::new(angle.cos(), angle.sin());
+        let normal = Vec2::new(-angle.sin(), angle.cos());
+        let spacing = family.spacing_mm * units_per_mm;
+        let offset = family.offset_mm * units_per_mm;
+
+        let along_normal = |p: &Vec2| p.x * normal.x + p.y * normal.y;
+        let along_direction = |p: &Vec2| p.x * direction.x + p.y * direction.y;
+        let (low, high) = rings
+            .iter()
+            .flatten()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), p| {
+                (low.min(along_normal(p)), high.max(along_normal(p)))
+            });
+        if !low.is_finite() {
+            continue;
+        }
+
+        let first = ((low - offset) / spacing).ceil() as i64;
+        let last = ((high - offset) / spacing).floor() as i64;
+        for step in first..=last {
+            let level = offset + step as f64 * spacing;
+            let mut crossings: Vec<f64> = Vec::new();
+            for ring in rings {
+                for index in 0..ring.len() {
+                    let a = &ring[index];
+                    let b = &ring[(index + 1) % ring.len()];
+                    let (da, db) = (along_normal(a) - level, along_normal(b) - level);
+                    if (da > 0.0) != (db > 0.0) {
+                        let t = da / (da - db);
+                        crossings.push(
+                            along_direction(a) + (along_direction(b) - along_direction(a)) * t,
+                        );
+                    }
+                }
+            }
+            crossings.sort_by(f64::total_cmp);
+
+            let point = |s: f64| {
+                Vec2::new(
+                    normal.x * level + direction.x * s,
+                    normal.y * level + direction.y * s,
+                )
+            };
+            for span in crossings.chunks_exact(2) {
+                let (start, end) = (span[0], span[1]);
+                match family.dash_mm {
+                    None => lines.push(Segment2D::Line {
+                        start: point(start),
+                        end: point(end),
+                    }),
+                    Some((dash, gap)) => {
+                        let (dash, period) = (dash * units_per_mm, (dash + gap) * units_per_mm);
+                        let mut cursor = (start / period).floor() * period;
+                        while cursor < end {
+                            let (from, to) = (cursor.max(start), (cursor + dash).min(end));
+                            if to - from > EPSILON {
+                                lines.push(Segment2D::Line {
+                                    start: point(from),
+                                    end: point(to),
+                                });
+                            }
+                            cursor += period;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(half: f64) -> Vec<Vec2> {
+        vec![
+            Vec2::new(-half, -half),
+            Vec2::new(half, -half),
+            Vec2::new(half, half),
+            Vec2::new(-half, half),
+        ]
+    }
+
+    fn length(segment: &Segment2D) -> f64 {
+        let Segment2D::Line { start, end } = segment else {
+            panic!("hatch emits lines only");
+        };
+        ((end.x - start.x).powi(2) + (end.y - start.y).powi(2)).sqrt()
+    }
+
+    #[test]
+    fn ansi31_lines_skip_holes_and_stay_inside() {
+        let outer = square(10.0);
+        let hole = square(5.0);
+        let solid = hatch_lines(std::slice::from_ref(&outer), HatchPattern::Ansi31, 1.0);
+        let holed = hatch_lines(&[outer, hole], HatchPattern::Ansi31, 1.0);
+
+        assert!(!solid.is_empty());
+        let solid_ink: f64 = solid.iter().map(length).sum();
+        let holed_ink: f64 = holed.iter().map(length).sum();
+        // Ink is proportional to area: the hole removes a quarter of it.
+        assert!((holed_ink / solid_ink - 0.75).abs() < 0.05);
+        for segment in &holed {
+            let Segment2D::Line { start, end } = segment else {
+                unreachable!();
+            };
+            for p in [start, end] {
+                assert!(p.x.abs() <= 10.0 + 1.0e-9 && p.y.abs() <= 10.0 + 1.0e-9);
+                let mid_in_hole = |q: &Vec2| q.x.abs() < 5.0 - 1.0e-9 && q.y.abs() < 5.0 - 1.0e-9;
+                assert!(!mid_in_hole(p));
+            }
+        }
+    }
+
+    #[test]
+    fn dashed_patterns_use_less_ink_and_solid_fill_emits_no_lines() {
+        let outer = square(20.0);
+        let concrete: f64 = hatch_lines(std::slice::from_ref(&outer), HatchPattern::Concrete, 1.0)
+            .iter()
+            .map(length)
+            .sum();
+        let continuous_equivalent = 2.0 * (40.0 * 40.0) / 5.0;
+        assert!(concrete > 0.0 && concrete < continuous_equivalent * 0.6);
+        assert!(hatch_lines(&[outer], HatchPattern::SolidFill, 1.0).is_empty());
+    }
+
+    #[test]
+    fn config_resolves_kind_then_layer_then_default() {
+        let mut config = HatchConfig::default();
+        config
+            .by_kind
+            .insert("Insulation".to_string(), HatchPattern::Insulation);
+        config
+            .by_layer
+            .insert("A-WALL".to_string(), HatchPattern::Concrete);
+
+        assert_eq!(
+            config.resolve("insulation", Some("A-WALL")),
+            HatchPattern::Insulation
+        );
+        assert_eq!(
+            config.resolve("wall", Some("A-WALL")),
+            HatchPattern::Concrete
+        );
+        assert_eq!(config.resolve("beam", Some("S-BEAM")), HatchPattern::Ansi31);
+    }
+}
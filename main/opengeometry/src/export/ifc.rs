@@ -5,7 +5,7 @@ use openmaths::Vector3;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::brep::Brep;
+use crate::brep::{Brep, Face, SurfaceGeometry};
 use crate::operations::triangulate::triangulate_polygon_with_holes;
 
 use super::part21::{sanitize_string_literal, Part21Writer};
@@ -542,14 +542,17 @@ fn export_owned_entities_to_ifc_text<'a>(
 
         let class_name = resolve_ifc_class(&entity.entity_id, semantics, config, &mut report)?;
 
-        // D9: prefer an analytic IFCADVANCEDBREP when the brep carries analytic
-        // surfaces; otherwise fall back to the tessellated face set.
-        let analytic_rep =
-            if config.analytic_surfaces && brep.faces.iter().any(|f| f.surface.is_some()) {
-                emit_ifc_advanced_brep(&mut writer, brep, scale, geom_context)
-            } else {
-                None
-            };
+        // D9: prefer an analytic IFCADVANCEDBREP when every face carries a
+        // surface it can represent; otherwise fall back to the tessellated
+        // face set.
+        let analytic_rep = if config.analytic_surfaces
+            && !brep.faces.is_empty()
+            && brep.faces.iter().all(has_ifc_surface)
+        {
+            emit_ifc_advanced_brep(&mut writer, brep, scale, geom_context)
+        } else {
+            None
+        };
 
         let shape_representation = if let Some((rep, face_count)) = analytic_rep {
             report.exported_faces += face_count;
@@ -714,6 +717,14 @@ fn export_owned_entities_to_ifc_text<'a>(
     Ok((text, report))
 }
 
+/// Whether `face` carries a surface `IFCADVANCEDBREP` represents exactly.
+fn has_ifc_surface(face: &Face) -> bool {
+    matches!(
+        face.surface,
+        Some(SurfaceGeometry::Plane { .. } | SurfaceGeometry::Cylinder { .. })
+    )
+}
+
 /// D9: emits an analytic `IFCADVANCEDBREP` shape representation for a brep whose
 /// faces carry analytic surfaces (D1) — IFCPLANE / IFCCYLINDRICALSURFACE faces
 /// with IFCLINE / IFCCIRCLE edge curves. Returns the IFCSHAPEREPRESENTATION ref
@@ -724,8 +735,6 @@ fn emit_ifc_advanced_brep(
     scale: f64,
     geom_context: usize,
 ) -> Option<(usize, usize)> {
-    use crate::brep::SurfaceGeometry;
-
     let mut edge_curves: HashMap<u32, usize> = HashMap::new();
     let mut vertex_points: HashMap<u32, usize> = HashMap::new();
     let mut surfaces: HashMap<String, usize> = HashMap::new();
@@ -769,6 +778,9 @@ fn emit_ifc_advanced_brep(
                             format_ifc_real(radius * scale)
                         ))
                     }
+                    // Tessellated spheres are bounded by chords, not curves
+                    // on the sphere — fall back to tessellation.
+                    SurfaceGeometry::Sphere { .. } => return None,
                 };
                 surfaces.insert(key, id);
                 id
@@ -1025,8 +1037,7 @@ fn ifc_any_perpendicular(n: Vector3) -> Vector3 {
     }
 }
 
-fn ifc_surface_key(surface: &crate::brep::SurfaceGeometry, scale: f64) -> String {
-    use crate::brep::SurfaceGeometry;
+fn ifc_surface_key(surface: &SurfaceGeometry, scale: f64) -> String {
     match surface {
         SurfaceGeometry::Plane { origin, normal } => format!(
             "P|{:.6}|{:.6}|{:.6}|{:.6}|{:.6}|{:.6}",
@@ -1052,6 +1063,13 @@ fn ifc_surface_key(surface: &crate::brep::SurfaceGeometry, scale: f64) -> String
             axis.z,
             radius * scale
         ),
+        SurfaceGeometry::Sphere { center, radius } => format!(
+            "S|{:.6}|{:.6}|{:.6}|{:.6}",
+            center.x * scale,
+            center.y * scale,
+            center.z * scale,
+            radius * scale
+        ),
     }
 }
 
@@ -1,20 +1,44 @@
+pub mod annotation;
+pub mod dxf;
+pub mod hatch;
 pub mod ifc;
 pub mod part21;
 pub mod projection;
+pub mod sheet;
+pub mod standard_views;
 pub mod step;
 pub mod stl;
+pub mod svg;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod pdf;
 
+pub use annotation::{
+    overall_dimensions, AngularDimension, Annotation, AnnotationGraphics, AnnotationText,
+    ArrowheadStyle, DimensionStandard, DimensionStyle, Leader, LinearDimension, LinearOrientation,
+    ModelDimension, OrdinateAxis, OrdinateDimension, RadialDimension, TextAlign, TextNote,
+};
+pub use dxf::{
+    export_scene_to_dxf_bytes, export_sheet_to_dxf_bytes, DxfExportConfig, DxfExportError,
+};
+pub use hatch::{hatch_lines, HatchConfig, HatchFill, HatchPattern};
 pub use ifc::{
     export_brep_to_ifc_text, export_breps_to_ifc_text, export_scene_entities_to_ifc_text,
     IfcEntityInput, IfcEntitySemantics, IfcErrorPolicy, IfcExportConfig, IfcExportError,
     IfcExportReport, IfcPropertyValue, IfcSchemaVersion,
 };
 pub use projection::{
-    project_brep_to_scene, CameraParameters, ClassifiedSegment, EdgeClass, HlrOptions, Line2D,
-    Path2D, ProjectionMode, Scene2D, Scene2DLines, Segment2D, Vec2,
+    project_brep_to_scene, project_breps_to_scenes, project_point, CameraParameters,
+    ClassifiedSegment, EdgeClass, HlrOptions, Line2D, Path2D, ProjectionMode, Scene2D,
+    Scene2DLines, Segment2D, Vec2,
+};
+pub use sheet::{
+    DrawingScale, PaperSize, ScaleBar, Sheet, SheetBorder, SheetViewport, TitleBlock,
+    TitleBlockField,
+};
+pub use standard_views::{
+    standard_views, standard_views_sheet, PrincipalView, ProjectionAngle, StandardView,
+    StandardViewSet, StandardViewsConfig,
 };
 pub use step::{
     export_brep_to_step_text, export_breps_to_step_text, StepErrorPolicy, StepExportConfig,
@@ -24,6 +48,7 @@ pub use stl::{
     export_brep_to_stl_bytes, export_breps_to_stl_bytes, StlErrorPolicy, StlExportConfig,
     StlExportError, StlExportReport,
 };
+pub use svg::{export_scene_to_svg, export_sheet_to_svg, SvgExportConfig};
 
 #[cfg(not(target_arch = "wasm32"))]
 pub use ifc::{
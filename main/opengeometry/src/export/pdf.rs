@@ -1,8 +1,11 @@
 use crate::brep::Brep;
+use crate::export::annotation::{text_width, AnnotationGraphics, TextAlign, CAP_HEIGHT_PER_EM};
+use crate::export::hatch::{hatch_lines, HatchFill, HATCH_LINE_WIDTH_MM};
 use crate::export::projection::{
     project_brep_to_scene, CameraParameters, ClassifiedSegment, EdgeClass, HlrOptions, Scene2D,
-    Segment2D, Vec2,
+    Segment2D, Vec2, THIN_LINE_WIDTH_MM,
 };
+use crate::export::sheet::Sheet;
 use printpdf::*;
 use std::fs::File;
 use std::io::BufWriter;
@@ -12,13 +15,7 @@ const DEFAULT_MARGIN_MM: f64 = 10.0;
 
 /// ISO 128 line weight in mm per edge class.
 fn iso128_line_width_mm(class: EdgeClass) -> f64 {
-    match class {
-        EdgeClass::VisibleOutline => 0.50,
-        EdgeClass::VisibleCrease => 0.25,
-        EdgeClass::VisibleSmooth => 0.18,
-        EdgeClass::Hidden => 0.18,
-        EdgeClass::SectionCut => 0.70,
-    }
+    class.line_width_mm()
 }
 
 #[derive(Clone, Debug)]
@@ -124,7 +121,7 @@ pub fn export_scene_to_pdf_with_config(
     let (doc, page1, layer1, scale, offset_x, offset_y) = build_pdf_document(scene, config)?;
 
     let layer = doc.get_page(page1).get_layer(layer1);
-    draw_classified_segments(&layer, scene.segments(), scale, offset_x, offset_y);
+    draw_scene(&doc, &layer, scene, scale, offset_x, offset_y)?;
 
     let file = File::create(file_path).map_err(|e| PdfExportError::FileWrite(e.to_string()))?;
     let mut writer = BufWriter::new(file);
@@ -141,12 +138,53 @@ pub fn export_scene_to_pdf_bytes(
     let (doc, page1, layer1, scale, offset_x, offset_y) = build_pdf_document(scene, config)?;
 
     let layer = doc.get_page(page1).get_layer(layer1);
-    draw_classified_segments(&layer, scene.segments(), scale, offset_x, offset_y);
+    draw_scene(&doc, &layer, scene, scale, offset_x, offset_y)?;
 
     doc.save_to_bytes()
         .map_err(|e| PdfExportError::DocumentCreation(e.to_string()))
 }
 
+/// Renders a sheet at 1:1 on its paper size.
+pub fn export_sheet_to_pdf(sheet: &Sheet, file_path: &str) -> PdfExportResult<()> {
+    let doc = build_sheet_document(sheet)?;
+    let file = File::create(file_path).map_err(|e| PdfExportError::FileWrite(e.to_string()))?;
+    let mut writer = BufWriter::new(file);
+    doc.save(&mut writer)
+        .map_err(|e| PdfExportError::FileWrite(e.to_string()))
+}
+
+pub fn export_sheet_to_pdf_bytes(sheet: &Sheet) -> PdfExportResult<Vec<u8>> {
+    build_sheet_document(sheet)?
+        .save_to_bytes()
+        .map_err(|e| PdfExportError::DocumentCreation(e.to_string()))
+}
+
+fn build_sheet_document(sheet: &Sheet) -> PdfExportResult<PdfDocumentReference> {
+    if sheet.paper.width_mm <= 0.0 || sheet.paper.height_mm <= 0.0 {
+        return Err(PdfExportError::InvalidConfig(
+            "Paper size must be positive".to_string(),
+        ));
+    }
+    let scene = sheet.compose();
+    if scene.is_empty() {
+        return Err(PdfExportError::EmptyScene);
+    }
+    let title = sheet
+        .name
+        .clone()
+        .unwrap_or_else(|| "OpenGeometry Sheet".to_string());
+    let (doc, page1, layer1) = PdfDocument::new(
+        &title,
+        Mm(sheet.paper.width_mm),
+        Mm(sheet.paper.height_mm),
+        "Layer 1",
+    );
+    // The composed sheet is already in paper millimetres.
+    let layer = doc.get_page(page1).get_layer(layer1);
+    draw_scene(&doc, &layer, &scene, 1.0 / METERS_TO_MM, 0.0, 0.0)?;
+    Ok(doc)
+}
+
 #[allow(clippy::type_complexity)]
 fn build_pdf_document(
     scene: &Scene2D,
@@ -215,6 +253,19 @@ fn build_pdf_document(
     Ok((doc, page1, layer1, scale, offset_x, offset_y))
 }
 
+fn draw_scene(
+    doc: &PdfDocumentReference,
+    layer: &PdfLayerReference,
+    scene: &Scene2D,
+    scale: f64,
+    offset_x: f64,
+    offset_y: f64,
+) -> PdfExportResult<()> {
+    draw_hatch_fills(layer, &scene.fills, scale, offset_x, offset_y);
+    draw_classified_segments(layer, scene.segments(), scale, offset_x, offset_y);
+    draw_annotations(doc, layer, scene, scale, offset_x, offset_y)
+}
+
 fn draw_classified_segments(
     layer: &PdfLayerReference,
     segments: &[ClassifiedSegment],
@@ -246,10 +297,181 @@ fn draw_classified_segments(
 
                 layer.add_shape(line);
             }
-            // Arc, Ellipse, CubicBezier segments are produced in Phase 2+.
-            _ => {}
+            curve => {
+                let mut points = Vec::new();
+                for [p0, p1, p2, p3] in curve.to_cubic_beziers() {
+                    let [p0, p1, p2, p3] = [p0, p1, p2, p3].map(|p| {
+                        let (x, y) = transform_point(&p, scale, offset_x, offset_y);
+                        Point::new(Mm(x), Mm(y))
+                    });
+                    if points.is_empty() {
+                        points.push((p0, true));
+                    } else if let Some(last) = points.last_mut() {
+                        last.1 = true;
+                    }
+                    points.extend([(p1, true), (p2, false), (p3, false)]);
+                }
+                if points.is_empty() {
+                    continue;
+                }
+                layer.add_shape(Line {
+                    points,
+                    is_closed: false,
+                    has_fill: false,
+                    has_stroke: true,
+                    is_clipping_path: false,
+                });
+            }
+        }
+    }
+}
+
+/// Draws section cut regions: solid fills as one even-odd filled path, other
+/// patterns as thin hatch lines laid out in paper millimetres.
+fn draw_hatch_fills(
+    layer: &PdfLayerReference,
+    fills: &[HatchFill],
+    scale: f64,
+    offset_x: f64,
+    offset_y: f64,
+) {
+    let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+    layer.set_fill_color(black.clone());
+    layer.set_outline_color(black);
+    layer.set_outline_thickness(HATCH_LINE_WIDTH_MM);
+
+    for fill in fills {
+        let rings_mm: Vec<Vec<Vec2>> = fill
+            .boundary
+            .iter()
+            .filter(|ring| ring.len() >= 3)
+            .map(|ring| {
+                ring.iter()
+                    .map(|point| {
+                        let (x, y) = transform_point(point, scale, offset_x, offset_y);
+                        Vec2::new(x, y)
+                    })
+                    .collect()
+            })
+            .collect();
+        if rings_mm.is_empty() {
+            continue;
+        }
+
+        if fill.pattern.is_solid() {
+            for (index, ring) in rings_mm.iter().enumerate() {
+                for (vertex, point) in ring.iter().enumerate() {
+                    let operator = if vertex == 0 { "m" } else { "l" };
+                    layer.add_operation(lopdf::content::Operation::new(
+                        operator,
+                        vec![
+                            Pt::from(Mm(point.x)).0.into(),
+                            Pt::from(Mm(point.y)).0.into(),
+                        ],
+                    ));
+                }
+                layer.add_operation(lopdf::content::Operation::new("h", vec![]));
+                if index + 1 == rings_mm.len() {
+                    layer.add_operation(lopdf::content::Operation::new("f*", vec![]));
+                }
+            }
+            continue;
+        }
+
+        for segment in hatch_lines(&rings_mm, fill.pattern, 1.0) {
+            if let Segment2D::Line { start, end } = segment {
+                layer.add_shape(Line {
+                    points: vec![
+                        (Point::new(Mm(start.x), Mm(start.y)), false),
+                        (Point::new(Mm(end.x), Mm(end.y)), false),
+                    ],
+                    is_closed: false,
+                    has_fill: false,
+                    has_stroke: true,
+                    is_clipping_path: false,
+                });
+            }
+        }
+    }
+}
+
+/// Lays annotations out at the drawing scale and draws their lines, filled
+/// arrowheads and Helvetica text.
+fn draw_annotations(
+    doc: &PdfDocumentReference,
+    layer: &PdfLayerReference,
+    scene: &Scene2D,
+    scale: f64,
+    offset_x: f64,
+    offset_y: f64,
+) -> PdfExportResult<()> {
+    if scene.annotations.is_empty() {
+        return Ok(());
+    }
+    let font = doc
+        .add_builtin_font(BuiltinFont::Helvetica)
+        .map_err(|e| PdfExportError::DocumentCreation(e.to_string()))?;
+    let units_per_mm = 1.0 / (METERS_TO_MM * scale);
+    let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+    layer.set_fill_color(black.clone());
+    layer.set_outline_color(black);
+    layer.set_outline_thickness(THIN_LINE_WIDTH_MM);
+
+    let graphics: Vec<AnnotationGraphics> = scene
+        .annotations
+        .iter()
+        .map(|annotation| annotation.layout(&scene.dimension_style, units_per_mm))
+        .collect();
+    for graphic in &graphics {
+        let segments: Vec<ClassifiedSegment> = graphic
+            .lines
+            .iter()
+            .map(|line| ClassifiedSegment {
+                geometry: line.clone(),
+                class: EdgeClass::VisibleCrease,
+                layer: None,
+                source_entity_id: None,
+            })
+            .collect();
+        draw_classified_segments(layer, &segments, scale, offset_x, offset_y);
+
+        for solid in graphic.solids.iter().filter(|solid| solid.len() >= 3) {
+            for (vertex, point) in solid.iter().enumerate() {
+                let (x, y) = transform_point(point, scale, offset_x, offset_y);
+                let operator = if vertex == 0 { "m" } else { "l" };
+                layer.add_operation(lopdf::content::Operation::new(
+                    operator,
+                    vec![Pt::from(Mm(x)).0.into(), Pt::from(Mm(y)).0.into()],
+                ));
+            }
+            layer.add_operation(lopdf::content::Operation::new("h", vec![]));
+            layer.add_operation(lopdf::content::Operation::new("f", vec![]));
+        }
+
+        for text in &graphic.texts {
+            let height_mm = text.height / units_per_mm;
+            let shift = match text.align {
+                TextAlign::Left => 0.0,
+                TextAlign::Center => 0.5 * text_width(&text.text, height_mm),
+                TextAlign::Right => text_width(&text.text, height_mm),
+            };
+            let (x, y) = transform_point(&text.position, scale, offset_x, offset_y);
+            let (x, y) = (
+                x - shift * text.rotation.cos(),
+                y - shift * text.rotation.sin(),
+            );
+            layer.begin_text_section();
+            layer.set_font(&font, Pt::from(Mm(height_mm / CAP_HEIGHT_PER_EM)).0);
+            layer.set_text_matrix(TextMatrix::TranslateRotate(
+                Pt::from(Mm(x)),
+                Pt::from(Mm(y)),
+                text.rotation.to_degrees(),
+            ));
+            layer.write_text(text.text.clone(), &font);
+            layer.end_text_section();
         }
     }
+    Ok(())
 }
 
 fn transform_point(point: &Vec2, scale: f64, offset_x: f64, offset_y: f64) -> (f64, f64) {
@@ -291,4 +513,82 @@ mod tests {
             assert!(w >= 0.13 && w <= 2.0, "width {w} outside ISO 128 range");
         }
     }
+
+    #[test]
+    fn hatched_section_fills_render_into_pdf_bytes() {
+        use crate::export::hatch::HatchPattern;
+
+        let square = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(0.0, 1.0),
+        ];
+        let mut scene = Scene2D::with_name("section");
+        let mut sizes = Vec::new();
+        for pattern in [HatchPattern::SolidFill, HatchPattern::Ansi31] {
+            scene.fills = vec![HatchFill {
+                boundary: vec![square.clone()],
+                pattern,
+                layer: None,
+                source_entity_id: None,
+            }];
+            let bytes = export_scene_to_pdf_bytes(&scene, &PdfExportConfig::default())
+                .expect("fill-only scene exports");
+            assert!(bytes.starts_with(b"%PDF"));
+            sizes.push(bytes.len());
+        }
+        // Hatch lines add far more content than a single filled path.
+        assert!(sizes[1] > sizes[0]);
+    }
+
+    #[test]
+    fn dimensions_render_into_pdf_bytes() {
+        use crate::export::annotation::{Annotation, LinearDimension, LinearOrientation};
+
+        let mut scene = Scene2D::with_name("dimensioned");
+        scene.add_segment(ClassifiedSegment {
+            geometry: Segment2D::Line {
+                start: Vec2::new(0.0, 0.0),
+                end: Vec2::new(2.0, 0.0),
+            },
+            class: EdgeClass::VisibleOutline,
+            layer: None,
+            source_entity_id: None,
+        });
+        let plain =
+            export_scene_to_pdf_bytes(&scene, &PdfExportConfig::default()).expect("scene exports");
+        scene.add_annotation(Annotation::Linear(LinearDimension {
+            p1: Vec2::new(0.0, 0.0),
+            p2: Vec2::new(2.0, 0.0),
+            orientation: LinearOrientation::Horizontal,
+            offset: -0.5,
+            text: None,
+        }));
+        let dimensioned = export_scene_to_pdf_bytes(&scene, &PdfExportConfig::default())
+            .expect("dimensioned scene exports");
+        assert!(dimensioned.len() > plain.len());
+        let haystack = String::from_utf8_lossy(&dimensioned);
+        assert!(haystack.contains("Helvetica"));
+    }
+
+    #[test]
+    fn sheets_render_on_their_paper_size() {
+        use crate::export::sheet::{PaperSize, TitleBlock};
+
+        let mut sheet = Sheet::new(PaperSize::iso_a(3).unwrap());
+        sheet.title_block = Some(TitleBlock::default());
+        let bytes = export_sheet_to_pdf_bytes(&sheet).expect("sheet exports");
+        let haystack = String::from_utf8_lossy(&bytes);
+        // A3 landscape media box in points.
+        assert!(haystack.contains("1190.55"), "A3 width in points");
+        assert!(haystack.contains("Helvetica"));
+
+        sheet.border = None;
+        sheet.title_block = None;
+        assert!(matches!(
+            export_sheet_to_pdf_bytes(&sheet),
+            Err(PdfExportError::EmptyScene)
+        ));
+    }
 }
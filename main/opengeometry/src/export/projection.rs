@@ -1,9 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use openmaths::Vector3;
 use serde::{Deserialize, Serialize};
 
-use crate::brep::Brep;
+use crate::brep::{Brep, CurveGeometry, FaceRole, SurfaceGeometry};
+use crate::export::annotation::{Annotation, DimensionStyle};
+use crate::export::hatch::{HatchFill, HatchPattern};
 
 const EPSILON: f64 = crate::tolerance::MODELING_TOLERANCE_FLOOR;
 const CREASE_COS_THRESHOLD: f64 = 0.9995;
@@ -14,6 +16,15 @@ pub enum ProjectionMode {
     Perspective,
 }
 
+/// A look-at camera. Output is in view units — scene units for
+/// orthographic, `x / depth` for perspective. Without a viewport (`fov` for
+/// perspective, the ortho extents for orthographic) only the near plane
+/// clips; with one, edges are clipped to the full frustum. Set
+/// `normalize_to_viewport` to have coordinates normalised to the viewport
+/// the way a three.js camera frames it: `y` spans `[-1, 1]` bottom to top
+/// and `x` spans `[-aspect, aspect]`, so shapes keep their proportions and
+/// NDC `x` is `x / aspect`. Dimensions and sheet viewports measure in scene
+/// units, so leave it off for drawings.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct CameraParameters {
     pub position: Vector3,
@@ -21,6 +32,27 @@ pub struct CameraParameters {
     pub up: Vector3,
     pub near: f64,
     pub projection_mode: ProjectionMode,
+    /// Vertical field of view in degrees, as `PerspectiveCamera.fov`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fov: Option<f64>,
+    /// Viewport width over height. Defaults to 1 when a viewport is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aspect: Option<f64>,
+    /// Far clipping distance along the view axis.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub far: Option<f64>,
+    /// Full width of the orthographic viewport in scene units. Derived from
+    /// `ortho_height` and `aspect` when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ortho_width: Option<f64>,
+    /// Full height of the orthographic viewport in scene units. Derived from
+    /// `ortho_width` and `aspect` when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ortho_height: Option<f64>,
+    /// Scale output to the viewport instead of view units. Ignored without
+    /// a viewport.
+    #[serde(default)]
+    pub normalize_to_viewport: bool,
 }
 
 impl Default for CameraParameters {
@@ -31,6 +63,12 @@ impl Default for CameraParameters {
             up: Vector3::new(0.0, 1.0, 0.0),
             near: 0.01,
             projection_mode: ProjectionMode::Orthographic,
+            fov: None,
+            aspect: None,
+            far: None,
+            ortho_width: None,
+            ortho_height: None,
+            normalize_to_viewport: false,
         }
     }
 }
@@ -38,12 +76,21 @@ impl Default for CameraParameters {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct HlrOptions {
     pub hide_hidden_edges: bool,
+    /// Test edges against every projected face and split them where they pass
+    /// behind one. When off, only back-facing adjacency decides `Hidden`.
+    #[serde(default = "default_occlusion")]
+    pub occlusion: bool,
+}
+
+fn default_occlusion() -> bool {
+    true
 }
 
 impl Default for HlrOptions {
     fn default() -> Self {
         Self {
             hide_hidden_edges: true,
+            occlusion: true,
         }
     }
 }
@@ -75,6 +122,27 @@ pub enum EdgeClass {
     SectionCut,
 }
 
+/// ISO 128 narrow continuous line (type 01.1) for dimension, extension and
+/// leader lines.
+pub const THIN_LINE_WIDTH_MM: f64 = 0.25;
+
+impl EdgeClass {
+    /// ISO 128 line weight in paper millimetres.
+    pub fn line_width_mm(self) -> f64 {
+        match self {
+            EdgeClass::VisibleOutline => 0.50,
+            EdgeClass::VisibleCrease => 0.25,
+            EdgeClass::VisibleSmooth => 0.18,
+            EdgeClass::Hidden => 0.18,
+            EdgeClass::SectionCut => 0.70,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        edge_class_str(self)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Segment2D {
     Line {
@@ -103,6 +171,88 @@ pub enum Segment2D {
     },
 }
 
+impl Segment2D {
+    /// Cubic Bézier pieces `[p0, p1, p2, p3]` tracing an arc, ellipse or
+    /// Bézier; arcs and ellipses are split into spans of at most 90°. Lines
+    /// give none.
+    pub fn to_cubic_beziers(&self) -> Vec<[Vec2; 4]> {
+        let (center, rx, ry, rotation, start, end) = match *self {
+            Segment2D::Line { .. } => return Vec::new(),
+            Segment2D::CubicBezier { p0, p1, p2, p3 } => return vec![[p0, p1, p2, p3]],
+            Segment2D::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+            } => (center, radius, radius, 0.0, start_angle, end_angle),
+            Segment2D::Ellipse {
+                center,
+                rx,
+                ry,
+                rotation,
+                start_angle,
+                end_angle,
+            } => (center, rx, ry, rotation, start_angle, end_angle),
+        };
+        let (cos_r, sin_r) = (rotation.cos(), rotation.sin());
+        let at = |angle: f64| {
+            let (x, y) = (rx * angle.cos(), ry * angle.sin());
+            let (dx, dy) = (-rx * angle.sin(), ry * angle.cos());
+            (
+                Vec2::new(
+                    center.x + x * cos_r - y * sin_r,
+                    center.y + x * sin_r + y * cos_r,
+                ),
+                Vec2::new(dx * cos_r - dy * sin_r, dx * sin_r + dy * cos_r),
+            )
+        };
+        let pieces = ((end - start).abs() / std::f64::consts::FRAC_PI_2)
+            .ceil()
+            .max(1.0) as usize;
+        let step = (end - start) / pieces as f64;
+        let k = 4.0 / 3.0 * (step / 4.0).tan();
+        (0..pieces)
+            .map(|index| {
+                let ((p0, d0), (p3, d3)) = (
+                    at(start + step * index as f64),
+                    at(start + step * (index + 1) as f64),
+                );
+                [
+                    p0,
+                    Vec2::new(p0.x + d0.x * k, p0.y + d0.y * k),
+                    Vec2::new(p3.x - d3.x * k, p3.y - d3.y * k),
+                    p3,
+                ]
+            })
+            .collect()
+    }
+
+    /// Polyline through the segment, for consumers that only draw lines.
+    pub fn flatten(&self) -> Vec<Vec2> {
+        if let Segment2D::Line { start, end } = *self {
+            return vec![start, end];
+        }
+        let mut points = Vec::new();
+        for [p0, p1, p2, p3] in self.to_cubic_beziers() {
+            if points.is_empty() {
+                points.push(p0);
+            }
+            for step in 1..=FLATTEN_STEPS_PER_BEZIER {
+                let t = step as f64 / FLATTEN_STEPS_PER_BEZIER as f64;
+                let s = 1.0 - t;
+                let (a, b, c, d) = (s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t);
+                points.push(Vec2::new(
+                    a * p0.x + b * p1.x + c * p2.x + d * p3.x,
+                    a * p0.y + b * p1.y + c * p2.y + d * p3.y,
+                ));
+            }
+        }
+        points
+    }
+}
+
+const FLATTEN_STEPS_PER_BEZIER: usize = 8;
+
 /// One classified output segment from the HLR projection.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ClassifiedSegment {
@@ -147,6 +297,17 @@ impl Path2D {
 pub struct Scene2D {
     pub name: Option<String>,
     pub segments: Vec<ClassifiedSegment>,
+    /// Section cut regions to hatch, drawn beneath the segments.
+    #[serde(default)]
+    pub fills: Vec<HatchFill>,
+    /// Dimensions, leaders and notes, drawn above the segments.
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+    #[serde(default)]
+    pub dimension_style: DimensionStyle,
+    /// Entities left out of the drawing, each with the reason.
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
 /// Flat line representation used by the existing WASM `projectTo2DLines` API.
@@ -180,7 +341,7 @@ impl Scene2D {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.segments.is_empty()
+        self.segments.is_empty() && self.fills.is_empty() && self.annotations.is_empty()
     }
 
     pub fn add_segment(&mut self, seg: ClassifiedSegment) {
@@ -199,6 +360,10 @@ impl Scene2D {
         }
     }
 
+    pub fn add_annotation(&mut self, annotation: Annotation) {
+        self.annotations.push(annotation);
+    }
+
     pub fn segments(&self) -> &[ClassifiedSegment] {
         &self.segments
     }
@@ -213,19 +378,48 @@ impl Scene2D {
 
     pub fn extend(&mut self, other: Scene2D) {
         self.segments.extend(other.segments);
+        self.fills.extend(other.fills);
+        self.annotations.extend(other.annotations);
+        self.warnings.extend(other.warnings);
     }
 
+    /// Extents of segments, fills and the points annotations are defined by.
+    /// Annotation text and offsets laid out at paper size are not included.
     pub fn bounding_box(&self) -> Option<(Vec2, Vec2)> {
+        let annotation_points: Vec<Vec2> = self
+            .annotations
+            .iter()
+            .flat_map(Annotation::reference_points)
+            .collect();
+        let geometry = self.geometry_bounding_box();
+        match (geometry, points_bounds(&annotation_points)) {
+            (Some((lo, hi)), Some((a_lo, a_hi))) => Some((
+                Vec2::new(lo.x.min(a_lo.x), lo.y.min(a_lo.y)),
+                Vec2::new(hi.x.max(a_hi.x), hi.y.max(a_hi.y)),
+            )),
+            (geometry, annotations) => geometry.or(annotations),
+        }
+    }
+
+    /// Extents of segments and fills only.
+    pub fn geometry_bounding_box(&self) -> Option<(Vec2, Vec2)> {
         let mut min_x = f64::INFINITY;
         let mut min_y = f64::INFINITY;
         let mut max_x = f64::NEG_INFINITY;
         let mut max_y = f64::NEG_INFINITY;
         let mut has_data = false;
 
-        for seg in &self.segments {
-            let Some((lo, hi)) = segment_bounds(&seg.geometry) else {
-                continue;
-            };
+        let fill_bounds = self
+            .fills
+            .iter()
+            .filter_map(|fill| fill.boundary.first())
+            .filter_map(|ring| points_bounds(ring));
+        for (lo, hi) in self
+            .segments
+            .iter()
+            .filter_map(|seg| segment_bounds(&seg.geometry))
+            .chain(fill_bounds)
+        {
             min_x = min_x.min(lo.x);
             min_y = min_y.min(lo.y);
             max_x = max_x.max(hi.x);
@@ -244,10 +438,10 @@ impl Scene2D {
     pub fn to_lines(&self) -> Scene2DLines {
         let mut lines = Vec::new();
         for seg in &self.segments {
-            if let Segment2D::Line { start, end } = seg.geometry {
+            for pair in seg.geometry.flatten().windows(2) {
                 lines.push(Line2D {
-                    start,
-                    end,
+                    start: pair[0],
+                    end: pair[1],
                     stroke_width: None,
                     stroke_color: None,
                     class: Some(edge_class_str(seg.class).to_string()),
@@ -277,45 +471,7 @@ fn segment_bounds(seg: &Segment2D) -> Option<(Vec2, Vec2)> {
             Vec2::new(start.x.min(end.x), start.y.min(end.y)),
             Vec2::new(start.x.max(end.x), start.y.max(end.y)),
         )),
-        Segment2D::Arc {
-            center,
-            radius,
-            start_angle,
-            end_angle,
-        } => {
-            let pts = [
-                Vec2::new(
-                    center.x + radius * start_angle.cos(),
-                    center.y + radius * start_angle.sin(),
-                ),
-                Vec2::new(
-                    center.x + radius * end_angle.cos(),
-                    center.y + radius * end_angle.sin(),
-                ),
-            ];
-            points_bounds(&pts)
-        }
-        Segment2D::Ellipse {
-            center,
-            rx,
-            ry,
-            rotation,
-            start_angle,
-            end_angle,
-        } => {
-            let cos_r = rotation.cos();
-            let sin_r = rotation.sin();
-            let ellipse_pt = |a: f64| {
-                let ex = rx * a.cos();
-                let ey = ry * a.sin();
-                Vec2::new(
-                    center.x + ex * cos_r - ey * sin_r,
-                    center.y + ex * sin_r + ey * cos_r,
-                )
-            };
-            let pts = [ellipse_pt(*start_angle), ellipse_pt(*end_angle)];
-            points_bounds(&pts)
-        }
+        Segment2D::Arc { .. } | Segment2D::Ellipse { .. } => points_bounds(&seg.flatten()),
         Segment2D::CubicBezier { p0, p1, p2, p3 } => points_bounds(&[*p0, *p1, *p2, *p3]),
     }
 }
@@ -344,9 +500,66 @@ struct CameraFrame {
     up: [f64; 3],
     forward: [f64; 3],
     near: f64,
+    /// Far clipping distance, infinite when the camera sets none.
+    far: f64,
+    /// Half width and height of the viewport: in scene units for
+    /// orthographic, as slopes `x / depth` and `y / depth` for perspective.
+    extent: Option<(f64, f64)>,
+    /// Factor from projected view units to output: 1, or the viewport
+    /// normalisation when `normalize_to_viewport` is set.
+    scale: f64,
     mode: ProjectionMode,
 }
 
+impl CameraFrame {
+    /// Half-spaces bounding the view volume: near, far when finite, and the
+    /// four sides when the viewport is known.
+    fn clip_planes(&self) -> Vec<HalfSpace> {
+        let mut planes = vec![HalfSpace {
+            normal: [0.0, 0.0, 1.0],
+            offset: -self.near,
+        }];
+        if self.far.is_finite() {
+            planes.push(HalfSpace {
+                normal: [0.0, 0.0, -1.0],
+                offset: self.far,
+            });
+        }
+        if let Some((half_x, half_y)) = self.extent {
+            let sides = [
+                ([-1.0, 0.0], half_x),
+                ([1.0, 0.0], half_x),
+                ([0.0, -1.0], half_y),
+                ([0.0, 1.0], half_y),
+            ];
+            for ([nx, ny], half) in sides {
+                planes.push(match self.mode {
+                    ProjectionMode::Orthographic => HalfSpace {
+                        normal: [nx, ny, 0.0],
+                        offset: half,
+                    },
+                    ProjectionMode::Perspective => HalfSpace {
+                        normal: [nx, ny, half],
+                        offset: 0.0,
+                    },
+                });
+            }
+        }
+        planes
+    }
+
+    fn contains(&self, point: ViewPoint) -> bool {
+        self.clip_planes()
+            .iter()
+            .all(|plane| plane.eval(point) >= -EPSILON)
+    }
+
+    /// Projects a view-space point to output coordinates.
+    fn project(&self, point: ViewPoint) -> Option<Vec2> {
+        project_view_point(point, self.mode).map(|p| Vec2::new(p.x * self.scale, p.y * self.scale))
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 struct ViewPoint {
     x: f64,
@@ -382,27 +595,75 @@ struct EdgeCandidate {
 struct FaceInfo {
     front_facing: bool,
     normal: [f64; 3],
+    section_cap: bool,
 }
 
 pub fn project_brep_to_scene(brep: &Brep, camera: &CameraParameters, hlr: &HlrOptions) -> Scene2D {
-    let mut scene = Scene2D::with_name(format!("BRep {}", brep.id));
-    if brep.vertices.is_empty() {
-        return scene;
+    project_breps_to_scenes(&[brep], camera, hlr)
+        .pop()
+        .unwrap_or_default()
+}
+
+/// Projects a single world point the way edges are projected, or `None`
+/// if the camera is degenerate or the point lies outside the view frustum.
+pub fn project_point(point: &Vector3, camera: &CameraParameters) -> Option<Vec2> {
+    let frame = build_camera_frame(camera)?;
+    let view = world_to_view(point, &frame);
+    if !frame.contains(view) {
+        return None;
     }
+    frame.project(view)
+}
 
+/// Projects several B-reps with shared hidden line removal: every edge is
+/// tested against the faces of all of them, so one object hides another.
+/// Returns one scene per input, in order.
+pub fn project_breps_to_scenes(
+    breps: &[&Brep],
+    camera: &CameraParameters,
+    hlr: &HlrOptions,
+) -> Vec<Scene2D> {
     let Some(frame) = build_camera_frame(camera) else {
-        return scene;
+        return breps
+            .iter()
+            .map(|brep| Scene2D::with_name(format!("BRep {}", brep.id)))
+            .collect();
     };
+    let occluders = hlr.occlusion.then(|| Occluders::build(breps, &frame));
+    breps
+        .iter()
+        .map(|brep| project_with_frame(brep, &frame, hlr, occluders.as_ref()))
+        .collect()
+}
+
+fn project_with_frame(
+    brep: &Brep,
+    frame: &CameraFrame,
+    hlr: &HlrOptions,
+    occluders: Option<&Occluders>,
+) -> Scene2D {
+    let frame = *frame;
+    let mut scene = Scene2D::with_name(format!("BRep {}", brep.id));
+    if brep.vertices.is_empty() {
+        return scene;
+    }
 
     let face_info = compute_face_info(brep, &frame);
     let adjacency = build_edge_adjacency(brep);
     let candidates = collect_candidate_edges(brep);
+    let (patch_of_face, patches) = curved_patches(brep);
     let source_id = brep.id.to_string();
+    let mut arcs = AnalyticArcs::default();
 
     for edge in candidates {
         if !is_edge_vertex_index_valid(edge.a, edge.b, brep.vertices.len()) {
             continue;
         }
+        // Facet edges inside one curved surface are tessellation artefacts;
+        // its true outline is drawn analytically below.
+        if is_facet_edge(edge.id, &adjacency, &patch_of_face) {
+            continue;
+        }
 
         let class = classify_edge(edge.id, &adjacency, &face_info);
         let should_emit = match class {
@@ -419,35 +680,1112 @@ pub fn project_brep_to_scene(brep: &Brep, camera: &CameraParameters, hlr: &HlrOp
 
         let start_view = world_to_view(start_world, &frame);
         let end_view = world_to_view(end_world, &frame);
-        let Some((start_clipped, end_clipped)) =
-            clip_segment_to_near_plane(start_view, end_view, frame.near)
-        else {
+        let circle = brep.edges[edge.id as usize]
+            .curve
+            .as_ref()
+            .and_then(|curve| CircleView::from_curve(curve, &frame));
+        // Chords of a known circle lie in front of the near plane and are
+        // clipped exactly as arcs later; other edges are clipped here.
+        let clipped = match circle {
+            Some(_) => Some((start_view, end_view)),
+            None => clip_segment_to_frustum(start_view, end_view, &frame),
+        };
+        let Some((start_clipped, end_clipped)) = clipped else {
             continue;
         };
 
-        let Some(start_2d) = project_view_point(start_clipped, frame.mode) else {
+        let occluded = match occluders {
+            Some(occluders) if class != EdgeClass::Hidden => {
+                occluders.occluded_spans(start_clipped, end_clipped)
+            }
+            _ => Vec::new(),
+        };
+        let pieces = split_at_occlusion(&occluded, class);
+        if let Some(circle) = circle {
+            // Chords of a known circle are replaced by its exact projection.
+            arcs.add_chord(circle, start_clipped, end_clipped, &pieces, hlr);
             continue;
+        }
+        push_line_pieces(
+            &mut scene,
+            (start_clipped, end_clipped),
+            &pieces,
+            &frame,
+            hlr,
+            &source_id,
+        );
+    }
+
+    for patch in &patches {
+        let cylinder = match patch.surface {
+            SurfaceGeometry::Cylinder {
+                origin,
+                axis,
+                ref_direction,
+                radius,
+                ..
+            } => CylinderSurface::new(origin, axis, ref_direction, *radius),
+            SurfaceGeometry::Sphere { center, radius } => {
+                let sphere = (vec3_to_arr(center), *radius);
+                add_sphere_outline(
+                    &mut arcs,
+                    brep,
+                    &patch.faces,
+                    sphere,
+                    &frame,
+                    hlr,
+                    occluders,
+                );
+                continue;
+            }
+            SurfaceGeometry::Plane { .. } => continue,
         };
-        let Some(end_2d) = project_view_point(end_clipped, frame.mode) else {
+        let generators = cylinder
+            .map(|cylinder| cylinder.silhouettes(brep, &patch.faces, &frame))
+            .unwrap_or_default();
+        for (start, end) in generators {
+            push_outline(&mut scene, (start, end), &frame, hlr, occluders, &source_id);
+        }
+    }
+
+    for (geometry, class) in arcs.into_segments(&frame) {
+        scene.add_segment(ClassifiedSegment {
+            geometry,
+            class,
+            layer: None,
+            source_entity_id: Some(source_id.clone()),
+        });
+    }
+
+    for (face, info) in brep.faces.iter().zip(&face_info) {
+        if !info.section_cap || !info.front_facing {
+            continue;
+        }
+        let (outer, holes) = brep.get_vertices_and_holes_by_face_id(face.id);
+        let mut rings = std::iter::once(outer).chain(holes).map(|ring| {
+            let view: Vec<ViewPoint> = ring.iter().map(|p| world_to_view(p, &frame)).collect();
+            clip_polygon_to_frustum(view, &frame)
+                .into_iter()
+                .map(|p| frame.project(p))
+                .collect::<Option<Vec<Vec2>>>()
+                .filter(|ring| ring.len() >= 3)
+        });
+        let Some(outer) = rings.next().flatten() else {
             continue;
         };
+        scene.fills.push(HatchFill {
+            boundary: std::iter::once(outer).chain(rings.flatten()).collect(),
+            pattern: HatchPattern::default(),
+            layer: None,
+            source_entity_id: Some(source_id.clone()),
+        });
+    }
 
+    scene
+}
+
+/// Emits the classified pieces of a clipped view-space segment as lines.
+fn push_line_pieces(
+    scene: &mut Scene2D,
+    (start, end): (ViewPoint, ViewPoint),
+    pieces: &[(f64, f64, EdgeClass)],
+    frame: &CameraFrame,
+    hlr: &HlrOptions,
+    source_id: &str,
+) {
+    for &(t0, t1, piece_class) in pieces {
+        if piece_class == EdgeClass::Hidden && hlr.hide_hidden_edges {
+            continue;
+        }
+        let Some(start_2d) = frame.project(interpolate(start, end, t0)) else {
+            continue;
+        };
+        let Some(end_2d) = frame.project(interpolate(start, end, t1)) else {
+            continue;
+        };
         if is_zero_length_2d(start_2d, end_2d) {
             continue;
         }
-
         scene.add_segment(ClassifiedSegment {
             geometry: Segment2D::Line {
                 start: start_2d,
                 end: end_2d,
             },
-            class,
+            class: piece_class,
             layer: None,
-            source_entity_id: Some(source_id.clone()),
+            source_entity_id: Some(source_id.to_string()),
         });
     }
+}
 
-    scene
+/// Emits a world-space silhouette line as `VisibleOutline`, hidden where
+/// something lies in front of it.
+fn push_outline(
+    scene: &mut Scene2D,
+    (start, end): ([f64; 3], [f64; 3]),
+    frame: &CameraFrame,
+    hlr: &HlrOptions,
+    occluders: Option<&Occluders>,
+    source_id: &str,
+) {
+    let to_view = |p: [f64; 3]| world_to_view(&Vector3::new(p[0], p[1], p[2]), frame);
+    let Some(clipped) = clip_segment_to_frustum(to_view(start), to_view(end), frame) else {
+        return;
+    };
+    let occluded = occluders
+        .map(|occluders| occluders.occluded_spans(clipped.0, clipped.1))
+        .unwrap_or_default();
+    let pieces = split_at_occlusion(&occluded, EdgeClass::VisibleOutline);
+    push_line_pieces(scene, clipped, &pieces, frame, hlr, source_id);
+}
+
+/// Faces tessellating one curved analytic surface (cylinder or sphere).
+struct CurvedPatch<'a> {
+    surface: &'a SurfaceGeometry,
+    faces: Vec<usize>,
+}
+
+/// Groups faces by the curved surface they carry. Returns the patch index of
+/// every face (`None` for planar or untagged faces) and the patches.
+fn curved_patches(brep: &Brep) -> (Vec<Option<usize>>, Vec<CurvedPatch<'_>>) {
+    let mut patches: Vec<CurvedPatch> = Vec::new();
+    let mut index: HashMap<Vec<u64>, usize> = HashMap::new();
+    let patch_of_face = brep
+        .faces
+        .iter()
+        .enumerate()
+        .map(|(face_index, face)| {
+            let surface = face.surface.as_ref()?;
+            let key = curved_surface_key(surface)?;
+            let slot = *index.entry(key).or_insert_with(|| {
+                patches.push(CurvedPatch {
+                    surface,
+                    faces: Vec::new(),
+                });
+                patches.len() - 1
+            });
+            patches[slot].faces.push(face_index);
+            Some(slot)
+        })
+        .collect();
+    (patch_of_face, patches)
+}
+
+/// Bitwise identity of a curved surface; `None` for planes.
+fn curved_surface_key(surface: &SurfaceGeometry) -> Option<Vec<u64>> {
+    let bits = |v: &Vector3| [v.x.to_bits(), v.y.to_bits(), v.z.to_bits()];
+    let mut key = Vec::with_capacity(12);
+    match surface {
+        SurfaceGeometry::Plane { .. } => return None,
+        SurfaceGeometry::Cylinder {
+            origin,
+            axis,
+            ref_direction,
+            radius,
+            height,
+        } => {
+            key.push(0);
+            key.extend(
+                bits(origin)
+                    .into_iter()
+                    .chain(bits(axis))
+                    .chain(bits(ref_direction)),
+            );
+            key.extend([radius.to_bits(), height.to_bits()]);
+        }
+        SurfaceGeometry::Sphere { center, radius } => {
+            key.push(1);
+            key.extend(bits(center));
+            key.push(radius.to_bits());
+        }
+    }
+    Some(key)
+}
+
+/// Whether every face using the edge belongs to the same curved patch.
+fn is_facet_edge(
+    edge_id: u32,
+    adjacency: &HashMap<u32, Vec<usize>>,
+    patch_of_face: &[Option<usize>],
+) -> bool {
+    let Some(faces) = adjacency.get(&edge_id) else {
+        return false;
+    };
+    let mut patches = faces
+        .iter()
+        .map(|&face| patch_of_face.get(face).copied().flatten());
+    match patches.next() {
+        Some(Some(first)) => faces.len() > 1 && patches.all(|patch| patch == Some(first)),
+        _ => false,
+    }
+}
+
+/// A cylinder in its own frame: the point at angle θ and height s is
+/// `origin + radius (e1 cos θ + e2 sin θ) + s·axis`.
+struct CylinderSurface {
+    origin: [f64; 3],
+    axis: [f64; 3],
+    e1: [f64; 3],
+    e2: [f64; 3],
+    radius: f64,
+}
+
+impl CylinderSurface {
+    fn new(origin: &Vector3, axis: &Vector3, ref_direction: &Vector3, radius: f64) -> Option<Self> {
+        let axis = normalize(vec3_to_arr(axis))?;
+        let reference = vec3_to_arr(ref_direction);
+        let e1 = normalize(sub(reference, mul_scalar(axis, dot(reference, axis))))?;
+        Some(Self {
+            origin: vec3_to_arr(origin),
+            axis,
+            e1,
+            e2: cross(axis, e1),
+            radius,
+        })
+    }
+
+    fn point(&self, theta: f64, s: f64) -> [f64; 3] {
+        let radial = add(
+            mul_scalar(self.e1, theta.cos()),
+            mul_scalar(self.e2, theta.sin()),
+        );
+        add(
+            self.origin,
+            add(mul_scalar(radial, self.radius), mul_scalar(self.axis, s)),
+        )
+    }
+
+    /// `(θ, s)` of a point.
+    fn parameters(&self, p: &Vector3) -> (f64, f64) {
+        let q = sub(vec3_to_arr(p), self.origin);
+        (dot(q, self.e2).atan2(dot(q, self.e1)), dot(q, self.axis))
+    }
+
+    /// The silhouette generators: where the surface normal
+    /// `cos θ e1 + sin θ e2` is perpendicular to the line of sight. Along a
+    /// generator the condition doesn't depend on s, so it reduces to
+    /// `α cos θ + β sin θ = c`, clipped to the patch's faces.
+    fn silhouettes(
+        &self,
+        brep: &Brep,
+        faces: &[usize],
+        frame: &CameraFrame,
+    ) -> Vec<([f64; 3], [f64; 3])> {
+        let (alpha, beta, c) = match frame.mode {
+            ProjectionMode::Orthographic => {
+                let d = frame.forward;
+                (dot(self.e1, d), dot(self.e2, d), 0.0)
+            }
+            ProjectionMode::Perspective => {
+                let w = sub(self.origin, frame.position);
+                (dot(self.e1, w), dot(self.e2, w), -self.radius)
+            }
+        };
+        let rho = alpha.hypot(beta);
+        if rho <= EPSILON || c.abs() >= rho {
+            // Looking down the axis, or from inside: no tangent generators.
+            return Vec::new();
+        }
+        let phi = beta.atan2(alpha);
+        let spread = (c / rho).acos();
+
+        let mut lines = Vec::new();
+        for theta in [phi + spread, phi - spread] {
+            for (s0, s1) in self.generator_spans(brep, faces, theta) {
+                lines.push((self.point(theta, s0), self.point(theta, s1)));
+            }
+        }
+        lines
+    }
+
+    /// Merged height spans where the generator at `theta` crosses the
+    /// patch's faces, found in the faces' `(θ, s)` parameter polygons.
+    fn generator_spans(&self, brep: &Brep, faces: &[usize], theta: f64) -> Vec<(f64, f64)> {
+        let wrap = |angle: f64| {
+            (angle - theta + std::f64::consts::PI).rem_euclid(std::f64::consts::TAU)
+                - std::f64::consts::PI
+        };
+        let mut spans: Vec<(f64, f64)> = Vec::new();
+        for &face_index in faces {
+            let Some(face) = brep.faces.get(face_index) else {
+                continue;
+            };
+            let polygon: Vec<(f64, f64)> = brep
+                .get_vertices_by_face_id(face.id)
+                .iter()
+                .map(|p| {
+                    let (angle, s) = self.parameters(p);
+                    (wrap(angle), s)
+                })
+                .collect();
+            let (low, high) = polygon
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), p| {
+                    (low.min(p.0), high.max(p.0))
+                });
+            if polygon.len() < 3 || high - low > std::f64::consts::PI {
+                continue; // straddles the opposite side of the surface
+            }
+
+            let mut crossings: Vec<f64> = Vec::new();
+            for index in 0..polygon.len() {
+                let (a, b) = (polygon[index], polygon[(index + 1) % polygon.len()]);
+                if (a.0 > 0.0) != (b.0 > 0.0) {
+                    crossings.push(a.1 + (b.1 - a.1) * a.0 / (a.0 - b.0));
+                }
+            }
+            crossings.sort_by(f64::total_cmp);
+            spans.extend(crossings.chunks_exact(2).map(|pair| (pair[0], pair[1])));
+        }
+
+        spans.sort_by(|x, y| x.0.total_cmp(&y.0));
+        let mut merged: Vec<(f64, f64)> = Vec::with_capacity(spans.len());
+        for (lo, hi) in spans {
+            match merged.last_mut() {
+                Some(last) if lo <= last.1 + EPSILON => last.1 = last.1.max(hi),
+                _ => merged.push((lo, hi)),
+            }
+        }
+        merged.retain(|(lo, hi)| hi - lo > EPSILON);
+        merged
+    }
+}
+
+/// Chords sampling a sphere's outline circle for occlusion and clipping.
+const SPHERE_OUTLINE_CHORDS: usize = 128;
+
+/// Adds the outline circle of a sphere patch — where the lines of sight
+/// graze the sphere — to `arcs` as `VisibleOutline`, limited to the part
+/// the patch's faces cover.
+fn add_sphere_outline(
+    arcs: &mut AnalyticArcs,
+    brep: &Brep,
+    faces: &[usize],
+    (center, radius): ([f64; 3], f64),
+    frame: &CameraFrame,
+    hlr: &HlrOptions,
+    occluders: Option<&Occluders>,
+) {
+    // Orthographic: the great circle facing the view. Perspective: the
+    // smaller circle where the tangent cone from the eye touches.
+    let (circle_center, normal, circle_radius) = match frame.mode {
+        ProjectionMode::Orthographic => (center, frame.forward, radius),
+        ProjectionMode::Perspective => {
+            let to_center = sub(center, frame.position);
+            let distance = dot(to_center, to_center).sqrt();
+            if distance <= radius + EPSILON {
+                return;
+            }
+            let normal = mul_scalar(to_center, 1.0 / distance);
+            (
+                sub(center, mul_scalar(normal, radius * radius / distance)),
+                normal,
+                radius * (distance * distance - radius * radius).sqrt() / distance,
+            )
+        }
+    };
+    let Some(x_axis) = normalize(sub(
+        frame.right,
+        mul_scalar(normal, dot(frame.right, normal)),
+    )) else {
+        return;
+    };
+    let to_vector = |v: [f64; 3]| Vector3::new(v[0], v[1], v[2]);
+    let curve = CurveGeometry::Circle {
+        center: to_vector(circle_center),
+        normal: to_vector(normal),
+        x_axis: to_vector(x_axis),
+        radius: circle_radius,
+        start_angle: 0.0,
+        end_angle: std::f64::consts::TAU,
+    };
+    let Some(circle) = CircleView::from_curve(&curve, frame) else {
+        return;
+    };
+
+    let facets: Vec<Vec<[f64; 3]>> = faces
+        .iter()
+        .filter_map(|&face_index| brep.faces.get(face_index))
+        .map(|face| {
+            brep.get_vertices_by_face_id(face.id)
+                .iter()
+                .map(|p| sub(vec3_to_arr(p), center))
+                .collect()
+        })
+        .collect();
+    let covered = |direction: [f64; 3]| {
+        facets
+            .iter()
+            .any(|facet| radial_ray_hits_facet(direction, facet))
+    };
+
+    let y_axis = cross(normal, x_axis);
+    let step = std::f64::consts::TAU / SPHERE_OUTLINE_CHORDS as f64;
+    // Chord ends on the circumscribed polygon keep every chord outside the
+    // sphere, so the tessellation never hides its own outline.
+    let reach = circle_radius / (0.5 * step).cos();
+    let on_circle = |angle: f64, r: f64| {
+        add(
+            circle_center,
+            add(
+                mul_scalar(x_axis, r * angle.cos()),
+                mul_scalar(y_axis, r * angle.sin()),
+            ),
+        )
+    };
+    let to_view = |p: [f64; 3]| world_to_view(&to_vector(p), frame);
+    for index in 0..SPHERE_OUTLINE_CHORDS {
+        let angle = step * index as f64;
+        if !covered(sub(on_circle(angle + 0.5 * step, circle_radius), center)) {
+            continue;
+        }
+        let (start, end) = (
+            to_view(on_circle(angle, reach)),
+            to_view(on_circle(angle + step, reach)),
+        );
+        let occluded = occluders
+            .map(|occluders| occluders.occluded_spans(start, end))
+            .unwrap_or_default();
+        let pieces = split_at_occlusion(&occluded, EdgeClass::VisibleOutline);
+        arcs.add_chord(circle, start, end, &pieces, hlr);
+    }
+}
+
+/// Whether the ray from the sphere centre along `direction` passes through
+/// a convex facet given relative to the centre.
+fn radial_ray_hits_facet(direction: [f64; 3], facet: &[[f64; 3]]) -> bool {
+    if facet.len() < 3 {
+        return false;
+    }
+    let centroid = facet.iter().fold([0.0; 3], |sum, p| add(sum, *p));
+    if dot(direction, centroid) <= 0.0 {
+        return false;
+    }
+    let mut sign = 0.0;
+    for index in 0..facet.len() {
+        let side = dot(
+            direction,
+            cross(facet[index], facet[(index + 1) % facet.len()]),
+        );
+        if side.abs() <= EPSILON {
+            continue;
+        }
+        if sign == 0.0 {
+            sign = side.signum();
+        } else if side.signum() != sign {
+            return false;
+        }
+    }
+    true
+}
+
+/// A circle in view space: `center + u cos θ + v sin θ`, with `u` and `v`
+/// the circle's x axis and in-plane y axis scaled by its radius.
+#[derive(Clone, Copy, Debug)]
+struct CircleView {
+    center: [f64; 3],
+    u: [f64; 3],
+    v: [f64; 3],
+}
+
+impl CircleView {
+    /// `None` unless the curve is a circle lying wholly in front of the near
+    /// plane whose projection does not collapse to a line.
+    fn from_curve(curve: &CurveGeometry, frame: &CameraFrame) -> Option<Self> {
+        let CurveGeometry::Circle {
+            center,
+            normal,
+            x_axis,
+            radius,
+            ..
+        } = curve
+        else {
+            return None;
+        };
+        let to_view = |v: [f64; 3]| [dot(v, frame.right), dot(v, frame.up), dot(v, frame.forward)];
+        let normal = normalize(vec3_to_arr(normal))?;
+        let x_axis = normalize(vec3_to_arr(x_axis))?;
+        let y_axis = cross(normal, x_axis);
+        let view = world_to_view(center, frame);
+        let circle = Self {
+            center: [view.x, view.y, view.z],
+            u: mul_scalar(to_view(x_axis), *radius),
+            v: mul_scalar(to_view(y_axis), *radius),
+        };
+
+        let depth_reach = (circle.u[2].powi(2) + circle.v[2].powi(2)).sqrt();
+        if circle.center[2] - depth_reach < frame.near {
+            return None;
+        }
+        let view_normal = normalize(cross(circle.u, circle.v))?;
+        let edge_on = match frame.mode {
+            ProjectionMode::Orthographic => view_normal[2].abs(),
+            ProjectionMode::Perspective => dot(view_normal, normalize(circle.center)?).abs(),
+        };
+        (edge_on > 1.0e-6).then_some(circle)
+    }
+
+    fn point(&self, angle: f64) -> [f64; 3] {
+        add(
+            self.center,
+            add(
+                mul_scalar(self.u, angle.cos()),
+                mul_scalar(self.v, angle.sin()),
+            ),
+        )
+    }
+
+    /// Angle in `[0, 2π)` of a point, measured about the circle centre.
+    fn angle_of(&self, point: ViewPoint) -> f64 {
+        let offset = sub([point.x, point.y, point.z], self.center);
+        dot(offset, self.v)
+            .atan2(dot(offset, self.u))
+            .rem_euclid(std::f64::consts::TAU)
+    }
+
+    fn key(&self) -> [u64; 9] {
+        let mut key = [0u64; 9];
+        for (slot, value) in key
+            .iter_mut()
+            .zip(self.center.iter().chain(&self.u).chain(&self.v))
+        {
+            *slot = value.to_bits();
+        }
+        key
+    }
+
+    /// Parts of the arc from `start` to `end` inside every half-space.
+    /// Each bound `n · p + d` is `a cos θ + b sin θ + c` along the circle,
+    /// so its crossings are found in closed form.
+    fn clip(&self, start: f64, end: f64, planes: &[HalfSpace]) -> Vec<(f64, f64)> {
+        let mut cuts = vec![start, end];
+        for plane in planes {
+            let a = dot(plane.normal, self.u);
+            let b = dot(plane.normal, self.v);
+            let c = dot(plane.normal, self.center) + plane.offset;
+            let amplitude = (a * a + b * b).sqrt();
+            if amplitude <= EPSILON || c.abs() >= amplitude {
+                continue;
+            }
+            let phase = b.atan2(a);
+            let spread = (-c / amplitude).acos();
+            for root in [phase - spread, phase + spread] {
+                let mut angle = start + (root - start).rem_euclid(std::f64::consts::TAU);
+                while angle < end {
+                    cuts.push(angle);
+                    angle += std::f64::consts::TAU;
+                }
+            }
+        }
+        cuts.sort_by(f64::total_cmp);
+
+        let inside = |angle: f64| {
+            let p = self.point(angle);
+            let p = ViewPoint {
+                x: p[0],
+                y: p[1],
+                z: p[2],
+            };
+            planes.iter().all(|plane| plane.eval(p) >= 0.0)
+        };
+        let mut spans: Vec<(f64, f64)> = Vec::new();
+        for pair in cuts.windows(2) {
+            let (lo, hi) = (pair[0], pair[1]);
+            if hi - lo <= ARC_JOIN_EPSILON || !inside(0.5 * (lo + hi)) {
+                continue;
+            }
+            match spans.last_mut() {
+                Some(last) if lo <= last.1 + ARC_JOIN_EPSILON => last.1 = hi,
+                _ => spans.push((lo, hi)),
+            }
+        }
+        spans
+    }
+
+    /// Exact 2D geometry of the arc from `start` to `end` (radians, start <
+    /// end): an arc when seen face-on, an ellipse under orthographic
+    /// projection, cubic Béziers under perspective.
+    fn project(&self, start: f64, end: f64, frame: &CameraFrame) -> Vec<Segment2D> {
+        let mode = frame.mode;
+        let view_normal = normalize(cross(self.u, self.v)).unwrap_or([0.0, 0.0, 1.0]);
+        let scale = frame.scale
+            * match mode {
+                ProjectionMode::Orthographic => 1.0,
+                ProjectionMode::Perspective => 1.0 / self.center[2],
+            };
+        let c = Vec2::new(self.center[0] * scale, self.center[1] * scale);
+        let u = Vec2::new(self.u[0] * scale, self.u[1] * scale);
+        let v = Vec2::new(self.v[0] * scale, self.v[1] * scale);
+        let turns = u.x * v.y - u.y * v.x;
+
+        if view_normal[2].abs() > 1.0 - FACE_ON_TOLERANCE {
+            let phase = u.y.atan2(u.x);
+            let (start_angle, end_angle) = if turns > 0.0 {
+                (phase + start, phase + end)
+            } else {
+                (phase - end, phase - start)
+            };
+            return vec![Segment2D::Arc {
+                center: c,
+                radius: (u.x * u.x + u.y * u.y).sqrt(),
+                start_angle,
+                end_angle,
+            }];
+        }
+
+        match mode {
+            ProjectionMode::Orthographic => {
+                // Principal axes from the conjugate semi-diameters u, v.
+                let uu = u.x * u.x + u.y * u.y;
+                let vv = v.x * v.x + v.y * v.y;
+                let uv = u.x * v.x + u.y * v.y;
+                let t0 = 0.5 * (2.0 * uv).atan2(uu - vv);
+                let a = Vec2::new(
+                    u.x * t0.cos() + v.x * t0.sin(),
+                    u.y * t0.cos() + v.y * t0.sin(),
+                );
+                let b = Vec2::new(
+                    -u.x * t0.sin() + v.x * t0.cos(),
+                    -u.y * t0.sin() + v.y * t0.cos(),
+                );
+                let (start_angle, end_angle) = if a.x * b.y - a.y * b.x > 0.0 {
+                    (start - t0, end - t0)
+                } else {
+                    (t0 - end, t0 - start)
+                };
+                vec![Segment2D::Ellipse {
+                    center: c,
+                    rx: (a.x * a.x + a.y * a.y).sqrt(),
+                    ry: (b.x * b.x + b.y * b.y).sqrt(),
+                    rotation: a.y.atan2(a.x),
+                    start_angle,
+                    end_angle,
+                }]
+            }
+            ProjectionMode::Perspective => {
+                let pieces = ((end - start) / PERSPECTIVE_BEZIER_SPAN).ceil().max(1.0) as usize;
+                let step = (end - start) / pieces as f64;
+                let projected = |angle: f64| {
+                    let p = self.point(angle);
+                    let d = add(
+                        mul_scalar(self.u, -angle.sin()),
+                        mul_scalar(self.v, angle.cos()),
+                    );
+                    let z2 = p[2] * p[2] / frame.scale;
+                    (
+                        Vec2::new(frame.scale * p[0] / p[2], frame.scale * p[1] / p[2]),
+                        Vec2::new(
+                            (d[0] * p[2] - p[0] * d[2]) / z2,
+                            (d[1] * p[2] - p[1] * d[2]) / z2,
+                        ),
+                    )
+                };
+                (0..pieces)
+                    .map(|index| {
+                        let (a0, a1) = (
+                            start + step * index as f64,
+                            start + step * (index + 1) as f64,
+                        );
+                        let ((p0, d0), (p3, d3)) = (projected(a0), projected(a1));
+                        let h = step / 3.0;
+                        Segment2D::CubicBezier {
+                            p0,
+                            p1: Vec2::new(p0.x + d0.x * h, p0.y + d0.y * h),
+                            p2: Vec2::new(p3.x - d3.x * h, p3.y - d3.y * h),
+                            p3,
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// A circle counts as face-on when its view normal is within this of the
+/// view axis.
+const FACE_ON_TOLERANCE: f64 = 1.0e-9;
+/// Largest angular span approximated by one perspective Bézier piece.
+const PERSPECTIVE_BEZIER_SPAN: f64 = std::f64::consts::FRAC_PI_4;
+/// Angular slack when stitching chord spans back into one arc.
+const ARC_JOIN_EPSILON: f64 = 1.0e-7;
+
+/// Angular span `(start, end)` of a circle, in radians, with its class.
+type ArcSpan = (f64, f64, EdgeClass);
+
+/// Classified angular spans collected from the chords of each circle.
+#[derive(Default)]
+struct AnalyticArcs {
+    circles: Vec<(CircleView, Vec<ArcSpan>)>,
+    index: HashMap<[u64; 9], usize>,
+}
+
+impl AnalyticArcs {
+    fn add_chord(
+        &mut self,
+        circle: CircleView,
+        start: ViewPoint,
+        end: ViewPoint,
+        pieces: &[(f64, f64, EdgeClass)],
+        hlr: &HlrOptions,
+    ) {
+        let slot = *self.index.entry(circle.key()).or_insert_with(|| {
+            self.circles.push((circle, Vec::new()));
+            self.circles.len() - 1
+        });
+        let spans = &mut self.circles[slot].1;
+        for &(t0, t1, class) in pieces {
+            if class == EdgeClass::Hidden && hlr.hide_hidden_edges {
+                continue;
+            }
+            let a = circle.angle_of(interpolate(start, end, t0));
+            let mut b = circle.angle_of(interpolate(start, end, t1));
+            // Chords subtend less than half a turn; take the short way round.
+            if b - a > std::f64::consts::PI {
+                b -= std::f64::consts::TAU;
+            } else if a - b > std::f64::consts::PI {
+                b += std::f64::consts::TAU;
+            }
+            let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+            if hi - lo > ARC_JOIN_EPSILON {
+                let shift = if lo < 0.0 { std::f64::consts::TAU } else { 0.0 };
+                spans.push((lo + shift, hi + shift, class));
+            }
+        }
+    }
+
+    fn into_segments(self, frame: &CameraFrame) -> Vec<(Segment2D, EdgeClass)> {
+        let planes = frame.clip_planes();
+        let mut segments = Vec::new();
+        for (circle, mut spans) in self.circles {
+            spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let mut merged: Vec<ArcSpan> = Vec::with_capacity(spans.len());
+            for span in spans {
+                match merged.last_mut() {
+                    Some(last) if last.2 == span.2 && span.0 <= last.1 + ARC_JOIN_EPSILON => {
+                        last.1 = last.1.max(span.1);
+                    }
+                    _ => merged.push(span),
+                }
+            }
+            // Join the span crossing angle zero with the one it continues.
+            if merged.len() > 1 {
+                let (first, last) = (merged[0], merged[merged.len() - 1]);
+                if first.2 == last.2 && last.1 >= first.0 + std::f64::consts::TAU - ARC_JOIN_EPSILON
+                {
+                    merged.pop();
+                    merged[0] = (
+                        last.0 - std::f64::consts::TAU,
+                        first.1.max(last.1 - std::f64::consts::TAU),
+                        first.2,
+                    );
+                }
+            }
+            for (start, end, class) in merged {
+                let end = end.min(start + std::f64::consts::TAU);
+                for (start, end) in circle.clip(start, end, &planes) {
+                    for geometry in circle.project(start, end, frame) {
+                        segments.push((geometry, class));
+                    }
+                }
+            }
+        }
+        segments
+    }
+}
+
+/// Pieces of the unit parameter range, classified: spans inside `occluded`
+/// (sorted, disjoint) become `Hidden`, the rest keep `class`.
+fn split_at_occlusion(occluded: &[(f64, f64)], class: EdgeClass) -> Vec<(f64, f64, EdgeClass)> {
+    let mut pieces = Vec::with_capacity(occluded.len() * 2 + 1);
+    let mut cursor = 0.0;
+    for &(start, end) in occluded {
+        if start > cursor {
+            pieces.push((cursor, start, class));
+        }
+        pieces.push((start, end, EdgeClass::Hidden));
+        cursor = end;
+    }
+    if cursor < 1.0 {
+        pieces.push((cursor, 1.0, class));
+    }
+    pieces
+}
+
+/// A half-space `normal · p + offset >= 0` in view space.
+#[derive(Clone, Copy, Debug)]
+struct HalfSpace {
+    normal: [f64; 3],
+    offset: f64,
+}
+
+impl HalfSpace {
+    fn eval(&self, p: ViewPoint) -> f64 {
+        self.normal[0] * p.x + self.normal[1] * p.y + self.normal[2] * p.z + self.offset
+    }
+}
+
+/// A view-space triangle as the region it hides: inside the three edge
+/// half-spaces (prisms for orthographic, pyramids from the eye for
+/// perspective) and strictly behind its plane.
+#[derive(Clone, Copy, Debug)]
+struct OccluderTriangle {
+    sides: [HalfSpace; 3],
+    behind: HalfSpace,
+}
+
+/// Every face of the projected B-reps, triangulated, with a uniform grid
+/// over their 2D footprints so each edge only meets nearby triangles.
+struct Occluders {
+    triangles: Vec<OccluderTriangle>,
+    cells: Vec<Vec<u32>>,
+    origin: Vec2,
+    cell_size: Vec2,
+    columns: usize,
+    rows: usize,
+    mode: ProjectionMode,
+}
+
+const MAX_GRID_CELLS_PER_AXIS: usize = 128;
+
+impl Occluders {
+    fn build(breps: &[&Brep], frame: &CameraFrame) -> Self {
+        let mut low = [f64::INFINITY; 3];
+        let mut high = [f64::NEG_INFINITY; 3];
+        for vertex in breps.iter().flat_map(|brep| brep.vertices.iter()) {
+            let p = vec3_to_arr(&vertex.position);
+            for axis in 0..3 {
+                low[axis] = low[axis].min(p[axis]);
+                high[axis] = high[axis].max(p[axis]);
+            }
+        }
+        let diagonal = dot(sub(high, low), sub(high, low)).sqrt();
+        let tolerance = crate::tolerance::ToleranceContext::derived_modeling_for_diagonal(diagonal)
+            .max(diagonal * 1.0e-9);
+
+        let mut triangles = Vec::new();
+        let mut footprints: Vec<(Vec2, Vec2)> = Vec::new();
+        for brep in breps {
+            for corners in brep.get_triangle_vertex_buffer().chunks_exact(9) {
+                let view = [0, 3, 6].map(|i| {
+                    world_to_view(
+                        &Vector3::new(corners[i], corners[i + 1], corners[i + 2]),
+                        frame,
+                    )
+                });
+                // Triangles reaching behind the near plane have no stable
+                // projection; they are not used as occluders.
+                if view.iter().any(|p| p.z < frame.near) {
+                    continue;
+                }
+                let Some(projected) = view
+                    .iter()
+                    .map(|p| project_view_point(*p, frame.mode))
+                    .collect::<Option<Vec<Vec2>>>()
+                else {
+                    continue;
+                };
+                let Some(triangle) = Self::occluder(view, frame.mode, tolerance) else {
+                    continue;
+                };
+                let Some(bounds) = points_bounds(&projected) else {
+                    continue;
+                };
+                triangles.push(triangle);
+                footprints.push(bounds);
+            }
+        }
+
+        let extent = footprints.iter().fold(
+            (
+                Vec2::new(f64::INFINITY, f64::INFINITY),
+                Vec2::new(f64::NEG_INFINITY, f64::NEG_INFINITY),
+            ),
+            |(lo, hi), (a, b)| {
+                (
+                    Vec2::new(lo.x.min(a.x), lo.y.min(a.y)),
+                    Vec2::new(hi.x.max(b.x), hi.y.max(b.y)),
+                )
+            },
+        );
+        let per_axis =
+            ((triangles.len() as f64).sqrt().ceil() as usize).clamp(1, MAX_GRID_CELLS_PER_AXIS);
+        let mut occluders = Self {
+            triangles,
+            cells: vec![Vec::new(); per_axis * per_axis],
+            origin: extent.0,
+            cell_size: Vec2::new(
+                ((extent.1.x - extent.0.x) / per_axis as f64).max(EPSILON),
+                ((extent.1.y - extent.0.y) / per_axis as f64).max(EPSILON),
+            ),
+            columns: per_axis,
+            rows: per_axis,
+            mode: frame.mode,
+        };
+        for (index, (lo, hi)) in footprints.iter().enumerate() {
+            let Some((c0, r0, c1, r1)) = occluders.cell_range(*lo, *hi) else {
+                continue;
+            };
+            for row in r0..=r1 {
+                for column in c0..=c1 {
+                    occluders.cells[row * occluders.columns + column].push(index as u32);
+                }
+            }
+        }
+        occluders
+    }
+
+    fn occluder(
+        view: [ViewPoint; 3],
+        mode: ProjectionMode,
+        tolerance: f64,
+    ) -> Option<OccluderTriangle> {
+        let [a, b, c] = view.map(|p| [p.x, p.y, p.z]);
+        let normal = normalize(cross(sub(b, a), sub(c, a)))?;
+        // Which side of the plane the eye is on: the eye sits at z = -inf
+        // for orthographic views and at the origin for perspective ones.
+        let eye_side = match mode {
+            ProjectionMode::Orthographic => -normal[2],
+            ProjectionMode::Perspective => -dot(normal, a),
+        };
+        if eye_side.abs() <= EPSILON {
+            return None;
+        }
+        let sign = eye_side.signum();
+        let behind = HalfSpace {
+            normal: mul_scalar(normal, -sign),
+            offset: sign * dot(normal, a) - tolerance,
+        };
+
+        let side = |p: [f64; 3], q: [f64; 3], opposite: [f64; 3]| -> Option<HalfSpace> {
+            let plane = match mode {
+                ProjectionMode::Orthographic => {
+                    let n = normalize([-(q[1] - p[1]), q[0] - p[0], 0.0])?;
+                    HalfSpace {
+                        normal: n,
+                        offset: -(n[0] * p[0] + n[1] * p[1]),
+                    }
+                }
+                ProjectionMode::Perspective => HalfSpace {
+                    normal: normalize(cross(p, q))?,
+                    offset: 0.0,
+                },
+            };
+            let o = ViewPoint {
+                x: opposite[0],
+                y: opposite[1],
+                z: opposite[2],
+            };
+            let inward = if plane.eval(o) >= 0.0 { 1.0 } else { -1.0 };
+            Some(HalfSpace {
+                normal: mul_scalar(plane.normal, inward),
+                offset: plane.offset * inward + tolerance,
+            })
+        };
+        Some(OccluderTriangle {
+            sides: [side(a, b, c)?, side(b, c, a)?, side(c, a, b)?],
+            behind,
+        })
+    }
+
+    fn cell_range(&self, lo: Vec2, hi: Vec2) -> Option<(usize, usize, usize, usize)> {
+        let column = |x: f64| ((x - self.origin.x) / self.cell_size.x).floor();
+        let row = |y: f64| ((y - self.origin.y) / self.cell_size.y).floor();
+        let (c0, c1, r0, r1) = (column(lo.x), column(hi.x), row(lo.y), row(hi.y));
+        let (max_c, max_r) = ((self.columns - 1) as f64, (self.rows - 1) as f64);
+        // A footprint touching the far border lands one past the last cell.
+        let outside = c1 < 0.0 || r1 < 0.0 || c0 > max_c + 1.0 || r0 > max_r + 1.0;
+        if outside || !(c0.is_finite() && r0.is_finite()) {
+            return None;
+        }
+        Some((
+            c0.clamp(0.0, max_c) as usize,
+            r0.clamp(0.0, max_r) as usize,
+            c1.clamp(0.0, max_c) as usize,
+            r1.clamp(0.0, max_r) as usize,
+        ))
+    }
+
+    /// Sorted, merged parameter spans of the view-space segment that lie
+    /// behind some occluding triangle.
+    fn occluded_spans(&self, start: ViewPoint, end: ViewPoint) -> Vec<(f64, f64)> {
+        if self.triangles.is_empty() {
+            return Vec::new();
+        }
+        let (Some(a), Some(b)) = (
+            project_view_point(start, self.mode),
+            project_view_point(end, self.mode),
+        ) else {
+            return Vec::new();
+        };
+        let Some((c0, r0, c1, r1)) = self.cell_range(
+            Vec2::new(a.x.min(b.x), a.y.min(b.y)),
+            Vec2::new(a.x.max(b.x), a.y.max(b.y)),
+        ) else {
+            return Vec::new();
+        };
+
+        // Triangles spanning several cells are tested once. Only the
+        // triangles actually visited are recorded, so the cost follows the
+        // cells the edge crosses rather than the scene's triangle count.
+        let mut seen: HashSet<u32> = HashSet::new();
+        let mut spans: Vec<(f64, f64)> = Vec::new();
+        for row in r0..=r1 {
+            for column in c0..=c1 {
+                for &index in &self.cells[row * self.columns + column] {
+                    if !seen.insert(index) {
+                        continue;
+                    }
+                    let triangle = &self.triangles[index as usize];
+                    let mut span = Some((0.0, 1.0));
+                    for plane in triangle
+                        .sides
+                        .iter()
+                        .chain(std::iter::once(&triangle.behind))
+                    {
+                        span = span.and_then(|(lo, hi)| {
+                            clip_span(lo, hi, plane.eval(start), plane.eval(end))
+                        });
+                    }
+                    if let Some(span) = span {
+                        spans.push(span);
+                    }
+                }
+            }
+        }
+
+        spans.sort_by(|x, y| x.0.total_cmp(&y.0));
+        let mut merged: Vec<(f64, f64)> = Vec::with_capacity(spans.len());
+        for (lo, hi) in spans {
+            match merged.last_mut() {
+                Some(last) if lo <= last.1 + OCCLUSION_SPAN_EPSILON => last.1 = last.1.max(hi),
+                _ => merged.push((lo, hi)),
+            }
+        }
+        merged
+    }
+}
+
+/// Parameter spans shorter than this are treated as touching or empty.
+const OCCLUSION_SPAN_EPSILON: f64 = 1.0e-9;
+
+/// Restricts `[lo, hi]` to where the linear function with values `f0` at
+/// t = 0 and `f1` at t = 1 is non-negative.
+fn clip_span(lo: f64, hi: f64, f0: f64, f1: f64) -> Option<(f64, f64)> {
+    let slope = f1 - f0;
+    let (lo, hi) = if slope.abs() <= f64::MIN_POSITIVE {
+        if f0 < 0.0 {
+            return None;
+        }
+        (lo, hi)
+    } else {
+        let root = -f0 / slope;
+        if slope > 0.0 {
+            (lo.max(root), hi)
+        } else {
+            (lo, hi.min(root))
+        }
+    };
+    (hi - lo > OCCLUSION_SPAN_EPSILON).then_some((lo, hi))
 }
 
 fn classify_edge(
@@ -471,6 +1809,10 @@ fn classify_edge(
         return EdgeClass::Hidden;
     }
 
+    if front_faces.iter().any(|f| f.section_cap) {
+        return EdgeClass::SectionCut;
+    }
+
     if front_faces.len() < adjacent_faces.len() {
         return EdgeClass::VisibleOutline;
     }
@@ -487,6 +1829,34 @@ fn build_camera_frame(camera: &CameraParameters) -> Option<CameraFrame> {
     let target = vec3_to_arr(&camera.target);
     let up_hint = vec3_to_arr(&camera.up);
     let near = camera.near.max(EPSILON);
+    let far = camera
+        .far
+        .filter(|far| *far > near)
+        .unwrap_or(f64::INFINITY);
+    let aspect = camera
+        .aspect
+        .filter(|aspect| aspect.is_finite() && *aspect > 0.0)
+        .unwrap_or(1.0);
+    let positive = |value: Option<f64>| value.filter(|v| v.is_finite() && *v > 0.0);
+    let extent = match camera.projection_mode {
+        ProjectionMode::Perspective => {
+            camera
+                .fov
+                .filter(|fov| *fov > 0.0 && *fov < 180.0)
+                .map(|fov| {
+                    let half = (0.5 * fov.to_radians()).tan();
+                    (half * aspect, half)
+                })
+        }
+        ProjectionMode::Orthographic => {
+            match (positive(camera.ortho_width), positive(camera.ortho_height)) {
+                (Some(width), Some(height)) => Some((0.5 * width, 0.5 * height)),
+                (Some(width), None) => Some((0.5 * width, 0.5 * width / aspect)),
+                (None, Some(height)) => Some((0.5 * height * aspect, 0.5 * height)),
+                (None, None) => None,
+            }
+        }
+    };
 
     let forward = normalize(sub(target, position))?;
     let right = normalize(cross(forward, up_hint))
@@ -500,6 +1870,11 @@ fn build_camera_frame(camera: &CameraParameters) -> Option<CameraFrame> {
         up,
         forward,
         near,
+        far,
+        extent,
+        scale: extent
+            .filter(|_| camera.normalize_to_viewport)
+            .map_or(1.0, |(_, half_height)| 1.0 / half_height),
         mode: camera.projection_mode,
     })
 }
@@ -513,34 +1888,40 @@ fn world_to_view(point: &Vector3, frame: &CameraFrame) -> ViewPoint {
     }
 }
 
-fn clip_segment_to_near_plane(
-    mut start: ViewPoint,
-    mut end: ViewPoint,
-    near: f64,
+/// Clips a view-space segment to the camera's view volume.
+fn clip_segment_to_frustum(
+    start: ViewPoint,
+    end: ViewPoint,
+    frame: &CameraFrame,
 ) -> Option<(ViewPoint, ViewPoint)> {
-    if start.z < near && end.z < near {
-        return None;
+    let (mut lo, mut hi) = (0.0, 1.0);
+    for plane in frame.clip_planes() {
+        (lo, hi) = clip_span(lo, hi, plane.eval(start), plane.eval(end))?;
     }
+    Some((interpolate(start, end, lo), interpolate(start, end, hi)))
+}
 
-    if start.z < near {
-        let denominator = end.z - start.z;
-        if denominator.abs() < EPSILON {
-            return None;
+/// Clips a view-space polygon to the camera's view volume, one plane at a
+/// time (Sutherland–Hodgman).
+fn clip_polygon_to_frustum(mut ring: Vec<ViewPoint>, frame: &CameraFrame) -> Vec<ViewPoint> {
+    for plane in frame.clip_planes() {
+        let mut clipped = Vec::with_capacity(ring.len() + 1);
+        for (index, &current) in ring.iter().enumerate() {
+            let next = ring[(index + 1) % ring.len()];
+            let (f0, f1) = (plane.eval(current), plane.eval(next));
+            if f0 >= 0.0 {
+                clipped.push(current);
+            }
+            if (f0 >= 0.0) != (f1 >= 0.0) {
+                clipped.push(interpolate(current, next, f0 / (f0 - f1)));
+            }
         }
-        let t = (near - start.z) / denominator;
-        start = interpolate(start, end, t);
-        start.z = near;
-    } else if end.z < near {
-        let denominator = end.z - start.z;
-        if denominator.abs() < EPSILON {
-            return None;
+        ring = clipped;
+        if ring.is_empty() {
+            break;
         }
-        let t = (near - start.z) / denominator;
-        end = interpolate(start, end, t);
-        end.z = near;
     }
-
-    Some((start, end))
+    ring
 }
 
 fn project_view_point(point: ViewPoint, mode: ProjectionMode) -> Option<Vec2> {
@@ -630,10 +2011,12 @@ fn collect_candidate_edges(brep: &Brep) -> Vec<EdgeCandidate> {
 fn compute_face_info(brep: &Brep, frame: &CameraFrame) -> Vec<FaceInfo> {
     let mut info = Vec::with_capacity(brep.faces.len());
     for face in &brep.faces {
+        let section_cap = face.role == Some(FaceRole::SectionCap);
         let Some((normal, center)) = compute_face_normal_and_center(brep, face) else {
             info.push(FaceInfo {
                 front_facing: true,
                 normal: [0.0, 0.0, 0.0],
+                section_cap,
             });
             continue;
         };
@@ -643,6 +2026,7 @@ fn compute_face_info(brep: &Brep, frame: &CameraFrame) -> Vec<FaceInfo> {
         info.push(FaceInfo {
             front_facing,
             normal,
+            section_cap,
         });
     }
     info
@@ -769,12 +2153,61 @@ mod tests {
             y: 0.0,
             z: 2.0,
         };
-        let clipped = clip_segment_to_near_plane(start, end, 1.0).unwrap();
+        let frame = build_camera_frame(&CameraParameters {
+            near: 1.0,
+            ..CameraParameters::default()
+        })
+        .unwrap();
+        let clipped = clip_segment_to_frustum(start, end, &frame).unwrap();
 
         assert!((clipped.0.z - 1.0).abs() < 1.0e-9);
         assert!((clipped.1.z - 2.0).abs() < 1.0e-9);
     }
 
+    #[test]
+    fn perspective_viewport_clips_to_the_frustum_and_normalises() {
+        let camera = CameraParameters {
+            position: Vector3::new(0.0, 0.0, 0.0),
+            target: Vector3::new(0.0, 0.0, -1.0),
+            up: Vector3::new(0.0, 1.0, 0.0),
+            projection_mode: ProjectionMode::Perspective,
+            fov: Some(90.0),
+            aspect: Some(2.0),
+            far: Some(10.0),
+            normalize_to_viewport: true,
+            ..CameraParameters::default()
+        };
+        let frame = build_camera_frame(&camera).unwrap();
+        let across = |z: f64| {
+            (
+                ViewPoint {
+                    x: -100.0,
+                    y: 1.0,
+                    z,
+                },
+                ViewPoint {
+                    x: 100.0,
+                    y: 1.0,
+                    z,
+                },
+            )
+        };
+
+        let (start, end) = across(5.0);
+        let (start, end) = clip_segment_to_frustum(start, end, &frame).unwrap();
+        assert!((start.x + 10.0).abs() < 1.0e-9 && (end.x - 10.0).abs() < 1.0e-9);
+        let (a, b) = (frame.project(start).unwrap(), frame.project(end).unwrap());
+        assert!((a.x + 2.0).abs() < 1.0e-9 && (b.x - 2.0).abs() < 1.0e-9);
+        assert!((a.y - 0.2).abs() < 1.0e-9);
+
+        let (start, end) = across(12.0);
+        assert!(clip_segment_to_frustum(start, end, &frame).is_none());
+        assert!(project_point(&Vector3::new(0.0, 0.0, -12.0), &camera).is_none());
+        assert!(project_point(&Vector3::new(0.0, 6.0, -5.0), &camera).is_none());
+        let top = project_point(&Vector3::new(0.0, 5.0, -5.0), &camera).unwrap();
+        assert!((top.y - 1.0).abs() < 1.0e-9);
+    }
+
     #[test]
     fn test_project_perspective() {
         let point = ViewPoint {
@@ -800,6 +2233,7 @@ mod tests {
             up: Vector3::new(0.0, 1.0, 0.0),
             near: 0.01,
             projection_mode: ProjectionMode::Orthographic,
+            ..CameraParameters::default()
         };
 
         let scene = project_brep_to_scene(&brep, &camera, &HlrOptions::default());
@@ -858,4 +2292,399 @@ mod tests {
         assert!(!lines.lines.is_empty());
         assert!(lines.lines[0].class.is_some());
     }
+
+    fn box_at(center: Vector3, size: f64) -> Brep {
+        let mut cuboid = crate::primitives::cuboid::OGCuboid::new("hlr-box".to_string());
+        cuboid
+            .set_config(Vector3::new(0.0, 0.0, 0.0), size, size, size)
+            .unwrap();
+        let mut brep = cuboid.brep().clone();
+        for vertex in &mut brep.vertices {
+            vertex.position.x += center.x;
+            vertex.position.y += center.y;
+            vertex.position.z += center.z;
+        }
+        brep
+    }
+
+    fn front_camera(mode: ProjectionMode) -> CameraParameters {
+        CameraParameters {
+            position: Vector3::new(0.5, 0.0, 10.0),
+            target: Vector3::new(0.5, 0.0, 0.0),
+            up: Vector3::new(0.0, 1.0, 0.0),
+            near: 0.01,
+            projection_mode: mode,
+            ..CameraParameters::default()
+        }
+    }
+
+    /// Segments of `scene` lying on the horizontal line y = `y`, as sorted
+    /// x ranges with their class.
+    fn spans_at_height(scene: &Scene2D, y: f64) -> Vec<(f64, f64, EdgeClass)> {
+        let mut spans: Vec<(f64, f64, EdgeClass)> = scene
+            .segments
+            .iter()
+            .filter_map(|seg| match seg.geometry {
+                Segment2D::Line { start, end }
+                    if (start.y - y).abs() < 1.0e-9 && (end.y - y).abs() < 1.0e-9 =>
+                {
+                    Some((start.x.min(end.x), start.x.max(end.x), seg.class))
+                }
+                _ => None,
+            })
+            .collect();
+        spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+        spans
+    }
+
+    #[test]
+    fn edges_behind_another_solid_are_split_into_hidden_and_visible() {
+        // Front box spans x in [-1, 1]; the rear box spans x in [0, 2].
+        let front = box_at(Vector3::new(0.0, 0.0, 0.0), 2.0);
+        let rear = box_at(Vector3::new(1.0, 0.0, -5.0), 2.0);
+        let hlr = HlrOptions {
+            hide_hidden_edges: false,
+            ..HlrOptions::default()
+        };
+        let scenes = project_breps_to_scenes(
+            &[&front, &rear],
+            &front_camera(ProjectionMode::Orthographic),
+            &hlr,
+        );
+
+        // Camera sits at x = 0.5, so in view space the rear box's top edge
+        // runs x in [-0.5, 1.5] and the front box covers it up to x = 0.5.
+        let top = spans_at_height(&scenes[1], 1.0);
+        let hidden: Vec<_> = top
+            .iter()
+            .filter(|span| span.2 == EdgeClass::Hidden)
+            .collect();
+        let visible: Vec<_> = top
+            .iter()
+            .filter(|span| span.2 != EdgeClass::Hidden)
+            .collect();
+        assert!(hidden
+            .iter()
+            .any(|span| (span.0 + 0.5).abs() < 1.0e-6 && (span.1 - 0.5).abs() < 1.0e-6));
+        assert!(visible
+            .iter()
+            .any(|span| (span.0 - 0.5).abs() < 1.0e-6 && (span.1 - 1.5).abs() < 1.0e-6));
+        assert!(visible.iter().all(|span| span.0 > 0.5 - 1.0e-6));
+
+        // The front box's top edge is not occluded by the rear box.
+        assert!(spans_at_height(&scenes[0], 1.0).iter().any(|span| {
+            span.2 == EdgeClass::VisibleOutline
+                && (span.0 + 1.5).abs() < 1.0e-6
+                && (span.1 - 0.5).abs() < 1.0e-6
+        }));
+
+        let adjacency_only = HlrOptions {
+            hide_hidden_edges: false,
+            occlusion: false,
+        };
+        let scenes = project_breps_to_scenes(
+            &[&front, &rear],
+            &front_camera(ProjectionMode::Orthographic),
+            &adjacency_only,
+        );
+        assert!(spans_at_height(&scenes[1], 1.0).iter().any(|span| {
+            span.2 == EdgeClass::VisibleOutline
+                && (span.0 + 0.5).abs() < 1.0e-6
+                && (span.1 - 1.5).abs() < 1.0e-6
+        }));
+    }
+
+    #[test]
+    fn perspective_view_hides_a_solid_fully_behind_another() {
+        let wall = box_at(Vector3::new(0.5, 0.0, 0.0), 6.0);
+        let small = box_at(Vector3::new(0.5, 0.0, -8.0), 1.0);
+        let scenes = project_breps_to_scenes(
+            &[&wall, &small],
+            &front_camera(ProjectionMode::Perspective),
+            &HlrOptions::default(),
+        );
+        assert!(!scenes[0].segments.is_empty());
+        assert!(scenes[1].segments.is_empty());
+    }
+
+    fn cylinder(radius: f64, height: f64, segments: u32) -> Brep {
+        let mut cylinder = crate::primitives::cylinder::OGCylinder::new("hlr-cyl".to_string());
+        cylinder
+            .set_config(
+                Vector3::new(0.0, 0.0, 0.0),
+                radius,
+                height,
+                std::f64::consts::TAU,
+                segments,
+            )
+            .unwrap();
+        cylinder.brep().clone()
+    }
+
+    #[test]
+    fn face_on_circle_projects_to_one_exact_arc() {
+        let camera = CameraParameters {
+            position: Vector3::new(0.0, 10.0, 0.0),
+            target: Vector3::new(0.0, 0.0, 0.0),
+            up: Vector3::new(0.0, 0.0, -1.0),
+            near: 0.01,
+            projection_mode: ProjectionMode::Orthographic,
+            ..CameraParameters::default()
+        };
+        let scene = project_brep_to_scene(&cylinder(1.5, 2.0, 32), &camera, &HlrOptions::default());
+
+        let arcs: Vec<_> = scene
+            .segments
+            .iter()
+            .filter_map(|seg| match seg.geometry {
+                Segment2D::Arc {
+                    center,
+                    radius,
+                    start_angle,
+                    end_angle,
+                } => Some((center, radius, end_angle - start_angle)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(arcs.len(), 1);
+        let (center, radius, sweep) = arcs[0];
+        assert!(center.x.abs() < 1.0e-9 && center.y.abs() < 1.0e-9);
+        assert!((radius - 1.5).abs() < 1.0e-9);
+        assert!((sweep - std::f64::consts::TAU).abs() < 1.0e-6);
+        assert!(!scene
+            .segments
+            .iter()
+            .any(|seg| matches!(seg.geometry, Segment2D::Line { .. })));
+
+        let (lo, hi) = scene.bounding_box().unwrap();
+        assert!((hi.x - lo.x - 3.0).abs() < 1.0e-6);
+        assert!(scene.to_lines().lines.len() > 16);
+    }
+
+    #[test]
+    fn orthographic_viewport_clips_circles_exactly_to_its_edges() {
+        let camera = CameraParameters {
+            position: Vector3::new(0.0, 10.0, 0.0),
+            target: Vector3::new(0.0, 0.0, 0.0),
+            up: Vector3::new(0.0, 0.0, -1.0),
+            ortho_height: Some(2.4),
+            normalize_to_viewport: true,
+            ..CameraParameters::default()
+        };
+        let scene = project_brep_to_scene(&cylinder(1.5, 2.0, 32), &camera, &HlrOptions::default());
+
+        let arcs: Vec<_> = scene
+            .segments
+            .iter()
+            .filter_map(|seg| match seg.geometry {
+                Segment2D::Arc {
+                    center,
+                    radius,
+                    start_angle,
+                    end_angle,
+                } => Some((center, radius, start_angle, end_angle)),
+                _ => None,
+            })
+            .collect();
+        // The circle overhangs each side of the square viewport once.
+        assert_eq!(arcs.len(), 4);
+        for (center, radius, start, end) in arcs {
+            assert!(center.x.abs() < 1.0e-9 && center.y.abs() < 1.0e-9);
+            assert!((radius - 1.25).abs() < 1.0e-9);
+            for angle in [start, end] {
+                let (x, y) = (radius * angle.cos(), radius * angle.sin());
+                assert!((x.abs().max(y.abs()) - 1.0).abs() < 1.0e-9);
+            }
+        }
+        let (lo, hi) = scene.bounding_box().unwrap();
+        assert!(lo.x >= -1.0 - 1.0e-9 && hi.y <= 1.0 + 1.0e-9);
+    }
+
+    #[test]
+    fn oblique_orthographic_circle_projects_to_ellipses_without_chords() {
+        let camera = CameraParameters {
+            position: Vector3::new(0.0, 5.0, 10.0),
+            target: Vector3::new(0.0, 0.0, 0.0),
+            up: Vector3::new(0.0, 1.0, 0.0),
+            near: 0.01,
+            projection_mode: ProjectionMode::Orthographic,
+            ..CameraParameters::default()
+        };
+        let scene = project_brep_to_scene(&cylinder(1.0, 2.0, 32), &camera, &HlrOptions::default());
+        let tilt = 5.0 / 125.0_f64.sqrt();
+
+        let mut ellipses = 0;
+        for seg in &scene.segments {
+            match seg.geometry {
+                Segment2D::Ellipse { rx, ry, .. } => {
+                    ellipses += 1;
+                    let (major, minor) = (rx.max(ry), rx.min(ry));
+                    assert!((major - 1.0).abs() < 1.0e-9);
+                    assert!((minor - tilt).abs() < 1.0e-9);
+                }
+                // Only the vertical silhouette generators remain as lines.
+                Segment2D::Line { start, end } => assert!((start.x - end.x).abs() < 1.0e-9),
+                _ => panic!("unexpected curve type"),
+            }
+        }
+        assert!(ellipses >= 2);
+    }
+
+    #[test]
+    fn perspective_circle_projects_to_beziers_on_the_true_curve() {
+        let camera = CameraParameters {
+            position: Vector3::new(0.0, 5.0, 10.0),
+            target: Vector3::new(0.0, 0.0, 0.0),
+            up: Vector3::new(0.0, 1.0, 0.0),
+            near: 0.01,
+            projection_mode: ProjectionMode::Perspective,
+            ..CameraParameters::default()
+        };
+        let scene = project_brep_to_scene(&cylinder(1.0, 2.0, 32), &camera, &HlrOptions::default());
+        let frame = build_camera_frame(&camera).unwrap();
+        let exact: Vec<Vec2> = [1.0, -1.0]
+            .iter()
+            .flat_map(|y| {
+                (0..3600).map(move |i| {
+                    let a = i as f64 * std::f64::consts::TAU / 3600.0;
+                    Vector3::new(a.cos(), *y, a.sin())
+                })
+            })
+            .filter_map(|p| project_view_point(world_to_view(&p, &frame), frame.mode))
+            .collect();
+
+        let mut beziers = 0;
+        for seg in &scene.segments {
+            if let Segment2D::CubicBezier { .. } = seg.geometry {
+                beziers += 1;
+                for point in seg.geometry.flatten() {
+                    let nearest = exact
+                        .iter()
+                        .map(|q| ((q.x - point.x).powi(2) + (q.y - point.y).powi(2)).sqrt())
+                        .fold(f64::INFINITY, f64::min);
+                    assert!(nearest < 2.0e-4, "bezier strays {nearest} from the circle");
+                }
+            }
+        }
+        assert!(beziers >= 8);
+    }
+
+    /// Straight segments of `scene` with their class.
+    fn lines_of(scene: &Scene2D) -> Vec<(Vec2, Vec2, EdgeClass)> {
+        scene
+            .segments
+            .iter()
+            .filter_map(|seg| match seg.geometry {
+                Segment2D::Line { start, end } => Some((start, end, seg.class)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cylinder_silhouettes_are_exact_generators_whatever_the_tessellation() {
+        let camera = CameraParameters {
+            position: Vector3::new(0.0, 0.0, 10.0),
+            target: Vector3::new(0.0, 0.0, 0.0),
+            up: Vector3::new(0.0, 1.0, 0.0),
+            near: 0.01,
+            projection_mode: ProjectionMode::Orthographic,
+            ..CameraParameters::default()
+        };
+        for segments in [7, 8, 64] {
+            let scene = project_brep_to_scene(
+                &cylinder(1.0, 2.0, segments),
+                &camera,
+                &HlrOptions::default(),
+            );
+            // The caps are edge-on here, so their rims show as horizontal
+            // lines; every other line must be an exact silhouette.
+            let mut xs: Vec<f64> = lines_of(&scene)
+                .into_iter()
+                .filter(|(start, end, _)| (start.y - end.y).abs() > 1.0e-9)
+                .map(|(start, end, class)| {
+                    assert_eq!(class, EdgeClass::VisibleOutline);
+                    assert!((start.x - end.x).abs() < 1.0e-9);
+                    assert!(((start.y - end.y).abs() - 2.0).abs() < 1.0e-9);
+                    start.x
+                })
+                .collect();
+            xs.sort_by(f64::total_cmp);
+            assert_eq!(xs.len(), 2, "{segments} segments: no facet edges survive");
+            assert!((xs[0] + 1.0).abs() < 1.0e-9 && (xs[1] - 1.0).abs() < 1.0e-9);
+        }
+
+        // Each half of a split cylinder keeps only its own generator.
+        let halves = crate::operations::split::split_by_plane(
+            &cylinder(1.0, 2.0, 7),
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+        )
+        .unwrap();
+        for (half, side) in [(&halves.positive, 1.0), (&halves.negative, -1.0)] {
+            let scene = project_brep_to_scene(half, &camera, &HlrOptions::default());
+            let outlines: Vec<_> = lines_of(&scene)
+                .into_iter()
+                .filter(|(start, end, _)| {
+                    (start.y - end.y).abs() > 1.0e-9 && (start.x.abs() - 1.0).abs() < 1.0e-9
+                })
+                .collect();
+            assert_eq!(outlines.len(), 1);
+            assert!((outlines[0].0.x - side).abs() < 1.0e-9);
+        }
+    }
+
+    #[test]
+    fn sphere_projects_to_its_exact_outline_without_facet_edges() {
+        let mut sphere = crate::primitives::sphere::OGSphere::new("hlr-sphere".to_string());
+        sphere
+            .set_config(Vector3::new(0.0, 0.0, 0.0), 2.0, 12, 8)
+            .unwrap();
+        let camera = CameraParameters {
+            position: Vector3::new(3.0, 4.0, 5.0),
+            target: Vector3::new(0.0, 0.0, 0.0),
+            up: Vector3::new(0.0, 1.0, 0.0),
+            near: 0.01,
+            projection_mode: ProjectionMode::Orthographic,
+            ..CameraParameters::default()
+        };
+        let scene = project_brep_to_scene(sphere.brep(), &camera, &HlrOptions::default());
+
+        assert_eq!(scene.segments.len(), 1);
+        let segment = &scene.segments[0];
+        assert_eq!(segment.class, EdgeClass::VisibleOutline);
+        let Segment2D::Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+        } = segment.geometry
+        else {
+            panic!("a sphere seen orthographically outlines as a circle");
+        };
+        assert!(center.x.abs() < 1.0e-9 && center.y.abs() < 1.0e-9);
+        assert!((radius - 2.0).abs() < 1.0e-9);
+        assert!((end_angle - start_angle - std::f64::consts::TAU).abs() < 1.0e-6);
+
+        // In perspective the outline is the smaller tangent circle, on which
+        // every sight line from the eye grazes the sphere.
+        let perspective = CameraParameters {
+            projection_mode: ProjectionMode::Perspective,
+            ..camera
+        };
+        let scene = project_brep_to_scene(sphere.brep(), &perspective, &HlrOptions::default());
+        let eye_distance = 50.0_f64.sqrt();
+        let expected = 2.0 / (eye_distance * eye_distance - 4.0).sqrt();
+        assert!(!scene.segments.is_empty());
+        for segment in &scene.segments {
+            assert_eq!(segment.class, EdgeClass::VisibleOutline);
+            for point in segment.geometry.flatten() {
+                let r = (point.x * point.x + point.y * point.y).sqrt();
+                assert!(
+                    (r - expected).abs() < 1.0e-4,
+                    "outline at {r}, expected {expected}"
+                );
+            }
+        }
+    }
 }
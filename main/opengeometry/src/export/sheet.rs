@@ -0,0 +1,979 @@
+//! Drawing sheets: a paper size with a border and grid references, a title
+//! block, a scale bar, and viewports that show scenes at fixed drawing
+//! scales.
+//!
+//! [`Sheet::compose`] lays the whole sheet out into one [`Scene2D`] in
+//! paper millimetres, which the PDF, SVG and DXF exporters draw at 1:1.
+//! Viewport dimensions are laid out at their viewport's scale first, so
+//! they keep measuring model lengths.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::export::annotation::{Annotation, DimensionStyle, TextAlign, TextNote};
+use crate::export::hatch::{HatchFill, HatchPattern};
+use crate::export::projection::{ClassifiedSegment, EdgeClass, Scene2D, Segment2D, Vec2};
+use crate::units::LengthUnit;
+
+/// Layer for the border, grid, title block and scale bar.
+pub const SHEET_LAYER: &str = "SHEET";
+/// Layer for exploded viewport dimensions.
+pub const SHEET_DIMENSION_LAYER: &str = "DIMENSIONS";
+/// ISO 5457 grid reference lettering.
+const GRID_TEXT_HEIGHT_MM: f64 = 3.5;
+const LABEL_TEXT_HEIGHT_MM: f64 = 2.5;
+const VALUE_TEXT_HEIGHT_MM: f64 = 3.5;
+const VIEW_TITLE_HEIGHT_MM: f64 = 5.0;
+/// Clearance between a view and its caption.
+const VIEW_TITLE_GAP_MM: f64 = 5.0;
+const SCALE_BAR_HEIGHT_MM: f64 = 2.0;
+/// ISO 5457 omits letters that read like digits.
+const GRID_LETTERS: &str = "ABCDEFGHJKLMNPQRSTUVWXYZ";
+
+/// Paper size in millimetres.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PaperSize {
+    pub width_mm: f64,
+    pub height_mm: f64,
+}
+
+impl PaperSize {
+    /// ISO 216 A-series sheet in landscape, A0 to A6.
+    pub fn iso_a(size: usize) -> Option<Self> {
+        const SIDES: [f64; 8] = [1189.0, 841.0, 594.0, 420.0, 297.0, 210.0, 148.0, 105.0];
+        (size + 1 < SIDES.len()).then(|| Self {
+            width_mm: SIDES[size],
+            height_mm: SIDES[size + 1],
+        })
+    }
+
+    pub fn portrait(self) -> Self {
+        Self {
+            width_mm: self.width_mm.min(self.height_mm),
+            height_mm: self.width_mm.max(self.height_mm),
+        }
+    }
+
+    pub fn landscape(self) -> Self {
+        Self {
+            width_mm: self.width_mm.max(self.height_mm),
+            height_mm: self.width_mm.min(self.height_mm),
+        }
+    }
+}
+
+/// Drawing scale `paper:model`, e.g. 1:50.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DrawingScale {
+    pub paper: f64,
+    pub model: f64,
+}
+
+impl DrawingScale {
+    pub fn new(paper: f64, model: f64) -> Self {
+        Self { paper, model }
+    }
+
+    /// Scale 1:`model`.
+    pub fn one_to(model: f64) -> Self {
+        Self::new(1.0, model)
+    }
+
+    /// Parses `"1:50"` or `"1/50"`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (paper, model) = text.split_once([':', '/'])?;
+        let scale = Self::new(paper.trim().parse().ok()?, model.trim().parse().ok()?);
+        scale.is_valid().then_some(scale)
+    }
+
+    /// Paper length per unit of model length.
+    pub fn ratio(self) -> f64 {
+        self.paper / self.model
+    }
+
+    pub fn is_valid(self) -> bool {
+        self.paper.is_finite() && self.model.is_finite() && self.paper > 0.0 && self.model > 0.0
+    }
+}
+
+impl fmt::Display for DrawingScale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.paper, self.model)
+    }
+}
+
+/// ISO 5457 border: a drawing frame inset from the trimmed edge, with grid
+/// reference fields numbered along the top and bottom and lettered down the
+/// sides.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SheetBorder {
+    pub margin_mm: f64,
+    /// Wider left margin for binding.
+    pub filing_margin_mm: f64,
+    /// Nominal grid field size; zero disables grid references.
+    pub grid_field_mm: f64,
+}
+
+impl Default for SheetBorder {
+    fn default() -> Self {
+        Self {
+            margin_mm: 10.0,
+            filing_margin_mm: 20.0,
+            grid_field_mm: 50.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TitleBlockField {
+    pub label: String,
+    #[serde(default)]
+    pub value: String,
+}
+
+/// Grid of labelled fields in the bottom-right corner of the frame. An empty
+/// `Scale` field is filled with the scales of the sheet's viewports.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TitleBlock {
+    /// ISO 7200 caps the width at 180 mm.
+    pub width_mm: f64,
+    pub row_height_mm: f64,
+    pub columns: usize,
+    pub fields: Vec<TitleBlockField>,
+}
+
+impl Default for TitleBlock {
+    fn default() -> Self {
+        let fields = [
+            "Title",
+            "Drawing number",
+            "Scale",
+            "Date",
+            "Drawn by",
+            "Sheet",
+        ]
+        .into_iter()
+        .map(|label| TitleBlockField {
+            label: label.to_string(),
+            value: String::new(),
+        })
+        .collect();
+        Self {
+            width_mm: 180.0,
+            row_height_mm: 10.0,
+            columns: 2,
+            fields,
+        }
+    }
+}
+
+impl TitleBlock {
+    /// Sets the value of the field labelled `label`, adding it if missing.
+    pub fn set(&mut self, label: &str, value: impl Into<String>) {
+        let value = value.into();
+        match self.fields.iter_mut().find(|field| field.label == label) {
+            Some(field) => field.value = value,
+            None => self.fields.push(TitleBlockField {
+                label: label.to_string(),
+                value,
+            }),
+        }
+    }
+
+    fn rows(&self) -> usize {
+        self.fields.len().div_ceil(self.columns.max(1))
+    }
+}
+
+/// Alternating black and white bar marking model lengths at a drawing
+/// scale, with its bottom-left corner at `position_mm`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScaleBar {
+    pub position_mm: Vec2,
+    pub scale: DrawingScale,
+    /// Model length of one division, in `unit`.
+    pub division_length: f64,
+    #[serde(default = "default_scale_bar_divisions")]
+    pub divisions: usize,
+    #[serde(default = "default_scale_bar_unit")]
+    pub unit: LengthUnit,
+}
+
+fn default_scale_bar_divisions() -> usize {
+    4
+}
+
+fn default_scale_bar_unit() -> LengthUnit {
+    LengthUnit::Metre
+}
+
+/// A scene placed on the sheet at a fixed scale.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SheetViewport {
+    pub id: String,
+    pub scene: Scene2D,
+    pub scale: DrawingScale,
+    /// Sheet position of the view's centre.
+    pub center_mm: Vec2,
+    /// Scene point drawn at `center_mm`; the centre of the scene's extents
+    /// when absent.
+    #[serde(default)]
+    pub view_center: Option<Vec2>,
+    /// Caption set under the view, followed by its scale.
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Width and height of the viewport window, centred on `center_mm`.
+    /// Geometry outside the window is clipped away; an absent size leaves
+    /// the view unbounded.
+    #[serde(default)]
+    pub size_mm: Option<Vec2>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Sheet {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub paper: PaperSize,
+    #[serde(default = "default_border")]
+    pub border: Option<SheetBorder>,
+    #[serde(default)]
+    pub title_block: Option<TitleBlock>,
+    #[serde(default)]
+    pub scale_bar: Option<ScaleBar>,
+    #[serde(default)]
+    pub viewports: Vec<SheetViewport>,
+}
+
+fn default_border() -> Option<SheetBorder> {
+    Some(SheetBorder::default())
+}
+
+impl Sheet {
+    /// An empty sheet with the default ISO 5457 border.
+    pub fn new(paper: PaperSize) -> Self {
+        Self {
+            name: None,
+            paper,
+            border: default_border(),
+            title_block: None,
+            scale_bar: None,
+            viewports: Vec::new(),
+        }
+    }
+
+    /// The drawing frame `(lower-left, upper-right)` in millimetres.
+    pub fn frame(&self) -> (Vec2, Vec2) {
+        match &self.border {
+            Some(border) => (
+                Vec2::new(border.filing_margin_mm, border.margin_mm),
+                Vec2::new(
+                    self.paper.width_mm - border.margin_mm,
+                    self.paper.height_mm - border.margin_mm,
+                ),
+            ),
+            None => (
+                Vec2::new(0.0, 0.0),
+                Vec2::new(self.paper.width_mm, self.paper.height_mm),
+            ),
+        }
+    }
+
+    /// Lays the sheet out as one scene in paper millimetres.
+    pub fn compose(&self) -> Scene2D {
+        let mut pen = SheetPen {
+            out: Scene2D {
+                name: self.name.clone(),
+                dimension_style: DimensionStyle {
+                    scene_unit: LengthUnit::Millimetre,
+                    ..DimensionStyle::iso()
+                },
+                ..Scene2D::default()
+            },
+        };
+        for viewport in &self.viewports {
+            pen.viewport(viewport);
+        }
+        if let Some(border) = &self.border {
+            pen.border(self, border);
+        }
+        if let Some(title_block) = &self.title_block {
+            pen.title_block(self, title_block);
+        }
+        if let Some(scale_bar) = &self.scale_bar {
+            pen.scale_bar(scale_bar);
+        }
+        pen.out
+    }
+
+    /// Distinct viewport scales, in viewport order.
+    fn scales(&self) -> Vec<DrawingScale> {
+        let mut scales: Vec<DrawingScale> = Vec::new();
+        for viewport in &self.viewports {
+            if !scales.contains(&viewport.scale) {
+                scales.push(viewport.scale);
+            }
+        }
+        scales
+    }
+}
+
+struct SheetPen {
+    out: Scene2D,
+}
+
+impl SheetPen {
+    fn line(&mut self, start: Vec2, end: Vec2, class: EdgeClass) {
+        self.out.add_segment(ClassifiedSegment {
+            geometry: Segment2D::Line { start, end },
+            class,
+            layer: Some(SHEET_LAYER.to_string()),
+            source_entity_id: None,
+        });
+    }
+
+    fn rectangle(&mut self, lo: Vec2, hi: Vec2, class: EdgeClass) {
+        let corners = [lo, Vec2::new(hi.x, lo.y), hi, Vec2::new(lo.x, hi.y)];
+        for index in 0..4 {
+            self.line(corners[index], corners[(index + 1) % 4], class);
+        }
+    }
+
+    fn text(&mut self, text: String, position: Vec2, height_mm: f64, align: TextAlign) {
+        self.out.add_annotation(Annotation::Text(TextNote {
+            position,
+            text,
+            height_mm: Some(height_mm),
+            rotation: 0.0,
+            align,
+        }));
+    }
+
+    fn viewport(&mut self, viewport: &SheetViewport) {
+        let scene = &viewport.scene;
+        if !viewport.scale.is_valid() {
+            return;
+        }
+        let k = scene
+            .dimension_style
+            .scene_unit
+            .conversion_factor_to(LengthUnit::Millimetre)
+            * viewport.scale.ratio();
+        let anchor = viewport.view_center.unwrap_or_else(|| {
+            scene
+                .bounding_box()
+                .map(|(lo, hi)| Vec2::new(0.5 * (lo.x + hi.x), 0.5 * (lo.y + hi.y)))
+                .unwrap_or(Vec2::new(0.0, 0.0))
+        });
+        let center = viewport.center_mm;
+        let place = |p: Vec2| {
+            Vec2::new(
+                center.x + (p.x - anchor.x) * k,
+                center.y + (p.y - anchor.y) * k,
+            )
+        };
+        let mut placed = Scene2D {
+            warnings: scene.warnings.clone(),
+            ..Scene2D::default()
+        };
+
+        for fill in &scene.fills {
+            placed.fills.push(HatchFill {
+                boundary: fill
+                    .boundary
+                    .iter()
+                    .map(|ring| ring.iter().map(|p| place(*p)).collect())
+                    .collect(),
+                ..fill.clone()
+            });
+        }
+        for segment in &scene.segments {
+            placed.add_segment(ClassifiedSegment {
+                geometry: place_segment(&segment.geometry, &place, k),
+                ..segment.clone()
+            });
+        }
+        for annotation in &scene.annotations {
+            let graphics = annotation.layout(&scene.dimension_style, 1.0 / k);
+            for line in &graphics.lines {
+                placed.add_segment(ClassifiedSegment {
+                    geometry: place_segment(line, &place, k),
+                    class: EdgeClass::VisibleCrease,
+                    layer: Some(SHEET_DIMENSION_LAYER.to_string()),
+                    source_entity_id: None,
+                });
+            }
+            for solid in &graphics.solids {
+                placed.fills.push(HatchFill {
+                    boundary: vec![solid.iter().map(|p| place(*p)).collect()],
+                    pattern: HatchPattern::SolidFill,
+                    layer: Some(SHEET_DIMENSION_LAYER.to_string()),
+                    source_entity_id: None,
+                });
+            }
+            for text in &graphics.texts {
+                placed.add_annotation(Annotation::Text(TextNote {
+                    position: place(text.position),
+                    text: text.text.clone(),
+                    height_mm: Some(text.height * k),
+                    rotation: text.rotation,
+                    align: text.align,
+                }));
+            }
+        }
+
+        if let Some(size) = viewport.size_mm {
+            let half = Vec2::new(0.5 * size.x.abs(), 0.5 * size.y.abs());
+            placed = clip_to_window(
+                placed,
+                Vec2::new(center.x - half.x, center.y - half.y),
+                Vec2::new(center.x + half.x, center.y + half.y),
+            );
+        }
+
+        if let Some(title) = &viewport.title {
+            let (x, bottom) = match placed.bounding_box() {
+                Some((lo, hi)) => (0.5 * (lo.x + hi.x), lo.y),
+                None => (center.x, center.y),
+            };
+            placed.add_annotation(Annotation::Text(TextNote {
+                position: Vec2::new(x, bottom - VIEW_TITLE_GAP_MM - VIEW_TITLE_HEIGHT_MM),
+                text: format!("{}  {}", title, viewport.scale),
+                height_mm: Some(VIEW_TITLE_HEIGHT_MM),
+                rotation: 0.0,
+                align: TextAlign::Center,
+            }));
+        }
+        self.out.extend(placed);
+    }
+
+    fn border(&mut self, sheet: &Sheet, border: &SheetBorder) {
+        let (lo, hi) = sheet.frame();
+        self.rectangle(lo, hi, EdgeClass::VisibleOutline);
+        if border.grid_field_mm <= 0.0 {
+            return;
+        }
+
+        // Grid references run in the margin band outside the frame.
+        let band = border.margin_mm.min(border.filing_margin_mm) * 0.5;
+        let fields = |length: f64| ((length / border.grid_field_mm).round() as usize).max(2);
+        let (columns, rows) = (fields(hi.x - lo.x), fields(hi.y - lo.y));
+        let (column_width, row_height) =
+            ((hi.x - lo.x) / columns as f64, (hi.y - lo.y) / rows as f64);
+        let baseline = 0.5 * GRID_TEXT_HEIGHT_MM;
+
+        for column in 0..columns {
+            let x = lo.x + column as f64 * column_width;
+            if column > 0 {
+                self.line(
+                    Vec2::new(x, lo.y),
+                    Vec2::new(x, lo.y - band),
+                    EdgeClass::VisibleCrease,
+                );
+                self.line(
+                    Vec2::new(x, hi.y),
+                    Vec2::new(x, hi.y + band),
+                    EdgeClass::VisibleCrease,
+                );
+            }
+            let label = (column + 1).to_string();
+            let middle = x + 0.5 * column_width;
+            for y in [lo.y - 0.5 * band, hi.y + 0.5 * band] {
+                self.text(
+                    label.clone(),
+                    Vec2::new(middle, y - baseline),
+                    GRID_TEXT_HEIGHT_MM,
+                    TextAlign::Center,
+                );
+            }
+        }
+        let letters: Vec<char> = GRID_LETTERS.chars().collect();
+        for row in 0..rows {
+            // Lettered from the top down.
+            let y = hi.y - row as f64 * row_height;
+            if row > 0 {
+                self.line(
+                    Vec2::new(lo.x, y),
+                    Vec2::new(lo.x - band, y),
+                    EdgeClass::VisibleCrease,
+                );
+                self.line(
+                    Vec2::new(hi.x, y),
+                    Vec2::new(hi.x + band, y),
+                    EdgeClass::VisibleCrease,
+                );
+            }
+            let letter = letters[row % letters.len()].to_string();
+            let middle = y - 0.5 * row_height;
+            for x in [lo.x - 0.5 * band, hi.x + 0.5 * band] {
+                self.text(
+                    letter.clone(),
+                    Vec2::new(x, middle - baseline),
+                    GRID_TEXT_HEIGHT_MM,
+                    TextAlign::Center,
+                );
+            }
+        }
+    }
+
+    fn title_block(&mut self, sheet: &Sheet, title_block: &TitleBlock) {
+        let (frame_lo, frame_hi) = sheet.frame();
+        let columns = title_block.columns.max(1);
+        let rows = title_block.rows();
+        if rows == 0 {
+            return;
+        }
+        let width = title_block.width_mm;
+        let cell_width = width / columns as f64;
+        let row_height = title_block.row_height_mm;
+        let lo = Vec2::new(frame_hi.x - width, frame_lo.y);
+        let hi = Vec2::new(frame_hi.x, frame_lo.y + rows as f64 * row_height);
+        self.rectangle(lo, hi, EdgeClass::VisibleOutline);
+        for row in 1..rows {
+            let y = lo.y + row as f64 * row_height;
+            self.line(
+                Vec2::new(lo.x, y),
+                Vec2::new(hi.x, y),
+                EdgeClass::VisibleCrease,
+            );
+        }
+        for column in 1..columns {
+            let x = lo.x + column as f64 * cell_width;
+            self.line(
+                Vec2::new(x, lo.y),
+                Vec2::new(x, hi.y),
+                EdgeClass::VisibleCrease,
+            );
+        }
+
+        let scales = sheet
+            .scales()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let inset = 1.5;
+        for (index, field) in title_block.fields.iter().enumerate() {
+            let (row, column) = (index / columns, index % columns);
+            let x = lo.x + column as f64 * cell_width + inset;
+            let top = hi.y - row as f64 * row_height;
+            self.text(
+                field.label.clone(),
+                Vec2::new(x, top - inset - LABEL_TEXT_HEIGHT_MM),
+                LABEL_TEXT_HEIGHT_MM,
+                TextAlign::Left,
+            );
+            let value = if field.value.is_empty() && field.label == "Scale" {
+                scales.clone()
+            } else {
+                field.value.clone()
+            };
+            if !value.is_empty() {
+                self.text(
+                    value,
+                    Vec2::new(x, top - row_height + inset + 0.5),
+                    VALUE_TEXT_HEIGHT_MM,
+                    TextAlign::Left,
+                );
+            }
+        }
+    }
+
+    fn scale_bar(&mut self, bar: &ScaleBar) {
+        if !bar.scale.is_valid() || bar.divisions == 0 || bar.division_length <= 0.0 {
+            return;
+        }
+        let step = bar.division_length
+            * bar.unit.conversion_factor_to(LengthUnit::Millimetre)
+            * bar.scale.ratio();
+        let origin = bar.position_mm;
+        let top = origin.y + SCALE_BAR_HEIGHT_MM;
+        let end = origin.x + step * bar.divisions as f64;
+        self.rectangle(origin, Vec2::new(end, top), EdgeClass::VisibleCrease);
+
+        let labels = DimensionStyle {
+            precision: 3,
+            suppress_trailing_zeros: true,
+            unit: bar.unit,
+            scene_unit: bar.unit,
+            ..DimensionStyle::iso()
+        };
+        for division in 0..=bar.divisions {
+            let x = origin.x + step * division as f64;
+            if division > 0 && division < bar.divisions {
+                self.line(
+                    Vec2::new(x, origin.y),
+                    Vec2::new(x, top),
+                    EdgeClass::VisibleCrease,
+                );
+            }
+            if division < bar.divisions && division % 2 == 0 {
+                self.out.fills.push(HatchFill {
+                    boundary: vec![vec![
+                        Vec2::new(x, origin.y),
+                        Vec2::new(x + step, origin.y),
+                        Vec2::new(x + step, top),
+                        Vec2::new(x, top),
+                    ]],
+                    pattern: HatchPattern::SolidFill,
+                    layer: Some(SHEET_LAYER.to_string()),
+                    source_entity_id: None,
+                });
+            }
+            let mut label = labels.format_length(bar.division_length * division as f64);
+            if division == bar.divisions {
+                label = format!("{} {}", label, bar.unit.symbol());
+            }
+            self.text(
+                label,
+                Vec2::new(x, origin.y - 1.5 - LABEL_TEXT_HEIGHT_MM),
+                LABEL_TEXT_HEIGHT_MM,
+                TextAlign::Center,
+            );
+        }
+        self.text(
+            bar.scale.to_string(),
+            Vec2::new(0.5 * (origin.x + end), top + 1.5),
+            LABEL_TEXT_HEIGHT_MM,
+            TextAlign::Center,
+        );
+    }
+}
+
+/// Places a segment by a uniform scale `k` and the point map `place`.
+fn place_segment(segment: &Segment2D, place: &impl Fn(Vec2) -> Vec2, k: f64) -> Segment2D {
+    match *segment {
+        Segment2D::Line { start, end } => Segment2D::Line {
+            start: place(start),
+            end: place(end),
+        },
+        Segment2D::Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+        } => Segment2D::Arc {
+            center: place(center),
+            radius: radius * k,
+            start_angle,
+            end_angle,
+        },
+        Segment2D::Ellipse {
+            center,
+            rx,
+            ry,
+            rotation,
+            start_angle,
+            end_angle,
+        } => Segment2D::Ellipse {
+            center: place(center),
+            rx: rx * k,
+            ry: ry * k,
+            rotation,
+            start_angle,
+            end_angle,
+        },
+        Segment2D::CubicBezier { p0, p1, p2, p3 } => Segment2D::CubicBezier {
+            p0: place(p0),
+            p1: place(p1),
+            p2: place(p2),
+            p3: place(p3),
+        },
+    }
+}
+
+/// Keeps the parts of a placed view inside the window `(lo, hi)`. Lines
+/// are cut at the window edge, curves crossing it are flattened first, fill
+/// boundaries are clipped ring by ring, and text outside is dropped.
+fn clip_to_window(scene: Scene2D, lo: Vec2, hi: Vec2) -> Scene2D {
+    let inside = |p: Vec2| p.x >= lo.x && p.x <= hi.x && p.y >= lo.y && p.y <= hi.y;
+    let mut clipped = Scene2D {
+        warnings: scene.warnings,
+        ..Scene2D::default()
+    };
+    for segment in scene.segments {
+        let points = segment.geometry.flatten();
+        if points.iter().all(|p| inside(*p)) {
+            clipped.add_segment(segment);
+            continue;
+        }
+        for pair in points.windows(2) {
+            if let Some((start, end)) = clip_line(pair[0], pair[1], lo, hi) {
+                clipped.add_segment(ClassifiedSegment {
+                    geometry: Segment2D::Line { start, end },
+                    ..segment.clone()
+                });
+            }
+        }
+    }
+    for fill in scene.fills {
+        let boundary: Vec<Vec<Vec2>> = fill
+            .boundary
+            .iter()
+            .map(|ring| clip_ring(ring, lo, hi))
+            .filter(|ring| ring.len() >= 3)
+            .collect();
+        if !boundary.is_empty() {
+            clipped.fills.push(HatchFill { boundary, ..fill });
+        }
+    }
+    for annotation in scene.annotations {
+        if let Annotation::Text(note) = &annotation {
+            if !inside(note.position) {
+                continue;
+            }
+        }
+        clipped.add_annotation(annotation);
+    }
+    clipped
+}
+
+/// The part of the line `start`–`end` inside the window `(lo, hi)`.
+fn clip_line(start: Vec2, end: Vec2, lo: Vec2, hi: Vec2) -> Option<(Vec2, Vec2)> {
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    let (mut t0, mut t1) = (0.0f64, 1.0f64);
+    // Each window edge as `p * t <= q`.
+    for (p, q) in [
+        (-dx, start.x - lo.x),
+        (dx, hi.x - start.x),
+        (-dy, start.y - lo.y),
+        (dy, hi.y - start.y),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else if p < 0.0 {
+            t0 = t0.max(q / p);
+        } else {
+            t1 = t1.min(q / p);
+        }
+    }
+    let at = |t: f64| Vec2::new(start.x + dx * t, start.y + dy * t);
+    (t1 - t0 > 1.0e-12).then(|| (at(t0), at(t1)))
+}
+
+/// Sutherland–Hodgman clip of a closed ring to the window `(lo, hi)`.
+fn clip_ring(ring: &[Vec2], lo: Vec2, hi: Vec2) -> Vec<Vec2> {
+    // Signed distance inside each window edge.
+    let edges: [&dyn Fn(Vec2) -> f64; 4] =
+        [&|p| p.x - lo.x, &|p| hi.x - p.x, &|p| p.y - lo.y, &|p| {
+            hi.y - p.y
+        }];
+    let mut points = ring.to_vec();
+    for distance in edges {
+        let mut next = Vec::with_capacity(points.len() + 1);
+        for (index, &current) in points.iter().enumerate() {
+            let previous = points[(index + points.len() - 1) % points.len()];
+            let (d_previous, d_current) = (distance(previous), distance(current));
+            if (d_previous >= 0.0) != (d_current >= 0.0) {
+                let t = d_previous / (d_previous - d_current);
+                next.push(Vec2::new(
+                    previous.x + (current.x - previous.x) * t,
+                    previous.y + (current.y - previous.y) * t,
+                ));
+            }
+            if d_current >= 0.0 {
+                next.push(current);
+            }
+        }
+        points = next;
+        if points.is_empty() {
+            break;
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::annotation::{LinearDimension, LinearOrientation};
+
+    fn wall_scene(length: f64) -> Scene2D {
+        let mut scene = Scene2D::with_name("plan");
+        scene.add_segment(ClassifiedSegment {
+            geometry: Segment2D::Line {
+                start: Vec2::new(0.0, 0.0),
+                end: Vec2::new(length, 0.0),
+            },
+            class: EdgeClass::VisibleOutline,
+            layer: Some("A-WALL".to_string()),
+            source_entity_id: None,
+        });
+        scene
+    }
+
+    fn texts(scene: &Scene2D) -> Vec<&TextNote> {
+        scene
+            .annotations
+            .iter()
+            .filter_map(|annotation| match annotation {
+                Annotation::Text(note) => Some(note),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn viewports_draw_at_their_scale_around_their_centre() {
+        let mut sheet = Sheet::new(PaperSize::iso_a(3).unwrap());
+        sheet.border = None;
+        let mut scene = wall_scene(5.0);
+        scene.add_annotation(Annotation::Linear(LinearDimension {
+            p1: Vec2::new(0.0, 0.0),
+            p2: Vec2::new(5.0, 0.0),
+            orientation: LinearOrientation::Horizontal,
+            offset: -0.5,
+            text: None,
+        }));
+        sheet.viewports.push(SheetViewport {
+            id: "plan".to_string(),
+            scene,
+            scale: DrawingScale::one_to(50.0),
+            center_mm: Vec2::new(200.0, 150.0),
+            view_center: Some(Vec2::new(2.5, 0.0)),
+            title: Some("PLAN".to_string()),
+            size_mm: None,
+        });
+        let composed = sheet.compose();
+
+        let wall = &composed.segments[0];
+        assert_eq!(wall.layer.as_deref(), Some("A-WALL"));
+        let Segment2D::Line { start, end } = wall.geometry else {
+            panic!("wall stays a line");
+        };
+        // 5 m at 1:50 is 100 mm.
+        assert!((start.x - 150.0).abs() < 1e-9 && (end.x - 250.0).abs() < 1e-9);
+        assert!((start.y - 150.0).abs() < 1e-9);
+
+        let notes = texts(&composed);
+        let dimension = notes.iter().find(|note| note.text == "5000").unwrap();
+        // Dimension text keeps its paper height and lies 0.5 m (10 mm) below.
+        assert!((dimension.height_mm.unwrap() - 3.5).abs() < 1e-9);
+        assert!((dimension.position.y - (140.0 + 1.0)).abs() < 1e-9);
+        assert!(notes.iter().any(|note| note.text == "PLAN  1:50"));
+        assert_eq!(composed.dimension_style.scene_unit, LengthUnit::Millimetre);
+    }
+
+    #[test]
+    fn border_grid_and_title_block_fill_the_frame() {
+        let mut sheet = Sheet::new(PaperSize::iso_a(3).unwrap());
+        let mut title_block = TitleBlock::default();
+        title_block.set("Title", "Ground floor");
+        sheet.title_block = Some(title_block);
+        for (id, model) in [("plan", 50.0), ("detail", 10.0), ("plan-2", 50.0)] {
+            sheet.viewports.push(SheetViewport {
+                id: id.to_string(),
+                scene: wall_scene(1.0),
+                scale: DrawingScale::one_to(model),
+                center_mm: Vec2::new(100.0, 150.0),
+                view_center: None,
+                title: None,
+                size_mm: None,
+            });
+        }
+        let composed = sheet.compose();
+        let notes = texts(&composed);
+
+        // A3 frame 390 x 277 mm: 8 numbered columns and 6 lettered rows.
+        for label in ["1", "8", "A", "F"] {
+            assert_eq!(notes.iter().filter(|note| note.text == label).count(), 2);
+        }
+        assert!(!notes
+            .iter()
+            .any(|note| note.text == "9" || note.text == "G"));
+        assert!(notes.iter().any(|note| note.text == "Ground floor"));
+        assert!(notes.iter().any(|note| note.text == "1:50, 1:10"));
+
+        let (lo, hi) = composed.geometry_bounding_box().unwrap();
+        assert!((lo.x - 15.0).abs() < 1e-9 && (hi.x - 415.0).abs() < 1e-9);
+        assert!((lo.y - 5.0).abs() < 1e-9 && (hi.y - 292.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn scale_bar_marks_model_lengths() {
+        let mut sheet = Sheet::new(PaperSize::iso_a(4).unwrap());
+        sheet.border = None;
+        sheet.scale_bar = Some(ScaleBar {
+            position_mm: Vec2::new(20.0, 20.0),
+            scale: DrawingScale::parse("1:50").unwrap(),
+            division_length: 1.0,
+            divisions: 4,
+            unit: LengthUnit::Metre,
+        });
+        let composed = sheet.compose();
+
+        // 1 m at 1:50 is 20 mm per division; every other division is black.
+        assert_eq!(composed.fills.len(), 2);
+        assert!((composed.fills[1].boundary[0][0].x - 60.0).abs() < 1e-9);
+        let labels: Vec<&str> = texts(&composed)
+            .iter()
+            .map(|note| note.text.as_str())
+            .collect();
+        assert_eq!(labels, ["0", "1", "2", "3", "4 m", "1:50"]);
+        let (_, hi) = composed.geometry_bounding_box().unwrap();
+        assert!((hi.x - 100.0).abs() < 1e-9);
+        assert_eq!(DrawingScale::parse("1:0"), None);
+    }
+
+    #[test]
+    fn sized_viewports_clip_to_their_window() {
+        let mut sheet = Sheet::new(PaperSize::iso_a(3).unwrap());
+        sheet.border = None;
+        // 10 m at 1:50 is 200 mm, in a window 100 mm wide.
+        let mut scene = wall_scene(10.0);
+        scene.add_segment(ClassifiedSegment {
+            geometry: Segment2D::Arc {
+                center: Vec2::new(5.0, 0.0),
+                radius: 0.5,
+                start_angle: 0.0,
+                end_angle: std::f64::consts::TAU,
+            },
+            class: EdgeClass::VisibleCrease,
+            layer: None,
+            source_entity_id: None,
+        });
+        scene.fills.push(HatchFill {
+            boundary: vec![vec![
+                Vec2::new(0.0, -1.0),
+                Vec2::new(10.0, -1.0),
+                Vec2::new(10.0, 1.0),
+                Vec2::new(0.0, 1.0),
+            ]],
+            pattern: HatchPattern::SolidFill,
+            layer: None,
+            source_entity_id: None,
+        });
+        scene.add_annotation(Annotation::Text(TextNote {
+            position: Vec2::new(0.5, 0.0),
+            text: "outside".to_string(),
+            height_mm: None,
+            rotation: 0.0,
+            align: TextAlign::Left,
+        }));
+        sheet.viewports.push(SheetViewport {
+            id: "plan".to_string(),
+            scene,
+            scale: DrawingScale::one_to(50.0),
+            center_mm: Vec2::new(200.0, 150.0),
+            view_center: Some(Vec2::new(5.0, 0.0)),
+            title: None,
+            size_mm: Some(Vec2::new(100.0, 30.0)),
+        });
+        let composed = sheet.compose();
+
+        let (lo, hi) = composed.geometry_bounding_box().unwrap();
+        assert!((lo.x - 150.0).abs() < 1e-9 && (hi.x - 250.0).abs() < 1e-9);
+        assert!(lo.y >= 135.0 - 1e-9 && hi.y <= 165.0 + 1e-9);
+        // The circle fits the window and stays one arc.
+        assert!(composed
+            .segments
+            .iter()
+            .any(|segment| matches!(segment.geometry, Segment2D::Arc { .. })));
+        assert_eq!(composed.fills.len(), 1);
+        assert!(texts(&composed).is_empty());
+    }
+}
@@ -0,0 +1,466 @@
+//! Standard orthographic view sets (ISO 5456-2).
+//!
+//! The six principal views and an isometric are generated for a model's
+//! bounding box, given one common scale and placed on a sheet by first- or
+//! third-angle projection rules: each view sits in the same column as the
+//! front view if it shares its width, and in the same row if it shares its
+//! height, so features line up across views.
+
+use openmaths::Vector3;
+use serde::{Deserialize, Serialize};
+
+use crate::brep::Brep;
+use crate::export::projection::{
+    project_breps_to_scenes, project_point, CameraParameters, HlrOptions, ProjectionMode, Scene2D,
+    Vec2,
+};
+use crate::export::sheet::{DrawingScale, Sheet, SheetViewport};
+
+/// Paper reserved under each view for its caption, in millimetres.
+const CAPTION_ROOM_MM: f64 = 10.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProjectionAngle {
+    /// ISO "European" method: the plan goes below the front view and the
+    /// view from the left goes on the right.
+    #[default]
+    FirstAngle,
+    /// ANSI "American" method: the plan goes above the front view and the
+    /// view from the right goes on the right.
+    ThirdAngle,
+}
+
+/// A principal view of a Y-up model whose front faces +Z.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PrincipalView {
+    Front,
+    Top,
+    Right,
+    Left,
+    Bottom,
+    Back,
+    Isometric,
+}
+
+impl PrincipalView {
+    pub const ALL: [PrincipalView; 7] = [
+        PrincipalView::Front,
+        PrincipalView::Top,
+        PrincipalView::Right,
+        PrincipalView::Left,
+        PrincipalView::Bottom,
+        PrincipalView::Back,
+        PrincipalView::Isometric,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PrincipalView::Front => "FRONT",
+            PrincipalView::Top => "TOP",
+            PrincipalView::Right => "RIGHT",
+            PrincipalView::Left => "LEFT",
+            PrincipalView::Bottom => "BOTTOM",
+            PrincipalView::Back => "BACK",
+            PrincipalView::Isometric => "ISOMETRIC",
+        }
+    }
+
+    /// Direction from the model towards the viewer, and the camera's up.
+    fn direction_and_up(self) -> ([f64; 3], [f64; 3]) {
+        let y = [0.0, 1.0, 0.0];
+        match self {
+            PrincipalView::Front => ([0.0, 0.0, 1.0], y),
+            PrincipalView::Back => ([0.0, 0.0, -1.0], y),
+            PrincipalView::Right => ([1.0, 0.0, 0.0], y),
+            PrincipalView::Left => ([-1.0, 0.0, 0.0], y),
+            // Plans keep the front of the model towards the front view.
+            PrincipalView::Top => ([0.0, 1.0, 0.0], [0.0, 0.0, -1.0]),
+            PrincipalView::Bottom => ([0.0, -1.0, 0.0], [0.0, 0.0, 1.0]),
+            PrincipalView::Isometric => {
+                let c = 1.0 / 3f64.sqrt();
+                ([c, c, c], y)
+            }
+        }
+    }
+
+    /// Orthographic camera looking at `center` from outside a model of
+    /// bounding-box diagonal `diagonal`.
+    pub fn camera(self, center: Vector3, diagonal: f64) -> CameraParameters {
+        let (direction, up) = self.direction_and_up();
+        let distance = 2.0 * diagonal.max(1.0);
+        CameraParameters {
+            position: Vector3::new(
+                center.x + direction[0] * distance,
+                center.y + direction[1] * distance,
+                center.z + direction[2] * distance,
+            ),
+            target: center,
+            up: Vector3::new(up[0], up[1], up[2]),
+            near: 0.01,
+            projection_mode: ProjectionMode::Orthographic,
+            ..CameraParameters::default()
+        }
+    }
+
+    /// Grid cell `(column, row)` of the view, rows counted from the top.
+    fn cell(self, angle: ProjectionAngle) -> (usize, usize) {
+        let third = angle == ProjectionAngle::ThirdAngle;
+        match self {
+            PrincipalView::Front => (1, 1),
+            PrincipalView::Top => (1, if third { 0 } else { 2 }),
+            PrincipalView::Bottom => (1, if third { 2 } else { 0 }),
+            PrincipalView::Right => (if third { 2 } else { 0 }, 1),
+            PrincipalView::Left => (if third { 0 } else { 2 }, 1),
+            PrincipalView::Back => (3, 1),
+            PrincipalView::Isometric => (3, 0),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StandardViewsConfig {
+    pub projection: ProjectionAngle,
+    pub views: Vec<PrincipalView>,
+    /// Common scale of all views; the largest ISO 5455 scale that fits the
+    /// sheet when absent.
+    pub scale: Option<DrawingScale>,
+    /// Clear space between neighbouring views, in millimetres.
+    pub gap_mm: f64,
+    pub hlr: HlrOptions,
+}
+
+impl Default for StandardViewsConfig {
+    fn default() -> Self {
+        Self {
+            projection: ProjectionAngle::default(),
+            views: PrincipalView::ALL.to_vec(),
+            scale: None,
+            gap_mm: 20.0,
+            hlr: HlrOptions::default(),
+        }
+    }
+}
+
+/// One generated view: a camera for `projectCurrentToViews` and where its
+/// projection goes on the sheet.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StandardView {
+    pub id: String,
+    pub view: PrincipalView,
+    pub camera: CameraParameters,
+    pub center_mm: Vec2,
+    /// Projected model centre, drawn at `center_mm`.
+    pub view_center: Vec2,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StandardViewSet {
+    pub scale: DrawingScale,
+    pub views: Vec<StandardView>,
+}
+
+impl StandardViewSet {
+    /// Adds one labelled viewport per view, taking its scene from `project`.
+    pub fn place_on(&self, sheet: &mut Sheet, mut project: impl FnMut(&StandardView) -> Scene2D) {
+        for view in &self.views {
+            sheet.viewports.push(SheetViewport {
+                id: view.id.clone(),
+                scene: project(view),
+                scale: self.scale,
+                center_mm: view.center_mm,
+                view_center: Some(view.view_center),
+                title: Some(view.view.label().to_string()),
+                size_mm: None,
+            });
+        }
+    }
+}
+
+/// Lays out the configured views of a model with bounds `(min, max)` in
+/// scene units (metres) on `sheet`, above its title block. `None` if the
+/// bounds are empty or no view is requested.
+pub fn standard_views(
+    bounds: (Vector3, Vector3),
+    sheet: &Sheet,
+    config: &StandardViewsConfig,
+) -> Option<StandardViewSet> {
+    let (min, max) = bounds;
+    let center = Vector3::new(
+        0.5 * (min.x + max.x),
+        0.5 * (min.y + max.y),
+        0.5 * (min.z + max.z),
+    );
+    let diagonal =
+        ((max.x - min.x).powi(2) + (max.y - min.y).powi(2) + (max.z - min.z).powi(2)).sqrt();
+    if !diagonal.is_finite() {
+        return None;
+    }
+    let mut views: Vec<PrincipalView> = Vec::new();
+    for view in &config.views {
+        if !views.contains(view) {
+            views.push(*view);
+        }
+    }
+    if views.is_empty() {
+        return None;
+    }
+
+    // Model-space size of each view, and of each grid column and row.
+    let corners: Vec<Vector3> = (0..8)
+        .map(|i| {
+            Vector3::new(
+                if i & 1 == 0 { min.x } else { max.x },
+                if i & 2 == 0 { min.y } else { max.y },
+                if i & 4 == 0 { min.z } else { max.z },
+            )
+        })
+        .collect();
+    let layouts: Vec<(PrincipalView, CameraParameters, Vec2, (f64, f64))> = views
+        .iter()
+        .map(|&view| {
+            let camera = view.camera(center, diagonal);
+            let projected: Vec<Vec2> = corners
+                .iter()
+                .filter_map(|corner| project_point(corner, &camera))
+                .collect();
+            let (lo, hi) = projected.iter().fold(
+                (
+                    Vec2::new(f64::INFINITY, f64::INFINITY),
+                    Vec2::new(f64::NEG_INFINITY, f64::NEG_INFINITY),
+                ),
+                |(lo, hi), p| {
+                    (
+                        Vec2::new(lo.x.min(p.x), lo.y.min(p.y)),
+                        Vec2::new(hi.x.max(p.x), hi.y.max(p.y)),
+                    )
+                },
+            );
+            let view_center = project_point(&center, &camera).unwrap_or(Vec2::new(0.0, 0.0));
+            (view, camera, view_center, (hi.x - lo.x, hi.y - lo.y))
+        })
+        .collect();
+    let mut column_widths = [0.0f64; 4];
+    let mut row_heights = [0.0f64; 3];
+    let mut used_columns = [false; 4];
+    let mut used_rows = [false; 3];
+    for (view, _, _, (width, height)) in &layouts {
+        let (column, row) = view.cell(config.projection);
+        column_widths[column] = column_widths[column].max(*width);
+        row_heights[row] = row_heights[row].max(*height);
+        used_columns[column] = true;
+        used_rows[row] = true;
+    }
+
+    // Usable area: the frame above the title block.
+    let (frame_lo, frame_hi) = sheet.frame();
+    let title_height = sheet
+        .title_block
+        .as_ref()
+        .map(|block| {
+            let columns = block.columns.max(1);
+            block.fields.len().div_ceil(columns) as f64 * block.row_height_mm
+        })
+        .unwrap_or(0.0);
+    let area_lo = Vec2::new(frame_lo.x, frame_lo.y + title_height);
+    let (area_width, area_height) = (frame_hi.x - area_lo.x, frame_hi.y - area_lo.y);
+
+    let gaps = |used: &[bool]| {
+        used.iter().filter(|used| **used).count().saturating_sub(1) as f64 * config.gap_mm
+    };
+    let (column_gaps, row_gaps) = (gaps(&used_columns), gaps(&used_rows));
+    // Views are in metres; paper is in millimetres.
+    let paper_size = |scale: DrawingScale| {
+        let k = 1000.0 * scale.ratio();
+        (
+            column_widths.iter().sum::<f64>() * k + column_gaps,
+            row_heights.iter().sum::<f64>() * k + row_gaps + CAPTION_ROOM_MM,
+        )
+    };
+    let scale = config
+        .scale
+        .filter(|scale| scale.is_valid())
+        .unwrap_or_else(|| {
+            iso_5455_scales()
+                .into_iter()
+                .find(|scale| {
+                    let (width, height) = paper_size(*scale);
+                    width <= area_width && height <= area_height
+                })
+                .unwrap_or(DrawingScale::one_to(100_000.0))
+        });
+    let k = 1000.0 * scale.ratio();
+    let (total_width, total_height) = paper_size(scale);
+
+    // Centre the grid in the usable area; the caption room sits at the bottom.
+    let mut column_centers = [0.0f64; 4];
+    let mut x = area_lo.x + 0.5 * (area_width - total_width);
+    for column in 0..4 {
+        if !used_columns[column] {
+            continue;
+        }
+        column_centers[column] = x + 0.5 * column_widths[column] * k;
+        x += column_widths[column] * k + config.gap_mm;
+    }
+    let mut row_centers = [0.0f64; 3];
+    let mut y = area_lo.y + 0.5 * (area_height + total_height);
+    for row in 0..3 {
+        if !used_rows[row] {
+            continue;
+        }
+        row_centers[row] = y - 0.5 * row_heights[row] * k;
+        y -= row_heights[row] * k + config.gap_mm;
+    }
+
+    let views = layouts
+        .into_iter()
+        .map(|(view, camera, view_center, _)| {
+            let (column, row) = view.cell(config.projection);
+            StandardView {
+                id: view.label().to_lowercase(),
+                view,
+                camera,
+                center_mm: Vec2::new(column_centers[column], row_centers[row]),
+                view_center,
+            }
+        })
+        .collect();
+    Some(StandardViewSet { scale, views })
+}
+
+/// ISO 5455 recommended scales, largest first.
+fn iso_5455_scales() -> Vec<DrawingScale> {
+    let mut scales: Vec<DrawingScale> = [50.0, 20.0, 10.0, 5.0, 2.0]
+        .into_iter()
+        .map(|paper| DrawingScale::new(paper, 1.0))
+        .collect();
+    for decade in [1.0, 10.0, 100.0, 1_000.0, 10_000.0] {
+        for step in [1.0, 2.0, 5.0] {
+            scales.push(DrawingScale::one_to(step * decade));
+        }
+    }
+    scales
+}
+
+/// Projects `breps` into the configured standard views and adds them to
+/// `sheet` as labelled viewports, with shared hidden line removal per view.
+pub fn standard_views_sheet(
+    breps: &[&Brep],
+    mut sheet: Sheet,
+    config: &StandardViewsConfig,
+) -> Sheet {
+    let Some(bounds) = breps_bounds(breps) else {
+        return sheet;
+    };
+    if let Some(set) = standard_views(bounds, &sheet, config) {
+        set.place_on(&mut sheet, |view| {
+            let mut scene = Scene2D::with_name(view.id.clone());
+            for projected in project_breps_to_scenes(breps, &view.camera, &config.hlr) {
+                scene.extend(projected);
+            }
+            scene
+        });
+    }
+    sheet
+}
+
+/// Combined bounds of several B-reps.
+pub fn breps_bounds(breps: &[&Brep]) -> Option<(Vector3, Vector3)> {
+    breps
+        .iter()
+        .filter_map(|brep| brep.bounds())
+        .reduce(|(lo, hi), (b_lo, b_hi)| {
+            (
+                Vector3::new(lo.x.min(b_lo.x), lo.y.min(b_lo.y), lo.z.min(b_lo.z)),
+                Vector3::new(hi.x.max(b_hi.x), hi.y.max(b_hi.y), hi.z.max(b_hi.z)),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::sheet::{PaperSize, TitleBlock};
+    use crate::primitives::cuboid::OGCuboid;
+
+    fn block() -> Brep {
+        // 0.4 wide (x), 0.2 high (y), 0.1 deep (z), centred on the origin.
+        let mut cuboid = OGCuboid::new("block".to_string());
+        cuboid
+            .set_config(Vector3::new(0.0, 0.0, 0.0), 0.4, 0.2, 0.1)
+            .unwrap();
+        cuboid.brep().clone()
+    }
+
+    fn view(set: &StandardViewSet, which: PrincipalView) -> &StandardView {
+        set.views.iter().find(|view| view.view == which).unwrap()
+    }
+
+    #[test]
+    fn views_align_by_projection_rules() {
+        let brep = block();
+        let sheet = Sheet::new(PaperSize::iso_a(3).unwrap());
+        for angle in [ProjectionAngle::FirstAngle, ProjectionAngle::ThirdAngle] {
+            let config = StandardViewsConfig {
+                projection: angle,
+                ..StandardViewsConfig::default()
+            };
+            let set = standard_views(brep.bounds().unwrap(), &sheet, &config).unwrap();
+            let front = view(&set, PrincipalView::Front).center_mm;
+            let top = view(&set, PrincipalView::Top).center_mm;
+            let right = view(&set, PrincipalView::Right).center_mm;
+            let left = view(&set, PrincipalView::Left).center_mm;
+            let bottom = view(&set, PrincipalView::Bottom).center_mm;
+
+            assert!((top.x - front.x).abs() < 1e-9 && (bottom.x - front.x).abs() < 1e-9);
+            assert!((right.y - front.y).abs() < 1e-9 && (left.y - front.y).abs() < 1e-9);
+            if angle == ProjectionAngle::ThirdAngle {
+                assert!(top.y > front.y && bottom.y < front.y);
+                assert!(right.x > front.x && left.x < front.x);
+            } else {
+                assert!(top.y < front.y && bottom.y > front.y);
+                assert!(right.x < front.x && left.x > front.x);
+            }
+        }
+    }
+
+    #[test]
+    fn views_share_the_largest_fitting_scale() {
+        let brep = block();
+        let mut sheet = Sheet::new(PaperSize::iso_a(3).unwrap());
+        sheet.title_block = Some(TitleBlock::default());
+        let set = standard_views(
+            brep.bounds().unwrap(),
+            &sheet,
+            &StandardViewsConfig::default(),
+        )
+        .unwrap();
+        // Side, front, side and isometric columns span about 1.03 m: 577 mm
+        // with three gaps at 1:2, too wide for A3; 1:5 gives 267 mm.
+        assert_eq!(set.scale, DrawingScale::one_to(5.0));
+
+        let sheet = standard_views_sheet(&[&brep], sheet, &StandardViewsConfig::default());
+        assert_eq!(sheet.viewports.len(), 7);
+        assert!(sheet
+            .viewports
+            .iter()
+            .all(|viewport| viewport.scale == set.scale && !viewport.scene.is_empty()));
+
+        // The front view is 0.4 m wide: 80 mm at 1:5, centred on its cell.
+        let front = sheet
+            .viewports
+            .iter()
+            .find(|viewport| viewport.id == "front")
+            .unwrap();
+        let composed = Sheet {
+            viewports: vec![front.clone()],
+            border: None,
+            title_block: None,
+            ..sheet.clone()
+        }
+        .compose();
+        let (lo, hi) = composed.geometry_bounding_box().unwrap();
+        assert!((hi.x - lo.x - 80.0).abs() < 1e-6);
+        assert!((0.5 * (lo.x + hi.x) - front.center_mm.x).abs() < 1e-6);
+    }
+}
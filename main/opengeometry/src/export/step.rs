@@ -523,7 +523,7 @@ fn emit_analytic_face(
     scale: f64,
 ) -> Option<usize> {
     let surface = face.surface.as_ref()?;
-    let surface_ref = emit_surface(writer, point_map, emitter, surface, scale);
+    let surface_ref = emit_surface(writer, point_map, emitter, surface, scale)?;
 
     let outer = emit_edge_loop_bound(
         writer,
@@ -609,7 +609,9 @@ fn emit_merged_cylinder_faces(
             .find(|f| f.id == group[0])
             .and_then(|f| f.surface.clone());
         let Some(surface) = surface else { continue };
-        let surface_ref = emit_surface(writer, point_map, emitter, &surface, scale);
+        let Some(surface_ref) = emit_surface(writer, point_map, emitter, &surface, scale) else {
+            continue;
+        };
 
         let mut bounds = Vec::new();
         for (i, edge_loop) in loops.iter().enumerate() {
@@ -821,11 +823,11 @@ fn emit_surface(
     emitter: &mut AnalyticEmitter,
     surface: &crate::brep::SurfaceGeometry,
     scale: f64,
-) -> usize {
+) -> Option<usize> {
     use crate::brep::SurfaceGeometry;
     let key = surface_key(surface, scale);
     if let Some(existing) = emitter.surfaces.get(&key) {
-        return *existing;
+        return Some(*existing);
     }
     let id = match surface {
         SurfaceGeometry::Plane { origin, normal } => {
@@ -858,9 +860,12 @@ fn emit_surface(
                 format_real(radius * scale)
             ))
         }
+        // Tessellated spheres are bounded by chords, not curves on the
+        // sphere, so they can't form a valid ADVANCED_FACE; facet instead.
+        SurfaceGeometry::Sphere { .. } => return None,
     };
     emitter.surfaces.insert(key, id);
-    id
+    Some(id)
 }
 
 fn emit_axis_placement(
@@ -997,6 +1002,13 @@ fn surface_key(surface: &crate::brep::SurfaceGeometry, scale: f64) -> String {
             axis.z,
             radius * scale
         ),
+        SurfaceGeometry::Sphere { center, radius } => format!(
+            "S|{:.6}|{:.6}|{:.6}|{:.6}",
+            center.x * scale,
+            center.y * scale,
+            center.z * scale,
+            radius * scale
+        ),
     }
 }
 
@@ -0,0 +1,371 @@
+//! SVG export of 2D drawings.
+//!
+//! The document is sized in paper millimetres and laid out like the PDF
+//! export: the scene is fitted into the margins, edges get ISO 128 line
+//! weights, section fills are hatched, and annotations are laid out at the
+//! resulting scale.
+
+use std::fmt::Write as _;
+
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+use crate::export::annotation::{TextAlign, CAP_HEIGHT_PER_EM};
+use crate::export::hatch::{hatch_lines, HATCH_LINE_WIDTH_MM};
+use crate::export::projection::{EdgeClass, Scene2D, Segment2D, Vec2, THIN_LINE_WIDTH_MM};
+use crate::export::sheet::Sheet;
+use crate::units::LengthUnit;
+
+/// ISO 128 dashed line (type 02) proportions at a 0.18 mm pen.
+const HIDDEN_DASH_ARRAY: &str = "3 1.5";
+const FONT_FAMILY: &str = "Helvetica, Arial, sans-serif";
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct SvgExportConfig {
+    pub page_width_mm: f64,
+    pub page_height_mm: f64,
+    pub margin_mm: f64,
+    /// Fit the drawing into the margins; otherwise draw it at 1:1 with the
+    /// scene origin at the bottom-left margin corner.
+    pub auto_fit: bool,
+}
+
+impl Default for SvgExportConfig {
+    fn default() -> Self {
+        Self {
+            page_width_mm: 297.0,
+            page_height_mm: 210.0,
+            margin_mm: 10.0,
+            auto_fit: true,
+        }
+    }
+}
+
+/// Wasm entry point: render a serialized [`Scene2D`] as an SVG document.
+/// `config_json` may be empty for an A4 landscape page.
+#[wasm_bindgen(js_name = exportScene2DToSvg)]
+pub fn export_scene_to_svg_wasm(
+    scene_json: String,
+    config_json: String,
+) -> Result<String, JsValue> {
+    let scene: Scene2D = serde_json::from_str(&scene_json)
+        .map_err(|e| JsValue::from_str(&format!("Invalid Scene2D JSON: {}", e)))?;
+    let config = if config_json.trim().is_empty() {
+        SvgExportConfig::default()
+    } else {
+        serde_json::from_str(&config_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid SVG config JSON: {}", e)))?
+    };
+    Ok(export_scene_to_svg(&scene, &config))
+}
+
+/// Wasm entry point: render a serialized [`Sheet`] as an SVG document.
+#[wasm_bindgen(js_name = exportSheetToSvg)]
+pub fn export_sheet_to_svg_wasm(sheet_json: String) -> Result<String, JsValue> {
+    let sheet: Sheet = serde_json::from_str(&sheet_json)
+        .map_err(|e| JsValue::from_str(&format!("Invalid sheet JSON: {}", e)))?;
+    Ok(export_sheet_to_svg(&sheet))
+}
+
+/// Maps scene coordinates to SVG user units (millimetres, y down).
+struct PaperFit {
+    /// Paper millimetres per scene unit.
+    scale: f64,
+    offset_x: f64,
+    offset_y: f64,
+    page_height: f64,
+}
+
+impl PaperFit {
+    fn new(scene: &Scene2D, config: &SvgExportConfig) -> Self {
+        let natural = scene
+            .dimension_style
+            .scene_unit
+            .conversion_factor_to(LengthUnit::Millimetre);
+        let drawable_width = (config.page_width_mm - 2.0 * config.margin_mm).max(0.0);
+        let drawable_height = (config.page_height_mm - 2.0 * config.margin_mm).max(0.0);
+        let mut fit = Self {
+            scale: natural,
+            offset_x: config.margin_mm,
+            offset_y: config.margin_mm,
+            page_height: config.page_height_mm,
+        };
+        let Some((lo, hi)) = scene.bounding_box() else {
+            return fit;
+        };
+        let (width, height) = (hi.x - lo.x, hi.y - lo.y);
+        if config.auto_fit && (width > 0.0 || height > 0.0) {
+            let scale_x = if width > 0.0 {
+                drawable_width / width
+            } else {
+                f64::INFINITY
+            };
+            let scale_y = if height > 0.0 {
+                drawable_height / height
+            } else {
+                f64::INFINITY
+            };
+            fit.scale = scale_x.min(scale_y);
+            fit.offset_x =
+                config.margin_mm + (drawable_width - width * fit.scale) / 2.0 - lo.x * fit.scale;
+            fit.offset_y =
+                config.margin_mm + (drawable_height - height * fit.scale) / 2.0 - lo.y * fit.scale;
+        }
+        fit
+    }
+
+    fn map(&self, point: Vec2) -> (f64, f64) {
+        (
+            point.x * self.scale + self.offset_x,
+            self.page_height - (point.y * self.scale + self.offset_y),
+        )
+    }
+
+    /// SVG path data for a segment.
+    fn path_data(&self, segment: &Segment2D) -> String {
+        let mut data = String::new();
+        if let Segment2D::Line { start, end } = segment {
+            let ((x0, y0), (x1, y1)) = (self.map(*start), self.map(*end));
+            let _ = write!(data, "M{x0:.3} {y0:.3}L{x1:.3} {y1:.3}");
+            return data;
+        }
+        for (index, [p0, p1, p2, p3]) in segment.to_cubic_beziers().into_iter().enumerate() {
+            if index == 0 {
+                let (x, y) = self.map(p0);
+                let _ = write!(data, "M{x:.3} {y:.3}");
+            }
+            let [(x1, y1), (x2, y2), (x3, y3)] = [p1, p2, p3].map(|p| self.map(p));
+            let _ = write!(data, "C{x1:.3} {y1:.3} {x2:.3} {y2:.3} {x3:.3} {y3:.3}");
+        }
+        data
+    }
+
+    fn ring_data(&self, rings: &[Vec<Vec2>]) -> String {
+        let mut data = String::new();
+        for ring in rings.iter().filter(|ring| ring.len() >= 3) {
+            for (index, point) in ring.iter().enumerate() {
+                let (x, y) = self.map(*point);
+                let command = if index == 0 { 'M' } else { 'L' };
+                let _ = write!(data, "{command}{x:.3} {y:.3}");
+            }
+            data.push('Z');
+        }
+        data
+    }
+}
+
+/// Renders a scene as a standalone SVG document.
+pub fn export_scene_to_svg(scene: &Scene2D, config: &SvgExportConfig) -> String {
+    let fit = PaperFit::new(scene, config);
+    let mut svg = String::new();
+    let _ = writeln!(svg, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}mm" height="{h}mm" viewBox="0 0 {w} {h}">"#,
+        w = config.page_width_mm,
+        h = config.page_height_mm,
+    );
+    if let Some(name) = &scene.name {
+        let _ = writeln!(svg, "<title>{}</title>", escape(name));
+    }
+
+    if !scene.fills.is_empty() {
+        let _ = writeln!(
+            svg,
+            r#"<g id="fills" stroke="black" stroke-width="{HATCH_LINE_WIDTH_MM}" fill="black">"#
+        );
+        for fill in &scene.fills {
+            if fill.pattern.is_solid() {
+                let _ = writeln!(
+                    svg,
+                    r#"<path d="{}" fill-rule="evenodd" stroke="none"/>"#,
+                    fit.ring_data(&fill.boundary)
+                );
+                continue;
+            }
+            // Hatch in scene orientation at paper scale, so pattern angles
+            // are not mirrored by the y flip.
+            let rings: Vec<Vec<Vec2>> = fill
+                .boundary
+                .iter()
+                .filter(|ring| ring.len() >= 3)
+                .cloned()
+                .collect();
+            for segment in hatch_lines(&rings, fill.pattern, 1.0 / fit.scale) {
+                if let Segment2D::Line { start, end } = segment {
+                    let ((x1, y1), (x2, y2)) = (fit.map(start), fit.map(end));
+                    let _ = writeln!(
+                        svg,
+                        r#"<line x1="{x1:.3}" y1="{y1:.3}" x2="{x2:.3}" y2="{y2:.3}"/>"#
+                    );
+                }
+            }
+        }
+        svg.push_str("</g>\n");
+    }
+
+    let _ = writeln!(
+        svg,
+        r#"<g id="edges" fill="none" stroke="black" stroke-linecap="round">"#
+    );
+    for segment in &scene.segments {
+        let dash = if segment.class == EdgeClass::Hidden {
+            format!(r#" stroke-dasharray="{HIDDEN_DASH_ARRAY}""#)
+        } else {
+            String::new()
+        };
+        let _ = writeln!(
+            svg,
+            r#"<path class="{}" d="{}" stroke-width="{}"{dash}/>"#,
+            segment.class.as_str(),
+            fit.path_data(&segment.geometry),
+            segment.class.line_width_mm(),
+        );
+    }
+    svg.push_str("</g>\n");
+
+    if !scene.annotations.is_empty() {
+        write_annotations(&mut svg, scene, &fit);
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Renders a sheet at 1:1 on its paper size.
+pub fn export_sheet_to_svg(sheet: &Sheet) -> String {
+    let config = SvgExportConfig {
+        page_width_mm: sheet.paper.width_mm,
+        page_height_mm: sheet.paper.height_mm,
+        margin_mm: 0.0,
+        auto_fit: false,
+    };
+    export_scene_to_svg(&sheet.compose(), &config)
+}
+
+fn write_annotations(svg: &mut String, scene: &Scene2D, fit: &PaperFit) {
+    let units_per_mm = 1.0 / fit.scale;
+    let _ = writeln!(
+        svg,
+        r#"<g id="annotations" fill="black" stroke="black" stroke-width="{THIN_LINE_WIDTH_MM}" font-family="{FONT_FAMILY}">"#
+    );
+    for annotation in &scene.annotations {
+        let graphics = annotation.layout(&scene.dimension_style, units_per_mm);
+        for line in &graphics.lines {
+            let _ = writeln!(svg, r#"<path d="{}" fill="none"/>"#, fit.path_data(line));
+        }
+        for solid in &graphics.solids {
+            let _ = writeln!(
+                svg,
+                r#"<path d="{}" stroke="none"/>"#,
+                fit.ring_data(std::slice::from_ref(solid))
+            );
+        }
+        for text in &graphics.texts {
+            let (x, y) = fit.map(text.position);
+            let anchor = match text.align {
+                TextAlign::Left => "start",
+                TextAlign::Center => "middle",
+                TextAlign::Right => "end",
+            };
+            let font_size = text.height * fit.scale / CAP_HEIGHT_PER_EM;
+            // SVG's y axis points down, so counter-clockwise is negative.
+            let rotation = -text.rotation.to_degrees();
+            let _ = writeln!(
+                svg,
+                r#"<text x="{x:.3}" y="{y:.3}" font-size="{font_size:.3}" text-anchor="{anchor}" stroke="none" transform="rotate({rotation:.3} {x:.3} {y:.3})">{}</text>"#,
+                escape(&text.text)
+            );
+        }
+    }
+    svg.push_str("</g>\n");
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::annotation::{Annotation, RadialDimension, TextNote};
+    use crate::export::hatch::{HatchFill, HatchPattern};
+    use crate::export::projection::ClassifiedSegment;
+
+    fn square_scene() -> Scene2D {
+        let corners = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(0.0, 1.0),
+        ];
+        let mut scene = Scene2D::with_name("square");
+        for index in 0..4 {
+            scene.add_segment(ClassifiedSegment {
+                geometry: Segment2D::Line {
+                    start: corners[index],
+                    end: corners[(index + 1) % 4],
+                },
+                class: if index == 0 {
+                    EdgeClass::Hidden
+                } else {
+                    EdgeClass::VisibleOutline
+                },
+                layer: None,
+                source_entity_id: None,
+            });
+        }
+        scene
+    }
+
+    #[test]
+    fn edges_fit_the_page_with_y_flipped_and_hidden_lines_dashed() {
+        let scene = square_scene();
+        let svg = export_scene_to_svg(&scene, &SvgExportConfig::default());
+        assert!(svg.starts_with("<?xml"));
+        assert!(svg.contains(r#"viewBox="0 0 297 210""#));
+        // A unit square fits the 190 mm drawable height, centred; its
+        // bottom edge lands 10 mm above the page's bottom.
+        assert!(svg.contains("M53.500 200.000L243.500 200.000"), "{svg}");
+        assert_eq!(svg.matches("stroke-dasharray").count(), 1);
+        assert_eq!(svg.matches(r#"class="VisibleOutline""#).count(), 3);
+    }
+
+    #[test]
+    fn annotations_and_fills_render_as_paths_and_escaped_text() {
+        let mut scene = square_scene();
+        scene.fills.push(HatchFill {
+            boundary: vec![vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(1.0, 0.0),
+                Vec2::new(1.0, 1.0),
+            ]],
+            pattern: HatchPattern::SolidFill,
+            layer: None,
+            source_entity_id: None,
+        });
+        scene.add_annotation(Annotation::Radial(RadialDimension {
+            center: Vec2::new(0.5, 0.5),
+            radius: 0.25,
+            angle: 0.0,
+            diameter: true,
+            text: None,
+        }));
+        scene.add_annotation(Annotation::Text(TextNote {
+            position: Vec2::new(0.0, 1.2),
+            text: "A & B <C>".to_string(),
+            height_mm: Some(5.0),
+            rotation: 0.0,
+            align: TextAlign::Left,
+        }));
+        let svg = export_scene_to_svg(&scene, &SvgExportConfig::default());
+        assert!(svg.contains(r#"fill-rule="evenodd""#));
+        assert!(svg.contains(">Ø500</text>"), "{svg}");
+        assert!(svg.contains(">A &amp; B &lt;C&gt;</text>"));
+        let size = 5.0 / CAP_HEIGHT_PER_EM;
+        assert!(svg.contains(&format!(r#"font-size="{size:.3}""#)));
+    }
+}
@@ -1,6 +1,7 @@
 //! Generic 2D nonzero-winding boolean: self-intersection resolution + multi-contour union.
 
 use crate::geometry::poly2d::*;
+use serde::{Deserialize, Serialize};
 
 /// Decompose a (possibly self-intersecting) closed ring into simple loops by
 /// splitting at every self-crossing (stack method). Port of `decomposeSelfIntersections`.
@@ -108,6 +109,7 @@ pub fn edge_probe2(ring: &[Pt2], side: f64) -> Option<Pt2> {
 }
 
 /// A nonzero-winding region: outer boundary (CCW) + inner-void holes (CW).
+#[derive(Clone, Debug)]
 pub struct RingRegion {
     pub outer: Vec<Pt2>,
     pub holes: Vec<Vec<Pt2>>,
@@ -207,7 +209,65 @@ pub fn resolve_polygons_positive(polys: &[Vec<Pt2>], eps: f64) -> Vec<RingRegion
     winding_regions(polys, eps, |winding| winding > 0)
 }
 
+/// Set operation applied by `boolean_regions`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RegionBooleanOp {
+    Union,
+    Intersection,
+    /// `a` minus `b`.
+    Difference,
+    Xor,
+}
+
+/// Boolean of two region sets (outer + holes each, any winding). A point is
+/// in a set when it lies inside some region's outer and none of that
+/// region's holes, so overlapping input regions are fine. Returns CCW-outer /
+/// CW-hole regions: every disjoint piece, holes nested in the smallest outer
+/// that contains them.
+pub fn boolean_regions(
+    a: &[RingRegion],
+    b: &[RingRegion],
+    op: RegionBooleanOp,
+    eps: f64,
+) -> Vec<RingRegion> {
+    let rings: Vec<Vec<Pt2>> = a
+        .iter()
+        .chain(b)
+        .flat_map(|region| std::iter::once(&region.outer).chain(&region.holes))
+        .cloned()
+        .collect();
+    let in_set = |p: Pt2, regions: &[RingRegion]| {
+        regions.iter().any(|region| {
+            winding_number2(p, &region.outer) != 0
+                && region
+                    .holes
+                    .iter()
+                    .all(|hole| winding_number2(p, hole) == 0)
+        })
+    };
+    arrangement_regions(&rings, eps, |p| {
+        let (in_a, in_b) = (in_set(p, a), in_set(p, b));
+        match op {
+            RegionBooleanOp::Union => in_a || in_b,
+            RegionBooleanOp::Intersection => in_a && in_b,
+            RegionBooleanOp::Difference => in_a && !in_b,
+            RegionBooleanOp::Xor => in_a != in_b,
+        }
+    })
+}
+
 fn winding_regions(polys: &[Vec<Pt2>], eps: f64, filled: impl Fn(i32) -> bool) -> Vec<RingRegion> {
+    let winding_at = |p: Pt2| -> i32 { polys.iter().map(|poly| winding_number2(p, poly)).sum() };
+    arrangement_regions(polys, eps, |p| filled(winding_at(p)))
+}
+
+/// Boundary of the area where `filled` holds, for a fill that can only change
+/// across an edge of `polys`.
+fn arrangement_regions(
+    polys: &[Vec<Pt2>],
+    eps: f64,
+    filled: impl Fn(Pt2) -> bool,
+) -> Vec<RingRegion> {
     // 1. directed edges from all oriented polygons + the vertex set for T-touch splits.
     let mut edges: Vec<Edge2> = Vec::new();
     for poly in polys {
@@ -262,7 +322,6 @@ fn winding_regions(polys: &[Vec<Pt2>], eps: f64, filled: impl Fn(i32) -> bool) -
     }
 
     // 3. keep sub-edges with filled on exactly one side; orient filled-on-LEFT.
-    let winding_at = |p: Pt2| -> i32 { polys.iter().map(|poly| winding_number2(p, poly)).sum() };
     let mut boundary: Vec<Edge2> = Vec::new();
     for &(a, b) in &split {
         let dx = b.x - a.x;
@@ -276,8 +335,8 @@ fn winding_regions(polys: &[Vec<Pt2>], eps: f64, filled: impl Fn(i32) -> bool) -
         let nz = dx / len;
         let mx = (a.x + b.x) / 2.0;
         let mz = (a.z + b.z) / 2.0;
-        let left = filled(winding_at(Pt2::new(mx + nx * probe, mz + nz * probe)));
-        let right = filled(winding_at(Pt2::new(mx - nx * probe, mz - nz * probe)));
+        let left = filled(Pt2::new(mx + nx * probe, mz + nz * probe));
+        let right = filled(Pt2::new(mx - nx * probe, mz - nz * probe));
         if left == right {
             continue; // interior or exterior — not a boundary
         }
@@ -363,10 +422,17 @@ fn winding_regions(polys: &[Vec<Pt2>], eps: f64, filled: impl Fn(i32) -> bool) -
             holes.push(ring);
         }
     }
+    // A hole belongs to the innermost outer around it: an island inside
+    // another region's hole also lies inside that region's outer.
     for hole in holes {
         if let Some(region) = regions
             .iter_mut()
-            .find(|region| ring_inside2(&hole, &region.outer, eps))
+            .filter(|region| ring_inside2(&hole, &region.outer, eps))
+            .min_by(|a, b| {
+                signed_area2(&a.outer)
+                    .partial_cmp(&signed_area2(&b.outer))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
         {
             region.holes.push(hole);
         }
@@ -459,4 +525,72 @@ mod tests {
         let expected = 100.0 - 9.0 - 1.0; // corner bite 3×3 (clipped to base) + 1×1 extra strip
         assert!((signed_area2(&region.outer) - expected).abs() < 1e-6);
     }
+
+    fn region(outer: Vec<Pt2>, holes: Vec<Vec<Pt2>>) -> RingRegion {
+        RingRegion { outer, holes }
+    }
+
+    fn total_area(regions: &[RingRegion]) -> f64 {
+        regions
+            .iter()
+            .map(|region| {
+                signed_area2(&region.outer)
+                    + region
+                        .holes
+                        .iter()
+                        .map(|hole| signed_area2(hole))
+                        .sum::<f64>()
+            })
+            .sum()
+    }
+
+    /// Two overlapping 4×4 squares (overlap 2×2) through every operation;
+    /// input winding does not matter.
+    #[test]
+    fn region_boolean_operations_on_overlapping_squares() {
+        let a = [region(ccw_square(0.0, 0.0, 4.0, 4.0), vec![])];
+        let b = [region(cw(ccw_square(2.0, 2.0, 6.0, 6.0)), vec![])];
+
+        for (op, expected, count) in [
+            (RegionBooleanOp::Union, 28.0, 1),
+            (RegionBooleanOp::Intersection, 4.0, 1),
+            (RegionBooleanOp::Difference, 12.0, 1),
+            (RegionBooleanOp::Xor, 24.0, 2),
+        ] {
+            let regions = boolean_regions(&a, &b, op, DEFAULT_EPS);
+            assert_eq!(regions.len(), count, "{:?}", op);
+            assert!(
+                (total_area(&regions) - expected).abs() < 1e-6,
+                "{:?}: area {}",
+                op,
+                total_area(&regions)
+            );
+        }
+    }
+
+    /// A slab with an opening, minus a zone straddling the opening, and an
+    /// island inside the opening: holes nest in the innermost outer.
+    #[test]
+    fn region_boolean_respects_holes_and_nests_islands() {
+        let slab = [region(
+            ccw_square(0.0, 0.0, 10.0, 10.0),
+            vec![cw(ccw_square(4.0, 4.0, 6.0, 6.0))],
+        )];
+        let zone = [region(ccw_square(3.0, 3.0, 5.0, 7.0), vec![])];
+        let cut = boolean_regions(&slab, &zone, RegionBooleanOp::Difference, DEFAULT_EPS);
+        assert_eq!(cut.len(), 1);
+        // The zone is 2×4, a quarter of it already open.
+        assert!((total_area(&cut) - (96.0 - 6.0)).abs() < 1e-6);
+
+        let island = [region(
+            ccw_square(4.5, 4.5, 5.5, 5.5),
+            vec![cw(ccw_square(4.8, 4.8, 5.2, 5.2))],
+        )];
+        let merged = boolean_regions(&slab, &island, RegionBooleanOp::Union, DEFAULT_EPS);
+        assert_eq!(merged.len(), 2);
+        for region in &merged {
+            assert_eq!(region.holes.len(), 1, "each outer keeps only its own hole");
+        }
+        assert!((total_area(&merged) - (96.0 + 1.0 - 0.16)).abs() < 1e-6);
+    }
 }
@@ -0,0 +1,219 @@
+//! Region booleans on any plane: union / intersection / difference / xor of
+//! polygons with holes, evaluated in a `WorkPlane`'s `(u, v)` frame and lifted
+//! back to world space. For plan-level region editing (floor zones, slab
+//! openings) on whatever level or sloped plane the regions live on.
+
+use crate::geometry::boolean2d::*;
+use crate::geometry::poly2d::*;
+use crate::spatial::workplane::WorkPlane;
+use openmaths::Vector3;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// A world-space polygon with holes lying on a work plane.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PlaneRegion {
+    pub outer: Vec<Vector3>,
+    #[serde(default)]
+    pub holes: Vec<Vec<Vector3>>,
+}
+
+/// Applies `op` to the region sets `a` and `b`, which must lie on `plane`
+/// within `eps`. Input winding is free. Output outers run CCW about the plane
+/// normal and holes CW — on a Y-up plan plane, the CW-in-XZ outers
+/// `OGPolygon` expects. `Err` names the first point off the plane.
+pub fn boolean_regions_on_plane(
+    plane: &WorkPlane,
+    a: &[PlaneRegion],
+    b: &[PlaneRegion],
+    op: RegionBooleanOp,
+    eps: f64,
+) -> Result<Vec<PlaneRegion>, String> {
+    let a = flatten_regions(plane, a, eps)?;
+    let b = flatten_regions(plane, b, eps)?;
+    let lift = |ring: &[Pt2]| -> Vec<Vector3> {
+        ring.iter().map(|p| plane.lift_point(p.x, p.z)).collect()
+    };
+    Ok(boolean_regions(&a, &b, op, eps)
+        .iter()
+        .map(|region| PlaneRegion {
+            outer: lift(&region.outer),
+            holes: region.holes.iter().map(|hole| lift(hole)).collect(),
+        })
+        .collect())
+}
+
+fn flatten_regions(
+    plane: &WorkPlane,
+    regions: &[PlaneRegion],
+    eps: f64,
+) -> Result<Vec<RingRegion>, String> {
+    let flatten = |ring: &[Vector3]| -> Result<Vec<Pt2>, String> {
+        ring.iter()
+            .map(|point| {
+                let distance = plane.signed_distance(*point);
+                if distance.abs() > eps {
+                    return Err(format!(
+                        "Point ({}, {}, {}) lies {:.3e} off the work plane",
+                        point.x, point.y, point.z, distance
+                    ));
+                }
+                let (u, v) = plane.project(*point);
+                Ok(Pt2::new(u, v))
+            })
+            .collect()
+    };
+    regions
+        .iter()
+        .map(|region| {
+            Ok(RingRegion {
+                outer: flatten(&region.outer)?,
+                holes: region
+                    .holes
+                    .iter()
+                    .map(|hole| flatten(hole))
+                    .collect::<Result<_, _>>()?,
+            })
+        })
+        .collect()
+}
+
+/// Result payload for a region boolean: the resulting regions as JSON
+/// `[{outer:[{x,y,z},…], holes:[[…],…]}, …]`.
+#[wasm_bindgen]
+pub struct OGRegionBooleanResult {
+    regions_serialized: String,
+}
+
+#[wasm_bindgen]
+impl OGRegionBooleanResult {
+    #[wasm_bindgen(getter, js_name = regionsSerialized)]
+    pub fn regions_serialized(&self) -> String {
+        self.regions_serialized.clone()
+    }
+}
+
+/// Wasm entry point: `a_json` and `b_json` are region arrays in the output
+/// format (the regions of `offsetPolylineRegions` fit as-is); `operation` is
+/// `"Union"`, `"Intersection"`, `"Difference"` or `"Xor"`. Returns no regions,
+/// without throwing, when the result is empty. THROWS on malformed JSON or a
+/// point off the plane.
+#[wasm_bindgen(js_name = regionBoolean)]
+pub fn region_boolean_wasm(
+    plane: &WorkPlane,
+    a_json: String,
+    b_json: String,
+    operation: String,
+) -> Result<OGRegionBooleanResult, JsValue> {
+    let parse = |payload: &str| -> Result<Vec<PlaneRegion>, JsValue> {
+        serde_json::from_str(payload)
+            .map_err(|error| JsValue::from_str(&format!("Invalid region JSON payload: {}", error)))
+    };
+    let op: RegionBooleanOp = serde_json::from_value(serde_json::Value::String(operation))
+        .map_err(|error| JsValue::from_str(&format!("Invalid region operation: {}", error)))?;
+    let regions =
+        boolean_regions_on_plane(plane, &parse(&a_json)?, &parse(&b_json)?, op, DEFAULT_EPS)
+            .map_err(|error| JsValue::from_str(&error))?;
+    let regions_serialized = serde_json::to_string(&regions).map_err(|error| {
+        JsValue::from_str(&format!("Failed to serialize region boolean: {}", error))
+    })?;
+    Ok(OGRegionBooleanResult { regions_serialized })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rectangle(plane: &WorkPlane, u0: f64, v0: f64, u1: f64, v1: f64) -> Vec<Vector3> {
+        plane.lift(&[(u0, v0), (u1, v0), (u1, v1), (u0, v1)])
+    }
+
+    fn area(plane: &WorkPlane, regions: &[PlaneRegion]) -> f64 {
+        let ring_area = |ring: &[Vector3]| {
+            let flat: Vec<Pt2> = ring
+                .iter()
+                .map(|point| {
+                    let (u, v) = plane.project(*point);
+                    Pt2::new(u, v)
+                })
+                .collect();
+            signed_area2(&flat)
+        };
+        regions
+            .iter()
+            .map(|region| {
+                ring_area(&region.outer) + region.holes.iter().map(|h| ring_area(h)).sum::<f64>()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn slab_opening_on_sloped_plane_stays_on_the_plane() {
+        let plane = WorkPlane::new(
+            Vector3::new(1.0, 3.0, -2.0),
+            Vector3::new(0.0, 1.0, 0.5),
+            Vector3::new(1.0, 0.0, 0.0),
+        );
+        let slab = [PlaneRegion {
+            outer: rectangle(&plane, 0.0, 0.0, 6.0, 4.0),
+            holes: vec![],
+        }];
+        let shaft = [PlaneRegion {
+            outer: rectangle(&plane, 2.0, 1.0, 3.0, 2.5),
+            holes: vec![],
+        }];
+
+        let result =
+            boolean_regions_on_plane(&plane, &slab, &shaft, RegionBooleanOp::Difference, 1e-6)
+                .unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].holes.len(), 1);
+        assert!((area(&plane, &result) - (24.0 - 1.5)).abs() < 1e-6);
+        assert!(result[0]
+            .outer
+            .iter()
+            .chain(result[0].holes.iter().flatten())
+            .all(|point| plane.signed_distance(*point).abs() < 1e-9));
+    }
+
+    #[test]
+    fn plan_plane_output_matches_polygon_winding_and_rejects_off_plane_points() {
+        let plane =
+            WorkPlane::from_origin_normal(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0));
+        let zone = |x0: f64, z0: f64, x1: f64, z1: f64, y: f64| PlaneRegion {
+            outer: vec![
+                Vector3::new(x0, y, z0),
+                Vector3::new(x1, y, z0),
+                Vector3::new(x1, y, z1),
+                Vector3::new(x0, y, z1),
+            ],
+            holes: vec![],
+        };
+
+        let merged = boolean_regions_on_plane(
+            &plane,
+            &[zone(0.0, 0.0, 2.0, 2.0, 0.0)],
+            &[zone(1.0, 0.0, 3.0, 2.0, 0.0)],
+            RegionBooleanOp::Union,
+            1e-6,
+        )
+        .unwrap();
+        assert_eq!(merged.len(), 1);
+        assert!(
+            (signed_area_xz(&merged[0].outer) + 6.0).abs() < 1e-6,
+            "CCW about +Y is CW in XZ"
+        );
+
+        let error = boolean_regions_on_plane(
+            &plane,
+            &[zone(0.0, 0.0, 2.0, 2.0, 0.0)],
+            &[zone(1.0, 0.0, 3.0, 2.0, 0.5)],
+            RegionBooleanOp::Intersection,
+            1e-6,
+        )
+        .err()
+        .expect("off-plane regions are rejected");
+        assert!(error.contains("off the work plane"));
+    }
+}
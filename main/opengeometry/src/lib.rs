@@ -4,13 +4,21 @@ pub mod geometry {
     pub mod offset2d;
     pub mod offset_regions;
     pub mod poly2d;
+    pub mod region_boolean;
     pub mod triangle;
 }
 
 pub mod operations {
+    pub mod clash;
+    pub(crate) mod common;
+    pub mod draft;
     pub mod extrude;
     pub mod offset;
+    pub mod pattern;
     pub mod profile;
+    pub mod section;
+    pub mod shell;
+    pub mod split;
     pub mod sweep;
     pub mod triangulate;
     pub mod windingsort;
@@ -0,0 +1,644 @@
+//! Clash (interference) detection between scene entities.
+//!
+//! Entity bounding boxes are swept along x to find candidate pairs, and each
+//! pair's triangles are culled against the other entity's box. The narrow
+//! phase measures exact triangle/triangle distance. Closed solids whose
+//! boxes overlap are also intersected with the native boolean. That gives
+//! an exact intersection volume, which separates real interference from
+//! solids that merely touch along a face or edge, and catches a solid buried
+//! inside another without any surface contact.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::booleans::is_closed_solid_operand;
+use crate::booleans::native::{brep_to_native_polygons, enclosed_volume, execute_native_boolean};
+use crate::booleans::solid::Vec3f;
+use crate::booleans::types::{BooleanOperation, BooleanOptions};
+use crate::brep::Brep;
+use crate::scenegraph::SceneEntity;
+
+/// Separator between the two entity ids of a [`ClashReport::pairs`] key.
+pub const PAIR_KEY_SEPARATOR: &str = "|";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClashOptions {
+    /// Required gap between entities. Pairs closer than this, but not
+    /// touching, are clearance violations. `0` reports only hard clashes and
+    /// contacts.
+    pub clearance: f64,
+    /// Contact tolerance in model units. `None` derives it per pair from the
+    /// pair's bounds, as the boolean pipeline does.
+    pub tolerance: Option<f64>,
+    /// Whether touching contacts are reported. Wall/slab junctions touch by
+    /// design, so callers checking coordination often turn this off.
+    pub include_touching: bool,
+}
+
+impl Default for ClashOptions {
+    fn default() -> Self {
+        Self {
+            clearance: 0.0,
+            tolerance: None,
+            include_touching: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClashKind {
+    /// The entities interpenetrate.
+    Hard,
+    /// The entities meet without interpenetrating.
+    Touching,
+    /// The entities are apart but closer than the required clearance.
+    Clearance,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClashRecord {
+    pub entity_a: String,
+    pub entity_b: String,
+    pub kind: ClashKind,
+    /// Minimum surface distance; `0` when the surfaces meet or cross.
+    pub distance: f64,
+    /// Shared volume of two interpenetrating closed solids. `None` for open
+    /// entities and for solids that do not overlap.
+    pub intersection_volume: Option<f64>,
+    /// Closest points on `entity_a` and `entity_b`. `None` for a solid
+    /// buried in another with no surface contact.
+    pub closest_points: Option<[[f64; 3]; 2]>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ClashReport {
+    pub entity_count: usize,
+    /// Pairs whose inflated bounds overlapped and reached the narrow phase.
+    pub candidate_pair_count: usize,
+    pub hard_count: usize,
+    pub touching_count: usize,
+    pub clearance_count: usize,
+    /// Clashes keyed by `"<id_a>|<id_b>"`, ids in lexicographic order.
+    pub pairs: BTreeMap<String, ClashRecord>,
+}
+
+#[derive(Clone, Copy)]
+struct Bounds {
+    min: Vec3f,
+    max: Vec3f,
+}
+
+impl Bounds {
+    fn of(points: impl IntoIterator<Item = Vec3f>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bounds = Self {
+            min: first,
+            max: first,
+        };
+        for p in points {
+            bounds.min = Vec3f::new(
+                bounds.min.x.min(p.x),
+                bounds.min.y.min(p.y),
+                bounds.min.z.min(p.z),
+            );
+            bounds.max = Vec3f::new(
+                bounds.max.x.max(p.x),
+                bounds.max.y.max(p.y),
+                bounds.max.z.max(p.z),
+            );
+        }
+        Some(bounds)
+    }
+
+    fn overlaps(&self, other: &Bounds, slack: f64) -> bool {
+        self.min.x <= other.max.x + slack
+            && other.min.x <= self.max.x + slack
+            && self.min.y <= other.max.y + slack
+            && other.min.y <= self.max.y + slack
+            && self.min.z <= other.max.z + slack
+            && other.min.z <= self.max.z + slack
+    }
+
+    fn diagonal(&self) -> f64 {
+        self.max.sub(self.min).norm()
+    }
+}
+
+type Triangle = [Vec3f; 3];
+
+/// An entity prepared for clash tests.
+struct ClashBody<'a> {
+    entity: &'a SceneEntity,
+    triangles: Vec<(Triangle, Bounds)>,
+    bounds: Bounds,
+    closed: bool,
+}
+
+impl<'a> ClashBody<'a> {
+    fn new(entity: &'a SceneEntity) -> Option<Self> {
+        let buffer = entity.brep.get_triangle_vertex_buffer();
+        let triangles: Vec<(Triangle, Bounds)> = buffer
+            .chunks_exact(9)
+            .filter_map(|chunk| {
+                let triangle = [
+                    Vec3f::new(chunk[0], chunk[1], chunk[2]),
+                    Vec3f::new(chunk[3], chunk[4], chunk[5]),
+                    Vec3f::new(chunk[6], chunk[7], chunk[8]),
+                ];
+                Some((triangle, Bounds::of(triangle)?))
+            })
+            .collect();
+        let bounds = Bounds::of(triangles.iter().flat_map(|(triangle, _)| *triangle))?;
+        Some(Self {
+            entity,
+            triangles,
+            bounds,
+            closed: is_closed_solid_operand(&entity.brep),
+        })
+    }
+}
+
+/// Finds every clashing pair among `entities`. Entities without faces
+/// (wires, points) take no part.
+pub fn detect_clashes<'a>(
+    entities: impl IntoIterator<Item = &'a SceneEntity>,
+    options: &ClashOptions,
+) -> ClashReport {
+    let mut bodies: Vec<ClashBody> = entities.into_iter().filter_map(ClashBody::new).collect();
+    let mut report = ClashReport {
+        entity_count: bodies.len(),
+        ..ClashReport::default()
+    };
+    let clearance = options.clearance.max(0.0);
+    // Deterministic output whatever order the caller's store iterates in.
+    bodies.sort_by(|a, b| a.entity.id.cmp(&b.entity.id));
+
+    // Sweep and prune along x.
+    let mut order: Vec<usize> = (0..bodies.len()).collect();
+    order.sort_by(|&a, &b| bodies[a].bounds.min.x.total_cmp(&bodies[b].bounds.min.x));
+    for (position, &i) in order.iter().enumerate() {
+        for &j in &order[position + 1..] {
+            let (a, b) = (&bodies[i], &bodies[j]);
+            let tolerance = pair_tolerance(a, b, options);
+            let slack = clearance + tolerance;
+            if b.bounds.min.x > a.bounds.max.x + slack {
+                break;
+            }
+            if !a.bounds.overlaps(&b.bounds, slack) {
+                continue;
+            }
+            report.candidate_pair_count += 1;
+
+            let (a, b) = if a.entity.id <= b.entity.id {
+                (a, b)
+            } else {
+                (b, a)
+            };
+            let Some(record) = classify_pair(a, b, clearance, tolerance) else {
+                continue;
+            };
+            match record.kind {
+                ClashKind::Hard => report.hard_count += 1,
+                ClashKind::Touching if options.include_touching => report.touching_count += 1,
+                ClashKind::Touching => continue,
+                ClashKind::Clearance => report.clearance_count += 1,
+            }
+            report.pairs.insert(
+                format!("{}{}{}", a.entity.id, PAIR_KEY_SEPARATOR, b.entity.id),
+                record,
+            );
+        }
+    }
+
+    report
+}
+
+fn pair_tolerance(a: &ClashBody, b: &ClashBody, options: &ClashOptions) -> f64 {
+    BooleanOptions {
+        tolerance: options.tolerance,
+        ..BooleanOptions::default()
+    }
+    .resolve_tolerance(&a.entity.brep, &b.entity.brep)
+}
+
+fn classify_pair(
+    a: &ClashBody,
+    b: &ClashBody,
+    clearance: f64,
+    tolerance: f64,
+) -> Option<ClashRecord> {
+    let contact = nearest_contact(a, b, clearance + tolerance, tolerance);
+
+    let solids = a.closed && b.closed;
+    let volume = (solids && a.bounds.overlaps(&b.bounds, 0.0))
+        .then(|| intersection_volume(&a.entity.brep, &b.entity.brep, tolerance))
+        .flatten();
+    // Interference thinner than the tolerance across the smaller body is
+    // contact noise: face-to-face and edge-on contacts enclose no volume.
+    let smaller = a.bounds.diagonal().min(b.bounds.diagonal());
+    let hard_volume = volume.filter(|volume| *volume > tolerance * smaller * smaller);
+
+    let (kind, distance, points) = match (hard_volume, contact) {
+        (Some(_), contact) => (ClashKind::Hard, 0.0, contact.map(|c| c.points)),
+        // Crossing surfaces are a clash unless the volume of two solids
+        // proves the crossing is contact noise; a solid the native boolean
+        // rejects (a curved face) has no volume to prove it.
+        (None, Some(contact)) if contact.crossing && (!solids || volume.is_none()) => {
+            (ClashKind::Hard, 0.0, Some(contact.points))
+        }
+        (None, Some(contact)) if contact.distance <= tolerance => {
+            (ClashKind::Touching, contact.distance, Some(contact.points))
+        }
+        (None, Some(contact)) if contact.distance < clearance => {
+            (ClashKind::Clearance, contact.distance, Some(contact.points))
+        }
+        _ => return None,
+    };
+
+    Some(ClashRecord {
+        entity_a: a.entity.id.clone(),
+        entity_b: b.entity.id.clone(),
+        kind,
+        distance,
+        intersection_volume: if kind == ClashKind::Hard {
+            hard_volume
+        } else {
+            None
+        },
+        closest_points: points.map(|points| points.map(|p| [p.x, p.y, p.z])),
+    })
+}
+
+/// Volume shared by two closed solids, or `None` when either cannot be
+/// handed to the native boolean.
+fn intersection_volume(a: &Brep, b: &Brep, tolerance: f64) -> Option<f64> {
+    let (a, _) = brep_to_native_polygons(a, tolerance).ok()?;
+    let (b, _) = brep_to_native_polygons(b, tolerance).ok()?;
+    let shared = execute_native_boolean(a, b, BooleanOperation::Intersection, tolerance);
+    Some(enclosed_volume(&shared).max(0.0))
+}
+
+#[derive(Clone, Copy)]
+struct Contact {
+    distance: f64,
+    points: [Vec3f; 2],
+    /// Some edge passes through the other surface rather than grazing it.
+    crossing: bool,
+}
+
+/// The closest approach between two bodies within `reach`, plus whether
+/// their surfaces cross anywhere. `None` when they are farther apart.
+fn nearest_contact(a: &ClashBody, b: &ClashBody, reach: f64, tolerance: f64) -> Option<Contact> {
+    let near = |body: &ClashBody, other: &Bounds| -> Vec<(Triangle, Bounds)> {
+        body.triangles
+            .iter()
+            .filter(|(_, bounds)| bounds.overlaps(other, reach))
+            .copied()
+            .collect()
+    };
+    let a_triangles = near(a, &b.bounds);
+    let b_triangles = near(b, &a.bounds);
+
+    let mut best: Option<Contact> = None;
+    for (ta, bounds_a) in &a_triangles {
+        for (tb, bounds_b) in &b_triangles {
+            if !bounds_a.overlaps(bounds_b, reach) {
+                continue;
+            }
+            let contact = triangle_contact(ta, tb, tolerance);
+            if contact.distance > reach {
+                continue;
+            }
+            best = Some(match best {
+                Some(current) => Contact {
+                    crossing: current.crossing || contact.crossing,
+                    ..if contact.distance < current.distance {
+                        contact
+                    } else {
+                        current
+                    }
+                },
+                None => contact,
+            });
+            if best.is_some_and(|best| best.crossing && best.distance == 0.0) {
+                return best;
+            }
+        }
+    }
+    best
+}
+
+/// Exact distance between two triangles with the closest points. Two
+/// triangles are closest either where an edge of one pierces the other or
+/// between an edge of one and the other triangle, so six edge/triangle
+/// queries cover every case.
+fn triangle_contact(a: &Triangle, b: &Triangle, tolerance: f64) -> Contact {
+    let mut best = Contact {
+        distance: f64::INFINITY,
+        points: [a[0], b[0]],
+        crossing: false,
+    };
+    for (edges_of, other, swap) in [(a, b, false), (b, a, true)] {
+        for i in 0..3 {
+            let (p, q) = (edges_of[i], edges_of[(i + 1) % 3]);
+            let (on_edge, on_triangle, crossing) = segment_triangle_closest(p, q, other, tolerance);
+            let distance = on_edge.sub(on_triangle).norm();
+            best.crossing |= crossing;
+            if distance < best.distance {
+                best.distance = distance;
+                best.points = if swap {
+                    [on_triangle, on_edge]
+                } else {
+                    [on_edge, on_triangle]
+                };
+            }
+        }
+    }
+    best
+}
+
+/// Closest points between segment `p`-`q` and a triangle, and whether the
+/// segment passes through the triangle's interior from one side to the
+/// other.
+fn segment_triangle_closest(
+    p: Vec3f,
+    q: Vec3f,
+    triangle: &Triangle,
+    tolerance: f64,
+) -> (Vec3f, Vec3f, bool) {
+    let [a, b, c] = *triangle;
+    if let Some(normal) = b.sub(a).cross(c.sub(a)).normalized(0.0) {
+        let (dp, dq) = (normal.dot(p.sub(a)), normal.dot(q.sub(a)));
+        if (dp > tolerance && dq < -tolerance) || (dp < -tolerance && dq > tolerance) {
+            let hit = p.add(q.sub(p).scale(dp / (dp - dq)));
+            if closest_point_on_triangle(hit, a, b, c).sub(hit).norm() <= tolerance {
+                return (hit, hit, true);
+            }
+        }
+    }
+
+    let mut best = {
+        let on_triangle = closest_point_on_triangle(p, a, b, c);
+        (p, on_triangle, p.sub(on_triangle).norm_sq())
+    };
+    let mut consider = |on_segment: Vec3f, on_triangle: Vec3f| {
+        let distance = on_segment.sub(on_triangle).norm_sq();
+        if distance < best.2 {
+            best = (on_segment, on_triangle, distance);
+        }
+    };
+    consider(q, closest_point_on_triangle(q, a, b, c));
+    for (start, end) in [(a, b), (b, c), (c, a)] {
+        let (on_segment, on_edge) = closest_points_on_segments(p, q, start, end);
+        consider(on_segment, on_edge);
+    }
+    (best.0, best.1, false)
+}
+
+/// Closest point to `p` on triangle `abc` (Ericson, Real-Time Collision
+/// Detection, 5.1.5).
+fn closest_point_on_triangle(p: Vec3f, a: Vec3f, b: Vec3f, c: Vec3f) -> Vec3f {
+    let ab = b.sub(a);
+    let ac = c.sub(a);
+    let ap = p.sub(a);
+    let (d1, d2) = (ab.dot(ap), ac.dot(ap));
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+    let bp = p.sub(b);
+    let (d3, d4) = (ab.dot(bp), ac.dot(bp));
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a.add(ab.scale(d1 / (d1 - d3)));
+    }
+    let cp = p.sub(c);
+    let (d5, d6) = (ab.dot(cp), ac.dot(cp));
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a.add(ac.scale(d2 / (d2 - d6)));
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return b.add(c.sub(b).scale((d4 - d3) / ((d4 - d3) + (d5 - d6))));
+    }
+    let denominator = va + vb + vc;
+    if denominator.abs() <= f64::MIN_POSITIVE {
+        // Degenerate triangle: fall back to its longest edge.
+        let (start, end) = [(a, b), (b, c), (c, a)]
+            .into_iter()
+            .max_by(|x, y| x.1.sub(x.0).norm_sq().total_cmp(&y.1.sub(y.0).norm_sq()))
+            .unwrap_or((a, b));
+        return closest_points_on_segments(p, p, start, end).1;
+    }
+    let v = vb / denominator;
+    let w = vc / denominator;
+    a.add(ab.scale(v)).add(ac.scale(w))
+}
+
+/// Closest points between segments `p1`-`q1` and `p2`-`q2` (Ericson,
+/// Real-Time Collision Detection, 5.1.9).
+fn closest_points_on_segments(p1: Vec3f, q1: Vec3f, p2: Vec3f, q2: Vec3f) -> (Vec3f, Vec3f) {
+    let d1 = q1.sub(p1);
+    let d2 = q2.sub(p2);
+    let r = p1.sub(p2);
+    let a = d1.norm_sq();
+    let e = d2.norm_sq();
+    let f = d2.dot(r);
+    let epsilon = f64::MIN_POSITIVE;
+
+    let (s, t) = if a <= epsilon && e <= epsilon {
+        (0.0, 0.0)
+    } else if a <= epsilon {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = d1.dot(r);
+        if e <= epsilon {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = d1.dot(d2);
+            let denominator = a * e - b * b;
+            let mut s = if denominator > epsilon {
+                ((b * f - c * e) / denominator).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let mut t = (b * s + f) / e;
+            if t < 0.0 {
+                t = 0.0;
+                s = (-c / a).clamp(0.0, 1.0);
+            } else if t > 1.0 {
+                t = 1.0;
+                s = ((b - c) / a).clamp(0.0, 1.0);
+            }
+            (s, t)
+        }
+    };
+    (p1.add(d1.scale(s)), p2.add(d2.scale(t)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::booleans::test_support;
+    use crate::primitives::polygon::OGPolygon;
+    use openmaths::Vector3;
+
+    fn cuboid(id: &str, center: Vector3, width: f64, height: f64, depth: f64) -> SceneEntity {
+        SceneEntity {
+            id: id.to_string(),
+            kind: "cuboid".to_string(),
+            brep: test_support::cuboid(center, width, height, depth),
+        }
+    }
+
+    fn record<'a>(report: &'a ClashReport, a: &str, b: &str) -> Option<&'a ClashRecord> {
+        report
+            .pairs
+            .get(&format!("{}{}{}", a, PAIR_KEY_SEPARATOR, b))
+    }
+
+    #[test]
+    fn overlapping_solids_are_hard_clashes_with_exact_volume() {
+        let entities = [
+            cuboid("beam", Vector3::new(0.0, 0.0, 0.0), 2.0, 1.0, 1.0),
+            cuboid("duct", Vector3::new(1.5, 0.0, 0.0), 2.0, 0.5, 0.5),
+        ];
+
+        let report = detect_clashes(&entities, &ClashOptions::default());
+
+        let clash = record(&report, "beam", "duct").expect("clash");
+        assert_eq!(clash.kind, ClashKind::Hard);
+        assert_eq!(clash.distance, 0.0);
+        let volume = clash.intersection_volume.expect("volume");
+        assert!((volume - 0.5 * 0.5 * 0.5).abs() < 1.0e-9);
+        assert_eq!(report.hard_count, 1);
+    }
+
+    #[test]
+    fn solid_buried_in_another_is_a_hard_clash() {
+        let entities = [
+            cuboid("slab", Vector3::new(0.0, 0.0, 0.0), 4.0, 1.0, 4.0),
+            cuboid("sleeve", Vector3::new(0.0, 0.0, 0.0), 0.2, 0.2, 0.2),
+        ];
+
+        let report = detect_clashes(&entities, &ClashOptions::default());
+
+        let clash = record(&report, "slab", "sleeve").expect("clash");
+        assert_eq!(clash.kind, ClashKind::Hard);
+        assert!(clash.closest_points.is_none());
+        assert!((clash.intersection_volume.unwrap() - 0.008).abs() < 1.0e-9);
+    }
+
+    #[test]
+    fn face_and_edge_contacts_are_touching_not_hard() {
+        let entities = [
+            cuboid("a", Vector3::new(0.0, 0.0, 0.0), 1.0, 1.0, 1.0),
+            cuboid("b", Vector3::new(1.0, 0.0, 0.0), 1.0, 1.0, 1.0),
+            cuboid("c", Vector3::new(1.0, 1.0, 0.0), 1.0, 1.0, 1.0),
+        ];
+
+        let report = detect_clashes(&entities, &ClashOptions::default());
+
+        assert_eq!(report.hard_count, 0);
+        assert_eq!(record(&report, "a", "b").unwrap().kind, ClashKind::Touching);
+        assert_eq!(record(&report, "a", "c").unwrap().kind, ClashKind::Touching);
+
+        let quiet = detect_clashes(
+            &entities,
+            &ClashOptions {
+                include_touching: false,
+                ..ClashOptions::default()
+            },
+        );
+        assert!(quiet.pairs.is_empty());
+    }
+
+    #[test]
+    fn clearance_violation_reports_gap_and_closest_points() {
+        let entities = [
+            cuboid("pipe", Vector3::new(0.0, 0.0, 0.0), 1.0, 1.0, 1.0),
+            cuboid("wall", Vector3::new(1.03, 0.0, 0.0), 1.0, 1.0, 1.0),
+        ];
+
+        let far = detect_clashes(&entities, &ClashOptions::default());
+        assert!(far.pairs.is_empty());
+        assert_eq!(far.candidate_pair_count, 0);
+
+        let report = detect_clashes(
+            &entities,
+            &ClashOptions {
+                clearance: 0.05,
+                ..ClashOptions::default()
+            },
+        );
+        let clash = record(&report, "pipe", "wall").expect("clearance");
+        assert_eq!(clash.kind, ClashKind::Clearance);
+        assert!((clash.distance - 0.03).abs() < 1.0e-9);
+        let [on_pipe, on_wall] = clash.closest_points.expect("closest points");
+        assert!((on_pipe[0] - 0.5).abs() < 1.0e-9);
+        assert!((on_wall[0] - 0.53).abs() < 1.0e-9);
+    }
+
+    #[test]
+    fn crossing_solids_without_a_volume_are_hard_clashes() {
+        // Lifting one corner warps three faces off their planes, so the
+        // native boolean cannot measure the shared volume.
+        let mut warped = cuboid("warped", Vector3::new(0.0, 0.0, 0.0), 1.0, 1.0, 1.0);
+        let corner = warped
+            .brep
+            .vertices
+            .iter_mut()
+            .max_by(|a, b| {
+                let sum = |v: &Vector3| v.x + v.y + v.z;
+                sum(&a.position).total_cmp(&sum(&b.position))
+            })
+            .unwrap();
+        corner.position.y += 0.2;
+        let entities = [
+            warped,
+            cuboid("post", Vector3::new(0.5, 0.0, 0.5), 0.4, 2.0, 0.4),
+        ];
+
+        let report = detect_clashes(&entities, &ClashOptions::default());
+
+        let clash = record(&report, "post", "warped").expect("clash");
+        assert_eq!(clash.kind, ClashKind::Hard);
+        assert!(clash.intersection_volume.is_none());
+    }
+
+    #[test]
+    fn sheet_piercing_a_solid_is_a_hard_clash() {
+        let mut polygon = OGPolygon::new("sheet".to_string());
+        polygon
+            .set_config(vec![
+                Vector3::new(-1.0, 0.0, -1.0),
+                Vector3::new(1.0, 0.0, -1.0),
+                Vector3::new(1.0, 0.0, 1.0),
+                Vector3::new(-1.0, 0.0, 1.0),
+            ])
+            .unwrap();
+        let entities = [
+            cuboid("box", Vector3::new(0.0, 0.0, 0.0), 1.0, 1.0, 1.0),
+            SceneEntity {
+                id: "sheet".to_string(),
+                kind: "polygon".to_string(),
+                brep: polygon.world_brep(),
+            },
+        ];
+
+        let report = detect_clashes(&entities, &ClashOptions::default());
+
+        let clash = record(&report, "box", "sheet").expect("clash");
+        assert_eq!(clash.kind, ClashKind::Hard);
+        assert!(clash.intersection_volume.is_none());
+    }
+}
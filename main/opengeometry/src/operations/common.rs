@@ -0,0 +1,89 @@
+//! Helpers shared by the solid operations.
+
+use crate::brep::Brep;
+use openmaths::Vector3;
+
+const EPSILON: f64 = crate::tolerance::MODELING_TOLERANCE_FLOOR;
+
+/// Even-odd containment of a point on the polygon's plane, tested in the
+/// coordinate plane that best preserves the polygon's area.
+pub(crate) fn point_in_polygon(point: Vector3, normal: Vector3, polygon: &[Vector3]) -> bool {
+    let (ax, ay, az) = (normal.x.abs(), normal.y.abs(), normal.z.abs());
+    let project = |p: &Vector3| -> (f64, f64) {
+        if ax >= ay && ax >= az {
+            (p.y, p.z)
+        } else if ay >= az {
+            (p.z, p.x)
+        } else {
+            (p.x, p.y)
+        }
+    };
+    let (px, py) = project(&point);
+    let mut inside = false;
+    for index in 0..polygon.len() {
+        let (x1, y1) = project(&polygon[index]);
+        let (x2, y2) = project(&polygon[(index + 1) % polygon.len()]);
+        if (y1 > py) != (y2 > py) && px < x1 + (py - y1) * (x2 - x1) / (y2 - y1) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// As [`point_in_polygon`], for a face outline with holes.
+pub(crate) fn point_in_face(
+    point: Vector3,
+    normal: Vector3,
+    outer: &[Vector3],
+    holes: &[Vec<Vector3>],
+) -> bool {
+    point_in_polygon(point, normal, outer)
+        && !holes
+            .iter()
+            .any(|hole| point_in_polygon(point, normal, hole))
+}
+
+/// Vertex ids of a face's outer loop and of each of its holes.
+pub(crate) fn face_loops(brep: &Brep, face_id: u32) -> (Vec<u32>, Vec<Vec<u32>>) {
+    let face = &brep.faces[face_id as usize];
+    let outer = brep.get_loop_vertex_indices(face.outer_loop);
+    let holes = face
+        .inner_loops
+        .iter()
+        .map(|loop_id| brep.get_loop_vertex_indices(*loop_id))
+        .collect();
+    (outer, holes)
+}
+
+/// Every vertex id on a face, outer loop first.
+pub(crate) fn face_vertex_ids(brep: &Brep, face_id: u32) -> Vec<u32> {
+    let (mut ids, holes) = face_loops(brep, face_id);
+    ids.extend(holes.into_iter().flatten());
+    ids
+}
+
+pub(crate) fn add(a: Vector3, b: Vector3) -> Vector3 {
+    Vector3::new(a.x + b.x, a.y + b.y, a.z + b.z)
+}
+
+pub(crate) fn sub(a: Vector3, b: Vector3) -> Vector3 {
+    Vector3::new(a.x - b.x, a.y - b.y, a.z - b.z)
+}
+
+pub(crate) fn scale(v: Vector3, s: f64) -> Vector3 {
+    Vector3::new(v.x * s, v.y * s, v.z * s)
+}
+
+pub(crate) fn length(v: Vector3) -> f64 {
+    v.dot(&v).sqrt()
+}
+
+/// `v` scaled to unit length; near-zero vectors come back unchanged.
+pub(crate) fn normalize(v: Vector3) -> Vector3 {
+    let len = length(v);
+    if len <= EPSILON {
+        v
+    } else {
+        scale(v, 1.0 / len)
+    }
+}
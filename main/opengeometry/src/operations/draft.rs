@@ -0,0 +1,418 @@
+//! Draft operation: tilts selected planar faces of a solid about a neutral
+//! plane, as needed for moulded parts (release draft) and battered walls.
+//!
+//! Each drafted face pivots about its intersection line with the neutral
+//! plane, so geometry on the neutral plane stays put and the face leans
+//! further the farther it is from it. Topology is unchanged: every vertex of
+//! a drafted face is re-intersected from the (tilted or original) planes of
+//! the faces meeting there, so neighbouring faces stretch but stay planar.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::brep::{Brep, CurveGeometry, Face, SurfaceGeometry};
+use crate::operations::common::{add, face_loops, face_vertex_ids, normalize, scale, sub};
+use crate::operations::shell::solve_plane_offsets;
+use openmaths::Vector3;
+use wasm_bindgen::prelude::*;
+
+const EPSILON: f64 = crate::tolerance::MODELING_TOLERANCE_FLOOR;
+/// Faces whose normal is within this (as `1 - |cos|`) of the pull direction
+/// lie parallel to the neutral plane and have no hinge line to tilt about.
+const PARALLEL_TOLERANCE: f64 = 1.0e-9;
+
+#[derive(Clone)]
+pub struct DraftOptions {
+    /// Planar faces to tilt.
+    pub faces: Vec<u32>,
+    /// Draft angle in radians. Positive angles lean faces toward the pull
+    /// direction, so the part narrows away from the neutral plane.
+    pub angle: f64,
+    /// Mould pull direction; also the normal of the neutral plane.
+    pub pull_direction: Vector3,
+    /// Any point on the neutral plane.
+    pub neutral_origin: Vector3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DraftErrorKind {
+    InvalidAngle,
+    InvalidNeutralPlane,
+    InvalidFaceSelection,
+    NonPlanarFace,
+    FaceParallelToNeutralPlane,
+    TopologyCollapse,
+}
+
+#[derive(Debug, Clone)]
+pub struct DraftError {
+    kind: DraftErrorKind,
+    message: String,
+}
+
+impl DraftError {
+    fn new(kind: DraftErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    pub fn kind(&self) -> DraftErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for DraftError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for DraftError {}
+
+/// Returns a copy of `brep` with the selected faces drafted. Vertex, edge and
+/// face ids are preserved.
+pub fn draft_faces(brep: &Brep, options: &DraftOptions) -> Result<Brep, DraftError> {
+    let angle = options.angle;
+    if !angle.is_finite() || angle.abs() >= std::f64::consts::FRAC_PI_2 - EPSILON {
+        return Err(DraftError::new(
+            DraftErrorKind::InvalidAngle,
+            "Draft angle must be finite and within (-90°, 90°)",
+        ));
+    }
+
+    let pull = options.pull_direction;
+    let pull_length = pull.dot(&pull).sqrt();
+    if !pull_length.is_finite() || pull_length <= EPSILON {
+        return Err(DraftError::new(
+            DraftErrorKind::InvalidNeutralPlane,
+            "Draft pull direction must be a non-zero vector",
+        ));
+    }
+    let pull = scale(pull, 1.0 / pull_length);
+
+    if options.faces.is_empty() {
+        return Err(DraftError::new(
+            DraftErrorKind::InvalidFaceSelection,
+            "Draft requires at least one face",
+        ));
+    }
+
+    let mut planes: Vec<(Vector3, f64)> = brep
+        .faces
+        .iter()
+        .map(|face| face_plane(brep, face.id))
+        .collect();
+    let mut drafted: HashMap<u32, Vector3> = HashMap::new();
+    for face_id in &options.faces {
+        let Some(face) = brep.faces.get(*face_id as usize) else {
+            return Err(DraftError::new(
+                DraftErrorKind::InvalidFaceSelection,
+                format!("Draft face {} does not exist", face_id),
+            ));
+        };
+        if is_curved(face) {
+            return Err(DraftError::new(
+                DraftErrorKind::NonPlanarFace,
+                format!("Draft face {} is not planar", face_id),
+            ));
+        }
+
+        let (normal, constant) = planes[*face_id as usize];
+        let along_pull = normal.dot(&pull);
+        if along_pull.abs() >= 1.0 - PARALLEL_TOLERANCE {
+            return Err(DraftError::new(
+                DraftErrorKind::FaceParallelToNeutralPlane,
+                format!("Draft face {} is parallel to the neutral plane", face_id),
+            ));
+        }
+
+        // Rotate the normal toward the pull direction within span(n, pull);
+        // the rotation axis is the hinge line shared with the neutral plane.
+        let toward_pull = normalize(sub(pull, scale(normal, along_pull)));
+        let tilted = add(scale(normal, angle.cos()), scale(toward_pull, angle.sin()));
+        let hinge = hinge_point(normal, constant, pull, pull.dot(&options.neutral_origin));
+        planes[*face_id as usize] = (tilted, tilted.dot(&hinge));
+        drafted.insert(*face_id, hinge);
+    }
+
+    let mut vertex_faces: Vec<Vec<u32>> = vec![Vec::new(); brep.vertices.len()];
+    for face in &brep.faces {
+        for vertex_id in face_vertex_ids(brep, face.id) {
+            let incident = &mut vertex_faces[vertex_id as usize];
+            if !incident.contains(&face.id) {
+                incident.push(face.id);
+            }
+        }
+    }
+
+    let mut result = brep.clone();
+    for (vertex_id, incident) in vertex_faces.iter().enumerate() {
+        if !incident.iter().any(|face_id| drafted.contains_key(face_id)) {
+            continue;
+        }
+        if let Some(face_id) = incident
+            .iter()
+            .find(|face_id| is_curved(&brep.faces[**face_id as usize]))
+        {
+            return Err(DraftError::new(
+                DraftErrorKind::NonPlanarFace,
+                format!(
+                    "Draft cannot move vertex {} of curved face {}",
+                    vertex_id, face_id
+                ),
+            ));
+        }
+
+        let position = brep.vertices[vertex_id].position;
+        let constraints: Vec<(Vector3, f64)> = incident
+            .iter()
+            .map(|face_id| {
+                let (normal, constant) = planes[*face_id as usize];
+                (normal, normal.dot(&position) - constant)
+            })
+            .collect();
+        let delta = solve_plane_offsets(&constraints).ok_or_else(|| {
+            DraftError::new(
+                DraftErrorKind::TopologyCollapse,
+                format!("Drafted faces do not meet at vertex {}", vertex_id),
+            )
+        })?;
+        result.vertices[vertex_id].position = add(position, delta);
+    }
+
+    for face in &brep.faces {
+        let (outer, holes) = face_loops(brep, face.id);
+        let collapsed = std::iter::once(&outer).chain(holes.iter()).any(|loop_ids| {
+            (0..loop_ids.len()).any(|index| {
+                let from = loop_ids[index] as usize;
+                let to = loop_ids[(index + 1) % loop_ids.len()] as usize;
+                let before = sub(brep.vertices[to].position, brep.vertices[from].position);
+                let after = sub(result.vertices[to].position, result.vertices[from].position);
+                after.dot(&before) <= EPSILON * before.dot(&before).sqrt()
+            })
+        });
+        if collapsed {
+            return Err(DraftError::new(
+                DraftErrorKind::TopologyCollapse,
+                format!("Draft angle collapses face {}", face.id),
+            ));
+        }
+    }
+
+    result.recompute_face_normals();
+    for (face_id, hinge) in &drafted {
+        let face = &mut result.faces[*face_id as usize];
+        if face.surface.is_some() {
+            face.surface = Some(SurfaceGeometry::Plane {
+                origin: *hinge,
+                normal: planes[*face_id as usize].0,
+            });
+        }
+    }
+    for index in 0..result.edges.len() {
+        let Some((from, to)) = result.get_edge_endpoints(index as u32) else {
+            continue;
+        };
+        if let Some(CurveGeometry::Line { .. }) = result.edges[index].curve {
+            result.edges[index].curve = Some(CurveGeometry::Line {
+                start: result.vertices[from as usize].position,
+                end: result.vertices[to as usize].position,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+/// Whether `face` lies on an analytic surface other than a plane.
+fn is_curved(face: &Face) -> bool {
+    !matches!(face.surface, None | Some(SurfaceGeometry::Plane { .. }))
+}
+
+/// The face's plane as `(unit normal, n·p)`, taken from its surface when
+/// tagged and otherwise from the loop normal and first vertex.
+fn face_plane(brep: &Brep, face_id: u32) -> (Vector3, f64) {
+    let face = &brep.faces[face_id as usize];
+    if let Some(SurfaceGeometry::Plane { origin, normal }) = &face.surface {
+        let normal = normalize(*normal);
+        return (normal, normal.dot(origin));
+    }
+    let normal = normalize(face.normal);
+    let anchor = brep
+        .get_loop_vertex_indices(face.outer_loop)
+        .first()
+        .map(|vertex_id| brep.vertices[*vertex_id as usize].position)
+        .unwrap_or(Vector3::new(0.0, 0.0, 0.0));
+    (normal, normal.dot(&anchor))
+}
+
+/// The point of the line `n1·x = c1, n2·x = c2` closest to the origin.
+fn hinge_point(n1: Vector3, c1: f64, n2: Vector3, c2: f64) -> Vector3 {
+    let cosine = n1.dot(&n2);
+    let determinant = 1.0 - cosine * cosine;
+    let a = (c1 - cosine * c2) / determinant;
+    let b = (c2 - cosine * c1) / determinant;
+    add(scale(n1, a), scale(n2, b))
+}
+
+/// Wasm entry point for [`draft_faces`]. `pull_direction` and
+/// `neutral_origin` are `[x,y,z]` arrays; `angle` is in radians.
+#[wasm_bindgen(js_name = draftBrepFaces)]
+pub fn draft_brep_faces_wasm(
+    local_brep_serialized: String,
+    face_ids: Vec<u32>,
+    angle: f64,
+    pull_direction: Vec<f64>,
+    neutral_origin: Vec<f64>,
+) -> Result<String, JsValue> {
+    if pull_direction.len() != 3 || neutral_origin.len() != 3 {
+        return Err(JsValue::from_str(
+            "pull_direction and neutral_origin must be 3-element [x,y,z] arrays",
+        ));
+    }
+    let brep: Brep = serde_json::from_str(&local_brep_serialized).map_err(|error| {
+        JsValue::from_str(&format!("Invalid local BRep JSON payload: {}", error))
+    })?;
+    let options = DraftOptions {
+        faces: face_ids,
+        angle,
+        pull_direction: Vector3::new(pull_direction[0], pull_direction[1], pull_direction[2]),
+        neutral_origin: Vector3::new(neutral_origin[0], neutral_origin[1], neutral_origin[2]),
+    };
+    let drafted = draft_faces(&brep, &options)
+        .map_err(|error| JsValue::from_str(&format!("Failed to draft BRep faces: {}", error)))?;
+    drafted.validate_topology().map_err(|error| {
+        JsValue::from_str(&format!("Drafted BRep topology is invalid: {}", error))
+    })?;
+    serde_json::to_string(&drafted)
+        .map_err(|error| JsValue::from_str(&format!("Failed to serialize drafted BRep: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::cuboid::OGCuboid;
+
+    fn cube() -> Brep {
+        let mut cuboid = OGCuboid::new("draft-cuboid".to_string());
+        cuboid
+            .set_config(Vector3::new(0.0, 0.0, 0.0), 2.0, 2.0, 2.0)
+            .expect("cuboid config");
+        cuboid.brep().clone()
+    }
+
+    fn side_faces(brep: &Brep) -> Vec<u32> {
+        brep.faces
+            .iter()
+            .filter(|face| face.normal.y.abs() < 0.5)
+            .map(|face| face.id)
+            .collect()
+    }
+
+    fn options(faces: Vec<u32>, angle: f64) -> DraftOptions {
+        DraftOptions {
+            faces,
+            angle,
+            pull_direction: Vector3::new(0.0, 1.0, 0.0),
+            neutral_origin: Vector3::new(0.0, -1.0, 0.0),
+        }
+    }
+
+    #[test]
+    fn drafting_all_side_faces_narrows_top_and_keeps_neutral_plane() {
+        let source = cube();
+        let drafted =
+            draft_faces(&source, &options(side_faces(&source), 0.1_f64.atan())).expect("draft");
+
+        drafted.validate_topology().expect("topology");
+        for vertex in &drafted.vertices {
+            let p = vertex.position;
+            let expected = if p.y > 0.0 { 0.8 } else { 1.0 };
+            assert!(
+                (p.x.abs() - expected).abs() < 1.0e-9,
+                "x {} at y {}",
+                p.x,
+                p.y
+            );
+            assert!(
+                (p.z.abs() - expected).abs() < 1.0e-9,
+                "z {} at y {}",
+                p.z,
+                p.y
+            );
+        }
+        for face_id in side_faces(&source) {
+            let normal = drafted.faces[face_id as usize].normal;
+            assert!((normal.y - 0.1_f64.atan().sin()).abs() < 1.0e-9);
+        }
+    }
+
+    #[test]
+    fn drafting_one_wall_batters_it_and_stretches_neighbours() {
+        let source = cube();
+        let front = source
+            .faces
+            .iter()
+            .find(|face| face.normal.x > 0.9)
+            .map(|face| face.id)
+            .expect("+x face");
+        let drafted = draft_faces(&source, &options(vec![front], 0.25_f64.atan())).expect("draft");
+
+        drafted.validate_topology().expect("topology");
+        for vertex in &drafted.vertices {
+            let p = vertex.position;
+            let expected = if p.x > 0.0 && p.y > 0.0 { 0.5 } else { 1.0 };
+            assert!((p.x.abs() - expected).abs() < 1.0e-9);
+            assert!((p.y.abs() - 1.0).abs() < 1.0e-9);
+            assert!((p.z.abs() - 1.0).abs() < 1.0e-9);
+        }
+    }
+
+    #[test]
+    fn draft_rejects_faces_parallel_to_neutral_plane_and_collapse() {
+        let source = cube();
+        let top = source
+            .faces
+            .iter()
+            .find(|face| face.normal.y > 0.9)
+            .map(|face| face.id)
+            .expect("top face");
+        let Err(error) = draft_faces(&source, &options(vec![top], 0.1)) else {
+            panic!("a face parallel to the neutral plane cannot be drafted");
+        };
+        assert_eq!(error.kind(), DraftErrorKind::FaceParallelToNeutralPlane);
+
+        let Err(error) = draft_faces(&source, &options(side_faces(&source), 1.0)) else {
+            panic!("a draft that inverts the top face must fail");
+        };
+        assert_eq!(error.kind(), DraftErrorKind::TopologyCollapse);
+    }
+
+    #[test]
+    fn draft_rejects_every_curved_surface() {
+        let mut source = cube();
+        let face_id = side_faces(&source)[0];
+        for surface in [
+            SurfaceGeometry::Sphere {
+                center: Vector3::new(0.0, 0.0, 0.0),
+                radius: 2.0,
+            },
+            SurfaceGeometry::Cylinder {
+                origin: Vector3::new(0.0, -1.0, 0.0),
+                axis: Vector3::new(0.0, 1.0, 0.0),
+                ref_direction: Vector3::new(1.0, 0.0, 0.0),
+                radius: 2.0,
+                height: 2.0,
+            },
+        ] {
+            source.faces[face_id as usize].surface = Some(surface);
+            let Err(error) = draft_faces(&source, &options(vec![face_id], 0.1)) else {
+                panic!("a curved face cannot be drafted");
+            };
+            assert_eq!(error.kind(), DraftErrorKind::NonPlanarFace);
+        }
+    }
+}
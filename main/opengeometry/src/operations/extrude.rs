@@ -1,5 +1,6 @@
 use super::windingsort;
 use crate::brep::{Brep, BrepBuilder};
+use crate::operations::common::point_in_face;
 use crate::operations::triangulate::compute_polygon_normal;
 use openmaths::Vector3;
 use uuid::Uuid;
@@ -16,31 +17,69 @@ pub enum ExtrudeExtent {
     Symmetric { distance: f64 },
     /// Grows `plus` along the direction and `minus` against it (distinct).
     TwoSided { plus: f64, minus: f64 },
+    /// Grows along the direction until the profile reaches face `face_id` of
+    /// the target passed to [`extrude_profile_loops_up_to`]; the end cap is
+    /// trimmed to that face's plane.
+    UpToFace { face_id: u32 },
+    /// Grows along the direction until the profile reaches the nearest face
+    /// of the target in front of it, trimming the end cap to that face's
+    /// plane.
+    UpToNext,
 }
 
 impl ExtrudeExtent {
-    /// The (base-plane shift, extrusion vector) for a unit `direction`. The
-    /// source cap is built at `profile + shift`; the end cap at `+ vector`.
-    fn resolve(self, direction: Vector3) -> (Vector3, Vector3) {
+    /// Where the end cap goes for a unit `direction`. Up-to extents need
+    /// `target`; the others ignore it.
+    fn end_cap(self, direction: Vector3, target: Option<&Brep>) -> Result<EndCap<'_>, String> {
         let scaled = |t: f64| Vector3::new(direction.x * t, direction.y * t, direction.z * t);
-        match self {
-            ExtrudeExtent::OneSided { distance } => (scaled(0.0), scaled(distance)),
-            ExtrudeExtent::Symmetric { distance } => (scaled(-distance / 2.0), scaled(distance)),
-            ExtrudeExtent::TwoSided { plus, minus } => (scaled(-minus), scaled(plus + minus)),
-        }
-    }
-
-    /// Total span; must be finite and non-zero for a valid extrusion.
-    fn span(self) -> f64 {
-        match self {
-            ExtrudeExtent::OneSided { distance } | ExtrudeExtent::Symmetric { distance } => {
-                distance
+        let up_to_target = || {
+            target.ok_or_else(|| {
+                "Up-to extrude extents need a target BRep; use extrude_profile_loops_up_to"
+                    .to_string()
+            })
+        };
+        let (shift, span) = match self {
+            ExtrudeExtent::OneSided { distance } => (0.0, distance),
+            ExtrudeExtent::Symmetric { distance } => (-distance / 2.0, distance),
+            ExtrudeExtent::TwoSided { plus, minus } => (-minus, plus + minus),
+            ExtrudeExtent::UpToFace { face_id } => {
+                return Ok(EndCap::UpTo(UpToTarget::Face {
+                    target: up_to_target()?,
+                    face_id,
+                }))
             }
-            ExtrudeExtent::TwoSided { plus, minus } => plus + minus,
+            ExtrudeExtent::UpToNext => {
+                return Ok(EndCap::UpTo(UpToTarget::Next {
+                    target: up_to_target()?,
+                }))
+            }
+        };
+        if !span.is_finite() || span.abs() <= EXTRUDE_EPSILON {
+            return Err("Extrude extent must be a finite non-zero distance".to_string());
         }
+        // The source cap is built at `profile + shift`; the end cap a further
+        // `extrusion` along.
+        Ok(EndCap::Fixed {
+            shift: scaled(shift),
+            extrusion: scaled(span),
+        })
     }
 }
 
+/// The target an up-to extent trims its end cap against.
+#[derive(Clone, Copy)]
+enum UpToTarget<'a> {
+    Face { target: &'a Brep, face_id: u32 },
+    Next { target: &'a Brep },
+}
+
+/// Where the end cap of an extrusion goes.
+#[derive(Clone, Copy)]
+enum EndCap<'a> {
+    Fixed { shift: Vector3, extrusion: Vector3 },
+    UpTo(UpToTarget<'a>),
+}
+
 pub fn extrude_profile_loops(
     brep_id: Uuid,
     outer: &[Vector3],
@@ -66,9 +105,58 @@ pub fn extrude_profile_loops_with(
     direction: Vector3,
     extent: ExtrudeExtent,
 ) -> Result<Brep, String> {
-    let span = extent.span();
-    if !span.is_finite() || span.abs() <= EXTRUDE_EPSILON {
-        return Err("Extrude extent must be a finite non-zero distance".to_string());
+    extrude_profile_loops_tapered(brep_id, outer, holes, direction, extent, 0.0)
+}
+
+/// As [`extrude_profile_loops_with`], with drafted side walls.
+///
+/// The walls draft from the start cap: `taper_angle` (radians) offsets the
+/// end cap within its plane by `span * tan(taper_angle)`. Symmetric and
+/// two-sided extents therefore taper across their whole span, and the
+/// profile lies inside the solid rather than on a crease. Positive angles
+/// shrink the end cap (holes grow), negative angles flare it; `0.0` keeps
+/// the walls parallel to `direction`.
+pub fn extrude_profile_loops_tapered(
+    brep_id: Uuid,
+    outer: &[Vector3],
+    holes: &[Vec<Vector3>],
+    direction: Vector3,
+    extent: ExtrudeExtent,
+    taper_angle: f64,
+) -> Result<Brep, String> {
+    extrude_loops(brep_id, outer, holes, direction, extent, None, taper_angle)
+}
+
+/// As [`extrude_profile_loops_with`], with `target` for the
+/// [`ExtrudeExtent::UpToFace`] and [`ExtrudeExtent::UpToNext`] extents. Each
+/// end cap vertex slides along `direction` onto the target face's plane, so
+/// the walls stay parallel to `direction` and the cap may be sloped. Fixed
+/// distance extents ignore `target`. `target` must be expressed in the same
+/// frame as the profile.
+pub fn extrude_profile_loops_up_to(
+    brep_id: Uuid,
+    outer: &[Vector3],
+    holes: &[Vec<Vector3>],
+    direction: Vector3,
+    extent: ExtrudeExtent,
+    target: &Brep,
+) -> Result<Brep, String> {
+    extrude_loops(brep_id, outer, holes, direction, extent, Some(target), 0.0)
+}
+
+fn extrude_loops(
+    brep_id: Uuid,
+    outer: &[Vector3],
+    holes: &[Vec<Vector3>],
+    direction: Vector3,
+    extent: ExtrudeExtent,
+    target: Option<&Brep>,
+    taper_angle: f64,
+) -> Result<Brep, String> {
+    if !taper_angle.is_finite()
+        || taper_angle.abs() >= std::f64::consts::FRAC_PI_2 - EXTRUDE_EPSILON
+    {
+        return Err("Extrude taper angle must be finite and within (-90°, 90°)".to_string());
     }
 
     let dir_len =
@@ -81,7 +169,6 @@ pub fn extrude_profile_loops_with(
         direction.y / dir_len,
         direction.z / dir_len,
     );
-    let (base_shift, extrusion) = extent.resolve(direction);
 
     let outer = sanitize_loop_points(outer);
     if outer.len() < 3 {
@@ -94,39 +181,66 @@ pub fn extrude_profile_loops_with(
         .filter(|hole| hole.len() >= 3)
         .collect();
 
-    // Shift the profile onto the start cap (no-op for one-sided extents).
-    let shift =
-        |p: &Vector3| Vector3::new(p.x + base_shift.x, p.y + base_shift.y, p.z + base_shift.z);
-    let outer: Vec<Vector3> = outer.iter().map(shift).collect();
-    let holes: Vec<Vec<Vector3>> = holes
-        .iter()
-        .map(|hole| hole.iter().map(shift).collect())
-        .collect();
-
     let Some(profile_normal) = compute_polygon_normal(&outer) else {
         return Err("Failed to compute a stable normal from the extrusion profile".to_string());
     };
 
-    let flip_source_face = profile_normal.dot(&extrusion) > 0.0;
+    let (base_shift, extrusion, end_plane) = match extent.end_cap(direction, target)? {
+        EndCap::Fixed { shift, extrusion } => (shift, extrusion, None),
+        EndCap::UpTo(target) => (
+            Vector3::new(0.0, 0.0, 0.0),
+            direction,
+            Some(resolve_up_to_plane(target, &outer, &holes, direction)?),
+        ),
+    };
 
-    let mut builder = BrepBuilder::new(brep_id);
-    let mut source_positions = Vec::new();
+    // The walls draft from the start cap, so only the end cap is inset.
+    let taper = taper_angle.tan();
+    let end_inset = extrusion.dot(&direction).abs() * taper;
 
-    source_positions.extend(outer.iter().copied());
-    for hole in &holes {
-        source_positions.extend(hole.iter().copied());
+    let mut source_loops = Vec::with_capacity(holes.len() + 1);
+    let mut end_loops = Vec::with_capacity(holes.len() + 1);
+    for (index, profile_loop) in std::iter::once(&outer).chain(holes.iter()).enumerate() {
+        let miters = if taper == 0.0 {
+            vec![Vector3::new(0.0, 0.0, 0.0); profile_loop.len()]
+        } else {
+            taper_miters(profile_loop, index > 0)?
+        };
+        let place = |inset: f64, offset: Vector3| -> Vec<Vector3> {
+            profile_loop
+                .iter()
+                .zip(miters.iter())
+                .map(|(point, miter)| {
+                    Vector3::new(
+                        point.x + offset.x + miter.x * inset,
+                        point.y + offset.y + miter.y * inset,
+                        point.z + offset.z + miter.z * inset,
+                    )
+                })
+                .collect()
+        };
+        let end_offset = Vector3::new(
+            base_shift.x + extrusion.x,
+            base_shift.y + extrusion.y,
+            base_shift.z + extrusion.z,
+        );
+        let source_loop = place(0.0, base_shift);
+        let end_loop = match end_plane {
+            Some(plane) => project_loop_onto_plane(profile_loop, direction, plane)?,
+            None => place(end_inset, end_offset),
+        };
+        if taper != 0.0 && loop_collapses(profile_loop, &end_loop) {
+            return Err("Extrude taper angle collapses the profile before the end cap".to_string());
+        }
+        source_loops.push(source_loop);
+        end_loops.push(end_loop);
     }
 
-    let end_positions: Vec<Vector3> = source_positions
-        .iter()
-        .map(|point| {
-            Vector3::new(
-                point.x + extrusion.x,
-                point.y + extrusion.y,
-                point.z + extrusion.z,
-            )
-        })
-        .collect();
+    let flip_source_face = profile_normal.dot(&extrusion) > 0.0;
+
+    let mut builder = BrepBuilder::new(brep_id);
+    let source_positions: Vec<Vector3> = source_loops.iter().flatten().copied().collect();
+    let end_positions: Vec<Vector3> = end_loops.iter().flatten().copied().collect();
 
     let mut all_positions = source_positions.clone();
     all_positions.extend(end_positions.iter().copied());
@@ -229,7 +343,8 @@ pub fn extrude_brep_face_wasm(
 /// Wasm entry point for general extrude. `direction` is `[x,y,z]`; `extent_kind`
 /// is `"one_sided" | "symmetric" | "two_sided"`; `d_plus`/`d_minus` are the
 /// extent distances (`d_minus` ignored unless two-sided, `d_plus` is the
-/// distance for one-sided/symmetric).
+/// distance for one-sided/symmetric). `taper_angle` is the optional wall draft
+/// in radians (see [`extrude_profile_loops_tapered`]).
 #[wasm_bindgen(js_name = extrudeBrepFaceDirectional)]
 pub fn extrude_brep_face_directional_wasm(
     local_brep_serialized: String,
@@ -237,6 +352,7 @@ pub fn extrude_brep_face_directional_wasm(
     extent_kind: String,
     d_plus: f64,
     d_minus: f64,
+    taper_angle: Option<f64>,
 ) -> Result<String, JsValue> {
     if direction.len() != 3 {
         return Err(JsValue::from_str(
@@ -272,7 +388,61 @@ pub fn extrude_brep_face_directional_wasm(
         )
     };
 
-    let extruded = extrude_profile_loops_with(brep.id, &outer, &holes, dir, extent)
+    let extruded = extrude_profile_loops_tapered(
+        brep.id,
+        &outer,
+        &holes,
+        dir,
+        extent,
+        taper_angle.unwrap_or(0.0),
+    )
+    .map_err(|error| JsValue::from_str(&format!("Failed to extrude BRep face: {}", error)))?;
+    extruded.validate_topology().map_err(|error| {
+        JsValue::from_str(&format!("Extruded BRep topology is invalid: {}", error))
+    })?;
+    serde_json::to_string(&extruded).map_err(|error| {
+        JsValue::from_str(&format!("Failed to serialize extruded BRep: {}", error))
+    })
+}
+
+/// Wasm entry point for up-to extrusion along `direction` (`[x,y,z]`). With
+/// `target_face_id` the end cap is trimmed to that face of the target BRep;
+/// without it, to the first target face the profile reaches.
+#[wasm_bindgen(js_name = extrudeBrepFaceUpTo)]
+pub fn extrude_brep_face_up_to_wasm(
+    local_brep_serialized: String,
+    direction: Vec<f64>,
+    target_brep_serialized: String,
+    target_face_id: Option<u32>,
+) -> Result<String, JsValue> {
+    if direction.len() != 3 {
+        return Err(JsValue::from_str(
+            "direction must be a 3-element [x,y,z] array",
+        ));
+    }
+    let dir = Vector3::new(direction[0], direction[1], direction[2]);
+
+    let brep: Brep = serde_json::from_str(&local_brep_serialized).map_err(|error| {
+        JsValue::from_str(&format!("Invalid local BRep JSON payload: {}", error))
+    })?;
+    let target: Brep = serde_json::from_str(&target_brep_serialized).map_err(|error| {
+        JsValue::from_str(&format!("Invalid target BRep JSON payload: {}", error))
+    })?;
+    let extent = match target_face_id {
+        Some(face_id) => ExtrudeExtent::UpToFace { face_id },
+        None => ExtrudeExtent::UpToNext,
+    };
+
+    let (outer, holes) = if let Some(face) = brep.faces.first() {
+        brep.get_vertices_and_holes_by_face_id(face.id)
+    } else {
+        (
+            windingsort::ccw_test(brep.get_flattened_vertices()),
+            Vec::new(),
+        )
+    };
+
+    let extruded = extrude_profile_loops_up_to(brep.id, &outer, &holes, dir, extent, &target)
         .map_err(|error| JsValue::from_str(&format!("Failed to extrude BRep face: {}", error)))?;
     extruded.validate_topology().map_err(|error| {
         JsValue::from_str(&format!("Extruded BRep topology is invalid: {}", error))
@@ -352,6 +522,147 @@ fn add_side_faces(
     Ok(())
 }
 
+/// The plane `(unit normal, n·x)` an up-to extrusion trims its end cap to:
+/// the chosen target face for [`ExtrudeExtent::UpToFace`], or the nearest
+/// target face hit by a ray from the profile for [`ExtrudeExtent::UpToNext`].
+/// Rays start at every profile vertex and at the outer loop's centroid.
+fn resolve_up_to_plane(
+    target: UpToTarget<'_>,
+    outer: &[Vector3],
+    holes: &[Vec<Vector3>],
+    direction: Vector3,
+) -> Result<(Vector3, f64), String> {
+    let (target, candidates): (&Brep, Vec<u32>) = match target {
+        UpToTarget::Face { target, face_id } => {
+            if face_id as usize >= target.faces.len() {
+                return Err(format!("Extrude target face {} does not exist", face_id));
+            }
+            (target, vec![face_id])
+        }
+        UpToTarget::Next { target } => (target, target.faces.iter().map(|face| face.id).collect()),
+    };
+
+    let count = outer.len() as f64;
+    let centroid = outer.iter().fold(Vector3::new(0.0, 0.0, 0.0), |sum, p| {
+        Vector3::new(
+            sum.x + p.x / count,
+            sum.y + p.y / count,
+            sum.z + p.z / count,
+        )
+    });
+    let ray_origins: Vec<Vector3> = outer
+        .iter()
+        .chain(holes.iter().flatten())
+        .copied()
+        .chain(std::iter::once(centroid))
+        .collect();
+
+    let mut nearest: Option<(f64, Vector3, f64)> = None;
+    for face_id in candidates {
+        let (face_outer, face_holes) = target.get_vertices_and_holes_by_face_id(face_id);
+        let Some(normal) = compute_polygon_normal(&face_outer) else {
+            continue;
+        };
+        let constant = normal.dot(&face_outer[0]);
+        let facing = normal.dot(&direction);
+        if facing.abs() <= EXTRUDE_EPSILON {
+            continue;
+        }
+        for origin in &ray_origins {
+            let distance = (constant - normal.dot(origin)) / facing;
+            if distance <= EXTRUDE_EPSILON || nearest.is_some_and(|(best, _, _)| distance >= best) {
+                continue;
+            }
+            let hit = Vector3::new(
+                origin.x + direction.x * distance,
+                origin.y + direction.y * distance,
+                origin.z + direction.z * distance,
+            );
+            if point_in_face(hit, normal, &face_outer, &face_holes) {
+                nearest = Some((distance, normal, constant));
+            }
+        }
+    }
+
+    nearest
+        .map(|(_, normal, constant)| (normal, constant))
+        .ok_or_else(|| "Extrude profile does not reach the up-to target".to_string())
+}
+
+/// Slides each loop vertex along `direction` onto `plane`. Every vertex must
+/// land strictly in front of the profile, otherwise the target plane cuts
+/// through the profile and no single end cap exists.
+fn project_loop_onto_plane(
+    profile_loop: &[Vector3],
+    direction: Vector3,
+    (normal, constant): (Vector3, f64),
+) -> Result<Vec<Vector3>, String> {
+    let facing = normal.dot(&direction);
+    profile_loop
+        .iter()
+        .map(|point| {
+            let distance = (constant - normal.dot(point)) / facing;
+            if !distance.is_finite() || distance <= EXTRUDE_EPSILON {
+                return Err("Extrude up-to target plane intersects the profile".to_string());
+            }
+            Ok(Vector3::new(
+                point.x + direction.x * distance,
+                point.y + direction.y * distance,
+                point.z + direction.z * distance,
+            ))
+        })
+        .collect()
+}
+
+/// Per-vertex miter vectors that move a profile loop into the material by a
+/// unit perpendicular distance: toward the loop interior for the outer loop,
+/// away from it for holes.
+fn taper_miters(profile_loop: &[Vector3], is_hole: bool) -> Result<Vec<Vector3>, String> {
+    let Some(loop_normal) = compute_polygon_normal(profile_loop) else {
+        return Err("Failed to compute a stable normal for a tapered profile loop".to_string());
+    };
+    let side = if is_hole { -1.0 } else { 1.0 };
+    let count = profile_loop.len();
+    let edge_inward = |index: usize| -> Vector3 {
+        let edge = vector_difference(profile_loop[(index + 1) % count], profile_loop[index]);
+        let inward = loop_normal.cross(&edge);
+        let length = inward.dot(&inward).sqrt().max(EXTRUDE_EPSILON);
+        Vector3::new(
+            side * inward.x / length,
+            side * inward.y / length,
+            side * inward.z / length,
+        )
+    };
+
+    (0..count)
+        .map(|index| {
+            let previous = edge_inward((index + count - 1) % count);
+            let next = edge_inward(index);
+            let denominator = 1.0 + previous.dot(&next);
+            if denominator <= EXTRUDE_EPSILON {
+                return Err("Tapered profile has a degenerate fold-back vertex".to_string());
+            }
+            Ok(Vector3::new(
+                (previous.x + next.x) / denominator,
+                (previous.y + next.y) / denominator,
+                (previous.z + next.z) / denominator,
+            ))
+        })
+        .collect()
+}
+
+/// A tapered loop has collapsed when any of its edges shrank to nothing or
+/// flipped relative to the profile edge it was offset from.
+fn loop_collapses(profile_loop: &[Vector3], tapered_loop: &[Vector3]) -> bool {
+    let count = profile_loop.len();
+    (0..count).any(|index| {
+        let next = (index + 1) % count;
+        let profile_edge = vector_difference(profile_loop[next], profile_loop[index]);
+        let tapered_edge = vector_difference(tapered_loop[next], tapered_loop[index]);
+        tapered_edge.dot(&profile_edge) <= EXTRUDE_EPSILON * profile_edge.dot(&profile_edge).sqrt()
+    })
+}
+
 fn vector_difference(lhs: Vector3, rhs: Vector3) -> Vector3 {
     Vector3::new(lhs.x - rhs.x, lhs.y - rhs.y, lhs.z - rhs.z)
 }
@@ -584,4 +895,198 @@ mod tests {
 
         assert!(error.contains("finite non-zero"));
     }
+
+    #[test]
+    fn tapered_extrude_shrinks_end_cap_and_grows_holes() {
+        let outer = rectangle(-1.0, -1.0, 1.0, 1.0);
+        let hole = rectangle(-0.25, -0.25, 0.25, 0.25)
+            .into_iter()
+            .rev()
+            .collect::<Vec<_>>();
+        let brep = extrude_profile_loops_tapered(
+            Uuid::new_v4(),
+            &outer,
+            &[hole],
+            Vector3::new(0.0, 1.0, 0.0),
+            ExtrudeExtent::OneSided { distance: 1.0 },
+            0.25_f64.atan(),
+        )
+        .expect("tapered extrude");
+        brep.validate_topology().expect("topology");
+
+        let top: Vec<Vector3> = brep
+            .vertices
+            .iter()
+            .map(|v| v.position)
+            .filter(|p| (p.y - 1.0).abs() < 1.0e-9)
+            .collect();
+        let bottom_max_x = brep
+            .vertices
+            .iter()
+            .filter(|v| v.position.y.abs() < 1.0e-9)
+            .fold(f64::MIN, |hi, v| hi.max(v.position.x));
+        let top_max_x = top.iter().fold(f64::MIN, |hi, p| hi.max(p.x));
+        let top_hole_x = top
+            .iter()
+            .map(|p| p.x.abs())
+            .fold(f64::MAX, |lo, x| lo.min(x));
+
+        assert!((bottom_max_x - 1.0).abs() < 1.0e-9);
+        assert!((top_max_x - 0.75).abs() < 1.0e-9, "got {}", top_max_x);
+        assert!((top_hole_x - 0.5).abs() < 1.0e-9, "got {}", top_hole_x);
+    }
+
+    #[test]
+    fn symmetric_taper_drafts_from_the_start_cap() {
+        let square = rectangle(-1.0, -1.0, 1.0, 1.0);
+        let brep = extrude_profile_loops_tapered(
+            Uuid::new_v4(),
+            &square,
+            &[],
+            Vector3::new(0.0, 1.0, 0.0),
+            ExtrudeExtent::Symmetric { distance: 2.0 },
+            -(0.5_f64.atan()),
+        )
+        .expect("flared symmetric extrude");
+        brep.validate_topology().expect("topology");
+
+        // The start cap keeps the profile's size and the end cap flares by
+        // the full 2.0 span, so the walls slope through the profile plane.
+        for vertex in &brep.vertices {
+            let p = vertex.position;
+            let expected = if p.y < 0.0 { 1.0 } else { 2.0 };
+            assert!((p.y.abs() - 1.0).abs() < 1.0e-9);
+            assert!((p.x.abs() - expected).abs() < 1.0e-9, "got {}", p.x);
+        }
+    }
+
+    #[test]
+    fn taper_that_collapses_the_profile_is_rejected() {
+        let square = rectangle(-1.0, -1.0, 1.0, 1.0);
+        let error = extrude_profile_loops_tapered(
+            Uuid::new_v4(),
+            &square,
+            &[],
+            Vector3::new(0.0, 1.0, 0.0),
+            ExtrudeExtent::OneSided { distance: 2.0 },
+            45.0_f64.to_radians(),
+        )
+        .err()
+        .expect("collapsing taper should fail");
+
+        assert!(error.contains("collapses"));
+    }
+
+    fn sloped_slab() -> Brep {
+        // A single slab face rising along +X: y = 2 + 0.5x, facing down.
+        let slab = vec![
+            Vector3::new(-3.0, 0.5, -3.0),
+            Vector3::new(-3.0, 0.5, 3.0),
+            Vector3::new(3.0, 3.5, 3.0),
+            Vector3::new(3.0, 3.5, -3.0),
+        ];
+        build_face_brep(&slab, &[])
+    }
+
+    #[test]
+    fn up_to_face_trims_end_cap_to_sloped_target() {
+        let column = rectangle(-0.5, -0.5, 0.5, 0.5);
+        let target = sloped_slab();
+        let brep = extrude_profile_loops_up_to(
+            Uuid::new_v4(),
+            &column,
+            &[],
+            Vector3::new(0.0, 1.0, 0.0),
+            ExtrudeExtent::UpToFace { face_id: 0 },
+            &target,
+        )
+        .expect("up to face extrude");
+        brep.validate_topology().expect("topology");
+
+        let top: Vec<Vector3> = brep.vertices[4..].iter().map(|v| v.position).collect();
+        assert_eq!(top.len(), 4);
+        for p in top {
+            assert!(
+                (p.y - (2.0 + 0.5 * p.x)).abs() < 1.0e-9,
+                "on slab, got {}",
+                p.y
+            );
+        }
+    }
+
+    #[test]
+    fn up_to_next_stops_at_nearest_target_face() {
+        let column = rectangle(-0.5, -0.5, 0.5, 0.5);
+        let target = extrude_profile_loops(
+            Uuid::new_v4(),
+            &rectangle(-2.0, -2.0, 2.0, 2.0)
+                .into_iter()
+                .map(|p| Vector3::new(p.x, 3.0, p.z))
+                .collect::<Vec<_>>(),
+            &[],
+            1.0,
+        )
+        .expect("target block");
+
+        let brep = extrude_profile_loops_up_to(
+            Uuid::new_v4(),
+            &column,
+            &[],
+            Vector3::new(0.0, 1.0, 0.0),
+            ExtrudeExtent::UpToNext,
+            &target,
+        )
+        .expect("up to next extrude");
+        brep.validate_topology().expect("topology");
+
+        let max_y = brep
+            .vertices
+            .iter()
+            .fold(f64::MIN, |hi, v| hi.max(v.position.y));
+        assert!(
+            (max_y - 3.0).abs() < 1.0e-9,
+            "stops at underside, got {}",
+            max_y
+        );
+    }
+
+    #[test]
+    fn up_to_extents_report_missed_targets() {
+        let column = rectangle(-0.5, -0.5, 0.5, 0.5);
+        let target = sloped_slab();
+        let error = extrude_profile_loops_up_to(
+            Uuid::new_v4(),
+            &column,
+            &[],
+            Vector3::new(0.0, -1.0, 0.0),
+            ExtrudeExtent::UpToNext,
+            &target,
+        )
+        .err()
+        .expect("target behind the profile");
+        assert!(error.contains("does not reach"));
+
+        let error = extrude_profile_loops_up_to(
+            Uuid::new_v4(),
+            &column,
+            &[],
+            Vector3::new(0.0, 1.0, 0.0),
+            ExtrudeExtent::UpToFace { face_id: 7 },
+            &target,
+        )
+        .err()
+        .expect("missing target face");
+        assert!(error.contains("does not exist"));
+
+        let error = extrude_profile_loops_with(
+            Uuid::new_v4(),
+            &column,
+            &[],
+            Vector3::new(0.0, 1.0, 0.0),
+            ExtrudeExtent::UpToNext,
+        )
+        .err()
+        .expect("up-to extent without a target");
+        assert!(error.contains("need a target"));
+    }
 }
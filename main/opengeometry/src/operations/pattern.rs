@@ -0,0 +1,801 @@
+//! Pattern operations: repeat a solid linearly, on a 2D grid, about an axis,
+//! or across a mirror plane, producing a single `Brep`.
+//!
+//! Instances are either kept as separate shells of one B-rep or fused into
+//! one solid with [`batch_union`]. Either way the output records, for every
+//! face, which instance (and which source face) it came from. Fused faces are
+//! traced back geometrically, since the boolean pipeline rebuilds topology.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::booleans::{batch_union, OGBooleanOptions as BooleanOptions};
+use crate::brep::{Brep, BrepBuilder, BrepError, CurveGeometry};
+use crate::operations::common::{add, face_loops, point_in_face, scale, sub};
+use crate::operations::triangulate::triangulate_polygon_with_holes;
+use openmaths::Vector3;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+const EPSILON: f64 = crate::tolerance::MODELING_TOLERANCE_FLOOR;
+const FULL_TURN_TOLERANCE: f64 = 1.0e-9;
+
+/// How instances are laid out. Instance 0 is always the untransformed source.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum PatternKind {
+    /// `count` instances spaced `spacing` apart along `direction`.
+    Linear {
+        direction: Vector3,
+        spacing: f64,
+        count: u32,
+    },
+    /// A `first_count × second_count` grid; instances are numbered row-major
+    /// with the second direction varying fastest.
+    Grid {
+        first_direction: Vector3,
+        first_spacing: f64,
+        first_count: u32,
+        second_direction: Vector3,
+        second_spacing: f64,
+        second_count: u32,
+    },
+    /// `count` instances rotated about the axis through `axis_origin`. A full
+    /// `total_angle` (2π) spaces them evenly around the circle; a partial one
+    /// places the last instance at `total_angle`.
+    Polar {
+        axis_origin: Vector3,
+        axis_direction: Vector3,
+        count: u32,
+        total_angle: f64,
+    },
+    /// The source plus its reflection across the plane through `plane_origin`.
+    Mirror {
+        plane_origin: Vector3,
+        plane_normal: Vector3,
+    },
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PatternOptions {
+    /// Union all instances into one solid instead of keeping one shell each.
+    pub fuse: bool,
+    /// Options forwarded to the union when `fuse` is set.
+    pub boolean: BooleanOptions,
+}
+
+/// Where an output face came from. `instance` is `None` only for a fused face
+/// that could not be matched to any instance face.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatternFaceOrigin {
+    pub face_id: u32,
+    pub instance: Option<u32>,
+    pub source_face_id: Option<u32>,
+}
+
+#[derive(Clone)]
+pub struct PatternOutput {
+    pub brep: Brep,
+    pub instance_count: u32,
+    pub fused: bool,
+    pub face_origins: Vec<PatternFaceOrigin>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternErrorKind {
+    InvalidParameter,
+    InvalidSource,
+    FuseFailed,
+    TopologyError,
+}
+
+#[derive(Debug, Clone)]
+pub struct PatternError {
+    kind: PatternErrorKind,
+    message: String,
+}
+
+impl PatternError {
+    fn new(kind: PatternErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    pub fn kind(&self) -> PatternErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for PatternError {}
+
+impl From<BrepError> for PatternError {
+    fn from(error: BrepError) -> Self {
+        PatternError::new(
+            PatternErrorKind::TopologyError,
+            format!("BRep construction failed: {}", error),
+        )
+    }
+}
+
+/// Rigid (or reflecting) placement of one instance: `x' = linear·x + offset`.
+#[derive(Clone, Copy)]
+struct InstanceTransform {
+    linear: [[f64; 3]; 3],
+    offset: Vector3,
+}
+
+impl InstanceTransform {
+    fn identity() -> Self {
+        Self::translation(Vector3::new(0.0, 0.0, 0.0))
+    }
+
+    fn translation(offset: Vector3) -> Self {
+        Self {
+            linear: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            offset,
+        }
+    }
+
+    /// Rotation by `angle` about the axis through `origin` (Rodrigues).
+    fn rotation(origin: Vector3, axis: Vector3, angle: f64) -> Self {
+        let (s, c) = angle.sin_cos();
+        let t = 1.0 - c;
+        let (x, y, z) = (axis.x, axis.y, axis.z);
+        let linear = [
+            [t * x * x + c, t * x * y - s * z, t * x * z + s * y],
+            [t * x * y + s * z, t * y * y + c, t * y * z - s * x],
+            [t * x * z - s * y, t * y * z + s * x, t * z * z + c],
+        ];
+        Self::about_point(linear, origin)
+    }
+
+    /// Reflection across the plane through `origin` with unit `normal`.
+    fn reflection(origin: Vector3, normal: Vector3) -> Self {
+        let n = [normal.x, normal.y, normal.z];
+        let mut linear = [[0.0; 3]; 3];
+        for (row, values) in linear.iter_mut().enumerate() {
+            for (col, value) in values.iter_mut().enumerate() {
+                let identity = if row == col { 1.0 } else { 0.0 };
+                *value = identity - 2.0 * n[row] * n[col];
+            }
+        }
+        Self::about_point(linear, origin)
+    }
+
+    fn about_point(linear: [[f64; 3]; 3], origin: Vector3) -> Self {
+        let moved = apply_linear(&linear, origin);
+        Self {
+            linear,
+            offset: Vector3::new(origin.x - moved.x, origin.y - moved.y, origin.z - moved.z),
+        }
+    }
+
+    fn apply(&self, point: Vector3) -> Vector3 {
+        let p = apply_linear(&self.linear, point);
+        Vector3::new(
+            p.x + self.offset.x,
+            p.y + self.offset.y,
+            p.z + self.offset.z,
+        )
+    }
+
+    fn is_reflection(&self) -> bool {
+        let m = &self.linear;
+        let determinant = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+        determinant < 0.0
+    }
+}
+
+fn apply_linear(m: &[[f64; 3]; 3], p: Vector3) -> Vector3 {
+    Vector3::new(
+        m[0][0] * p.x + m[0][1] * p.y + m[0][2] * p.z,
+        m[1][0] * p.x + m[1][1] * p.y + m[1][2] * p.z,
+        m[2][0] * p.x + m[2][1] * p.y + m[2][2] * p.z,
+    )
+}
+
+/// Lays out `source` according to `kind` and returns one B-rep holding every
+/// instance, fused when [`PatternOptions::fuse`] is set.
+pub fn pattern_brep(
+    source: &Brep,
+    kind: &PatternKind,
+    options: &PatternOptions,
+) -> Result<PatternOutput, PatternError> {
+    if source.faces.is_empty() {
+        return Err(PatternError::new(
+            PatternErrorKind::InvalidSource,
+            "Pattern source has no faces",
+        ));
+    }
+
+    let transforms = instance_transforms(kind)?;
+    let instances: Vec<Brep> = transforms
+        .iter()
+        .map(|transform| transform_instance(source, transform))
+        .collect::<Result<_, _>>()?;
+    let instance_count = instances.len() as u32;
+
+    if options.fuse && instances.len() > 1 {
+        let fused = batch_union(&instances, options.boolean.clone(), true).map_err(|error| {
+            PatternError::new(
+                PatternErrorKind::FuseFailed,
+                format!("Failed to fuse pattern instances: {}", error),
+            )
+        })?;
+        let face_origins = trace_fused_faces(&fused.brep, &instances);
+        return Ok(PatternOutput {
+            brep: fused.brep,
+            instance_count,
+            fused: true,
+            face_origins,
+        });
+    }
+
+    let (brep, face_origins) = combine_instances(source, &instances)?;
+    Ok(PatternOutput {
+        brep,
+        instance_count,
+        fused: false,
+        face_origins,
+    })
+}
+
+fn instance_transforms(kind: &PatternKind) -> Result<Vec<InstanceTransform>, PatternError> {
+    match kind {
+        PatternKind::Linear {
+            direction,
+            spacing,
+            count,
+        } => {
+            let step = scaled_step(*direction, *spacing, *count, "Linear")?;
+            Ok((0..*count)
+                .map(|index| InstanceTransform::translation(scale(step, index as f64)))
+                .collect())
+        }
+        PatternKind::Grid {
+            first_direction,
+            first_spacing,
+            first_count,
+            second_direction,
+            second_spacing,
+            second_count,
+        } => {
+            let first = scaled_step(*first_direction, *first_spacing, *first_count, "Grid")?;
+            let second = scaled_step(*second_direction, *second_spacing, *second_count, "Grid")?;
+            let mut transforms = Vec::with_capacity((*first_count * *second_count) as usize);
+            for row in 0..*first_count {
+                for col in 0..*second_count {
+                    let offset = add(scale(first, row as f64), scale(second, col as f64));
+                    transforms.push(InstanceTransform::translation(offset));
+                }
+            }
+            Ok(transforms)
+        }
+        PatternKind::Polar {
+            axis_origin,
+            axis_direction,
+            count,
+            total_angle,
+        } => {
+            let axis = unit(*axis_direction, "Polar pattern axis")?;
+            if *count == 0 || !total_angle.is_finite() || total_angle.abs() <= EPSILON {
+                return Err(PatternError::new(
+                    PatternErrorKind::InvalidParameter,
+                    "Polar pattern needs a positive count and a non-zero finite angle",
+                ));
+            }
+            let full_turn = total_angle.abs() >= 2.0 * PI - FULL_TURN_TOLERANCE;
+            let step = if full_turn || *count == 1 {
+                total_angle / *count as f64
+            } else {
+                total_angle / (*count - 1) as f64
+            };
+            Ok((0..*count)
+                .map(|index| {
+                    if index == 0 {
+                        InstanceTransform::identity()
+                    } else {
+                        InstanceTransform::rotation(*axis_origin, axis, step * index as f64)
+                    }
+                })
+                .collect())
+        }
+        PatternKind::Mirror {
+            plane_origin,
+            plane_normal,
+        } => {
+            let normal = unit(*plane_normal, "Mirror plane normal")?;
+            Ok(vec![
+                InstanceTransform::identity(),
+                InstanceTransform::reflection(*plane_origin, normal),
+            ])
+        }
+    }
+}
+
+fn scaled_step(
+    direction: Vector3,
+    spacing: f64,
+    count: u32,
+    label: &str,
+) -> Result<Vector3, PatternError> {
+    if count == 0 || !spacing.is_finite() {
+        return Err(PatternError::new(
+            PatternErrorKind::InvalidParameter,
+            format!(
+                "{} pattern needs a positive count and a finite spacing",
+                label
+            ),
+        ));
+    }
+    Ok(scale(unit(direction, label)?, spacing))
+}
+
+/// Copies `source` through `transform`. Reflections reverse every loop so
+/// faces keep pointing out of the material, and flip circle normals so arcs
+/// keep sweeping the same way.
+fn transform_instance(source: &Brep, transform: &InstanceTransform) -> Result<Brep, PatternError> {
+    let reflect = transform.is_reflection();
+    let mut builder = BrepBuilder::new(uuid::Uuid::new_v4());
+    let positions: Vec<Vector3> = source
+        .vertices
+        .iter()
+        .map(|vertex| transform.apply(vertex.position))
+        .collect();
+    builder.add_vertices(&positions);
+
+    let orient = |ids: Vec<u32>| -> Vec<u32> {
+        if reflect {
+            ids.into_iter().rev().collect()
+        } else {
+            ids
+        }
+    };
+    for face in &source.faces {
+        let outer = orient(source.get_loop_vertex_indices(face.outer_loop));
+        let holes: Vec<Vec<u32>> = face
+            .inner_loops
+            .iter()
+            .map(|loop_id| orient(source.get_loop_vertex_indices(*loop_id)))
+            .collect();
+        builder.add_face(&outer, &holes)?;
+    }
+    for wire in &source.wires {
+        builder.add_wire(&source.get_wire_vertex_indices(wire.id), wire.is_closed)?;
+    }
+    for shell in &source.shells {
+        builder.add_shell(&shell.faces, shell.is_closed)?;
+    }
+
+    let mut instance = builder.build()?;
+    let map_point = |point: Vector3| transform.apply(point);
+    for (face, source_face) in instance.faces.iter_mut().zip(source.faces.iter()) {
+        face.surface = source_face
+            .surface
+            .as_ref()
+            .map(|surface| surface.transformed_with(&map_point, 1.0));
+    }
+
+    let mut curves: HashMap<(u32, u32), CurveGeometry> = HashMap::new();
+    for edge in &source.edges {
+        if let (Some(curve), Some((from, to))) =
+            (edge.curve.as_ref(), source.get_edge_endpoints(edge.id))
+        {
+            curves.insert((from.min(to), from.max(to)), curve.clone());
+        }
+    }
+    for index in 0..instance.edges.len() {
+        let Some((from, to)) = instance.get_edge_endpoints(index as u32) else {
+            continue;
+        };
+        let Some(curve) = curves.get(&(from.min(to), from.max(to))) else {
+            continue;
+        };
+        let curve = match curve.transformed_with(&map_point, 1.0) {
+            CurveGeometry::Circle {
+                center,
+                normal,
+                x_axis,
+                radius,
+                start_angle,
+                end_angle,
+            } if reflect => CurveGeometry::Circle {
+                center,
+                normal: scale(normal, -1.0),
+                x_axis,
+                radius,
+                start_angle,
+                end_angle,
+            },
+            curve => curve,
+        };
+        instance.edges[index].curve = Some(curve);
+    }
+
+    Ok(instance)
+}
+
+/// Packs unfused instances into one B-rep, one shell per source shell per
+/// instance, keeping per-instance face order.
+fn combine_instances(
+    source: &Brep,
+    instances: &[Brep],
+) -> Result<(Brep, Vec<PatternFaceOrigin>), PatternError> {
+    let mut builder = BrepBuilder::new(source.id);
+    let mut face_origins = Vec::new();
+    let mut surfaces = Vec::new();
+    let mut curves: HashMap<(u32, u32), CurveGeometry> = HashMap::new();
+
+    for (instance_index, instance) in instances.iter().enumerate() {
+        let positions: Vec<Vector3> = instance.vertices.iter().map(|v| v.position).collect();
+        let base = builder
+            .add_vertices(&positions)
+            .first()
+            .copied()
+            .unwrap_or(0);
+
+        let mut face_ids = Vec::with_capacity(instance.faces.len());
+        for face in &instance.faces {
+            let (outer, holes) = face_loops(instance, face.id);
+            let outer: Vec<u32> = outer.iter().map(|id| id + base).collect();
+            let holes: Vec<Vec<u32>> = holes
+                .iter()
+                .map(|hole| hole.iter().map(|id| id + base).collect())
+                .collect();
+            let face_id = builder.add_face(&outer, &holes)?;
+            face_ids.push(face_id);
+            surfaces.push((face_id, face.surface.clone()));
+            face_origins.push(PatternFaceOrigin {
+                face_id,
+                instance: Some(instance_index as u32),
+                source_face_id: Some(face.id),
+            });
+        }
+        for wire in &instance.wires {
+            let ids: Vec<u32> = instance
+                .get_wire_vertex_indices(wire.id)
+                .iter()
+                .map(|id| id + base)
+                .collect();
+            builder.add_wire(&ids, wire.is_closed)?;
+        }
+        for shell in &instance.shells {
+            let faces: Vec<u32> = shell
+                .faces
+                .iter()
+                .map(|face_id| face_ids[*face_id as usize])
+                .collect();
+            builder.add_shell(&faces, shell.is_closed)?;
+        }
+        for edge in &instance.edges {
+            if let (Some(curve), Some((from, to))) =
+                (edge.curve.as_ref(), instance.get_edge_endpoints(edge.id))
+            {
+                let (from, to) = (from + base, to + base);
+                curves.insert((from.min(to), from.max(to)), curve.clone());
+            }
+        }
+    }
+
+    let mut brep = builder.build()?;
+    for (face_id, surface) in surfaces {
+        brep.faces[face_id as usize].surface = surface;
+    }
+    for index in 0..brep.edges.len() {
+        if let Some((from, to)) = brep.get_edge_endpoints(index as u32) {
+            brep.edges[index].curve = curves.get(&(from.min(to), from.max(to))).cloned();
+        }
+    }
+    Ok((brep, face_origins))
+}
+
+/// Matches every fused face to the first instance face that contains an
+/// interior sample point of it and shares its orientation.
+fn trace_fused_faces(fused: &Brep, instances: &[Brep]) -> Vec<PatternFaceOrigin> {
+    let tolerance = BooleanOptions::default()
+        .resolve_tolerance_many(&instances.iter().collect::<Vec<_>>())
+        .max(1.0e-7);
+
+    fused
+        .faces
+        .iter()
+        .map(|face| {
+            let origin = face_sample_point(fused, face.id).and_then(|sample| {
+                instances.iter().enumerate().find_map(|(index, instance)| {
+                    instance
+                        .faces
+                        .iter()
+                        .find(|candidate| {
+                            candidate.normal.dot(&face.normal) > 1.0 - 1.0e-6
+                                && point_on_face(instance, candidate.id, sample, tolerance)
+                        })
+                        .map(|candidate| (index as u32, candidate.id))
+                })
+            });
+            PatternFaceOrigin {
+                face_id: face.id,
+                instance: origin.map(|(instance, _)| instance),
+                source_face_id: origin.map(|(_, face_id)| face_id),
+            }
+        })
+        .collect()
+}
+
+/// Centroid of the face's largest triangle: strictly inside the face even for
+/// concave outlines and faces with holes.
+fn face_sample_point(brep: &Brep, face_id: u32) -> Option<Vector3> {
+    let (outer, holes) = brep.get_vertices_and_holes_by_face_id(face_id);
+    let mut points = outer.clone();
+    for hole in &holes {
+        points.extend(hole.iter().copied());
+    }
+    triangulate_polygon_with_holes(&outer, &holes)
+        .into_iter()
+        .map(|[a, b, c]| (points[a], points[b], points[c]))
+        .max_by(|lhs, rhs| triangle_area(lhs).total_cmp(&triangle_area(rhs)))
+        .map(|(a, b, c)| scale(add(add(a, b), c), 1.0 / 3.0))
+}
+
+fn triangle_area((a, b, c): &(Vector3, Vector3, Vector3)) -> f64 {
+    let cross = sub(*b, *a).cross(&sub(*c, *a));
+    cross.dot(&cross).sqrt()
+}
+
+fn point_on_face(brep: &Brep, face_id: u32, point: Vector3, tolerance: f64) -> bool {
+    let (outer, holes) = brep.get_vertices_and_holes_by_face_id(face_id);
+    let Some(anchor) = outer.first() else {
+        return false;
+    };
+    let normal = brep.faces[face_id as usize].normal;
+    if normal.dot(&sub(point, *anchor)).abs() > tolerance {
+        return false;
+    }
+
+    point_in_face(point, normal, &outer, &holes)
+}
+
+fn unit(v: Vector3, label: &str) -> Result<Vector3, PatternError> {
+    let length = v.dot(&v).sqrt();
+    if !length.is_finite() || length <= EPSILON {
+        return Err(PatternError::new(
+            PatternErrorKind::InvalidParameter,
+            format!("{} must be a non-zero vector", label),
+        ));
+    }
+    Ok(scale(v, 1.0 / length))
+}
+
+#[derive(Serialize)]
+struct PatternResultPayload<'a> {
+    brep: &'a Brep,
+    instance_count: u32,
+    fused: bool,
+    face_origins: &'a [PatternFaceOrigin],
+}
+
+/// Wasm entry point for [`pattern_brep`]. `pattern_json` is a tagged
+/// [`PatternKind`] (e.g. `{"type":"Linear","direction":{..},"spacing":2,
+/// "count":4}`); `options_json` is an optional [`PatternOptions`]. Returns
+/// `{ brep, instance_count, fused, face_origins }` as JSON.
+#[wasm_bindgen(js_name = patternBrep)]
+pub fn pattern_brep_wasm(
+    local_brep_serialized: String,
+    pattern_json: String,
+    options_json: Option<String>,
+) -> Result<String, JsValue> {
+    let source: Brep = serde_json::from_str(&local_brep_serialized).map_err(|error| {
+        JsValue::from_str(&format!("Invalid local BRep JSON payload: {}", error))
+    })?;
+    let kind: PatternKind = serde_json::from_str(&pattern_json)
+        .map_err(|error| JsValue::from_str(&format!("Invalid pattern JSON payload: {}", error)))?;
+    let options: PatternOptions = match options_json {
+        Some(payload) if !payload.trim().is_empty() => {
+            serde_json::from_str(&payload).map_err(|error| {
+                JsValue::from_str(&format!("Invalid pattern options JSON payload: {}", error))
+            })?
+        }
+        _ => PatternOptions::default(),
+    };
+
+    let output = pattern_brep(&source, &kind, &options)
+        .map_err(|error| JsValue::from_str(&format!("Failed to pattern BRep: {}", error)))?;
+    output.brep.validate_topology().map_err(|error| {
+        JsValue::from_str(&format!("Patterned BRep topology is invalid: {}", error))
+    })?;
+    serde_json::to_string(&PatternResultPayload {
+        brep: &output.brep,
+        instance_count: output.instance_count,
+        fused: output.fused,
+        face_origins: &output.face_origins,
+    })
+    .map_err(|error| JsValue::from_str(&format!("Failed to serialize pattern result: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::cuboid::OGCuboid;
+    use crate::primitives::cylinder::OGCylinder;
+
+    fn cube(size: f64) -> Brep {
+        let mut cuboid = OGCuboid::new("pattern-cuboid".to_string());
+        cuboid
+            .set_config(Vector3::new(0.0, 0.0, 0.0), size, size, size)
+            .expect("cuboid config");
+        cuboid.brep().clone()
+    }
+
+    fn x_bounds(brep: &Brep) -> (f64, f64) {
+        brep.vertices
+            .iter()
+            .fold((f64::MAX, f64::MIN), |(lo, hi), v| {
+                (lo.min(v.position.x), hi.max(v.position.x))
+            })
+    }
+
+    #[test]
+    fn linear_pattern_keeps_instances_as_separate_shells() {
+        let source = cube(1.0);
+        let kind = PatternKind::Linear {
+            direction: Vector3::new(1.0, 0.0, 0.0),
+            spacing: 2.0,
+            count: 3,
+        };
+        let output =
+            pattern_brep(&source, &kind, &PatternOptions::default()).expect("linear pattern");
+
+        output.brep.validate_topology().expect("topology");
+        assert_eq!(output.instance_count, 3);
+        assert_eq!(output.brep.shells.len(), 3);
+        assert_eq!(output.brep.faces.len(), 18);
+        let (min_x, max_x) = x_bounds(&output.brep);
+        assert!((min_x + 0.5).abs() < 1.0e-9 && (max_x - 4.5).abs() < 1.0e-9);
+        assert_eq!(output.face_origins[7].instance, Some(1));
+        assert_eq!(output.face_origins[7].source_face_id, Some(1));
+    }
+
+    #[test]
+    fn fused_grid_pattern_traces_faces_to_instances() {
+        let source = cube(1.0);
+        let kind = PatternKind::Grid {
+            first_direction: Vector3::new(1.0, 0.0, 0.0),
+            first_spacing: 0.75,
+            first_count: 2,
+            second_direction: Vector3::new(0.0, 0.0, 1.0),
+            second_spacing: 3.0,
+            second_count: 2,
+        };
+        let options = PatternOptions {
+            fuse: true,
+            ..PatternOptions::default()
+        };
+        let output = pattern_brep(&source, &kind, &options).expect("fused grid");
+
+        output.brep.validate_topology().expect("topology");
+        assert!(output.fused);
+        assert_eq!(output.instance_count, 4);
+        assert_eq!(output.face_origins.len(), output.brep.faces.len());
+        assert!(output
+            .face_origins
+            .iter()
+            .all(|origin| origin.instance.is_some()));
+        for instance in 0..4 {
+            assert!(output
+                .face_origins
+                .iter()
+                .any(|origin| origin.instance == Some(instance)));
+        }
+    }
+
+    #[test]
+    fn polar_pattern_places_bolt_circle() {
+        let mut cylinder = OGCylinder::new("pattern-bolt".to_string());
+        cylinder
+            .set_config(Vector3::new(0.0, 0.0, 0.0), 0.2, 1.0, 2.0 * PI, 12)
+            .expect("cylinder config");
+        let bolt = cylinder.brep().clone();
+        let kind = PatternKind::Polar {
+            axis_origin: Vector3::new(-2.0, 0.0, 0.0),
+            axis_direction: Vector3::new(0.0, 1.0, 0.0),
+            count: 4,
+            total_angle: 2.0 * PI,
+        };
+        let output = pattern_brep(&bolt, &kind, &PatternOptions::default()).expect("polar pattern");
+        output.brep.validate_topology().expect("topology");
+        assert_eq!(output.brep.shells.len(), 4);
+
+        let per_instance = bolt.vertices.len();
+        let expected_centers = [(0.0, 0.0), (-2.0, -2.0), (-4.0, 0.0), (-2.0, 2.0)];
+        for (instance, (cx, cz)) in expected_centers.iter().enumerate() {
+            let vertices = &output.brep.vertices[instance * per_instance..][..per_instance];
+            let count = vertices.len() as f64;
+            let mean_x = vertices.iter().map(|v| v.position.x).sum::<f64>() / count;
+            let mean_z = vertices.iter().map(|v| v.position.z).sum::<f64>() / count;
+            assert!(
+                (mean_x - cx).abs() < 1.0e-9,
+                "instance {} x {}",
+                instance,
+                mean_x
+            );
+            assert!(
+                (mean_z - cz).abs() < 1.0e-9,
+                "instance {} z {}",
+                instance,
+                mean_z
+            );
+        }
+        let radii_preserved = output.brep.faces.iter().all(|face| match &face.surface {
+            Some(crate::brep::SurfaceGeometry::Cylinder { radius, .. }) => {
+                (radius - 0.2).abs() < 1.0e-12
+            }
+            _ => true,
+        });
+        assert!(radii_preserved);
+    }
+
+    #[test]
+    fn mirror_pattern_flips_orientation_to_keep_normals_outward() {
+        let source = cube(1.0);
+        let kind = PatternKind::Mirror {
+            plane_origin: Vector3::new(2.0, 0.0, 0.0),
+            plane_normal: Vector3::new(1.0, 0.0, 0.0),
+        };
+        let output =
+            pattern_brep(&source, &kind, &PatternOptions::default()).expect("mirror pattern");
+        output.brep.validate_topology().expect("topology");
+
+        let mirrored_faces = output
+            .face_origins
+            .iter()
+            .filter(|origin| origin.instance == Some(1));
+        for origin in mirrored_faces {
+            let face = &output.brep.faces[origin.face_id as usize];
+            let points = output.brep.get_vertices_by_face_id(face.id);
+            let count = points.len() as f64;
+            let center = points.iter().fold(Vector3::new(0.0, 0.0, 0.0), |sum, p| {
+                add(sum, scale(*p, 1.0 / count))
+            });
+            let outward = sub(center, Vector3::new(4.0, 0.0, 0.0));
+            assert!(
+                face.normal.dot(&outward) > 0.0,
+                "face {} points inward",
+                face.id
+            );
+        }
+    }
+
+    #[test]
+    fn pattern_rejects_zero_count_and_zero_axis() {
+        let source = cube(1.0);
+        let linear = PatternKind::Linear {
+            direction: Vector3::new(1.0, 0.0, 0.0),
+            spacing: 1.0,
+            count: 0,
+        };
+        let polar = PatternKind::Polar {
+            axis_origin: Vector3::new(0.0, 0.0, 0.0),
+            axis_direction: Vector3::new(0.0, 0.0, 0.0),
+            count: 3,
+            total_angle: PI,
+        };
+        for kind in [linear, polar] {
+            let Err(error) = pattern_brep(&source, &kind, &PatternOptions::default()) else {
+                panic!("invalid pattern parameters must fail");
+            };
+            assert_eq!(error.kind(), PatternErrorKind::InvalidParameter);
+        }
+    }
+}
@@ -0,0 +1,483 @@
+//! Planar cross-sections of solids and scenes.
+//!
+//! The plane is intersected with every face of a closed solid; the cut
+//! segments are chained into closed loops and nested into planar regions.
+//! Each loop is returned as a closed wire B-rep and each region as a
+//! single-face B-rep with its holes. Section edges that run across a
+//! cylindrical face cut square to its axis keep an exact circle curve, so
+//! drawings can render true arcs instead of facets.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::brep::{Brep, BrepBuilder, BrepError, CurveGeometry, SurfaceGeometry};
+use crate::operations::split::{section_regions, SectionLoop, SplitErrorKind};
+use crate::scenegraph::{OGScene, SectionPlane};
+use openmaths::Vector3;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+const EPSILON: f64 = crate::tolerance::MODELING_TOLERANCE_FLOOR;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectionErrorKind {
+    InvalidPlane,
+    InvalidSolid,
+    NoIntersection,
+    CoplanarFace,
+    TopologyError,
+}
+
+#[derive(Debug, Clone)]
+pub struct SectionError {
+    kind: SectionErrorKind,
+    message: String,
+}
+
+impl SectionError {
+    fn new(kind: SectionErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    pub fn kind(&self) -> SectionErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for SectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for SectionError {}
+
+impl From<BrepError> for SectionError {
+    fn from(error: BrepError) -> Self {
+        SectionError::new(
+            SectionErrorKind::TopologyError,
+            format!("BRep construction failed: {}", error),
+        )
+    }
+}
+
+/// One closed section loop as a wire B-rep.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SectionWire {
+    /// Scene entity the loop was cut from; `None` for a bare B-rep section.
+    pub source_entity_id: Option<String>,
+    /// Index into [`SectionResult::faces`] of the region this loop bounds.
+    pub face_index: usize,
+    /// `true` for a region's outer boundary, `false` for a hole.
+    pub is_outer: bool,
+    pub wire: Brep,
+}
+
+/// One planar section region (outer loop plus holes) as a single-face B-rep
+/// whose normal is the section plane normal.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SectionFace {
+    pub source_entity_id: Option<String>,
+    pub brep: Brep,
+}
+
+/// A scene entity left out of a section because it could not be cut.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SectionFailure {
+    pub entity_id: String,
+    pub message: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SectionResult {
+    pub plane: SectionPlane,
+    pub wires: Vec<SectionWire>,
+    pub faces: Vec<SectionFace>,
+    /// Entities of a scene section that failed; the rest are still cut.
+    #[serde(default)]
+    pub failures: Vec<SectionFailure>,
+}
+
+/// Sections a closed solid by `plane`.
+pub fn section_brep(brep: &Brep, plane: &SectionPlane) -> Result<SectionResult, SectionError> {
+    let mut result = SectionResult {
+        plane: plane.clone(),
+        wires: Vec::new(),
+        faces: Vec::new(),
+        failures: Vec::new(),
+    };
+    append_section(&mut result, brep, None)?;
+    Ok(result)
+}
+
+/// Sections every solid entity of `scene` by `plane`. Entities the plane
+/// misses, and open entities such as wires or sheets, contribute nothing.
+/// An entity that cannot be cut is listed in [`SectionResult::failures`]
+/// and the others are still sectioned; only an invalid plane fails.
+pub fn section_scene(scene: &OGScene, plane: &SectionPlane) -> Result<SectionResult, SectionError> {
+    let mut result = SectionResult {
+        plane: plane.clone(),
+        wires: Vec::new(),
+        faces: Vec::new(),
+        failures: Vec::new(),
+    };
+    for entity in &scene.entities {
+        let (wire_count, face_count) = (result.wires.len(), result.faces.len());
+        match append_section(&mut result, &entity.brep, Some(&entity.id)) {
+            Ok(()) => {}
+            Err(error)
+                if matches!(
+                    error.kind(),
+                    SectionErrorKind::NoIntersection | SectionErrorKind::InvalidSolid
+                ) => {}
+            Err(error) if error.kind() == SectionErrorKind::InvalidPlane => return Err(error),
+            Err(error) => {
+                // Drop whatever the entity added before it failed.
+                result.wires.truncate(wire_count);
+                result.faces.truncate(face_count);
+                result.failures.push(SectionFailure {
+                    entity_id: entity.id.clone(),
+                    message: error.to_string(),
+                });
+            }
+        }
+    }
+    Ok(result)
+}
+
+fn append_section(
+    result: &mut SectionResult,
+    brep: &Brep,
+    source_entity_id: Option<&str>,
+) -> Result<(), SectionError> {
+    let origin = to_vector(result.plane.origin);
+    let normal = to_vector(result.plane.normal);
+    let regions = section_regions(brep, origin, normal).map_err(|error| {
+        let kind = match error.kind() {
+            SplitErrorKind::InvalidPlane | SplitErrorKind::NonPlanarTool => {
+                SectionErrorKind::InvalidPlane
+            }
+            SplitErrorKind::InvalidSolid => SectionErrorKind::InvalidSolid,
+            SplitErrorKind::NoIntersection => SectionErrorKind::NoIntersection,
+            SplitErrorKind::CoplanarFace => SectionErrorKind::CoplanarFace,
+            SplitErrorKind::TopologyError => SectionErrorKind::TopologyError,
+        };
+        SectionError::new(kind, error.to_string())
+    })?;
+    let length = normal.dot(&normal).sqrt();
+    let unit_normal = Vector3::new(normal.x / length, normal.y / length, normal.z / length);
+
+    for (outer, holes) in &regions {
+        let face_index = result.faces.len();
+        for (section_loop, is_outer) in
+            std::iter::once((outer, true)).chain(holes.iter().map(|hole| (hole, false)))
+        {
+            result.wires.push(SectionWire {
+                source_entity_id: source_entity_id.map(str::to_string),
+                face_index,
+                is_outer,
+                wire: build_wire(brep, section_loop, unit_normal)?,
+            });
+        }
+        result.faces.push(SectionFace {
+            source_entity_id: source_entity_id.map(str::to_string),
+            brep: build_face(brep, outer, holes, origin, unit_normal)?,
+        });
+    }
+    Ok(())
+}
+
+fn build_wire(
+    source: &Brep,
+    section_loop: &SectionLoop,
+    normal: Vector3,
+) -> Result<Brep, SectionError> {
+    let mut builder = BrepBuilder::new(uuid::Uuid::new_v4());
+    let ids = builder.add_vertices(&section_loop.points);
+    builder.add_wire(&ids, true)?;
+    let mut wire = builder.build()?;
+    assign_section_curves(&mut wire, source, &[section_loop], normal);
+    Ok(wire)
+}
+
+fn build_face(
+    source: &Brep,
+    outer: &SectionLoop,
+    holes: &[SectionLoop],
+    origin: Vector3,
+    normal: Vector3,
+) -> Result<Brep, SectionError> {
+    let mut builder = BrepBuilder::new(uuid::Uuid::new_v4());
+    let outer_ids = builder.add_vertices(&outer.points);
+    let hole_ids: Vec<Vec<u32>> = holes
+        .iter()
+        .map(|hole| builder.add_vertices(&hole.points))
+        .collect();
+    let face_id = builder.add_face(&outer_ids, &hole_ids)?;
+    builder.add_shell_from_all_faces(false)?;
+    let mut face = builder.build()?;
+    face.faces[face_id as usize].surface = Some(SurfaceGeometry::Plane { origin, normal });
+
+    let loops: Vec<&SectionLoop> = std::iter::once(outer).chain(holes.iter()).collect();
+    assign_section_curves(&mut face, source, &loops, normal);
+    Ok(face)
+}
+
+/// Gives every section edge that crosses a cylinder square to its axis the
+/// exact circle it lies on. `loops` are the loops whose points were added to
+/// `target` in order, so vertex ids index into them consecutively.
+fn assign_section_curves(
+    target: &mut Brep,
+    source: &Brep,
+    loops: &[&SectionLoop],
+    normal: Vector3,
+) {
+    let mut first_vertex = 0u32;
+    let mut edge_faces = std::collections::HashMap::new();
+    for section_loop in loops {
+        let count = section_loop.points.len() as u32;
+        for index in 0..count {
+            let from = first_vertex + index;
+            let to = first_vertex + (index + 1) % count;
+            edge_faces.insert(
+                (from.min(to), from.max(to)),
+                section_loop.edge_faces[index as usize],
+            );
+        }
+        first_vertex += count;
+    }
+
+    for index in 0..target.edges.len() {
+        let Some((from, to)) = target.get_edge_endpoints(index as u32) else {
+            continue;
+        };
+        let Some(face_id) = edge_faces.get(&(from.min(to), from.max(to))) else {
+            continue;
+        };
+        let Some(SurfaceGeometry::Cylinder {
+            origin,
+            axis,
+            ref_direction,
+            radius,
+            ..
+        }) = source.faces[*face_id as usize].surface
+        else {
+            continue;
+        };
+        let axis_length = axis.dot(&axis).sqrt();
+        if axis_length <= EPSILON || (axis.dot(&normal).abs() / axis_length - 1.0).abs() > 1.0e-9 {
+            // Oblique cuts of a cylinder are ellipses; keep them as lines.
+            continue;
+        }
+        let point = target.vertices[from as usize].position;
+        let height = axis.dot(&Vector3::new(
+            point.x - origin.x,
+            point.y - origin.y,
+            point.z - origin.z,
+        )) / (axis_length * axis_length);
+        target.edges[index].curve = Some(CurveGeometry::Circle {
+            center: Vector3::new(
+                origin.x + axis.x * height,
+                origin.y + axis.y * height,
+                origin.z + axis.z * height,
+            ),
+            normal: Vector3::new(
+                axis.x / axis_length,
+                axis.y / axis_length,
+                axis.z / axis_length,
+            ),
+            x_axis: ref_direction,
+            radius,
+            start_angle: 0.0,
+            end_angle: 2.0 * std::f64::consts::PI,
+        });
+    }
+}
+
+fn to_vector(values: [f64; 3]) -> Vector3 {
+    Vector3::new(values[0], values[1], values[2])
+}
+
+/// Wasm entry point for [`section_brep`]. `section_plane_json` is a scenegraph
+/// `SectionPlane`; returns the serialized [`SectionResult`].
+#[wasm_bindgen(js_name = sectionBrep)]
+pub fn section_brep_wasm(
+    local_brep_serialized: String,
+    section_plane_json: String,
+) -> Result<String, JsValue> {
+    let brep: Brep = serde_json::from_str(&local_brep_serialized).map_err(|error| {
+        JsValue::from_str(&format!("Invalid local BRep JSON payload: {}", error))
+    })?;
+    let plane: SectionPlane = serde_json::from_str(&section_plane_json).map_err(|error| {
+        JsValue::from_str(&format!("Invalid section plane JSON payload: {}", error))
+    })?;
+    let result = section_brep(&brep, &plane)
+        .map_err(|error| JsValue::from_str(&format!("Failed to section BRep: {}", error)))?;
+    serde_json::to_string(&result).map_err(|error| {
+        JsValue::from_str(&format!("Failed to serialize section result: {}", error))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::extrude::extrude_profile_loops;
+    use crate::primitives::cuboid::OGCuboid;
+    use crate::primitives::cylinder::OGCylinder;
+    use crate::scenegraph::SceneEntity;
+    use uuid::Uuid;
+
+    fn horizontal_plane(y: f64) -> SectionPlane {
+        SectionPlane {
+            origin: [0.0, y, 0.0],
+            normal: [0.0, 1.0, 0.0],
+        }
+    }
+
+    #[test]
+    fn section_of_holed_block_returns_face_with_hole_and_two_wires() {
+        let outer = vec![
+            Vector3::new(-2.0, 0.0, -2.0),
+            Vector3::new(2.0, 0.0, -2.0),
+            Vector3::new(2.0, 0.0, 2.0),
+            Vector3::new(-2.0, 0.0, 2.0),
+        ];
+        let hole = vec![
+            Vector3::new(-1.0, 0.0, -1.0),
+            Vector3::new(-1.0, 0.0, 1.0),
+            Vector3::new(1.0, 0.0, 1.0),
+            Vector3::new(1.0, 0.0, -1.0),
+        ];
+        let block = extrude_profile_loops(Uuid::new_v4(), &outer, &[hole], 2.0).expect("block");
+        let result = section_brep(&block, &horizontal_plane(0.5)).expect("section");
+
+        assert_eq!(result.faces.len(), 1);
+        let face = &result.faces[0].brep;
+        face.validate_topology().expect("valid section face");
+        assert_eq!(face.faces.len(), 1);
+        assert_eq!(face.faces[0].inner_loops.len(), 1);
+        assert!(face.faces[0].normal.y > 0.99);
+        assert!(face
+            .vertices
+            .iter()
+            .all(|v| (v.position.y - 0.5).abs() < 1.0e-12));
+
+        assert_eq!(result.wires.len(), 2);
+        assert_eq!(result.wires.iter().filter(|wire| wire.is_outer).count(), 1);
+        for wire in &result.wires {
+            assert_eq!(wire.wire.wires.len(), 1);
+            assert!(wire.wire.wires[0].is_closed);
+            assert_eq!(wire.wire.edges.len(), 4);
+            assert_eq!(wire.source_entity_id, None);
+        }
+    }
+
+    #[test]
+    fn section_across_cylinder_keeps_exact_circles() {
+        let mut cylinder = OGCylinder::new("section-cylinder".to_string());
+        cylinder
+            .set_config(
+                Vector3::new(0.0, 0.0, 0.0),
+                1.5,
+                2.0,
+                2.0 * std::f64::consts::PI,
+                24,
+            )
+            .expect("cylinder config");
+        let result = section_brep(cylinder.brep(), &horizontal_plane(0.3)).expect("section");
+
+        assert_eq!(result.wires.len(), 1);
+        let wire = &result.wires[0].wire;
+        assert_eq!(wire.edges.len(), 24);
+        for edge in &wire.edges {
+            let Some(CurveGeometry::Circle { center, radius, .. }) = edge.curve else {
+                panic!("section edge across the cylinder must be a circle");
+            };
+            assert!((radius - 1.5).abs() < 1.0e-12);
+            assert!((center.y - 0.3).abs() < 1.0e-12);
+            assert!(center.x.abs() < 1.0e-12 && center.z.abs() < 1.0e-12);
+        }
+        let face = &result.faces[0].brep;
+        assert!(face
+            .edges
+            .iter()
+            .all(|edge| matches!(edge.curve, Some(CurveGeometry::Circle { .. }))));
+    }
+
+    #[test]
+    fn scene_section_records_source_entities_and_skips_misses() {
+        let mut scene = OGScene::new("section-scene");
+        for (id, offset) in [("near", 0.0), ("far", 10.0)] {
+            let mut cuboid = OGCuboid::new(id.to_string());
+            cuboid
+                .set_config(Vector3::new(0.0, 0.0, 0.0), 1.0, 1.0, 1.0)
+                .expect("cuboid config");
+            let mut brep = cuboid.brep().clone();
+            for vertex in &mut brep.vertices {
+                vertex.position.y += offset;
+            }
+            scene.upsert_entity(SceneEntity {
+                id: id.to_string(),
+                kind: "column".to_string(),
+                brep,
+            });
+        }
+
+        let result = section_scene(&scene, &horizontal_plane(0.0)).expect("scene section");
+        assert_eq!(result.faces.len(), 1);
+        assert_eq!(result.faces[0].source_entity_id.as_deref(), Some("near"));
+        assert_eq!(result.wires.len(), 1);
+        assert_eq!(result.wires[0].source_entity_id.as_deref(), Some("near"));
+
+        let Err(error) = section_brep(&scene.entities[1].brep, &horizontal_plane(0.0)) else {
+            panic!("plane misses the far cuboid");
+        };
+        assert_eq!(error.kind(), SectionErrorKind::NoIntersection);
+    }
+
+    #[test]
+    fn scene_section_reports_failed_entities_and_cuts_the_rest() {
+        let mut scene = OGScene::new("section-scene");
+        let mut cuboid = OGCuboid::new("column".to_string());
+        cuboid
+            .set_config(Vector3::new(5.0, 0.0, 0.0), 1.0, 4.0, 1.0)
+            .expect("cuboid config");
+        scene.upsert_entity(SceneEntity {
+            id: "column".to_string(),
+            kind: "column".to_string(),
+            brep: cuboid.brep().clone(),
+        });
+        // A box whose side face carries a flipped normal, so the cut across
+        // that face cannot be ordered.
+        let mut broken = OGCuboid::new("broken".to_string());
+        broken
+            .set_config(Vector3::new(0.0, 0.0, 0.0), 1.0, 4.0, 1.0)
+            .expect("cuboid config");
+        let mut broken = broken.brep().clone();
+        let side = broken
+            .faces
+            .iter()
+            .position(|face| face.normal.x > 0.5)
+            .expect("side face");
+        let normal = broken.faces[side].normal;
+        broken.faces[side].normal = Vector3::new(-normal.x, -normal.y, -normal.z);
+        scene.upsert_entity(SceneEntity {
+            id: "broken".to_string(),
+            kind: "column".to_string(),
+            brep: broken,
+        });
+
+        let result = section_scene(&scene, &horizontal_plane(1.0)).expect("scene section");
+        assert_eq!(result.faces.len(), 1);
+        assert_eq!(result.faces[0].source_entity_id.as_deref(), Some("column"));
+        assert!(result
+            .wires
+            .iter()
+            .all(|wire| wire.source_entity_id.as_deref() == Some("column")));
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].entity_id, "broken");
+    }
+}
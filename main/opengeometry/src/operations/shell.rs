@@ -0,0 +1,924 @@
+//! Shell (hollow) operation: turns a closed solid into a thin-walled body.
+//!
+//! Every kept face is offset into the solid by the wall thickness — planar
+//! faces along their normal, cylindrical faces (tagged with
+//! [`SurfaceGeometry::Cylinder`]) by changing radius — and each inner vertex is
+//! re-intersected from the offset surfaces of the faces that meet there. Faces
+//! listed as open are removed from both skins and replaced by a rim face that
+//! spans the wall, so the result stays a closed manifold. Without open faces
+//! the result is the outer shell plus a closed inner void shell.
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+
+use crate::brep::{Brep, BrepBuilder, BrepError, CurveGeometry, SurfaceGeometry};
+use crate::operations::common::{add, face_loops, face_vertex_ids, length, normalize, scale, sub};
+use openmaths::Vector3;
+use wasm_bindgen::prelude::*;
+
+const EPSILON: f64 = crate::tolerance::MODELING_TOLERANCE_FLOOR;
+/// Normals closer than this (as `1 - cos`) are treated as the same plane
+/// direction when re-intersecting offset planes at a vertex.
+const PARALLEL_TOLERANCE: f64 = 1.0e-9;
+/// How far, relative to the largest offset, a solved vertex may sit off any
+/// of its offset planes.
+const OFFSET_RESIDUAL: f64 = 1.0e-9;
+/// A plane must cross a cylinder axis at least this steeply (as `|n·axis|`)
+/// to pin the axial position of an inner cylinder vertex.
+const AXIAL_CONSTRAINT_MIN: f64 = 0.1;
+
+#[derive(Clone, Debug, Default)]
+pub struct ShellOptions {
+    /// Wall thickness, measured inward from the original faces.
+    pub thickness: f64,
+    /// Faces removed to open the shell (e.g. the top of a tank).
+    pub open_faces: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellErrorKind {
+    InvalidSolid,
+    InvalidThickness,
+    InvalidFaceSelection,
+    WallTooThick,
+    TopologyError,
+}
+
+#[derive(Debug, Clone)]
+pub struct ShellError {
+    kind: ShellErrorKind,
+    message: String,
+}
+
+impl ShellError {
+    fn new(kind: ShellErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    pub fn kind(&self) -> ShellErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for ShellError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for ShellError {}
+
+impl From<BrepError> for ShellError {
+    fn from(error: BrepError) -> Self {
+        ShellError::new(
+            ShellErrorKind::TopologyError,
+            format!("BRep construction failed: {}", error),
+        )
+    }
+}
+
+/// Hollows `brep` into a thin-walled solid. The outer skin keeps its vertex
+/// and face order; inner vertices, inner faces and rim faces are appended.
+pub fn shell_solid(brep: &Brep, options: &ShellOptions) -> Result<Brep, ShellError> {
+    let thickness = options.thickness;
+    if !thickness.is_finite() || thickness <= EPSILON {
+        return Err(ShellError::new(
+            ShellErrorKind::InvalidThickness,
+            "Shell thickness must be a finite positive distance",
+        ));
+    }
+
+    validate_closed_solid(brep)?;
+    let open = resolve_open_faces(brep, &options.open_faces)?;
+
+    let vertex_count = brep.vertices.len();
+    let mut vertex_faces: Vec<Vec<u32>> = vec![Vec::new(); vertex_count];
+    for face in &brep.faces {
+        for vertex_id in face_vertex_ids(brep, face.id) {
+            let incident = &mut vertex_faces[vertex_id as usize];
+            if !incident.contains(&face.id) {
+                incident.push(face.id);
+            }
+        }
+    }
+
+    let mut inner_positions: Vec<Option<Vector3>> = vec![None; vertex_count];
+    for (vertex_id, incident) in vertex_faces.iter().enumerate() {
+        if incident.iter().all(|face_id| open.contains(face_id)) {
+            continue;
+        }
+        inner_positions[vertex_id] = Some(offset_vertex(
+            brep,
+            vertex_id as u32,
+            incident,
+            &open,
+            thickness,
+        )?);
+    }
+
+    let mut builder = BrepBuilder::new(brep.id);
+    let outer_positions: Vec<Vector3> = brep.vertices.iter().map(|v| v.position).collect();
+    builder.add_vertices(&outer_positions);
+
+    let mut inner_ids: Vec<Option<u32>> = vec![None; vertex_count];
+    let mut inner_to_outer: HashMap<u32, u32> = HashMap::new();
+    for (vertex_id, position) in inner_positions.iter().enumerate() {
+        if let Some(position) = position {
+            let inner_id = builder.add_vertex(*position);
+            inner_ids[vertex_id] = Some(inner_id);
+            inner_to_outer.insert(inner_id, vertex_id as u32);
+        }
+    }
+    let map_inner = |loop_ids: &[u32]| -> Vec<u32> {
+        loop_ids
+            .iter()
+            .rev()
+            .map(|vertex_id| inner_ids[*vertex_id as usize].expect("inner vertex resolved"))
+            .collect()
+    };
+
+    let mut surfaces: Vec<(u32, Option<SurfaceGeometry>)> = Vec::new();
+    let mut outer_faces = Vec::new();
+    let mut inner_faces = Vec::new();
+    let mut rim_faces = Vec::new();
+
+    for face in brep.faces.iter().filter(|face| !open.contains(&face.id)) {
+        let (outer, holes) = face_loops(brep, face.id);
+        let face_id = builder.add_face(&outer, &holes)?;
+        surfaces.push((face_id, face.surface.clone()));
+        outer_faces.push(face_id);
+    }
+
+    for face in brep.faces.iter().filter(|face| !open.contains(&face.id)) {
+        let (outer, holes) = face_loops(brep, face.id);
+        let inner_outer = map_inner(&outer);
+        let inner_holes: Vec<Vec<u32>> = holes.iter().map(|hole| map_inner(hole)).collect();
+
+        if loops_collapse(&outer, &holes, &outer_positions, &inner_positions) {
+            return Err(ShellError::new(
+                ShellErrorKind::WallTooThick,
+                format!(
+                    "Shell thickness {} collapses the inner wall of face {}",
+                    thickness, face.id
+                ),
+            ));
+        }
+
+        let face_id = builder.add_face(&inner_outer, &inner_holes)?;
+        let inner_vertices: Vec<Vector3> = face_vertex_ids(brep, face.id)
+            .iter()
+            .filter_map(|vertex_id| inner_positions[*vertex_id as usize])
+            .collect();
+        surfaces.push((
+            face_id,
+            inner_surface(brep, face.id, &inner_vertices, thickness),
+        ));
+        inner_faces.push(face_id);
+    }
+
+    // The rim lies in the open face's plane (it was not offset), between the
+    // original boundary and the inner skin's boundary.
+    for face in brep.faces.iter().filter(|face| open.contains(&face.id)) {
+        let (outer, holes) = face_loops(brep, face.id);
+        let face_id = builder.add_face(&outer, &[map_inner(&outer)])?;
+        surfaces.push((face_id, face.surface.clone()));
+        rim_faces.push(face_id);
+
+        for hole in &holes {
+            let face_id = builder.add_face(&map_inner(hole), std::slice::from_ref(hole))?;
+            surfaces.push((face_id, face.surface.clone()));
+            rim_faces.push(face_id);
+        }
+    }
+
+    if open.is_empty() {
+        builder.add_shell(&outer_faces, true)?;
+        builder.add_shell(&inner_faces, true)?;
+    } else {
+        builder.add_shell_from_all_faces(true)?;
+    }
+
+    let mut shelled = builder.build()?;
+    for (face_id, surface) in surfaces {
+        shelled.faces[face_id as usize].surface = surface;
+    }
+    transfer_edge_curves(brep, &mut shelled, &inner_to_outer);
+
+    Ok(shelled)
+}
+
+/// An inner edge that shrinks to nothing or runs against its outer edge means
+/// the offset walls have passed through each other.
+fn loops_collapse(
+    outer: &[u32],
+    holes: &[Vec<u32>],
+    outer_positions: &[Vector3],
+    inner_positions: &[Option<Vector3>],
+) -> bool {
+    std::iter::once(outer)
+        .chain(holes.iter().map(Vec::as_slice))
+        .any(|loop_ids| {
+            (0..loop_ids.len()).any(|index| {
+                let from = loop_ids[index] as usize;
+                let to = loop_ids[(index + 1) % loop_ids.len()] as usize;
+                let (Some(inner_from), Some(inner_to)) =
+                    (inner_positions[from], inner_positions[to])
+                else {
+                    return true;
+                };
+                let outer_edge = sub(outer_positions[to], outer_positions[from]);
+                let inner_edge = sub(inner_to, inner_from);
+                inner_edge.dot(&outer_edge) <= EPSILON * length(outer_edge)
+            })
+        })
+}
+
+fn validate_closed_solid(brep: &Brep) -> Result<(), ShellError> {
+    let closed = !brep.faces.is_empty()
+        && !brep.shells.is_empty()
+        && brep.shells.iter().all(|shell| shell.is_closed)
+        && brep.edges.iter().all(|edge| edge.twin_halfedge.is_some());
+    if !closed {
+        return Err(ShellError::new(
+            ShellErrorKind::InvalidSolid,
+            "Shell requires a closed manifold solid",
+        ));
+    }
+    Ok(())
+}
+
+/// Open faces must exist and must not touch each other: a rim is built per
+/// open face, which needs every boundary vertex to keep a wall behind it.
+fn resolve_open_faces(brep: &Brep, open_faces: &[u32]) -> Result<HashSet<u32>, ShellError> {
+    let mut open = HashSet::new();
+    let mut used_vertices = HashSet::new();
+    for face_id in open_faces {
+        if *face_id as usize >= brep.faces.len() {
+            return Err(ShellError::new(
+                ShellErrorKind::InvalidFaceSelection,
+                format!("Open face {} does not exist", face_id),
+            ));
+        }
+        if !open.insert(*face_id) {
+            continue;
+        }
+        for vertex_id in face_vertex_ids(brep, *face_id) {
+            if !used_vertices.insert(vertex_id) {
+                return Err(ShellError::new(
+                    ShellErrorKind::InvalidFaceSelection,
+                    format!("Open face {} touches another open face", face_id),
+                ));
+            }
+        }
+    }
+    if open.len() == brep.faces.len() {
+        return Err(ShellError::new(
+            ShellErrorKind::InvalidFaceSelection,
+            "Shell cannot open every face of the solid",
+        ));
+    }
+    Ok(open)
+}
+
+fn cylinder_of(brep: &Brep, face_id: u32) -> Option<(Vector3, Vector3, f64)> {
+    match &brep.faces[face_id as usize].surface {
+        Some(SurfaceGeometry::Cylinder {
+            origin,
+            axis,
+            radius,
+            ..
+        }) => Some((*origin, normalize(*axis), *radius)),
+        _ => None,
+    }
+}
+
+/// Whether a cylindrical face bounds material inside the cylinder (a boss)
+/// rather than outside it (a hole).
+fn cylinder_is_convex(brep: &Brep, face_id: u32, origin: Vector3, axis: Vector3) -> bool {
+    let points = brep.get_vertices_by_face_id(face_id);
+    let count = points.len().max(1) as f64;
+    let sum = points
+        .iter()
+        .fold(Vector3::new(0.0, 0.0, 0.0), |acc, p| add(acc, *p));
+    let centroid = scale(sum, 1.0 / count);
+    let relative = sub(centroid, origin);
+    let radial = sub(relative, scale(axis, relative.dot(&axis)));
+    brep.faces[face_id as usize].normal.dot(&radial) >= 0.0
+}
+
+fn offset_vertex(
+    brep: &Brep,
+    vertex_id: u32,
+    incident: &[u32],
+    open: &HashSet<u32>,
+    thickness: f64,
+) -> Result<Vector3, ShellError> {
+    let position = brep.vertices[vertex_id as usize].position;
+    let offset_of = |face_id: u32| {
+        if open.contains(&face_id) {
+            0.0
+        } else {
+            thickness
+        }
+    };
+
+    let planes: Vec<(Vector3, f64)> = incident
+        .iter()
+        .filter(|face_id| cylinder_of(brep, **face_id).is_none())
+        .map(|face_id| {
+            (
+                normalize(brep.faces[*face_id as usize].normal),
+                offset_of(*face_id),
+            )
+        })
+        .collect();
+
+    let cylinder = incident
+        .iter()
+        .find_map(|face_id| cylinder_of(brep, *face_id).map(|c| (*face_id, c)));
+
+    if let Some((face_id, (origin, axis, radius))) = cylinder {
+        let relative = sub(position, origin);
+        let height = relative.dot(&axis);
+        let radial = sub(relative, scale(axis, height));
+        let radial_length = length(radial);
+        if radial_length <= EPSILON || radius <= EPSILON {
+            return Err(ShellError::new(
+                ShellErrorKind::TopologyError,
+                format!("Vertex {} lies on its cylinder axis", vertex_id),
+            ));
+        }
+
+        let offset = offset_of(face_id);
+        let new_radius = if cylinder_is_convex(brep, face_id, origin, axis) {
+            radius - offset
+        } else {
+            radius + offset
+        };
+        if new_radius <= EPSILON {
+            return Err(ShellError::new(
+                ShellErrorKind::WallTooThick,
+                format!(
+                    "Shell thickness {} exceeds the radius of face {}",
+                    thickness, face_id
+                ),
+            ));
+        }
+
+        let new_radial = scale(radial, new_radius / radius);
+        let base = add(origin, add(scale(axis, height), new_radial));
+        let mut shift_sum = 0.0;
+        let mut shift_count = 0usize;
+        for (normal, offset) in &planes {
+            let along_axis = normal.dot(&axis);
+            if along_axis.abs() < AXIAL_CONSTRAINT_MIN {
+                continue;
+            }
+            shift_sum += (-offset - normal.dot(&sub(base, position))) / along_axis;
+            shift_count += 1;
+        }
+        let shift = if shift_count > 0 {
+            shift_sum / shift_count as f64
+        } else {
+            0.0
+        };
+        return Ok(add(base, scale(axis, shift)));
+    }
+
+    solve_plane_offsets(&planes)
+        .map(|delta| add(position, delta))
+        .ok_or_else(|| {
+            ShellError::new(
+                ShellErrorKind::TopologyError,
+                format!(
+                    "Offset faces do not meet in a single point at vertex {}",
+                    vertex_id
+                ),
+            )
+        })
+}
+
+/// Displacement `d` with `n_i·d = -offset_i` for every distinct plane normal.
+/// More than three planes only have a solution when their offsets still meet
+/// in one point, as at the apex of a regular pyramid; otherwise any single
+/// point would leave some walls thicker than others, so there is none.
+pub(crate) fn solve_plane_offsets(planes: &[(Vector3, f64)]) -> Option<Vector3> {
+    let mut distinct: Vec<(Vector3, f64)> = Vec::new();
+    for (normal, offset) in planes {
+        match distinct
+            .iter_mut()
+            .find(|(existing, _)| existing.dot(normal) > 1.0 - PARALLEL_TOLERANCE)
+        {
+            Some(existing) => existing.1 = existing.1.max(*offset),
+            None => distinct.push((*normal, *offset)),
+        }
+    }
+
+    let delta = match distinct.len() {
+        0 => None,
+        1 => Some(scale(distinct[0].0, -distinct[0].1)),
+        2 => solve_two_planes(distinct[0], distinct[1]),
+        _ => solve_least_squares(&distinct).or_else(|| {
+            // All normals share a common perpendicular: intersect the two
+            // planes that are furthest from parallel.
+            let mut best: Option<(f64, usize, usize)> = None;
+            for i in 0..distinct.len() {
+                for j in (i + 1)..distinct.len() {
+                    let alignment = distinct[i].0.dot(&distinct[j].0).abs();
+                    if best.is_none_or(|(value, _, _)| alignment < value) {
+                        best = Some((alignment, i, j));
+                    }
+                }
+            }
+            let (_, i, j) = best?;
+            solve_two_planes(distinct[i], distinct[j])
+        }),
+    }?;
+
+    let reach = distinct
+        .iter()
+        .fold(1.0f64, |reach, (_, offset)| reach.max(offset.abs()));
+    distinct
+        .iter()
+        .all(|(normal, offset)| (normal.dot(&delta) + offset).abs() <= OFFSET_RESIDUAL * reach)
+        .then_some(delta)
+}
+
+fn solve_two_planes(first: (Vector3, f64), second: (Vector3, f64)) -> Option<Vector3> {
+    let cosine = first.0.dot(&second.0);
+    let determinant = 1.0 - cosine * cosine;
+    if determinant <= PARALLEL_TOLERANCE {
+        return None;
+    }
+    let (b1, b2) = (-first.1, -second.1);
+    let a = (b1 - cosine * b2) / determinant;
+    let b = (b2 - cosine * b1) / determinant;
+    Some(add(scale(first.0, a), scale(second.0, b)))
+}
+
+fn solve_least_squares(planes: &[(Vector3, f64)]) -> Option<Vector3> {
+    let mut m = [[0.0f64; 3]; 3];
+    let mut rhs = [0.0f64; 3];
+    for (normal, offset) in planes {
+        let n = [normal.x, normal.y, normal.z];
+        for row in 0..3 {
+            for col in 0..3 {
+                m[row][col] += n[row] * n[col];
+            }
+            rhs[row] -= n[row] * offset;
+        }
+    }
+
+    let determinant = det3(&m);
+    if determinant.abs() <= 1.0e-9 {
+        return None;
+    }
+    let mut solution = [0.0f64; 3];
+    for (axis, value) in solution.iter_mut().enumerate() {
+        let mut replaced = m;
+        for row in 0..3 {
+            replaced[row][axis] = rhs[row];
+        }
+        *value = det3(&replaced) / determinant;
+    }
+    Some(Vector3::new(solution[0], solution[1], solution[2]))
+}
+
+fn det3(m: &[[f64; 3]; 3]) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+fn inner_surface(
+    brep: &Brep,
+    face_id: u32,
+    inner_vertices: &[Vector3],
+    thickness: f64,
+) -> Option<SurfaceGeometry> {
+    match brep.faces[face_id as usize].surface.as_ref()? {
+        SurfaceGeometry::Plane { origin, normal } => {
+            let normal = normalize(*normal);
+            Some(SurfaceGeometry::Plane {
+                origin: sub(*origin, scale(normal, thickness)),
+                normal: scale(normal, -1.0),
+            })
+        }
+        SurfaceGeometry::Cylinder {
+            origin,
+            axis,
+            ref_direction,
+            radius,
+            height,
+        } => {
+            let convex = cylinder_is_convex(brep, face_id, *origin, normalize(*axis));
+            Some(SurfaceGeometry::Cylinder {
+                origin: *origin,
+                axis: *axis,
+                ref_direction: *ref_direction,
+                radius: if convex {
+                    radius - thickness
+                } else {
+                    radius + thickness
+                },
+                height: *height,
+            })
+        }
+        SurfaceGeometry::Sphere { center, radius } => {
+            let inner_radius = radius - thickness;
+            let tolerance = EPSILON * radius.max(1.0);
+            let on_sphere = inner_vertices
+                .iter()
+                .all(|p| (length(sub(*p, *center)) - inner_radius).abs() <= tolerance);
+            if on_sphere {
+                return Some(SurfaceGeometry::Sphere {
+                    center: *center,
+                    radius: inner_radius,
+                });
+            }
+            // A flat facet offsets along its plane normal, which moves its
+            // corners off the smaller sphere; the inner facet is that plane.
+            let face = &brep.faces[face_id as usize];
+            let normal = normalize(face.normal);
+            let corner =
+                brep.vertices[brep.get_loop_vertex_indices(face.outer_loop)[0] as usize].position;
+            Some(SurfaceGeometry::Plane {
+                origin: sub(corner, scale(normal, thickness)),
+                normal: scale(normal, -1.0),
+            })
+        }
+    }
+}
+
+/// Copies analytic edge curves onto the rebuilt skins. Outer edges keep their
+/// curve; circles on inner edges are re-centred and re-sized to pass through
+/// the offset endpoints.
+fn transfer_edge_curves(source: &Brep, shelled: &mut Brep, inner_to_outer: &HashMap<u32, u32>) {
+    let mut curves: HashMap<(u32, u32), CurveGeometry> = HashMap::new();
+    for edge in &source.edges {
+        let Some(curve) = edge.curve.as_ref() else {
+            continue;
+        };
+        if let Some((from, to)) = source.get_edge_endpoints(edge.id) {
+            curves.insert((from.min(to), from.max(to)), curve.clone());
+        }
+    }
+
+    for index in 0..shelled.edges.len() {
+        let Some((from, to)) = shelled.get_edge_endpoints(index as u32) else {
+            continue;
+        };
+        let (outer_from, outer_to, is_inner) =
+            match (inner_to_outer.get(&from), inner_to_outer.get(&to)) {
+                (Some(a), Some(b)) => (*a, *b, true),
+                (None, None) => (from, to, false),
+                _ => continue,
+            };
+        let Some(curve) = curves.get(&(outer_from.min(outer_to), outer_from.max(outer_to))) else {
+            continue;
+        };
+
+        let curve = if !is_inner {
+            curve.clone()
+        } else {
+            let original = source.vertices[outer_from as usize].position;
+            let moved = shelled.vertices[from as usize].position;
+            let moved_to = shelled.vertices[to as usize].position;
+            match curve {
+                CurveGeometry::Line { .. } => CurveGeometry::Line {
+                    start: moved,
+                    end: moved_to,
+                },
+                CurveGeometry::Circle {
+                    center,
+                    normal,
+                    x_axis,
+                    start_angle,
+                    end_angle,
+                    ..
+                } => {
+                    let unit = normalize(*normal);
+                    let center = add(*center, scale(unit, sub(moved, original).dot(&unit)));
+                    let relative = sub(moved, center);
+                    let radial = sub(relative, scale(unit, relative.dot(&unit)));
+                    CurveGeometry::Circle {
+                        center,
+                        normal: *normal,
+                        x_axis: *x_axis,
+                        radius: length(radial),
+                        start_angle: *start_angle,
+                        end_angle: *end_angle,
+                    }
+                }
+            }
+        };
+        shelled.edges[index].curve = Some(curve);
+    }
+}
+
+/// Wasm entry point for [`shell_solid`]. `open_face_ids` lists the faces to
+/// remove; pass an empty array for a closed hollow with an internal void.
+#[wasm_bindgen(js_name = shellBrep)]
+pub fn shell_brep_wasm(
+    local_brep_serialized: String,
+    thickness: f64,
+    open_face_ids: Vec<u32>,
+) -> Result<String, JsValue> {
+    let brep: Brep = serde_json::from_str(&local_brep_serialized).map_err(|error| {
+        JsValue::from_str(&format!("Invalid local BRep JSON payload: {}", error))
+    })?;
+    let options = ShellOptions {
+        thickness,
+        open_faces: open_face_ids,
+    };
+    let shelled = shell_solid(&brep, &options)
+        .map_err(|error| JsValue::from_str(&format!("Failed to shell BRep: {}", error)))?;
+    shelled.validate_topology().map_err(|error| {
+        JsValue::from_str(&format!("Shelled BRep topology is invalid: {}", error))
+    })?;
+    serde_json::to_string(&shelled)
+        .map_err(|error| JsValue::from_str(&format!("Failed to serialize shelled BRep: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::cuboid::OGCuboid;
+    use crate::primitives::cylinder::OGCylinder;
+
+    fn cuboid(width: f64, height: f64, depth: f64) -> Brep {
+        let mut cuboid = OGCuboid::new("shell-cuboid".to_string());
+        cuboid
+            .set_config(Vector3::new(0.0, 0.0, 0.0), width, height, depth)
+            .expect("cuboid config");
+        cuboid.brep().clone()
+    }
+
+    fn top_face(brep: &Brep) -> u32 {
+        brep.faces
+            .iter()
+            .find(|face| face.normal.y > 0.9)
+            .map(|face| face.id)
+            .expect("top face")
+    }
+
+    fn bounds(points: impl Iterator<Item = Vector3>) -> (Vector3, Vector3) {
+        points.fold(
+            (
+                Vector3::new(f64::MAX, f64::MAX, f64::MAX),
+                Vector3::new(f64::MIN, f64::MIN, f64::MIN),
+            ),
+            |(min, max), p| {
+                (
+                    Vector3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+                    Vector3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+                )
+            },
+        )
+    }
+
+    #[test]
+    fn closed_cuboid_shell_adds_inner_void_shell() {
+        let source = cuboid(2.0, 2.0, 2.0);
+        let options = ShellOptions {
+            thickness: 0.2,
+            open_faces: Vec::new(),
+        };
+        let shelled = shell_solid(&source, &options).expect("shell cuboid");
+
+        shelled.validate_topology().expect("valid topology");
+        assert_eq!(shelled.shells.len(), 2);
+        assert!(shelled.shells.iter().all(|shell| shell.is_closed));
+        assert_eq!(shelled.faces.len(), 12);
+        assert_eq!(shelled.vertices.len(), 16);
+
+        let (min, max) = bounds(shelled.vertices[8..].iter().map(|v| v.position));
+        for value in [min.x, min.y, min.z] {
+            assert!((value + 0.8).abs() < 1.0e-9);
+        }
+        for value in [max.x, max.y, max.z] {
+            assert!((value - 0.8).abs() < 1.0e-9);
+        }
+        for face in &shelled.faces[6..] {
+            let center = shelled.get_vertices_by_face_id(face.id)[0];
+            assert!(face.normal.dot(&center) < 0.0, "inner faces face the void");
+        }
+    }
+
+    #[test]
+    fn open_top_cuboid_shell_builds_rim_face() {
+        let source = cuboid(2.0, 1.0, 2.0);
+        let top = top_face(&source);
+        let options = ShellOptions {
+            thickness: 0.1,
+            open_faces: vec![top],
+        };
+        let shelled = shell_solid(&source, &options).expect("shell open cuboid");
+
+        shelled.validate_topology().expect("valid topology");
+        assert_eq!(shelled.shells.len(), 1);
+        assert!(shelled.shells[0].is_closed);
+        assert_eq!(shelled.faces.len(), 11);
+
+        let rim = shelled.faces.last().expect("rim face");
+        assert_eq!(rim.inner_loops.len(), 1);
+        assert!(rim.normal.y > 0.99);
+
+        let (min, max) = bounds(shelled.vertices[8..].iter().map(|v| v.position));
+        assert!((min.y - (-0.4)).abs() < 1.0e-9);
+        assert!((max.y - 0.5).abs() < 1.0e-9);
+        assert!((max.x - 0.9).abs() < 1.0e-9);
+    }
+
+    #[test]
+    fn cylinder_shell_reduces_inner_radius() {
+        let mut cylinder = OGCylinder::new("shell-cylinder".to_string());
+        cylinder
+            .set_config(
+                Vector3::new(0.0, 0.0, 0.0),
+                1.0,
+                2.0,
+                2.0 * std::f64::consts::PI,
+                24,
+            )
+            .expect("cylinder config");
+        let source = cylinder.brep().clone();
+        let top = top_face(&source);
+        let options = ShellOptions {
+            thickness: 0.25,
+            open_faces: vec![top],
+        };
+        let shelled = shell_solid(&source, &options).expect("shell cylinder");
+
+        shelled.validate_topology().expect("valid topology");
+        let inner_radii: Vec<f64> = shelled
+            .faces
+            .iter()
+            .filter_map(|face| match &face.surface {
+                Some(SurfaceGeometry::Cylinder { radius, .. }) => Some(*radius),
+                _ => None,
+            })
+            .collect();
+        assert!(inner_radii.iter().any(|r| (r - 1.0).abs() < 1.0e-9));
+        assert!(inner_radii.iter().any(|r| (r - 0.75).abs() < 1.0e-9));
+
+        let source_vertex_count = source.vertices.len();
+        for vertex in &shelled.vertices[source_vertex_count..] {
+            let p = vertex.position;
+            assert!(((p.x * p.x + p.z * p.z).sqrt() - 0.75).abs() < 1.0e-9);
+            assert!(p.y > -0.75 - 1.0e-9 && p.y < 1.0 + 1.0e-9);
+        }
+
+        let inner_ring_circle = shelled.edges.iter().any(|edge| {
+            matches!(
+                &edge.curve,
+                Some(CurveGeometry::Circle { radius, center, .. })
+                    if (radius - 0.75).abs() < 1.0e-9 && (center.y + 0.75).abs() < 1.0e-9
+            )
+        });
+        assert!(inner_ring_circle);
+    }
+
+    #[test]
+    fn shell_rejects_wall_thicker_than_solid() {
+        let source = cuboid(1.0, 1.0, 1.0);
+        let options = ShellOptions {
+            thickness: 0.6,
+            open_faces: Vec::new(),
+        };
+        let Err(error) = shell_solid(&source, &options) else {
+            panic!("wall thicker than the solid must fail");
+        };
+        assert_eq!(error.kind(), ShellErrorKind::WallTooThick);
+
+        let options = ShellOptions {
+            thickness: 0.0,
+            open_faces: Vec::new(),
+        };
+        let Err(error) = shell_solid(&source, &options) else {
+            panic!("zero thickness must fail");
+        };
+        assert_eq!(error.kind(), ShellErrorKind::InvalidThickness);
+    }
+
+    /// A pyramid over a `width` by `depth` base with its apex one unit up:
+    /// four faces meet at the apex.
+    fn pyramid(width: f64, depth: f64) -> Brep {
+        let (x, z) = (width / 2.0, depth / 2.0);
+        let mut builder = BrepBuilder::new(uuid::Uuid::new_v4());
+        builder.add_vertices(&[
+            Vector3::new(-x, 0.0, -z),
+            Vector3::new(x, 0.0, -z),
+            Vector3::new(x, 0.0, z),
+            Vector3::new(-x, 0.0, z),
+            Vector3::new(0.0, 1.0, 0.0),
+        ]);
+        for face in [
+            &[0, 1, 2, 3][..],
+            &[1, 0, 4],
+            &[2, 1, 4],
+            &[3, 2, 4],
+            &[0, 3, 4],
+        ] {
+            builder.add_face(face, &[]).expect("pyramid face");
+        }
+        builder
+            .add_shell_from_all_faces(true)
+            .expect("pyramid shell");
+        builder.build().expect("pyramid")
+    }
+
+    #[test]
+    fn shell_keeps_walls_uniform_where_more_than_three_faces_meet() {
+        let options = ShellOptions {
+            thickness: 0.1,
+            open_faces: Vec::new(),
+        };
+        // Offsets of a square pyramid's sides still meet on its axis.
+        let shelled = shell_solid(&pyramid(2.0, 2.0), &options).expect("square pyramid shell");
+        let apex = shelled.vertices[9].position;
+        let side = normalize(Vector3::new(0.0, 1.0, 1.0));
+        assert!(apex.x.abs() < 1.0e-9 && apex.z.abs() < 1.0e-9);
+        assert!((side.dot(&sub(Vector3::new(0.0, 1.0, 0.0), apex)) - 0.1).abs() < 1.0e-9);
+
+        // On an oblong base they do not, and no single apex keeps every wall
+        // at the requested thickness.
+        let Err(error) = shell_solid(&pyramid(4.0, 2.0), &options) else {
+            panic!("an apex with inconsistent offset planes must fail");
+        };
+        assert_eq!(error.kind(), ShellErrorKind::TopologyError);
+    }
+
+    /// A unit octahedron whose facets are all tagged with the sphere through
+    /// its corners, as a tessellated sphere's facets are.
+    fn octahedron_on_sphere() -> Brep {
+        let mut builder = BrepBuilder::new(uuid::Uuid::new_v4());
+        builder.add_vertices(&[
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(-1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, -1.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::new(0.0, 0.0, -1.0),
+        ]);
+        for (x, y, z) in [
+            (0, 2, 4),
+            (1, 2, 4),
+            (0, 3, 4),
+            (1, 3, 4),
+            (0, 2, 5),
+            (1, 2, 5),
+            (0, 3, 5),
+            (1, 3, 5),
+        ] {
+            // Octants with an odd number of negative axes reverse the winding.
+            let face = if (x + y + z) % 2 == 0 {
+                [x, y, z]
+            } else {
+                [x, z, y]
+            };
+            builder.add_face(&face, &[]).expect("octahedron face");
+        }
+        builder
+            .add_shell_from_all_faces(true)
+            .expect("octahedron shell");
+        let mut brep = builder.build().expect("octahedron");
+        for face in &mut brep.faces {
+            face.surface = Some(SurfaceGeometry::Sphere {
+                center: Vector3::new(0.0, 0.0, 0.0),
+                radius: 1.0,
+            });
+        }
+        brep
+    }
+
+    #[test]
+    fn shell_tags_flat_sphere_facets_with_their_offset_plane() {
+        let source = octahedron_on_sphere();
+        let options = ShellOptions {
+            thickness: 0.1,
+            open_faces: Vec::new(),
+        };
+        let shelled = shell_solid(&source, &options).expect("octahedron shell");
+
+        // The inner corners sit sqrt(3) * 0.1 in, not 0.1, so the inner
+        // facets are planes rather than the sphere of radius 0.9.
+        let corner = shelled.vertices[6].position;
+        assert!((corner.x - (1.0 - 3.0f64.sqrt() * 0.1)).abs() < 1.0e-9);
+        for (outer, inner) in shelled.faces[..8].iter().zip(&shelled.faces[8..]) {
+            assert!(matches!(
+                outer.surface,
+                Some(SurfaceGeometry::Sphere { .. })
+            ));
+            let Some(SurfaceGeometry::Plane { origin, normal }) = &inner.surface else {
+                panic!("inner facet {} should be planar", inner.id);
+            };
+            assert!((normal.dot(&outer.normal) + 1.0).abs() < 1.0e-9);
+            assert!((outer.normal.dot(origin) - (1.0 / 3.0f64.sqrt() - 0.1)).abs() < 1.0e-9);
+        }
+    }
+}
@@ -0,0 +1,993 @@
+//! Split operation: cuts a closed solid with a plane into two closed solids.
+//!
+//! Faces are clipped exactly against the plane (no re-tessellation): every
+//! crossed edge gets one intersection vertex shared by both halves, and the
+//! cut segments left on each face are chained into planar cap faces. A face
+//! lying in the plane goes to the half its material is on and bounds that
+//! half's caps, so a solid can be cut at the height of a step. Caps are
+//! tagged [`FaceRole::SectionCap`] so drawings can hatch them. The plane may
+//! come from a scenegraph [`SectionPlane`] or from a planar face of another
+//! B-rep.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::brep::{Brep, BrepBuilder, BrepError, CurveGeometry, FaceRole, SurfaceGeometry};
+use crate::operations::common::{add, normalize, point_in_polygon, scale, sub};
+use crate::operations::triangulate::compute_polygon_normal;
+use crate::scenegraph::SectionPlane;
+use openmaths::Vector3;
+use wasm_bindgen::prelude::*;
+
+const EPSILON: f64 = crate::tolerance::MODELING_TOLERANCE_FLOOR;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitErrorKind {
+    InvalidSolid,
+    InvalidPlane,
+    NonPlanarTool,
+    NoIntersection,
+    CoplanarFace,
+    TopologyError,
+}
+
+#[derive(Debug, Clone)]
+pub struct SplitError {
+    kind: SplitErrorKind,
+    message: String,
+}
+
+impl SplitError {
+    fn new(kind: SplitErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    pub fn kind(&self) -> SplitErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for SplitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for SplitError {}
+
+impl From<BrepError> for SplitError {
+    fn from(error: BrepError) -> Self {
+        SplitError::new(
+            SplitErrorKind::TopologyError,
+            format!("BRep construction failed: {}", error),
+        )
+    }
+}
+
+/// Both halves of a split. `positive` lies on the side the plane normal
+/// points to. Each half is one closed shell (possibly with several lumps).
+#[derive(Clone)]
+pub struct SplitOutput {
+    pub positive: Brep,
+    pub negative: Brep,
+    pub positive_cap_faces: Vec<u32>,
+    pub negative_cap_faces: Vec<u32>,
+}
+
+/// Splits `brep` by the plane through `origin` with normal `normal`.
+pub fn split_by_plane(
+    brep: &Brep,
+    origin: Vector3,
+    normal: Vector3,
+) -> Result<SplitOutput, SplitError> {
+    let cutter = Cutter::for_solid(brep, origin, normal)?;
+    let (positive, positive_cap_faces) = cutter.build_half(1.0)?;
+    let (negative, negative_cap_faces) = cutter.build_half(-1.0)?;
+    Ok(SplitOutput {
+        positive,
+        negative,
+        positive_cap_faces,
+        negative_cap_faces,
+    })
+}
+
+/// Splits `brep` by a scenegraph section plane.
+pub fn split_by_section_plane(
+    brep: &Brep,
+    plane: &SectionPlane,
+) -> Result<SplitOutput, SplitError> {
+    split_by_plane(
+        brep,
+        Vector3::new(plane.origin[0], plane.origin[1], plane.origin[2]),
+        Vector3::new(plane.normal[0], plane.normal[1], plane.normal[2]),
+    )
+}
+
+/// Splits `brep` by the supporting plane of face `face_id` of `tool`. The
+/// face's plane is extended infinitely, so the face must be planar: curved
+/// and warped faces fail with `NonPlanarTool`. To cut along a curved or
+/// faceted sheet, use `booleans::sheet::split_solid_by_sheet`.
+pub fn split_by_face_plane(
+    brep: &Brep,
+    tool: &Brep,
+    face_id: u32,
+) -> Result<SplitOutput, SplitError> {
+    let Some(face) = tool.faces.get(face_id as usize) else {
+        return Err(SplitError::new(
+            SplitErrorKind::InvalidPlane,
+            format!("Split tool face {} does not exist", face_id),
+        ));
+    };
+    if matches!(
+        face.surface,
+        Some(SurfaceGeometry::Cylinder { .. } | SurfaceGeometry::Sphere { .. })
+    ) {
+        return Err(SplitError::new(
+            SplitErrorKind::NonPlanarTool,
+            format!("Split tool face {} is not planar", face_id),
+        ));
+    }
+    let points = tool.get_vertices_by_face_id(face_id);
+    let normal = compute_polygon_normal(&points).ok_or_else(|| {
+        SplitError::new(
+            SplitErrorKind::InvalidPlane,
+            format!("Split tool face {} has no stable normal", face_id),
+        )
+    })?;
+    let tolerance = modeling_tolerance(tool);
+    if points
+        .iter()
+        .any(|point| normal.dot(&sub(*point, points[0])).abs() > tolerance)
+    {
+        return Err(SplitError::new(
+            SplitErrorKind::NonPlanarTool,
+            format!("Split tool face {} is warped", face_id),
+        ));
+    }
+    split_by_plane(brep, points[0], normal)
+}
+
+/// One closed loop where a plane crosses a solid, wound counter-clockwise
+/// about the plane normal. `edge_faces[i]` is the source face that the edge
+/// from `points[i]` to `points[i + 1]` runs across.
+pub(crate) struct SectionLoop {
+    pub points: Vec<Vector3>,
+    pub edge_faces: Vec<u32>,
+}
+
+/// Section of a closed solid by a plane as (outer, holes) regions. These are
+/// exactly the caps a split would put on the negative half.
+pub(crate) fn section_regions(
+    brep: &Brep,
+    origin: Vector3,
+    normal: Vector3,
+) -> Result<Vec<(SectionLoop, Vec<SectionLoop>)>, SplitError> {
+    let cutter = Cutter::for_solid(brep, origin, normal)?;
+    let mut segment_faces: HashMap<CutSegment, u32> = HashMap::new();
+    let mut cap_segments: Vec<CutSegment> = Vec::new();
+    for face in &brep.faces {
+        let (_, segments) = cutter.clip_face(face.id, -1.0)?;
+        for (from, to) in segments {
+            segment_faces.insert((to, from), face.id);
+            cap_segments.push((to, from));
+        }
+    }
+    let loops = chain_segments(&cancel_opposite_segments(cap_segments)).ok_or_else(|| {
+        SplitError::new(
+            SplitErrorKind::TopologyError,
+            "Section segments do not close into loops",
+        )
+    })?;
+
+    let to_loop = |keys: &[PointKey]| SectionLoop {
+        points: keys.iter().map(|key| cutter.position(*key)).collect(),
+        edge_faces: (0..keys.len())
+            .map(|index| segment_faces[&(keys[index], keys[(index + 1) % keys.len()])])
+            .collect(),
+    };
+    Ok(cutter
+        .nest_loops(loops, cutter.normal)
+        .iter()
+        .map(|(outer, holes)| {
+            (
+                to_loop(outer),
+                holes.iter().map(|hole| to_loop(hole)).collect(),
+            )
+        })
+        .collect())
+}
+
+/// A point of the split result: an original vertex, or the crossing of the
+/// plane with the original edge between two vertices (stored low id first).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum PointKey {
+    Vertex(u32),
+    Crossing(u32, u32),
+}
+
+/// Closed face boundary as point keys: outer loop plus holes.
+type KeyedFace = (Vec<PointKey>, Vec<Vec<PointKey>>);
+
+/// Directed segment lying in the cut plane.
+type CutSegment = (PointKey, PointKey);
+
+struct Cutter<'a> {
+    brep: &'a Brep,
+    origin: Vector3,
+    normal: Vector3,
+    /// Signed distance of every vertex, snapped to exactly zero within the
+    /// modeling tolerance so near-plane vertices are treated as on it.
+    distances: Vec<f64>,
+}
+
+impl<'a> Cutter<'a> {
+    /// Validates the plane and solid, and rejects planes that miss it.
+    fn for_solid(brep: &'a Brep, origin: Vector3, normal: Vector3) -> Result<Self, SplitError> {
+        let length = normal.dot(&normal).sqrt();
+        if !length.is_finite() || length <= EPSILON {
+            return Err(SplitError::new(
+                SplitErrorKind::InvalidPlane,
+                "Split plane normal must be a non-zero vector",
+            ));
+        }
+        let normal = scale(normal, 1.0 / length);
+
+        let closed =
+            !brep.faces.is_empty() && brep.edges.iter().all(|edge| edge.twin_halfedge.is_some());
+        if !closed {
+            return Err(SplitError::new(
+                SplitErrorKind::InvalidSolid,
+                "Split requires a closed solid",
+            ));
+        }
+
+        let cutter = Cutter::new(brep, origin, normal);
+        if !cutter.distances.iter().any(|d| *d > 0.0) || !cutter.distances.iter().any(|d| *d < 0.0)
+        {
+            return Err(SplitError::new(
+                SplitErrorKind::NoIntersection,
+                "Split plane does not cut the solid",
+            ));
+        }
+        Ok(cutter)
+    }
+
+    fn new(brep: &'a Brep, origin: Vector3, normal: Vector3) -> Self {
+        let tolerance = modeling_tolerance(brep);
+        let distances = brep
+            .vertices
+            .iter()
+            .map(|vertex| {
+                let distance = normal.dot(&sub(vertex.position, origin));
+                if distance.abs() <= tolerance {
+                    0.0
+                } else {
+                    distance
+                }
+            })
+            .collect();
+        Self {
+            brep,
+            origin,
+            normal,
+            distances,
+        }
+    }
+
+    fn distance(&self, key: PointKey) -> f64 {
+        match key {
+            PointKey::Vertex(id) => self.distances[id as usize],
+            PointKey::Crossing(..) => 0.0,
+        }
+    }
+
+    fn position(&self, key: PointKey) -> Vector3 {
+        match key {
+            PointKey::Vertex(id) => self.brep.vertices[id as usize].position,
+            PointKey::Crossing(a, b) => {
+                let (pa, pb) = (
+                    self.brep.vertices[a as usize].position,
+                    self.brep.vertices[b as usize].position,
+                );
+                let (da, db) = (self.distances[a as usize], self.distances[b as usize]);
+                add(pa, scale(sub(pb, pa), da / (da - db)))
+            }
+        }
+    }
+
+    /// Builds the half on `side` (+1 along the normal, -1 against it) and
+    /// returns it with the ids of its cap faces.
+    fn build_half(&self, side: f64) -> Result<(Brep, Vec<u32>), SplitError> {
+        let mut faces: Vec<(u32, KeyedFace)> = Vec::new();
+        let mut line_segments: Vec<CutSegment> = Vec::new();
+        for face in &self.brep.faces {
+            let (pieces, segments) = self.clip_face(face.id, side)?;
+            faces.extend(pieces.into_iter().map(|piece| (face.id, piece)));
+            line_segments.extend(segments);
+        }
+
+        // Cap edges run against the face edges they share on the plane.
+        let cap_normal = scale(self.normal, -side);
+        let cap_segments = cancel_opposite_segments(
+            line_segments
+                .iter()
+                .map(|(from, to)| (*to, *from))
+                .collect(),
+        );
+        let cap_loops = chain_segments(&cap_segments).ok_or_else(|| {
+            SplitError::new(
+                SplitErrorKind::TopologyError,
+                "Split cut segments do not close into cap loops",
+            )
+        })?;
+        let caps = self.nest_loops(cap_loops, cap_normal);
+        if caps.is_empty() {
+            return Err(SplitError::new(
+                SplitErrorKind::NoIntersection,
+                "Split plane does not cut the solid",
+            ));
+        }
+
+        let mut builder = BrepBuilder::new(uuid::Uuid::new_v4());
+        let mut ids: HashMap<PointKey, u32> = HashMap::new();
+        let mut resolve = |builder: &mut BrepBuilder, keys: &[PointKey]| -> Vec<u32> {
+            keys.iter()
+                .map(|key| {
+                    *ids.entry(*key)
+                        .or_insert_with(|| builder.add_vertex(self.position(*key)))
+                })
+                .collect()
+        };
+
+        let mut surfaces = Vec::new();
+        for (source_face, (outer, holes)) in &faces {
+            let outer_ids = resolve(&mut builder, outer);
+            let hole_ids: Vec<Vec<u32>> = holes
+                .iter()
+                .map(|hole| resolve(&mut builder, hole))
+                .collect();
+            let face_id = builder.add_face(&outer_ids, &hole_ids)?;
+            surfaces.push((
+                face_id,
+                self.brep.faces[*source_face as usize].surface.clone(),
+            ));
+        }
+        let mut cap_faces = Vec::new();
+        for (outer, holes) in &caps {
+            let outer_ids = resolve(&mut builder, outer);
+            let hole_ids: Vec<Vec<u32>> = holes
+                .iter()
+                .map(|hole| resolve(&mut builder, hole))
+                .collect();
+            let face_id = builder.add_face(&outer_ids, &hole_ids)?;
+            surfaces.push((
+                face_id,
+                Some(SurfaceGeometry::Plane {
+                    origin: self.origin,
+                    normal: cap_normal,
+                }),
+            ));
+            cap_faces.push(face_id);
+        }
+        builder.add_shell_from_all_faces(true)?;
+
+        let mut half = builder.build()?;
+        for (face_id, surface) in surfaces {
+            half.faces[face_id as usize].surface = surface;
+        }
+        for face_id in &cap_faces {
+            half.faces[*face_id as usize].role = Some(FaceRole::SectionCap);
+        }
+        let keys: HashMap<u32, PointKey> = ids.into_iter().map(|(key, id)| (id, key)).collect();
+        self.transfer_edge_curves(&mut half, &keys);
+
+        Ok((half, cap_faces))
+    }
+
+    /// Clips one face to `side`, returning the kept pieces and the directed
+    /// segments each piece runs along the cut plane.
+    fn clip_face(
+        &self,
+        face_id: u32,
+        side: f64,
+    ) -> Result<(Vec<KeyedFace>, Vec<CutSegment>), SplitError> {
+        let face = &self.brep.faces[face_id as usize];
+        let outer = self.brep.get_loop_vertex_indices(face.outer_loop);
+        let holes: Vec<Vec<u32>> = face
+            .inner_loops
+            .iter()
+            .map(|loop_id| self.brep.get_loop_vertex_indices(*loop_id))
+            .collect();
+        let all_ids = || outer.iter().chain(holes.iter().flatten());
+
+        if all_ids().all(|id| self.distances[*id as usize] == 0.0) {
+            // A face in the plane belongs to the half behind it, where its
+            // material is. Its whole boundary runs along the plane, so the
+            // caps of that half stop at it.
+            if side * normalize(face.normal).dot(&self.normal) >= 0.0 {
+                return Ok((Vec::new(), Vec::new()));
+            }
+            let keyed = |ids: &[u32]| -> Vec<PointKey> {
+                ids.iter().map(|id| PointKey::Vertex(*id)).collect()
+            };
+            let segments = std::iter::once(&outer)
+                .chain(holes.iter())
+                .flat_map(|ids| {
+                    (0..ids.len()).map(move |index| {
+                        (
+                            PointKey::Vertex(ids[index]),
+                            PointKey::Vertex(ids[(index + 1) % ids.len()]),
+                        )
+                    })
+                })
+                .collect();
+            let piece = (
+                keyed(&outer),
+                holes.iter().map(|hole| keyed(hole)).collect(),
+            );
+            return Ok((vec![piece], segments));
+        }
+        if all_ids().all(|id| side * self.distances[*id as usize] <= 0.0) {
+            return Ok((Vec::new(), Vec::new()));
+        }
+
+        let face_normal = normalize(face.normal);
+        let mut closed_loops: Vec<Vec<PointKey>> = Vec::new();
+        let mut chains: Vec<Vec<PointKey>> = Vec::new();
+        let mut segments: Vec<CutSegment> = Vec::new();
+
+        for loop_ids in std::iter::once(&outer).chain(holes.iter()) {
+            let augmented = self.augment_loop(loop_ids);
+            let count = augmented.len();
+            let kept: Vec<bool> = (0..count)
+                .map(|index| {
+                    let from = augmented[index];
+                    let to = augmented[(index + 1) % count];
+                    let mid = self.distance(from) + self.distance(to);
+                    if mid != 0.0 {
+                        return side * mid > 0.0;
+                    }
+                    // Edge lying in the plane: kept when the face interior on
+                    // its left is on this side.
+                    let direction = sub(self.position(to), self.position(from));
+                    side * self.normal.dot(&face_normal.cross(&direction)) > 0.0
+                })
+                .collect();
+
+            for index in 0..count {
+                let from = augmented[index];
+                let to = augmented[(index + 1) % count];
+                if kept[index] && self.distance(from) == 0.0 && self.distance(to) == 0.0 {
+                    segments.push((from, to));
+                }
+            }
+
+            if kept.iter().all(|k| *k) {
+                closed_loops.push(augmented);
+                continue;
+            }
+            let Some(first_dropped) = kept.iter().position(|k| !k) else {
+                continue;
+            };
+            let mut current: Vec<PointKey> = Vec::new();
+            for step in 1..=count {
+                let index = (first_dropped + step) % count;
+                if kept[index] {
+                    if current.is_empty() {
+                        current.push(augmented[index]);
+                    }
+                    current.push(augmented[(index + 1) % count]);
+                } else if !current.is_empty() {
+                    chains.push(std::mem::take(&mut current));
+                }
+            }
+            if !current.is_empty() {
+                chains.push(current);
+            }
+        }
+
+        // Along the face/plane intersection line the kept region lies to the
+        // left of `line_direction`; each chain exit connects to the next entry.
+        let line_direction = scale(self.normal.cross(&face_normal), side);
+        let param = |key: PointKey| line_direction.dot(&self.position(key));
+        let mut endpoints: Vec<(f64, bool, usize)> = Vec::new();
+        for (index, chain) in chains.iter().enumerate() {
+            endpoints.push((param(*chain.last().unwrap()), false, index));
+            endpoints.push((param(chain[0]), true, index));
+        }
+        endpoints.sort_by(|a, b| a.0.total_cmp(&b.0).then(b.1.cmp(&a.1)));
+        let mut next_chain: HashMap<usize, usize> = HashMap::new();
+        for pair in endpoints.chunks(2) {
+            let [(_, false, exit), (_, true, entry)] = pair else {
+                return Err(SplitError::new(
+                    SplitErrorKind::TopologyError,
+                    format!(
+                        "Face {} has an ambiguous cut along the split plane",
+                        face_id
+                    ),
+                ));
+            };
+            next_chain.insert(*exit, *entry);
+            let from = *chains[*exit].last().unwrap();
+            let to = chains[*entry][0];
+            if from != to {
+                segments.push((from, to));
+            }
+        }
+
+        let mut visited = vec![false; chains.len()];
+        for start in 0..chains.len() {
+            if visited[start] {
+                continue;
+            }
+            let mut keys: Vec<PointKey> = Vec::new();
+            let mut index = start;
+            while !visited[index] {
+                visited[index] = true;
+                for key in &chains[index] {
+                    if keys.last() != Some(key) {
+                        keys.push(*key);
+                    }
+                }
+                index = next_chain[&index];
+            }
+            if keys.len() > 1 && keys.first() == keys.last() {
+                keys.pop();
+            }
+            closed_loops.push(keys);
+        }
+
+        Ok((self.nest_loops(closed_loops, face_normal), segments))
+    }
+
+    /// The loop's vertices with a crossing point inserted on every edge whose
+    /// endpoints lie strictly on opposite sides.
+    fn augment_loop(&self, loop_ids: &[u32]) -> Vec<PointKey> {
+        let mut augmented = Vec::with_capacity(loop_ids.len() + 2);
+        for index in 0..loop_ids.len() {
+            let from = loop_ids[index];
+            let to = loop_ids[(index + 1) % loop_ids.len()];
+            augmented.push(PointKey::Vertex(from));
+            if self.distances[from as usize] * self.distances[to as usize] < 0.0 {
+                augmented.push(PointKey::Crossing(from.min(to), from.max(to)));
+            }
+        }
+        augmented
+    }
+
+    /// Sorts closed loops into faces: loops winding counter-clockwise about
+    /// `normal` are outers, the rest are holes of the outer that contains them.
+    fn nest_loops(&self, loops: Vec<Vec<PointKey>>, normal: Vector3) -> Vec<KeyedFace> {
+        let mut outers: Vec<KeyedFace> = Vec::new();
+        let mut holes: Vec<Vec<PointKey>> = Vec::new();
+        for keys in loops.into_iter().filter(|keys| keys.len() >= 3) {
+            let points: Vec<Vector3> = keys.iter().map(|key| self.position(*key)).collect();
+            match compute_polygon_normal(&points) {
+                Some(loop_normal) if loop_normal.dot(&normal) > 0.0 => {
+                    outers.push((keys, Vec::new()))
+                }
+                Some(_) => holes.push(keys),
+                None => {}
+            }
+        }
+        for hole in holes {
+            let probe = self.position(hole[0]);
+            let target = if outers.len() == 1 {
+                Some(0)
+            } else {
+                outers.iter().position(|(outer, _)| {
+                    let points: Vec<Vector3> =
+                        outer.iter().map(|key| self.position(*key)).collect();
+                    point_in_polygon(probe, normal, &points)
+                })
+            };
+            if let Some(target) = target {
+                outers[target].1.push(hole);
+            }
+        }
+        outers
+    }
+
+    /// Keeps analytic curves on edges that are (pieces of) original edges:
+    /// circles carry over unchanged, lines are re-trimmed to the new ends.
+    fn transfer_edge_curves(&self, half: &mut Brep, keys: &HashMap<u32, PointKey>) {
+        let mut curves: HashMap<(u32, u32), &CurveGeometry> = HashMap::new();
+        for edge in &self.brep.edges {
+            if let (Some(curve), Some((a, b))) =
+                (edge.curve.as_ref(), self.brep.get_edge_endpoints(edge.id))
+            {
+                curves.insert((a.min(b), a.max(b)), curve);
+            }
+        }
+
+        for index in 0..half.edges.len() {
+            let Some((from, to)) = half.get_edge_endpoints(index as u32) else {
+                continue;
+            };
+            let original = match (keys[&from], keys[&to]) {
+                (PointKey::Vertex(a), PointKey::Vertex(b)) => Some((a.min(b), a.max(b))),
+                (PointKey::Vertex(v), PointKey::Crossing(a, b))
+                | (PointKey::Crossing(a, b), PointKey::Vertex(v))
+                    if v == a || v == b =>
+                {
+                    Some((a, b))
+                }
+                _ => None,
+            };
+            let Some(curve) = original.and_then(|key| curves.get(&key)) else {
+                continue;
+            };
+            half.edges[index].curve = Some(match curve {
+                CurveGeometry::Line { .. } => CurveGeometry::Line {
+                    start: half.vertices[from as usize].position,
+                    end: half.vertices[to as usize].position,
+                },
+                circle => (*circle).clone(),
+            });
+        }
+    }
+}
+
+/// Drops segments that meet their own reverse: the edge between a cut region
+/// and a face lying in the plane is walked once by each, and bounds no cap.
+fn cancel_opposite_segments(segments: Vec<CutSegment>) -> Vec<CutSegment> {
+    let mut counts: HashMap<CutSegment, usize> = HashMap::new();
+    for segment in &segments {
+        *counts.entry(*segment).or_default() += 1;
+    }
+    let mut excess: HashMap<CutSegment, usize> = counts
+        .iter()
+        .map(|(&(from, to), &count)| {
+            let reverse = counts.get(&(to, from)).copied().unwrap_or(0);
+            ((from, to), count.saturating_sub(reverse))
+        })
+        .collect();
+    segments
+        .into_iter()
+        .filter(|segment| match excess.get_mut(segment) {
+            Some(left) if *left > 0 => {
+                *left -= 1;
+                true
+            }
+            _ => false,
+        })
+        .collect()
+}
+
+/// Chains directed segments head-to-tail into closed loops. `None` when a
+/// chain dead-ends, i.e. the segments do not form closed boundaries.
+fn chain_segments(segments: &[CutSegment]) -> Option<Vec<Vec<PointKey>>> {
+    let mut outgoing: HashMap<PointKey, Vec<usize>> = HashMap::new();
+    for (index, (from, _)) in segments.iter().enumerate() {
+        outgoing.entry(*from).or_default().push(index);
+    }
+    let mut used = vec![false; segments.len()];
+    let mut loops = Vec::new();
+    for start in 0..segments.len() {
+        if used[start] {
+            continue;
+        }
+        let origin = segments[start].0;
+        let mut keys = Vec::new();
+        let mut index = start;
+        loop {
+            used[index] = true;
+            keys.push(segments[index].0);
+            let head = segments[index].1;
+            if head == origin {
+                break;
+            }
+            index = *outgoing.get(&head)?.iter().find(|next| !used[**next])?;
+        }
+        loops.push(keys);
+    }
+    Some(loops)
+}
+
+fn modeling_tolerance(brep: &Brep) -> f64 {
+    let mut min = Vector3::new(f64::INFINITY, f64::INFINITY, f64::INFINITY);
+    let mut max = Vector3::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for vertex in &brep.vertices {
+        let p = vertex.position;
+        min = Vector3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+        max = Vector3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+    }
+    let diagonal = sub(max, min);
+    crate::tolerance::ToleranceContext::derived_modeling_for_diagonal(
+        diagonal.dot(&diagonal).sqrt(),
+    )
+}
+
+/// Wasm entry point for [`split_by_section_plane`]. `section_plane_json` is a
+/// scenegraph `SectionPlane` (`{"origin":[x,y,z],"normal":[x,y,z]}`). Returns
+/// `[positiveBrep, negativeBrep]` as a JSON array; cap faces carry the
+/// `SectionCap` role.
+#[wasm_bindgen(js_name = splitBrepByPlane)]
+pub fn split_brep_by_plane_wasm(
+    local_brep_serialized: String,
+    section_plane_json: String,
+) -> Result<String, JsValue> {
+    let brep: Brep = serde_json::from_str(&local_brep_serialized).map_err(|error| {
+        JsValue::from_str(&format!("Invalid local BRep JSON payload: {}", error))
+    })?;
+    let plane: SectionPlane = serde_json::from_str(&section_plane_json).map_err(|error| {
+        JsValue::from_str(&format!("Invalid section plane JSON payload: {}", error))
+    })?;
+    let output = split_by_section_plane(&brep, &plane)
+        .map_err(|error| JsValue::from_str(&format!("Failed to split BRep: {}", error)))?;
+    for half in [&output.positive, &output.negative] {
+        half.validate_topology().map_err(|error| {
+            JsValue::from_str(&format!("Split BRep topology is invalid: {}", error))
+        })?;
+    }
+    serde_json::to_string(&[&output.positive, &output.negative])
+        .map_err(|error| JsValue::from_str(&format!("Failed to serialize split BReps: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::extrude::{
+        extrude_profile_loops, extrude_profile_loops_with, ExtrudeExtent,
+    };
+    use crate::primitives::cuboid::OGCuboid;
+    use crate::primitives::cylinder::OGCylinder;
+    use uuid::Uuid;
+
+    fn cube() -> Brep {
+        let mut cuboid = OGCuboid::new("split-cuboid".to_string());
+        cuboid
+            .set_config(Vector3::new(0.0, 0.0, 0.0), 2.0, 2.0, 2.0)
+            .expect("cuboid config");
+        cuboid.brep().clone()
+    }
+
+    fn assert_closed(brep: &Brep) {
+        brep.validate_topology().expect("valid topology");
+        assert!(brep.edges.iter().all(|edge| edge.twin_halfedge.is_some()));
+        assert!(brep.shells.iter().all(|shell| shell.is_closed));
+    }
+
+    fn volume(brep: &Brep) -> f64 {
+        let buffer = brep.get_triangle_vertex_buffer();
+        buffer
+            .chunks(9)
+            .map(|t| {
+                let a = Vector3::new(t[0], t[1], t[2]);
+                let b = Vector3::new(t[3], t[4], t[5]);
+                let c = Vector3::new(t[6], t[7], t[8]);
+                a.dot(&b.cross(&c)) / 6.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn plane_split_of_cube_returns_two_capped_solids() {
+        let source = cube();
+        let output = split_by_plane(
+            &source,
+            Vector3::new(0.0, 0.5, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        )
+        .expect("split cube");
+
+        assert_closed(&output.positive);
+        assert_closed(&output.negative);
+        assert_eq!(output.positive.faces.len(), 6);
+        assert_eq!(output.negative.faces.len(), 6);
+        assert!((volume(&output.positive) - 2.0).abs() < 1.0e-9);
+        assert!((volume(&output.negative) - 6.0).abs() < 1.0e-9);
+
+        assert_eq!(output.positive_cap_faces.len(), 1);
+        let cap = &output.positive.faces[output.positive_cap_faces[0] as usize];
+        assert_eq!(cap.role, Some(FaceRole::SectionCap));
+        assert!(cap.normal.y < -0.99);
+        let cap = &output.negative.faces[output.negative_cap_faces[0] as usize];
+        assert!(cap.normal.y > 0.99);
+    }
+
+    #[test]
+    fn oblique_split_through_vertices_stays_closed() {
+        let source = cube();
+        let section = SectionPlane {
+            origin: [0.0, 0.0, 0.0],
+            normal: [1.0, 0.0, 1.0],
+        };
+        let output = split_by_section_plane(&source, &section).expect("diagonal split");
+
+        assert_closed(&output.positive);
+        assert_closed(&output.negative);
+        assert!((volume(&output.positive) - 4.0).abs() < 1.0e-9);
+        assert!((volume(&output.negative) - 4.0).abs() < 1.0e-9);
+    }
+
+    #[test]
+    fn split_of_holed_wall_caps_both_sides_of_the_opening() {
+        // 4 x 4 footprint with a 2 x 2 hole, 1 high; cut across the hole.
+        let outer = vec![
+            Vector3::new(-2.0, 0.0, -2.0),
+            Vector3::new(2.0, 0.0, -2.0),
+            Vector3::new(2.0, 0.0, 2.0),
+            Vector3::new(-2.0, 0.0, 2.0),
+        ];
+        let hole = vec![
+            Vector3::new(-1.0, 0.0, -1.0),
+            Vector3::new(-1.0, 0.0, 1.0),
+            Vector3::new(1.0, 0.0, 1.0),
+            Vector3::new(1.0, 0.0, -1.0),
+        ];
+        let source = extrude_profile_loops(Uuid::new_v4(), &outer, &[hole], 1.0).expect("ring");
+        let output = split_by_plane(
+            &source,
+            Vector3::new(0.25, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+        )
+        .expect("split ring");
+
+        assert_closed(&output.positive);
+        assert_closed(&output.negative);
+        assert_eq!(output.positive_cap_faces.len(), 2);
+        assert_eq!(output.negative_cap_faces.len(), 2);
+        let total = volume(&output.positive) + volume(&output.negative);
+        assert!((total - 12.0).abs() < 1.0e-9);
+        assert!((volume(&output.positive) - 1.75 * 4.0 + 0.75 * 2.0).abs() < 1.0e-9);
+    }
+
+    #[test]
+    fn split_at_a_step_keeps_the_tread_on_the_half_below_it() {
+        // L-shaped step: 2 wide and 1 high, with a 1 x 1 riser on the left;
+        // the tread at y = 1 lies in the split plane.
+        let profile = vec![
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(2.0, 0.0, 0.0),
+            Vector3::new(2.0, 1.0, 0.0),
+            Vector3::new(1.0, 1.0, 0.0),
+            Vector3::new(1.0, 2.0, 0.0),
+            Vector3::new(0.0, 2.0, 0.0),
+        ];
+        let source = extrude_profile_loops_with(
+            Uuid::new_v4(),
+            &profile,
+            &[],
+            Vector3::new(0.0, 0.0, 1.0),
+            ExtrudeExtent::OneSided { distance: 1.0 },
+        )
+        .expect("step");
+        let output = split_by_plane(
+            &source,
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        )
+        .expect("split at the tread");
+
+        assert_closed(&output.positive);
+        assert_closed(&output.negative);
+        assert!((volume(&output.positive) - 1.0).abs() < 1.0e-9);
+        assert!((volume(&output.negative) - 2.0).abs() < 1.0e-9);
+        // Each half has one 1 x 1 cap; the tread stays an ordinary face of
+        // the lower half.
+        for (half, caps) in [
+            (&output.positive, &output.positive_cap_faces),
+            (&output.negative, &output.negative_cap_faces),
+        ] {
+            assert_eq!(caps.len(), 1);
+            let cap = half.get_vertices_by_face_id(caps[0]);
+            assert!(cap.iter().all(|p| p.x <= 1.0 + 1.0e-9));
+        }
+        assert_eq!(output.positive.faces.len(), 6);
+        assert_eq!(output.negative.faces.len(), 7);
+
+        let regions = section_regions(
+            &source,
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        )
+        .expect("section at the tread");
+        assert_eq!(regions.len(), 1);
+        assert!(regions[0].0.points.iter().all(|p| p.x <= 1.0 + 1.0e-9));
+    }
+
+    #[test]
+    fn split_keeps_cylinder_surfaces_and_caps_horizontally() {
+        let mut cylinder = OGCylinder::new("split-cylinder".to_string());
+        cylinder
+            .set_config(
+                Vector3::new(0.0, 0.0, 0.0),
+                1.0,
+                2.0,
+                2.0 * std::f64::consts::PI,
+                16,
+            )
+            .expect("cylinder config");
+        let output = split_by_plane(
+            cylinder.brep(),
+            Vector3::new(0.0, 0.25, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        )
+        .expect("split cylinder");
+
+        assert_closed(&output.positive);
+        assert_closed(&output.negative);
+        let cylindrical = output
+            .positive
+            .faces
+            .iter()
+            .filter(|face| matches!(face.surface, Some(SurfaceGeometry::Cylinder { .. })))
+            .count();
+        assert_eq!(cylindrical, 16);
+        let cut_ring_circles = output
+            .positive
+            .edges
+            .iter()
+            .filter(|edge| matches!(edge.curve, Some(CurveGeometry::Circle { .. })));
+        assert_eq!(cut_ring_circles.count(), 16);
+    }
+
+    #[test]
+    fn split_by_face_plane_uses_tool_plane_and_rejects_misses() {
+        let source = cube();
+        let tool = extrude_profile_loops(
+            Uuid::new_v4(),
+            &[
+                Vector3::new(-5.0, 0.0, -5.0),
+                Vector3::new(5.0, 0.0, -5.0),
+                Vector3::new(5.0, 0.0, 5.0),
+                Vector3::new(-5.0, 0.0, 5.0),
+            ],
+            &[],
+            0.1,
+        )
+        .expect("tool slab");
+        let output = split_by_face_plane(&source, &tool, 0).expect("split by tool face");
+        assert_closed(&output.positive);
+        assert_closed(&output.negative);
+        let total = volume(&output.positive) + volume(&output.negative);
+        assert!((total - 8.0).abs() < 1.0e-9);
+
+        let Err(error) = split_by_plane(
+            &source,
+            Vector3::new(0.0, 5.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        ) else {
+            panic!("a plane beside the solid must not split it");
+        };
+        assert_eq!(error.kind(), SplitErrorKind::NoIntersection);
+    }
+
+    #[test]
+    fn split_by_face_plane_rejects_curved_and_warped_tool_faces() {
+        let source = cube();
+        let mut tool = extrude_profile_loops(
+            Uuid::new_v4(),
+            &[
+                Vector3::new(-5.0, 0.0, -5.0),
+                Vector3::new(5.0, 0.0, -5.0),
+                Vector3::new(5.0, 0.0, 5.0),
+                Vector3::new(-5.0, 0.0, 5.0),
+            ],
+            &[],
+            0.1,
+        )
+        .expect("tool slab");
+        tool.faces[0].surface = Some(SurfaceGeometry::Sphere {
+            center: Vector3::new(0.0, -10.0, 0.0),
+            radius: 10.0,
+        });
+        let Err(error) = split_by_face_plane(&source, &tool, 0) else {
+            panic!("a curved tool face has no plane to split by");
+        };
+        assert_eq!(error.kind(), SplitErrorKind::NonPlanarTool);
+
+        tool.faces[0].surface = None;
+        let corner = tool.get_loop_vertex_indices(tool.faces[0].outer_loop)[0];
+        tool.vertices[corner as usize].position.y += 0.5;
+        let Err(error) = split_by_face_plane(&source, &tool, 0) else {
+            panic!("a warped tool face has no plane to split by");
+        };
+        assert_eq!(error.kind(), SplitErrorKind::NonPlanarTool);
+    }
+}
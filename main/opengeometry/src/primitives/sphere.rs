@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
-use crate::brep::{Brep, BrepBuilder};
+use crate::brep::{Brep, BrepBuilder, SurfaceGeometry};
 use crate::export::projection::{project_brep_to_scene, CameraParameters, HlrOptions, Scene2D};
 use crate::spatial::placement::Placement3D;
 use openmaths::Vector3;
@@ -197,6 +197,16 @@ impl OGSphere {
             JsValue::from_str(&format!("Failed to finalize sphere BREP: {}", err))
         })?;
 
+        // Every facet approximates one exact sphere; consumers such as hidden
+        // line projection use it to draw the true outline.
+        let sphere = SurfaceGeometry::Sphere {
+            center: Vector3::new(0.0, 0.0, 0.0),
+            radius: self.radius,
+        };
+        for face in &mut self.brep.faces {
+            face.surface = Some(sphere.clone());
+        }
+
         Ok(())
     }
 
@@ -5,19 +5,26 @@ use uuid::Uuid;
 use wasm_bindgen::prelude::*;
 
 use crate::brep::Brep;
+use crate::export::annotation::{DimensionStyle, ModelDimension};
+use crate::export::hatch::HatchConfig;
 use crate::export::ifc::{
     export_brep_to_ifc_text, export_scene_entities_to_ifc_text, IfcEntityInput, IfcExportConfig,
     IfcExportReport,
 };
 use crate::export::projection::{
-    project_brep_to_scene, CameraParameters, HlrOptions, Scene2D, Scene2DLines,
+    project_breps_to_scenes, CameraParameters, HlrOptions, Scene2D, Scene2DLines, Vec2,
 };
+use crate::export::sheet::{DrawingScale, Sheet, SheetViewport};
+use crate::export::standard_views::{breps_bounds, standard_views, StandardViewsConfig};
 use crate::export::step::{
     export_brep_to_step_text, export_breps_to_step_text, StepExportConfig, StepExportReport,
 };
 use crate::export::stl::{
     export_brep_to_stl_bytes, export_breps_to_stl_bytes, StlExportConfig, StlExportReport,
 };
+use crate::operations::clash::{detect_clashes, ClashOptions, ClashReport};
+use crate::operations::section::{section_scene, SectionResult};
+use crate::operations::split::{split_by_section_plane, SplitError, SplitErrorKind};
 use crate::primitives::arc::OGArc;
 use crate::primitives::cuboid::OGCuboid;
 use crate::primitives::cylinder::OGCylinder;
@@ -77,11 +84,18 @@ impl OGScene {
 
     pub fn project_to_2d(&self, camera: &CameraParameters, hlr: &HlrOptions) -> Scene2D {
         let mut projected = Scene2D::with_name(self.name.clone());
-        for entity in &self.entities {
-            projected.extend(project_brep_to_scene(&entity.brep, camera, hlr));
+        let breps: Vec<&Brep> = self.entities.iter().map(|entity| &entity.brep).collect();
+        for scene in project_breps_to_scenes(&breps, camera, hlr) {
+            projected.extend(scene);
         }
         projected
     }
+
+    /// Checks every pair of entities for hard clashes, touching contacts and
+    /// clearance violations.
+    pub fn detect_clashes(&self, options: &ClashOptions) -> ClashReport {
+        detect_clashes(&self.entities, options)
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -253,6 +267,14 @@ impl OGSceneManager {
         }
     }
 
+    fn parse_clash_options_json(options_json: Option<String>) -> Result<ClashOptions, String> {
+        match options_json {
+            Some(payload) if !payload.trim().is_empty() => serde_json::from_str(&payload)
+                .map_err(|err| format!("Invalid clash options JSON payload: {}", err)),
+            _ => Ok(ClashOptions::default()),
+        }
+    }
+
     fn upsert_entity_brep(
         &mut self,
         scene_id: &str,
@@ -394,6 +416,23 @@ impl OGSceneManager {
         Ok(scene.project_to_2d(camera, hlr))
     }
 
+    pub fn section_scene_internal(
+        &self,
+        scene_id: &str,
+        plane: &SectionPlane,
+    ) -> Result<SectionResult, String> {
+        let scene = self.get_scene(scene_id)?;
+        section_scene(scene, plane).map_err(|err| format!("Failed to section scene: {}", err))
+    }
+
+    pub fn detect_clashes_internal(
+        &self,
+        scene_id: &str,
+        options: &ClashOptions,
+    ) -> Result<ClashReport, String> {
+        Ok(self.get_scene(scene_id)?.detect_clashes(options))
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     pub fn project_scene_to_pdf_with_camera(
         &self,
@@ -928,6 +967,47 @@ impl OGSceneManager {
         self.add_wedge_to_scene(scene_id, entity_id, wedge)
     }
 
+    /// Cross-sections every solid in the scene by a `SectionPlane`
+    /// (`{"origin":[x,y,z],"normal":[x,y,z]}`) and returns a `SectionResult`
+    /// whose loops and faces carry their source entity ids.
+    #[wasm_bindgen(js_name = sectionScene)]
+    pub fn section_scene(
+        &self,
+        scene_id: String,
+        section_plane_json: String,
+    ) -> Result<String, JsValue> {
+        let plane: SectionPlane = serde_json::from_str(&section_plane_json)
+            .map_err(|err| JsValue::from_str(&format!("Invalid section plane JSON: {}", err)))?;
+        let result = self
+            .section_scene_internal(&scene_id, &plane)
+            .map_err(|err| JsValue::from_str(&err))?;
+        serde_json::to_string(&result).map_err(|err| {
+            JsValue::from_str(&format!("Failed to serialize section result: {}", err))
+        })
+    }
+
+    /// Runs clash detection over the scene (the current scene when
+    /// `scene_id` is omitted). `options_json` is an optional `ClashOptions`
+    /// (`{"clearance":0.05,"tolerance":null,"include_touching":true}`); the
+    /// result is a `ClashReport` keyed by `"idA|idB"`.
+    #[wasm_bindgen(js_name = detectClashes)]
+    pub fn detect_clashes(
+        &self,
+        scene_id: Option<String>,
+        options_json: Option<String>,
+    ) -> Result<String, JsValue> {
+        let scene_id = self
+            .scene_id_or_current(scene_id)
+            .map_err(|err| JsValue::from_str(&err))?;
+        let options =
+            Self::parse_clash_options_json(options_json).map_err(|err| JsValue::from_str(&err))?;
+        let report = self
+            .detect_clashes_internal(&scene_id, &options)
+            .map_err(|err| JsValue::from_str(&err))?;
+        serde_json::to_string(&report)
+            .map_err(|err| JsValue::from_str(&format!("Failed to serialize clash report: {}", err)))
+    }
+
     #[wasm_bindgen(js_name = projectTo2DCamera)]
     pub fn project_to_2d_camera(
         &self,
@@ -1227,6 +1307,46 @@ struct ViewRequest {
     #[serde(default)]
     hlr: HlrOptions,
     section_plane: Option<SectionPlane>,
+    /// Hatch patterns for section cut regions, by entity kind or layer.
+    #[serde(default)]
+    hatch: HatchConfig,
+    /// Dimensions placed on model points, projected into the view.
+    #[serde(default)]
+    dimensions: Vec<ModelDimension>,
+    #[serde(default)]
+    dimension_style: DimensionStyle,
+}
+
+/// One viewport of `projectSheet`: a view request plus its placement.
+#[derive(Deserialize)]
+struct SheetViewRequest {
+    #[serde(flatten)]
+    view: ViewRequest,
+    scale: DrawingScale,
+    center_mm: Vec2,
+    #[serde(default)]
+    view_center: Option<Vec2>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    size_mm: Option<Vec2>,
+}
+
+/// Sheet layout passed to `projectSheet`; `views` become its viewports.
+#[derive(Deserialize)]
+struct SheetRequest {
+    #[serde(flatten)]
+    sheet: Sheet,
+    views: Vec<SheetViewRequest>,
+}
+
+/// Sheet layout and view options passed to `projectStandardViews`.
+#[derive(Deserialize)]
+struct StandardViewsRequest {
+    #[serde(flatten)]
+    sheet: Sheet,
+    #[serde(flatten)]
+    config: StandardViewsConfig,
 }
 
 /// Map `OGEntityKind` strings to AIA/NCS layer codes (AIA CAD Layer Guidelines).
@@ -1262,22 +1382,104 @@ impl Default for OGEntityRegistry {
 impl OGEntityRegistry {
     fn project_view(&self, view: &ViewRequest) -> Scene2D {
         let mut out = Scene2D::with_name(view.id.clone());
+        let mut kept: Vec<(&SceneEntity, Option<Brep>)> = Vec::new();
         for entity in self.entities.values() {
-            let mut entity_scene = project_brep_to_scene(&entity.brep, &view.camera, &view.hlr);
+            match &view.section_plane {
+                Some(plane) => match cut_away_viewer_side(&entity.brep, plane, &view.camera) {
+                    Ok(Some(far_side)) => kept.push((entity, far_side)),
+                    Ok(None) => continue,
+                    Err(error) => out.warnings.push(format!(
+                        "Entity '{}' crosses the section plane but could not be cut: {}",
+                        entity.id, error
+                    )),
+                },
+                None => kept.push((entity, None)),
+            }
+        }
+
+        // All entities are projected together so they hide one another.
+        let breps: Vec<&Brep> = kept
+            .iter()
+            .map(|(entity, cut)| cut.as_ref().unwrap_or(&entity.brep))
+            .collect();
+        let scenes = project_breps_to_scenes(&breps, &view.camera, &view.hlr);
+        for ((entity, _), mut entity_scene) in kept.iter().zip(scenes) {
             let layer = aia_layer(&entity.kind).map(str::to_string);
             for seg in &mut entity_scene.segments {
                 seg.layer = layer.clone();
                 seg.source_entity_id = Some(entity.id.clone());
             }
+            let pattern = view.hatch.resolve(&entity.kind, layer.as_deref());
+            for fill in &mut entity_scene.fills {
+                fill.pattern = pattern;
+                fill.layer = layer.clone();
+                fill.source_entity_id = Some(entity.id.clone());
+            }
             out.extend(entity_scene);
         }
-        // Section-plane intersection is wired up here in Phase 3+.
-        // `view.section_plane` field is available for future use.
-        let _ = &view.section_plane;
+        out.annotations.extend(
+            view.dimensions
+                .iter()
+                .filter_map(|dimension| dimension.project(&view.camera)),
+        );
+        out.dimension_style = view.dimension_style.clone();
         out
     }
 }
 
+/// Removes the part of `brep` between the viewer and the section plane.
+/// `Ok(Some(Some(b)))` ⇒ the far half with capped, hatchable cut faces;
+/// `Ok(Some(None))` ⇒ the entity is uncut and wholly beyond the plane and is
+/// drawn as-is; `Ok(None)` ⇒ it lies on the viewer's side. An entity that
+/// has faces and crosses the plane but cannot be split is an error: drawn
+/// whole, it would hide everything behind the cut. Wires are drawn as-is.
+fn cut_away_viewer_side(
+    brep: &Brep,
+    plane: &SectionPlane,
+    camera: &CameraParameters,
+) -> Result<Option<Option<Brep>>, SplitError> {
+    let normal = plane.normal;
+    let along_normal = |p: [f64; 3]| {
+        (p[0] - plane.origin[0]) * normal[0]
+            + (p[1] - plane.origin[1]) * normal[1]
+            + (p[2] - plane.origin[2]) * normal[2]
+    };
+    let view_direction = [
+        camera.target.x - camera.position.x,
+        camera.target.y - camera.position.y,
+        camera.target.z - camera.position.z,
+    ];
+    let facing = -(view_direction[0] * normal[0]
+        + view_direction[1] * normal[1]
+        + view_direction[2] * normal[2]);
+    let viewer_side = if facing.abs() > f64::EPSILON {
+        facing.signum()
+    } else {
+        along_normal([camera.position.x, camera.position.y, camera.position.z]).signum()
+    };
+
+    match split_by_section_plane(brep, plane) {
+        Ok(output) if viewer_side > 0.0 => Ok(Some(Some(output.negative))),
+        Ok(output) => Ok(Some(Some(output.positive))),
+        Err(error) if error.kind() == SplitErrorKind::InvalidPlane => Ok(Some(None)),
+        Err(error) => {
+            let side = |sign: f64| {
+                brep.vertices.iter().any(|vertex| {
+                    let p = vertex.position;
+                    sign * viewer_side * along_normal([p.x, p.y, p.z]) > 0.0
+                })
+            };
+            let (beyond, near) = (side(-1.0), side(1.0));
+            let occludes = !brep.faces.is_empty();
+            if occludes && beyond && near && error.kind() != SplitErrorKind::NoIntersection {
+                Err(error)
+            } else {
+                Ok(beyond.then_some(None))
+            }
+        }
+    }
+}
+
 #[wasm_bindgen]
 impl OGEntityRegistry {
     #[wasm_bindgen(constructor)]
@@ -1317,9 +1519,26 @@ impl OGEntityRegistry {
         self.entities.clear();
     }
 
+    /// Clash detection across all registered entities.
+    ///
+    /// Input:  optional JSON `ClashOptions`.
+    /// Output: JSON `ClashReport` whose `pairs` are keyed by `"idA|idB"`.
+    #[wasm_bindgen(js_name = detectClashes)]
+    pub fn detect_clashes(&self, options_json: Option<String>) -> Result<String, JsValue> {
+        let options: ClashOptions = match options_json {
+            Some(payload) if !payload.trim().is_empty() => serde_json::from_str(&payload)
+                .map_err(|e| JsValue::from_str(&format!("Invalid clash options JSON: {}", e)))?,
+            _ => ClashOptions::default(),
+        };
+        let report = detect_clashes(self.entities.values(), &options);
+        serde_json::to_string(&report)
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize clash report: {}", e)))
+    }
+
     /// Batched multi-view projection.
     ///
-    /// Input:  JSON array of `{ id, camera, hlr?, section_plane? }` (ViewRequest).
+    /// Input:  JSON array of `{ id, camera, hlr?, section_plane?, hatch?,
+    ///         dimensions?, dimension_style? }` (ViewRequest).
     /// Output: JSON map of `{ viewportId: Scene2D }`.
     ///
     /// All viewports are projected in a single WASM call, amortising
@@ -1338,6 +1557,63 @@ impl OGEntityRegistry {
             JsValue::from_str(&format!("Failed to serialize projection result: {}", e))
         })
     }
+
+    /// Generates the standard orthographic views of all entities.
+    ///
+    /// Input:  JSON `Sheet` plus `projection?` ("FirstAngle" | "ThirdAngle"),
+    ///         `views?`, `scale?`, `gap_mm?` and `hlr?` (StandardViewsConfig).
+    /// Output: JSON `Sheet` with one labelled viewport per view, all at one
+    ///         scale and aligned by the projection method.
+    #[wasm_bindgen(js_name = projectStandardViews)]
+    pub fn project_standard_views(&self, request_json: String) -> Result<String, JsValue> {
+        let request: StandardViewsRequest = serde_json::from_str(&request_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid standard views JSON: {}", e)))?;
+        let mut sheet = request.sheet;
+        let breps: Vec<&Brep> = self.entities.values().map(|entity| &entity.brep).collect();
+        let view_set =
+            breps_bounds(&breps).and_then(|bounds| standard_views(bounds, &sheet, &request.config));
+        if let Some(view_set) = view_set {
+            view_set.place_on(&mut sheet, |view| {
+                self.project_view(&ViewRequest {
+                    id: view.id.clone(),
+                    camera: view.camera.clone(),
+                    hlr: request.config.hlr.clone(),
+                    section_plane: None,
+                    hatch: HatchConfig::default(),
+                    dimensions: Vec::new(),
+                    dimension_style: DimensionStyle::default(),
+                })
+            });
+        }
+        serde_json::to_string(&sheet)
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize sheet: {}", e)))
+    }
+
+    /// Projects every view of a sheet layout into its viewport.
+    ///
+    /// Input:  JSON `Sheet` whose `views` are ViewRequests with `scale`,
+    ///         `center_mm`, `view_center?`, `title?` and `size_mm?`.
+    /// Output: JSON `Sheet` with one viewport per view, ready for
+    ///         `exportSheetToSvg` / `exportSheetToDxf`.
+    #[wasm_bindgen(js_name = projectSheet)]
+    pub fn project_sheet(&self, sheet_json: String) -> Result<String, JsValue> {
+        let request: SheetRequest = serde_json::from_str(&sheet_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid sheet JSON: {}", e)))?;
+        let mut sheet = request.sheet;
+        for request in &request.views {
+            sheet.viewports.push(SheetViewport {
+                id: request.view.id.clone(),
+                scene: self.project_view(&request.view),
+                scale: request.scale,
+                center_mm: request.center_mm,
+                view_center: request.view_center,
+                title: request.title.clone(),
+                size_mm: request.size_mm,
+            });
+        }
+        serde_json::to_string(&sheet)
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize sheet: {}", e)))
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -1346,6 +1622,8 @@ impl OGEntityRegistry {
 mod tests {
     use super::*;
     use crate::brep::{Brep, BrepBuilder};
+    use crate::export::hatch::HatchPattern;
+    use crate::export::projection::{EdgeClass, Segment2D};
     use crate::primitives::cuboid::OGCuboid;
     use openmaths::Vector3;
     use uuid::Uuid;
@@ -1689,6 +1967,260 @@ mod tests {
         assert!(result.contains_key("elev"));
     }
 
+    #[test]
+    fn registry_section_view_cuts_hatches_and_drops_viewer_side() {
+        let mut registry = OGEntityRegistry::new();
+        for (id, kind, lift) in [("wall-1", "wall", 0.0), ("slab-1", "slab", 3.0)] {
+            let mut cuboid = OGCuboid::new(id.to_string());
+            cuboid
+                .set_config(Vector3::new(0.0, 0.0, 0.0), 2.0, 1.0, 0.5)
+                .unwrap();
+            let mut brep = cuboid.brep().clone();
+            for vertex in &mut brep.vertices {
+                vertex.position.y += lift;
+            }
+            registry
+                .register_entity(
+                    id.to_string(),
+                    kind.to_string(),
+                    serde_json::to_string(&brep).unwrap(),
+                )
+                .unwrap();
+        }
+
+        let camera = CameraParameters {
+            position: Vector3::new(0.0, 10.0, 0.0),
+            target: Vector3::new(0.0, 0.0, 0.0),
+            up: Vector3::new(0.0, 0.0, -1.0),
+            ..CameraParameters::default()
+        };
+        let views_json = format!(
+            r#"[{{"id":"plan","camera":{},"section_plane":{{"origin":[0,0.1,0],"normal":[0,1,0]}},
+                "hatch":{{"by_kind":{{"wall":"Concrete"}}}}}}]"#,
+            serde_json::to_string(&camera).unwrap()
+        );
+        let result_json = registry.project_current_to_views(views_json).unwrap();
+        let result: HashMap<String, Scene2D> = serde_json::from_str(&result_json).unwrap();
+        let plan = &result["plan"];
+
+        assert!(plan
+            .segments
+            .iter()
+            .all(|seg| seg.source_entity_id.as_deref() == Some("wall-1")));
+        let cut_edges = plan
+            .segments
+            .iter()
+            .filter(|seg| seg.class == EdgeClass::SectionCut)
+            .count();
+        assert_eq!(cut_edges, 4);
+        assert_eq!(plan.fills.len(), 1);
+        assert_eq!(plan.fills[0].pattern, HatchPattern::Concrete);
+        assert_eq!(plan.fills[0].layer.as_deref(), Some("A-WALL"));
+        assert_eq!(plan.fills[0].boundary[0].len(), 4);
+    }
+
+    #[test]
+    fn registry_section_view_skips_and_reports_entities_it_cannot_cut() {
+        let mut registry = OGEntityRegistry::new();
+        let mut cuboid = OGCuboid::new("wall-1".to_string());
+        cuboid
+            .set_config(Vector3::new(0.0, 0.0, 0.0), 2.0, 1.0, 0.5)
+            .unwrap();
+        registry
+            .register_entity(
+                "wall-1".to_string(),
+                "wall".to_string(),
+                serde_json::to_string(cuboid.brep()).unwrap(),
+            )
+            .unwrap();
+        // An open sheet standing across the plane, over the wall.
+        let mut builder = BrepBuilder::new(Uuid::new_v4());
+        builder.add_vertices(&[
+            Vector3::new(-2.0, -1.0, 0.0),
+            Vector3::new(2.0, -1.0, 0.0),
+            Vector3::new(2.0, 1.0, 0.0),
+            Vector3::new(-2.0, 1.0, 0.0),
+        ]);
+        builder.add_face(&[0, 1, 2, 3], &[]).unwrap();
+        registry
+            .register_entity(
+                "screen-1".to_string(),
+                "generic".to_string(),
+                serde_json::to_string(&builder.build().unwrap()).unwrap(),
+            )
+            .unwrap();
+
+        let camera = CameraParameters {
+            position: Vector3::new(0.0, 10.0, 0.0),
+            target: Vector3::new(0.0, 0.0, 0.0),
+            up: Vector3::new(0.0, 0.0, -1.0),
+            ..CameraParameters::default()
+        };
+        let views_json = format!(
+            r#"[{{"id":"plan","camera":{},"section_plane":{{"origin":[0,0.1,0],"normal":[0,1,0]}}}}]"#,
+            serde_json::to_string(&camera).unwrap()
+        );
+        let result_json = registry.project_current_to_views(views_json).unwrap();
+        let result: HashMap<String, Scene2D> = serde_json::from_str(&result_json).unwrap();
+        let plan = &result["plan"];
+
+        assert!(plan
+            .segments
+            .iter()
+            .all(|seg| seg.source_entity_id.as_deref() == Some("wall-1")));
+        assert_eq!(
+            plan.segments
+                .iter()
+                .filter(|seg| seg.class == EdgeClass::SectionCut)
+                .count(),
+            4
+        );
+        assert_eq!(plan.warnings.len(), 1);
+        assert!(plan.warnings[0].contains("screen-1"));
+    }
+
+    #[test]
+    fn registry_views_project_model_dimensions() {
+        use crate::export::annotation::{Annotation, DimensionStandard};
+
+        let mut registry = OGEntityRegistry::new();
+        let brep_json = serde_json::to_string(&wire_brep()).unwrap();
+        registry
+            .register_entity("e1".to_string(), "wall".to_string(), brep_json)
+            .unwrap();
+
+        let camera = CameraParameters {
+            position: Vector3::new(0.0, 10.0, 0.0),
+            target: Vector3::new(0.0, 0.0, 0.0),
+            up: Vector3::new(0.0, 0.0, -1.0),
+            ..CameraParameters::default()
+        };
+        let views_json = format!(
+            r#"[{{"id":"plan","camera":{},"dimension_style":{{"standard":"Ansi"}},
+                "dimensions":[{{"type":"Linear","p1":{{"x":0,"y":0,"z":0}},
+                "p2":{{"x":2,"y":0,"z":0}},"orientation":"Aligned","offset":0.5}}]}}]"#,
+            serde_json::to_string(&camera).unwrap()
+        );
+        let result_json = registry.project_current_to_views(views_json).unwrap();
+        let result: HashMap<String, Scene2D> = serde_json::from_str(&result_json).unwrap();
+        let plan = &result["plan"];
+
+        assert_eq!(plan.dimension_style.standard, DimensionStandard::Ansi);
+        assert_eq!(plan.annotations.len(), 1);
+        let Annotation::Linear(dimension) = &plan.annotations[0] else {
+            panic!("expected a linear dimension");
+        };
+        assert_close(dimension.measurement(), 2.0);
+    }
+
+    #[test]
+    fn registry_projects_sheet_views_into_scaled_viewports() {
+        let mut registry = OGEntityRegistry::new();
+        let brep_json = serde_json::to_string(&wire_brep()).unwrap();
+        registry
+            .register_entity("e1".to_string(), "wall".to_string(), brep_json)
+            .unwrap();
+
+        let camera_json = serde_json::to_string(&CameraParameters::default()).unwrap();
+        let sheet_json = format!(
+            r#"{{"name":"A-101","paper":{{"width_mm":420,"height_mm":297}},
+                "title_block":{{}},
+                "views":[{{"id":"iso","camera":{},"scale":{{"paper":1,"model":20}},
+                "center_mm":{{"x":150,"y":150}},"title":"ISOMETRIC"}}]}}"#,
+            camera_json
+        );
+        let sheet: Sheet =
+            serde_json::from_str(&registry.project_sheet(sheet_json).unwrap()).unwrap();
+
+        assert!(sheet.border.is_some());
+        assert_eq!(sheet.viewports.len(), 1);
+        assert_eq!(sheet.viewports[0].id, "iso");
+        assert_eq!(sheet.viewports[0].scale, DrawingScale::one_to(20.0));
+        assert!(!sheet.viewports[0].scene.segments.is_empty());
+        let svg = crate::export::svg::export_sheet_to_svg(&sheet);
+        assert!(svg.contains(r#"viewBox="0 0 420 297""#));
+        assert!(svg.contains("ISOMETRIC  1:20"));
+    }
+
+    #[test]
+    fn sheet_viewports_keep_scene_units_under_a_framed_camera() {
+        let mut registry = OGEntityRegistry::new();
+        let brep_json = serde_json::to_string(&wire_brep()).unwrap();
+        registry
+            .register_entity("e1".to_string(), "wall".to_string(), brep_json)
+            .unwrap();
+
+        let camera = CameraParameters {
+            position: Vector3::new(0.0, 10.0, 0.0),
+            target: Vector3::new(0.0, 0.0, 0.0),
+            up: Vector3::new(0.0, 0.0, -1.0),
+            ortho_height: Some(10.0),
+            ..CameraParameters::default()
+        };
+        let sheet_json = format!(
+            r#"{{"name":"A-101","paper":{{"width_mm":420,"height_mm":297}},
+                "views":[{{"id":"plan","camera":{},"scale":{{"paper":1,"model":20}},
+                "center_mm":{{"x":150,"y":150}}}}]}}"#,
+            serde_json::to_string(&camera).unwrap()
+        );
+        let sheet: Sheet =
+            serde_json::from_str(&registry.project_sheet(sheet_json).unwrap()).unwrap();
+
+        // The 2 m wire is 100 mm long at 1:20, whatever the viewport height.
+        let composed = sheet.compose();
+        let wall = composed
+            .segments
+            .iter()
+            .find(|seg| seg.layer.as_deref() == Some("A-WALL"))
+            .unwrap();
+        let Segment2D::Line { start, end } = wall.geometry else {
+            panic!("expected a line");
+        };
+        assert!(((end.x - start.x).hypot(end.y - start.y) - 100.0).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn registry_generates_aligned_third_angle_views() {
+        let mut registry = OGEntityRegistry::new();
+        let mut cuboid = OGCuboid::new("block".to_string());
+        cuboid
+            .set_config(Vector3::new(0.0, 0.0, 0.0), 2.0, 1.0, 0.5)
+            .unwrap();
+        registry
+            .register_entity(
+                "block".to_string(),
+                "wall".to_string(),
+                serde_json::to_string(cuboid.brep()).unwrap(),
+            )
+            .unwrap();
+
+        let request = r#"{"paper":{"width_mm":420,"height_mm":297},"projection":"ThirdAngle",
+            "views":["Front","Top","Right"]}"#;
+        let sheet: Sheet = serde_json::from_str(
+            &registry
+                .project_standard_views(request.to_string())
+                .unwrap(),
+        )
+        .unwrap();
+
+        let ids: Vec<&str> = sheet.viewports.iter().map(|v| v.id.as_str()).collect();
+        assert_eq!(ids, ["front", "top", "right"]);
+        let (front, top, right) = (
+            &sheet.viewports[0],
+            &sheet.viewports[1],
+            &sheet.viewports[2],
+        );
+        assert!(top.center_mm.y > front.center_mm.y);
+        assert!(right.center_mm.x > front.center_mm.x);
+        assert_eq!(front.scale, top.scale);
+        assert_eq!(front.title.as_deref(), Some("FRONT"));
+        assert!(front
+            .scene
+            .segments
+            .iter()
+            .all(|seg| seg.source_entity_id.as_deref() == Some("block")));
+    }
+
     #[test]
     fn registry_unregister_removes_entity_from_projection() {
         let mut registry = OGEntityRegistry::new();
@@ -1712,6 +2244,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn registry_detect_clashes_reports_duct_through_beam() {
+        let mut registry = OGEntityRegistry::new();
+        for (id, kind, center, size) in [
+            (
+                "beam-1",
+                "beam",
+                Vector3::new(0.0, 0.0, 0.0),
+                [4.0, 0.5, 0.3],
+            ),
+            (
+                "duct-1",
+                "duct",
+                Vector3::new(0.0, 0.0, 0.0),
+                [0.2, 0.2, 2.0],
+            ),
+            (
+                "column-1",
+                "column",
+                Vector3::new(5.0, 0.0, 0.0),
+                [0.3, 3.0, 0.3],
+            ),
+        ] {
+            let mut cuboid = OGCuboid::new(id.to_string());
+            cuboid
+                .set_config(center, size[0], size[1], size[2])
+                .unwrap();
+            registry
+                .register_entity(
+                    id.to_string(),
+                    kind.to_string(),
+                    serde_json::to_string(&cuboid.world_brep()).unwrap(),
+                )
+                .unwrap();
+        }
+
+        let report_json = registry
+            .detect_clashes(Some(r#"{"clearance":0.5}"#.to_string()))
+            .unwrap();
+        let report: ClashReport = serde_json::from_str(&report_json).unwrap();
+
+        assert_eq!(report.entity_count, 3);
+        assert_eq!(report.hard_count, 1);
+        let clash = &report.pairs["beam-1|duct-1"];
+        assert_close(clash.intersection_volume.unwrap(), 0.2 * 0.2 * 0.3);
+        // The column sits 0.85 from the beam end: outside the clearance.
+        assert!(!report.pairs.contains_key("beam-1|column-1"));
+    }
+
     #[test]
     fn aia_layer_maps_known_kinds() {
         assert_eq!(aia_layer("wall"), Some("A-WALL"));
@@ -100,6 +100,28 @@ impl WorkPlane {
     pub fn v_axis(&self) -> Vector3 {
         self.v_axis
     }
+
+    /// In-plane `(u, v)` coordinates of a world point, dropping its offset
+    /// along the normal; `lift_point` inverts it for points on the plane.
+    pub fn project(&self, point: Vector3) -> (f64, f64) {
+        let d = Vector3::new(
+            point.x - self.origin.x,
+            point.y - self.origin.y,
+            point.z - self.origin.z,
+        );
+        (dot(d, self.u_axis), dot(d, self.v_axis))
+    }
+
+    /// Signed distance of a world point from the plane, along the normal.
+    pub fn signed_distance(&self, point: Vector3) -> f64 {
+        let d = Vector3::new(
+            point.x - self.origin.x,
+            point.y - self.origin.y,
+            point.z - self.origin.z,
+        );
+        dot(d, self.normal)
+    }
+
     /// Lifts owned 2D points to world `Vector3`s.
     pub fn lift(&self, points: &[(f64, f64)]) -> Vec<Vector3> {
         points
@@ -109,6 +131,10 @@ impl WorkPlane {
     }
 }
 
+fn dot(a: Vector3, b: Vector3) -> f64 {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
 fn cross(a: Vector3, b: Vector3) -> Vector3 {
     Vector3::new(
         a.y * b.z - a.z * b.y,
@@ -80,6 +80,19 @@ impl LengthUnit {
             LengthUnit::Foot => "foot",
         }
     }
+
+    /// Abbreviation used on drawings ("mm", "in", …).
+    pub fn symbol(self) -> &'static str {
+        match self {
+            LengthUnit::Micrometre => "µm",
+            LengthUnit::Millimetre => "mm",
+            LengthUnit::Centimetre => "cm",
+            LengthUnit::Metre => "m",
+            LengthUnit::Kilometre => "km",
+            LengthUnit::Inch => "in",
+            LengthUnit::Foot => "ft",
+        }
+    }
 }
 
 #[cfg(test)]
@@ -48,6 +48,7 @@ fn cylinder_has_one_cylindrical_surface_and_circular_edges() {
                 cylinder_faces += 1;
             }
             SurfaceGeometry::Plane { .. } => plane_faces += 1,
+            other => panic!("unexpected {} surface on a cylinder", other.kind()),
         }
     }
     assert_eq!(plane_faces, 2, "top + bottom caps");
@@ -95,3 +96,44 @@ fn analytic_geometry_survives_world_transform() {
         "radius scaled by uniform factor 3"
     );
 }
+
+/// Entity types of a Part 21 payload in order, ignoring ids, GUIDs and
+/// timestamps.
+fn entity_types(payload: &str) -> Vec<&str> {
+    payload
+        .lines()
+        .filter_map(|line| line.split_once('=').map(|(_, rest)| rest.trim_start()))
+        .filter_map(|rest| rest.split_once('(').map(|(name, _)| name))
+        .collect()
+}
+
+#[test]
+fn sphere_surface_tags_do_not_change_exchange_output() {
+    use opengeometry::export::ifc::{export_brep_to_ifc_text, IfcExportConfig};
+    use opengeometry::export::step::{export_brep_to_step_text, StepExportConfig};
+    use opengeometry::primitives::sphere::OGSphere;
+
+    let mut sphere = OGSphere::new("sphere-analytic".to_string());
+    sphere
+        .set_config(Vector3::new(0.0, 0.0, 0.0), 1.0, 16, 8)
+        .unwrap();
+    let tagged = sphere.brep().clone();
+    assert!(tagged
+        .faces
+        .iter()
+        .all(|face| matches!(face.surface, Some(SurfaceGeometry::Sphere { .. }))));
+    let mut untagged = tagged.clone();
+    for face in &mut untagged.faces {
+        face.surface = None;
+    }
+
+    let step = |brep| export_brep_to_step_text(brep, &StepExportConfig::default()).unwrap();
+    let ((tagged_step, tagged_report), (untagged_step, untagged_report)) =
+        (step(&tagged), step(&untagged));
+    assert_eq!(entity_types(&tagged_step), entity_types(&untagged_step));
+    assert_eq!(tagged_report.exported_faces, untagged_report.exported_faces);
+
+    let ifc = |brep| export_brep_to_ifc_text(brep, &IfcExportConfig::default()).unwrap();
+    let ((tagged_ifc, _), (untagged_ifc, _)) = (ifc(&tagged), ifc(&untagged));
+    assert_eq!(entity_types(&tagged_ifc), entity_types(&untagged_ifc));
+}
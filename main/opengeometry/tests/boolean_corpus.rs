@@ -10,7 +10,7 @@
 use opengeometry::booleans::types::BooleanOptions;
 use opengeometry::booleans::{boolean_intersection, boolean_subtraction, boolean_union};
 use opengeometry::brep::validity::check_validity;
-use opengeometry::brep::Brep;
+use opengeometry::brep::{Brep, SurfaceGeometry};
 use opengeometry::primitives::cuboid::OGCuboid;
 use opengeometry::primitives::cylinder::OGCylinder;
 use opengeometry::primitives::sphere::OGSphere;
@@ -201,6 +201,128 @@ fn drilled_hole_wall_survives_as_analytic_cylinder() {
     );
 }
 
+#[test]
+fn chords_across_a_circle_stay_straight() {
+    // Halving a cylinder along its axis leaves each cap with a diameter whose
+    // ends lie on the rim circle; it must not be tagged as that circle.
+    use opengeometry::booleans::types::BooleanBackend;
+    use opengeometry::brep::CurveGeometry;
+    let rod = cylinder(Vector3::new(0.0, 0.0, 0.0), 1.0, 2.0);
+    let back = cuboid(Vector3::new(0.0, 0.0, -1.0), 4.0, 4.0, 2.0);
+    for backend in [BooleanBackend::Mesh, BooleanBackend::Native] {
+        let options = BooleanOptions {
+            backend,
+            ..BooleanOptions::default()
+        };
+        let out = boolean_subtraction(&rod, &back, options).expect("halve the rod");
+        let brep = &out.brep;
+
+        let mut diameters = 0;
+        let mut arcs = 0;
+        for edge in &brep.edges {
+            let (from, to) = brep.get_edge_endpoints(edge.id).expect("edge ends");
+            let (a, b) = (
+                brep.vertices[from as usize].position,
+                brep.vertices[to as usize].position,
+            );
+            let length = ((b.x - a.x).powi(2) + (b.y - a.y).powi(2) + (b.z - a.z).powi(2)).sqrt();
+            let is_circle = matches!(edge.curve, Some(CurveGeometry::Circle { .. }));
+            if (length - 2.0).abs() < 1.0e-6 && (a.y - b.y).abs() < 1.0e-9 {
+                diameters += 1;
+                assert!(!is_circle, "{:?}: diameter tagged as a circle", backend);
+            } else if is_circle {
+                arcs += 1;
+            }
+        }
+        assert_eq!(diameters, 2, "{:?}: one diameter per cap", backend);
+        assert!(arcs >= 24, "{:?}: rim facets keep their circle", backend);
+    }
+}
+
+#[test]
+fn drilled_hole_rims_carry_exact_circles() {
+    // The hole rims are new intersection edges where the block faces cut the
+    // cutter cylinder; each chord must carry the exact rim circle.
+    use opengeometry::brep::CurveGeometry;
+    let block = cuboid(Vector3::new(0.0, 0.0, 0.0), 3.0, 2.0, 3.0);
+    let drill = cylinder(Vector3::new(0.0, 0.0, 0.0), 0.6, 4.0);
+    let out = boolean_subtraction(&block, &drill, BooleanOptions::default()).expect("drill");
+
+    let mut rims = [0usize; 2];
+    for edge in &out.brep.edges {
+        let Some(CurveGeometry::Circle {
+            center,
+            normal,
+            radius,
+            ..
+        }) = &edge.curve
+        else {
+            continue;
+        };
+        assert!((radius - 0.6).abs() < 1.0e-9);
+        assert!((normal.y.abs() - 1.0).abs() < 1.0e-9);
+        assert!(center.x.abs() < 1.0e-9 && center.z.abs() < 1.0e-9);
+        assert!(
+            (center.y.abs() - 1.0).abs() < 1.0e-6,
+            "rim at y = {}",
+            center.y
+        );
+        rims[usize::from(center.y > 0.0)] += 1;
+    }
+    assert_eq!(rims, [24, 24], "one circular chord per cylinder segment");
+
+    // Block faces get the block's planes back, not per-triangle planes.
+    let block_planes = block
+        .faces
+        .iter()
+        .map(|f| {
+            let origin = block.get_vertices_by_face_id(f.id)[0];
+            (origin.x, origin.y, origin.z)
+        })
+        .collect::<Vec<_>>();
+    for face in &out.brep.faces {
+        match &face.surface {
+            Some(SurfaceGeometry::Plane { origin, .. }) => {
+                assert!(block_planes.contains(&(origin.x, origin.y, origin.z)));
+            }
+            Some(SurfaceGeometry::Cylinder { radius, .. }) => {
+                assert!((radius - 0.6).abs() < 1.0e-9)
+            }
+            _ => panic!("unexpected boolean face surface"),
+        }
+    }
+}
+
+#[test]
+fn cut_sphere_faces_stay_spherical() {
+    // Facets the cut passes through keep the sphere even though their new
+    // vertices lie on the chord plane inside it.
+    let ball = sphere(Vector3::new(0.0, 0.0, 0.0), 1.0);
+    let slab = cuboid(Vector3::new(0.0, -1.3, 0.0), 3.0, 3.0, 3.0);
+    let out = boolean_intersection(&ball, &slab, BooleanOptions::default()).expect("cut");
+
+    let spherical = out
+        .brep
+        .faces
+        .iter()
+        .filter(|f| matches!(f.surface, Some(SurfaceGeometry::Sphere { .. })))
+        .count();
+    let flat = out
+        .brep
+        .faces
+        .iter()
+        .filter(|f| matches!(f.surface, Some(SurfaceGeometry::Plane { .. })))
+        .count();
+    assert!(flat > 0 && spherical > 0);
+    for face in &out.brep.faces {
+        if let Some(SurfaceGeometry::Plane { origin, normal }) = face.surface {
+            // Only the cut face is planar: it lies on the slab top at y = 0.2.
+            assert!((origin.y - 0.2).abs() < 1.0e-9);
+            assert!((normal.y.abs() - 1.0).abs() < 1.0e-9);
+        }
+    }
+}
+
 #[test]
 fn booleans_are_deterministic_across_runs() {
     // NFR-1: identical inputs produce identical topology across runs.